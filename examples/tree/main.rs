@@ -18,7 +18,7 @@ fn print_tree(node: &gltf::Node, depth: i32) {
 }
 
 fn run(path: &str) -> Result<(), Box<dyn StdError>> {
-    let file = fs::File::open(&path)?;
+    let file = fs::File::open(path)?;
     let reader = io::BufReader::new(file);
     let gltf = gltf::Gltf::from_reader(reader)?;
     for scene in gltf.scenes() {
@@ -0,0 +1,20 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let (root, buffers) = gltf::testing::Generator::from_bytes(data).generate();
+    let json = root.to_vec().expect("generated document serializes");
+    let gltf = gltf::Gltf::from_slice(&json).expect("generated document is valid");
+    for mesh in gltf.meshes() {
+        for primitive in mesh.primitives() {
+            let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+            let positions = reader
+                .read_positions()
+                .expect("generated positions are readable");
+            assert_eq!(
+                positions.count(),
+                primitive.get(&gltf::Semantic::Positions).unwrap().count()
+            );
+        }
+    }
+});
@@ -25,10 +25,10 @@ fn expand(ast: &DeriveInput) -> proc_macro2::TokenStream {
     let ident = &ast.ident;
     let validations: Vec<TokenStream> = fields
         .iter()
-        .map(|f| f.ident.as_ref().unwrap())
-        .map(|ident| {
+        .map(|f| {
             use inflections::Inflect;
-            let field = ident.to_string().to_camel_case();
+            let ident = f.ident.as_ref().unwrap();
+            let field = rename(f).unwrap_or_else(|| ident.to_string().to_camel_case());
             quote!(
                 self.#ident.validate(
                     _root,
@@ -59,3 +59,27 @@ fn expand(ast: &DeriveInput) -> proc_macro2::TokenStream {
         }
     )
 }
+
+/// Returns the name given to a field by `#[serde(rename = "...")]`, if any.
+fn rename(field: &syn::Field) -> Option<String> {
+    use syn::{Lit, Meta, NestedMeta};
+
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .find_map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(ref pair)) if pair.path.is_ident("rename") => {
+                match pair.lit {
+                    Lit::Str(ref name) => Some(name.value()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+}
@@ -224,13 +224,33 @@ pub struct Accessor {
     pub sparse: Option<sparse::Sparse>,
 }
 
-impl Validate for Accessor {
-    fn validate<P, R>(&self, root: &Root, path: P, report: &mut R)
+impl Accessor {
+    /// Returns the size in bytes of each element, including the padding that
+    /// aligns each column of a matrix to four bytes, or `None` if the type or
+    /// component type is invalid.
+    pub fn element_size(&self) -> Option<usize> {
+        let (component_type, type_) = match (&self.component_type, &self.type_) {
+            (Checked::Valid(component_type), Checked::Valid(type_)) => (component_type.0, *type_),
+            _ => return None,
+        };
+        let size = component_type.size();
+        Some(match (type_, size) {
+            (Type::Mat2, 1) => 8,
+            (Type::Mat3, 1) => 12,
+            (Type::Mat3, 2) => 24,
+            _ => size * type_.multiplicity(),
+        })
+    }
+
+    /// Validates the accessor, where `decoded` is whether it belongs to a
+    /// primitive with its geometry compressed by `KHR_draco_mesh_compression`,
+    /// whose data is decoded rather than read from a buffer view.
+    pub(crate) fn validate_decoded<P, R>(&self, root: &Root, decoded: bool, path: P, report: &mut R)
     where
         P: Fn() -> Path,
         R: FnMut(&dyn Fn() -> Path, Error),
     {
-        if self.sparse.is_none() && self.buffer_view.is_none() {
+        if self.sparse.is_none() && self.buffer_view.is_none() && !decoded {
             // If sparse is missing, then bufferView must be present. Report that bufferView is
             // missing since it is the more common one to require.
             report(&|| path().field("bufferView"), Error::Missing);
@@ -254,6 +274,79 @@ impl Validate for Accessor {
             .validate(root, || path().field("normalized"), report);
         self.sparse
             .validate(root, || path().field("sparse"), report);
+
+        // spec: `count` **must** be at least one.
+        if self.count == 0 {
+            report(&|| path().field("count"), Error::Invalid);
+        }
+
+        // spec: the elements **must** fit within the buffer view, aligned to
+        // the size of their components, and no closer together than their
+        // size.
+        let view = self.buffer_view.and_then(|index| root.get(index));
+        if let (Some(view), Some(size)) = (view, self.element_size()) {
+            if let Checked::Valid(component_type) = self.component_type {
+                if self.byte_offset as usize % component_type.0.size() != 0 {
+                    report(&|| path().field("byteOffset"), Error::Invalid);
+                }
+            }
+            let stride = view.byte_stride.map_or(size, |stride| stride as usize);
+            if stride < size {
+                report(&|| path().field("bufferView"), Error::Invalid);
+            }
+            // Computed in `u64` so that it cannot overflow on 32-bit targets.
+            let end = match self.count {
+                0 => 0,
+                count => self.byte_offset as u64 + stride as u64 * (count as u64 - 1) + size as u64,
+            };
+            if end > view.byte_length as u64 {
+                report(&|| path().field("count"), Error::Invalid);
+            }
+        }
+    }
+}
+
+/// Returns the indices of the accessors of every primitive with its geometry
+/// compressed by `KHR_draco_mesh_compression`.
+#[cfg(feature = "KHR_draco_mesh_compression")]
+pub(crate) fn draco_decoded(root: &Root) -> std::collections::HashSet<usize> {
+    root.meshes
+        .iter()
+        .flat_map(|mesh| &mesh.primitives)
+        .filter(|primitive| {
+            primitive
+                .extensions
+                .as_ref()
+                .is_some_and(|extensions| extensions.khr_draco_mesh_compression.is_some())
+        })
+        .flat_map(|primitive| {
+            primitive
+                .indices
+                .iter()
+                .chain(primitive.attributes.values())
+        })
+        .map(|accessor| accessor.value())
+        .collect()
+}
+
+impl Validate for Accessor {
+    fn validate<P, R>(&self, root: &Root, path: P, report: &mut R)
+    where
+        P: Fn() -> Path,
+        R: FnMut(&dyn Fn() -> Path, Error),
+    {
+        // `Root` validates its accessors with `validate_decoded` directly, so
+        // that the decoded accessors are only found once.
+        #[cfg(feature = "KHR_draco_mesh_compression")]
+        let decoded = self.buffer_view.is_none()
+            && root
+                .accessors
+                .iter()
+                .position(|accessor| std::ptr::eq(accessor, self))
+                .is_some_and(|index| draco_decoded(root).contains(&index));
+        #[cfg(not(feature = "KHR_draco_mesh_compression"))]
+        let decoded = false;
+        self.validate_decoded(root, decoded, path, report);
     }
 }
 
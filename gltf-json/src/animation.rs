@@ -12,7 +12,7 @@ pub const VALID_INTERPOLATIONS: &[&str] = &["LINEAR", "STEP", "CUBICSPLINE"];
 pub const VALID_PROPERTIES: &[&str] = &["translation", "rotation", "scale", "weights"];
 
 /// Specifies an interpolation algorithm.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Default)]
 pub enum Interpolation {
     /// Linear interpolation.
     ///
@@ -20,6 +20,7 @@ pub enum Interpolation {
     /// When targeting a rotation, spherical linear interpolation (slerp) should be
     /// used to interpolate quaternions. The number output of elements must equal
     /// the number of input elements.
+    #[default]
     Linear = 1,
 
     /// Step interpolation.
@@ -153,21 +154,43 @@ impl Validate for Animation {
     {
         self.samplers
             .validate(root, || path().field("samplers"), report);
+        for (index, sampler) in self.samplers.iter().enumerate() {
+            // spec: sampler inputs **must** be scalar floats with `min` and
+            // `max` defined.
+            let input_path = || path().field("samplers").index(index).field("input");
+            if let Some(input) = root.get(sampler.input) {
+                // Invalid types are reported by the accessor.
+                if let (Checked::Valid(component_type), Checked::Valid(type_)) =
+                    (input.component_type, input.type_)
+                {
+                    if component_type.0 != accessor::ComponentType::F32
+                        || type_ != accessor::Type::Scalar
+                    {
+                        report(&input_path, Error::Invalid);
+                    }
+                }
+                if input.min.is_none() {
+                    report(&|| input_path().field("min"), Error::Missing);
+                }
+                if input.max.is_none() {
+                    report(&|| input_path().field("max"), Error::Missing);
+                }
+            }
+        }
         for (index, channel) in self.channels.iter().enumerate() {
-            if channel.sampler.value() as usize >= self.samplers.len() {
+            if channel.sampler.value() >= self.samplers.len() {
                 let path = || path().field("channels").index(index).field("sampler");
                 report(&path, Error::IndexOutOfBounds);
             }
+            channel.target.validate(
+                root,
+                || path().field("channels").index(index).field("target"),
+                report,
+            );
         }
     }
 }
 
-impl Default for Interpolation {
-    fn default() -> Self {
-        Interpolation::Linear
-    }
-}
-
 impl<'de> de::Deserialize<'de> for Checked<Interpolation> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -1,5 +1,5 @@
-use crate::validation::Checked;
-use crate::{extensions, Extras, Index};
+use crate::validation::{Checked, Error, Validate};
+use crate::{extensions, Extras, Index, Path, Root};
 use gltf_derive::Validate;
 use serde::{de, ser};
 use serde_derive::{Deserialize, Serialize};
@@ -73,7 +73,7 @@ pub struct Buffer {
 ///
 /// <https://github.com/KhronosGroup/glTF/tree/master/specification/2.0#reference-bufferview>
 ///
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct View {
     /// The parent `Buffer`.
     pub buffer: Index<Buffer>,
@@ -116,6 +116,46 @@ pub struct View {
     pub extras: Extras,
 }
 
+impl Validate for View {
+    fn validate<P, R>(&self, root: &Root, path: P, report: &mut R)
+    where
+        P: Fn() -> Path,
+        R: FnMut(&dyn Fn() -> Path, Error),
+    {
+        // Generated part
+        self.buffer
+            .validate(root, || path().field("buffer"), report);
+        self.byte_length
+            .validate(root, || path().field("byteLength"), report);
+        self.byte_offset
+            .validate(root, || path().field("byteOffset"), report);
+        self.byte_stride
+            .validate(root, || path().field("byteStride"), report);
+        self.target
+            .validate(root, || path().field("target"), report);
+        self.extensions
+            .validate(root, || path().field("extensions"), report);
+        self.extras
+            .validate(root, || path().field("extras"), report);
+
+        // Custom part
+        // spec: `byteStride` **must** be a multiple of four from 4 to 252.
+        if let Some(stride) = self.byte_stride {
+            if !(4..=252).contains(&stride) || stride % 4 != 0 {
+                report(&|| path().field("byteStride"), Error::Invalid);
+            }
+        }
+
+        // spec: the view **must** fit within its buffer.
+        if let Some(buffer) = root.get(self.buffer) {
+            let end = self.byte_offset.unwrap_or(0) as u64 + self.byte_length as u64;
+            if end > buffer.byte_length as u64 {
+                report(&|| path().field("byteLength"), Error::Invalid);
+            }
+        }
+    }
+}
+
 impl<'de> de::Deserialize<'de> for Checked<Target> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -1,5 +1,6 @@
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// Contains data structures for sparse storage.
 pub mod sparse {
@@ -7,18 +8,34 @@ pub mod sparse {
 
     /// Indices of those attributes that deviate from their initialization value.
     #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-    pub struct Indices {}
+    pub struct Indices {
+        /// Extension objects not recognized by this library.
+        #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+        pub others: Map<String, Value>,
+    }
 
     /// Sparse storage of attributes that deviate from their initialization value.
     #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-    pub struct Sparse {}
+    pub struct Sparse {
+        /// Extension objects not recognized by this library.
+        #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+        pub others: Map<String, Value>,
+    }
 
     /// Array of size `count * number_of_components` storing the displaced
     /// accessor attributes pointed by `accessor::sparse::Indices`.
     #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-    pub struct Values {}
+    pub struct Values {
+        /// Extension objects not recognized by this library.
+        #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+        pub others: Map<String, Value>,
+    }
 }
 
 /// A typed view into a buffer view.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Accessor {}
+pub struct Accessor {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
@@ -1,18 +1,35 @@
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// A keyframe animation.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
-pub struct Animation {}
+pub struct Animation {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
 
 /// Targets an animation's sampler at a node's property.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
-pub struct Channel {}
+pub struct Channel {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
 
 /// The index of the node and TRS property that an animation channel targets.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Target {}
+pub struct Target {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
 
 /// Defines a keyframe graph but not its target.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Sampler {}
+pub struct Sampler {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
@@ -1,6 +1,11 @@
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// Metadata about the glTF asset.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Asset {}
+pub struct Asset {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
@@ -1,10 +1,19 @@
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// A buffer points to binary data representing geometry, animations, or skins.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Buffer {}
+pub struct Buffer {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
 
 /// A view into a buffer generally representing a subset of the buffer.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct View {}
+pub struct View {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
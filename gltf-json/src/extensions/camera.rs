@@ -1,17 +1,30 @@
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// A camera's projection.
 ///
 /// A node can reference a camera to apply a transform to place the camera in the
 /// scene.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Camera {}
+pub struct Camera {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
 
 /// Values for an orthographic camera.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Orthographic {}
+pub struct Orthographic {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
 
 /// Values for a perspective camera.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Perspective {}
+pub struct Perspective {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
@@ -13,6 +13,7 @@ use crate::texture;
 use crate::{validation::Validate, Extras};
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// The material appearance of a primitive.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
@@ -64,12 +65,20 @@ pub struct Material {
         skip_serializing_if = "Option::is_none"
     )]
     pub ior: Option<Ior>,
+
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
 }
 
 /// A set of parameter values that are used to define the metallic-roughness
 /// material model from Physically-Based Rendering (PBR) methodology.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct PbrMetallicRoughness {}
+pub struct PbrMetallicRoughness {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
 
 /// A set of parameter values that are used to define the specular-glossiness
 /// material model from Physically-Based Rendering (PBR) methodology.
@@ -124,11 +133,19 @@ pub struct PbrSpecularGlossiness {
 
 /// Defines the normal texture of a material.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct NormalTexture {}
+pub struct NormalTexture {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
 
 /// Defines the occlusion texture of a material.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct OcclusionTexture {}
+pub struct OcclusionTexture {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
 
 /// The diffuse factor of a material.
 #[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
@@ -143,7 +160,19 @@ impl Default for PbrDiffuseFactor {
 }
 
 #[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
-impl Validate for PbrDiffuseFactor {}
+impl Validate for PbrDiffuseFactor {
+    fn validate<P, R>(&self, _: &crate::Root, path: P, report: &mut R)
+    where
+        P: Fn() -> crate::Path,
+        R: FnMut(&dyn Fn() -> crate::Path, crate::validation::Error),
+    {
+        for (i, &component) in self.0.iter().enumerate() {
+            if !(0.0..=1.0).contains(&component) {
+                report(&|| path().index(i), crate::validation::Error::Invalid);
+            }
+        }
+    }
+}
 
 /// The specular factor of a material.
 #[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
@@ -158,7 +187,19 @@ impl Default for PbrSpecularFactor {
 }
 
 #[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
-impl Validate for PbrSpecularFactor {}
+impl Validate for PbrSpecularFactor {
+    fn validate<P, R>(&self, _: &crate::Root, path: P, report: &mut R)
+    where
+        P: Fn() -> crate::Path,
+        R: FnMut(&dyn Fn() -> crate::Path, crate::validation::Error),
+    {
+        for (i, &component) in self.0.iter().enumerate() {
+            if !(0.0..=1.0).contains(&component) {
+                report(&|| path().index(i), crate::validation::Error::Invalid);
+            }
+        }
+    }
+}
 
 /// Empty struct that should be present for primitives which should not be shaded with the PBR shading model.
 #[cfg(feature = "KHR_materials_unlit")]
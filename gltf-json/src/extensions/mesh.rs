@@ -1,16 +1,34 @@
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+#[cfg(feature = "KHR_draco_mesh_compression")]
+use {
+    crate::{buffer, mesh::Semantic, validation::Checked, Index},
+    std::collections::HashMap,
+};
 
 /// A set of primitives to be rendered.
 ///
 /// A node can contain one or more meshes and its transform places the meshes in
 /// the scene.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Mesh {}
+pub struct Mesh {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
 
 /// Geometry to be rendered with the given material.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 pub struct Primitive {
+    #[cfg(feature = "KHR_draco_mesh_compression")]
+    #[serde(
+        default,
+        rename = "KHR_draco_mesh_compression",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub khr_draco_mesh_compression: Option<KhrDracoMeshCompression>,
+
     #[cfg(feature = "KHR_materials_variants")]
     #[serde(
         default,
@@ -18,6 +36,10 @@ pub struct Primitive {
         skip_serializing_if = "Option::is_none"
     )]
     pub khr_materials_variants: Option<KhrMaterialsVariants>,
+
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
 }
 
 #[cfg(feature = "KHR_materials_variants")]
@@ -32,3 +54,17 @@ pub struct Mapping {
     pub material: u32,
     pub variants: Vec<u32>,
 }
+
+/// Geometry compressed with the Draco library, replacing the vertex and index
+/// data of the primitive.
+#[cfg(feature = "KHR_draco_mesh_compression")]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+pub struct KhrDracoMeshCompression {
+    /// The buffer view containing the compressed data.
+    #[serde(rename = "bufferView")]
+    pub buffer_view: Index<buffer::View>,
+
+    /// Maps attribute semantic names to their unique ids in the compressed
+    /// data.
+    pub attributes: HashMap<Checked<Semantic>, u32>,
+}
@@ -1,3 +1,7 @@
+use crate::validation::Error;
+use crate::Path;
+use serde_json::{Map, Value};
+
 /// Contains `Accessor` and other related data structures.
 pub mod accessor;
 
@@ -38,6 +42,8 @@ pub use self::root::Root;
 
 /// Names of glTF 2.0 extensions enabled by the user.
 pub const ENABLED_EXTENSIONS: &[&str] = &[
+    #[cfg(feature = "KHR_draco_mesh_compression")]
+    "KHR_draco_mesh_compression",
     #[cfg(feature = "KHR_lights_punctual")]
     "KHR_lights_punctual",
     #[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
@@ -54,6 +60,7 @@ pub const ENABLED_EXTENSIONS: &[&str] = &[
 
 /// Names of glTF 2.0 extensions supported by the library.
 pub const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "KHR_draco_mesh_compression",
     "KHR_lights_punctual",
     "KHR_materials_pbrSpecularGlossiness",
     "KHR_materials_unlit",
@@ -61,3 +68,253 @@ pub const SUPPORTED_EXTENSIONS: &[&str] = &[
     "KHR_materials_transmission",
     "KHR_materials_ior",
 ];
+
+/// The object types on which each known extension may appear, named as in the
+/// glTF schema.
+const PLACEMENT: &[(&str, &[&str])] = &[
+    ("KHR_draco_mesh_compression", &["mesh.primitive"]),
+    ("KHR_lights_punctual", &["glTF", "node"]),
+    ("KHR_materials_ior", &["material"]),
+    ("KHR_materials_pbrSpecularGlossiness", &["material"]),
+    ("KHR_materials_specular", &["material"]),
+    ("KHR_materials_transmission", &["material"]),
+    ("KHR_materials_unlit", &["material"]),
+    ("KHR_materials_variants", &["glTF", "mesh.primitive"]),
+    ("KHR_materials_volume", &["material"]),
+    (
+        "KHR_texture_transform",
+        &[
+            "textureInfo",
+            "material.normalTextureInfo",
+            "material.occlusionTextureInfo",
+        ],
+    ),
+];
+
+/// Reports the unrecognized extension objects of `others` that belong to a
+/// known extension but not to the given object type.
+fn check_placement<R>(
+    others: &Map<String, Value>,
+    object: &str,
+    path: &dyn Fn() -> Path,
+    report: &mut R,
+) where
+    R: FnMut(&dyn Fn() -> Path, Error),
+{
+    for name in others.keys() {
+        let allowed = PLACEMENT
+            .iter()
+            .find(|(extension, _)| extension == name)
+            .map(|(_, objects)| objects);
+        if allowed.is_some_and(|objects| !objects.contains(&object)) {
+            report(&|| path().field(name), Error::Invalid);
+        }
+    }
+}
+
+/// Reports known extensions appearing on object types where their
+/// specifications do not allow them.
+///
+/// Such extension objects are not recognized during deserialization, and are
+/// found in the `others` maps of the extension containers.
+pub(crate) fn validate_placement<P, R>(root: &crate::Root, path: P, report: &mut R)
+where
+    P: Fn() -> Path,
+    R: FnMut(&dyn Fn() -> Path, Error),
+{
+    macro_rules! check {
+        ($object:expr, $type_:expr, $path:expr) => {
+            if let Some(extensions) = $object.extensions.as_ref() {
+                check_placement(
+                    &extensions.others,
+                    $type_,
+                    &|| $path.field("extensions"),
+                    report,
+                );
+            }
+        };
+    }
+
+    macro_rules! check_info {
+        ($info:expr, $path:expr) => {
+            if let Some(info) = $info.as_ref() {
+                check!(info, "textureInfo", $path);
+            }
+        };
+    }
+
+    check!(root, "glTF", path());
+    check!(root.asset, "asset", path().field("asset"));
+
+    for (i, accessor) in root.accessors.iter().enumerate() {
+        let path = || path().field("accessors").index(i);
+        check!(accessor, "accessor", path());
+        if let Some(sparse) = accessor.sparse.as_ref() {
+            let path = || path().field("sparse");
+            check!(sparse, "accessor.sparse", path());
+            check!(
+                sparse.indices,
+                "accessor.sparse.indices",
+                path().field("indices")
+            );
+            check!(
+                sparse.values,
+                "accessor.sparse.values",
+                path().field("values")
+            );
+        }
+    }
+
+    for (i, animation) in root.animations.iter().enumerate() {
+        let path = || path().field("animations").index(i);
+        check!(animation, "animation", path());
+        for (j, channel) in animation.channels.iter().enumerate() {
+            let path = || path().field("channels").index(j);
+            check!(channel, "animation.channel", path());
+            check!(
+                channel.target,
+                "animation.channel.target",
+                path().field("target")
+            );
+        }
+        for (j, sampler) in animation.samplers.iter().enumerate() {
+            check!(
+                sampler,
+                "animation.sampler",
+                path().field("samplers").index(j)
+            );
+        }
+    }
+
+    for (i, buffer) in root.buffers.iter().enumerate() {
+        check!(buffer, "buffer", path().field("buffers").index(i));
+    }
+
+    for (i, view) in root.buffer_views.iter().enumerate() {
+        check!(view, "bufferView", path().field("bufferViews").index(i));
+    }
+
+    for (i, camera) in root.cameras.iter().enumerate() {
+        let path = || path().field("cameras").index(i);
+        check!(camera, "camera", path());
+        if let Some(orthographic) = camera.orthographic.as_ref() {
+            check!(
+                orthographic,
+                "camera.orthographic",
+                path().field("orthographic")
+            );
+        }
+        if let Some(perspective) = camera.perspective.as_ref() {
+            check!(
+                perspective,
+                "camera.perspective",
+                path().field("perspective")
+            );
+        }
+    }
+
+    for (i, image) in root.images.iter().enumerate() {
+        check!(image, "image", path().field("images").index(i));
+    }
+
+    for (i, material) in root.materials.iter().enumerate() {
+        let path = || path().field("materials").index(i);
+        check!(material, "material", path());
+        let pbr = &material.pbr_metallic_roughness;
+        let pbr_path = || path().field("pbrMetallicRoughness");
+        check!(pbr, "material.pbrMetallicRoughness", pbr_path());
+        check_info!(pbr.base_color_texture, pbr_path().field("baseColorTexture"));
+        check_info!(
+            pbr.metallic_roughness_texture,
+            pbr_path().field("metallicRoughnessTexture")
+        );
+        if let Some(normal) = material.normal_texture.as_ref() {
+            check!(
+                normal,
+                "material.normalTextureInfo",
+                path().field("normalTexture")
+            );
+        }
+        if let Some(occlusion) = material.occlusion_texture.as_ref() {
+            check!(
+                occlusion,
+                "material.occlusionTextureInfo",
+                path().field("occlusionTexture")
+            );
+        }
+        check_info!(material.emissive_texture, path().field("emissiveTexture"));
+
+        #[allow(unused_variables)]
+        if let Some(extensions) = material.extensions.as_ref() {
+            let path = || path().field("extensions");
+            #[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
+            if let Some(spec_gloss) = extensions.pbr_specular_glossiness.as_ref() {
+                let path = || path().field("KHR_materials_pbrSpecularGlossiness");
+                check_info!(spec_gloss.diffuse_texture, path().field("diffuseTexture"));
+                check_info!(
+                    spec_gloss.specular_glossiness_texture,
+                    path().field("specularGlossinessTexture")
+                );
+            }
+            #[cfg(feature = "KHR_materials_transmission")]
+            if let Some(transmission) = extensions.transmission.as_ref() {
+                check_info!(
+                    transmission.transmission_texture,
+                    path()
+                        .field("KHR_materials_transmission")
+                        .field("transmissionTexture")
+                );
+            }
+            #[cfg(feature = "KHR_materials_volume")]
+            if let Some(volume) = extensions.volume.as_ref() {
+                check_info!(
+                    volume.thickness_texture,
+                    path()
+                        .field("KHR_materials_volume")
+                        .field("thicknessTexture")
+                );
+            }
+            #[cfg(feature = "KHR_materials_specular")]
+            if let Some(specular) = extensions.specular.as_ref() {
+                let path = || path().field("KHR_materials_specular");
+                check_info!(specular.specular_texture, path().field("specularTexture"));
+                check_info!(
+                    specular.specular_color_texture,
+                    path().field("specularColorTexture")
+                );
+            }
+        }
+    }
+
+    for (i, mesh) in root.meshes.iter().enumerate() {
+        let path = || path().field("meshes").index(i);
+        check!(mesh, "mesh", path());
+        for (j, primitive) in mesh.primitives.iter().enumerate() {
+            check!(
+                primitive,
+                "mesh.primitive",
+                path().field("primitives").index(j)
+            );
+        }
+    }
+
+    for (i, node) in root.nodes.iter().enumerate() {
+        check!(node, "node", path().field("nodes").index(i));
+    }
+
+    for (i, sampler) in root.samplers.iter().enumerate() {
+        check!(sampler, "sampler", path().field("samplers").index(i));
+    }
+
+    for (i, scene) in root.scenes.iter().enumerate() {
+        check!(scene, "scene", path().field("scenes").index(i));
+    }
+
+    for (i, skin) in root.skins.iter().enumerate() {
+        check!(skin, "skin", path().field("skins").index(i));
+    }
+
+    for (i, texture) in root.textures.iter().enumerate() {
+        check!(texture, "texture", path().field("textures").index(i));
+    }
+}
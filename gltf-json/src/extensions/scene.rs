@@ -1,5 +1,6 @@
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// A node in the node hierarchy.  When the node contains `skin`, all
 /// `mesh.primitives` must contain `JOINTS_0` and `WEIGHTS_0` attributes.
@@ -20,6 +21,10 @@ pub struct Node {
         skip_serializing_if = "Option::is_none"
     )]
     pub khr_lights_punctual: Option<khr_lights_punctual::KhrLightsPunctual>,
+
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
 }
 
 #[cfg(feature = "KHR_lights_punctual")]
@@ -121,6 +126,21 @@ pub mod khr_lights_punctual {
                 }
             }
 
+            for (i, &component) in self.color.iter().enumerate() {
+                if !(0.0..=1.0).contains(&component) {
+                    report(&|| path().field("color").index(i), Error::Invalid);
+                }
+            }
+            if !(0.0..).contains(&self.intensity) {
+                report(&|| path().field("intensity"), Error::Invalid);
+            }
+            if let Some(range) = self.range {
+                if range.is_nan() || range <= 0.0 {
+                    report(&|| path().field("range"), Error::Invalid);
+                }
+            }
+
+            self.spot.validate(root, || path().field("spot"), report);
             self.type_.validate(root, || path().field("type"), report);
             self.extensions
                 .validate(root, || path().field("extensions"), report);
@@ -138,7 +158,7 @@ pub mod khr_lights_punctual {
     }
 
     /// Spot light parameters.
-    #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct Spot {
         /// Angle in radians from centre of spotlight where falloff begins.
@@ -150,6 +170,24 @@ pub mod khr_lights_punctual {
         pub outer_cone_angle: f32,
     }
 
+    impl Validate for Spot {
+        fn validate<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where
+            P: Fn() -> Path,
+            R: FnMut(&dyn Fn() -> Path, Error),
+        {
+            // The cone angles must satisfy 0 <= inner < outer <= PI / 2.
+            if !(self.inner_cone_angle >= 0.0 && self.inner_cone_angle < self.outer_cone_angle) {
+                report(&|| path().field("innerConeAngle"), Error::Invalid);
+            }
+            if !(self.outer_cone_angle > 0.0
+                && self.outer_cone_angle <= std::f32::consts::FRAC_PI_2)
+            {
+                report(&|| path().field("outerConeAngle"), Error::Invalid);
+            }
+        }
+    }
+
     fn outer_cone_angle_default() -> f32 {
         std::f32::consts::FRAC_PI_4
     }
@@ -223,4 +261,8 @@ pub mod khr_materials_variants {
 
 /// The root `Node`s of a scene.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
-pub struct Scene {}
+pub struct Scene {
+    /// Extension objects not recognized by this library.
+    #[serde(default, flatten, skip_serializing_if = "Map::is_empty")]
+    pub others: Map<String, Value>,
+}
@@ -0,0 +1,239 @@
+use serde_json::ser::{CompactFormatter, Formatter, PrettyFormatter};
+use std::io;
+
+/// How floating point numbers are written.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum FloatFormat {
+    /// The shortest decimal that reads back as the same number, such as
+    /// `0.1` for the `f32` nearest to 0.1.
+    #[default]
+    Shortest,
+
+    /// The number rounded to the given number of decimal places, with
+    /// trailing zeros removed, trading precision for size.
+    ///
+    /// Accessor bounds are rounded outwards, `min` down and `max` up, so that
+    /// they still contain every element.
+    Decimals(usize),
+}
+
+/// Options for writing JSON with `Root::to_writer_with` and friends.
+///
+/// # Examples
+///
+/// ```
+/// use gltf_json::serialize::{FloatFormat, Options};
+///
+/// let root = gltf_json::Root::from_str(r#"{
+///     "asset": {"version": "2.0"},
+///     "nodes": [{"translation": [0.1, 0.3333333, -2.0]}]
+/// }"#).unwrap();
+/// let options = Options {
+///     float_format: FloatFormat::Decimals(3),
+///     ..Default::default()
+/// };
+/// let json = root.to_string_with(&options).unwrap();
+/// assert!(json.contains(r#""translation":[0.1,0.333,-2.0]"#));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Options {
+    /// Whether to indent the JSON over multiple lines.
+    pub pretty: bool,
+
+    /// How floating point numbers, such as transforms, factors, and accessor
+    /// bounds, are written.
+    pub float_format: FloatFormat,
+}
+
+/// The direction in which a number is rounded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Rounding {
+    Nearest,
+    Down,
+    Up,
+}
+
+/// Writes a number rounded to the given number of decimal places, keeping at
+/// least one so that it still reads as a float.
+fn write_decimals<W>(
+    writer: &mut W,
+    value: f64,
+    decimals: usize,
+    rounding: Rounding,
+) -> io::Result<()>
+where
+    W: ?Sized + io::Write,
+{
+    let scale = 10f64.powi(decimals.min(i32::MAX as usize) as i32);
+    let scaled = value * scale;
+    let value = match rounding {
+        _ if !scaled.is_finite() => value,
+        Rounding::Nearest => value,
+        Rounding::Down => scaled.floor() / scale,
+        Rounding::Up => scaled.ceil() / scale,
+    };
+    let mut text = format!("{:.*}", decimals, value);
+    if text.contains('.') {
+        let trimmed = text.trim_end_matches('0').len();
+        text.truncate(trimmed);
+    } else {
+        text.push('.');
+    }
+    if text.ends_with('.') {
+        text.push('0');
+    }
+    if text.parse::<f64>() == Ok(0.0) {
+        // Avoid writing `-0.0` for small negative numbers.
+        text = "0.0".to_string();
+    }
+    writer.write_all(text.as_bytes())
+}
+
+/// Either a compact or a pretty formatter, writing floats as configured.
+pub(crate) struct OptionsFormatter<'a> {
+    compact: CompactFormatter,
+    pretty: Option<PrettyFormatter<'a>>,
+    float_format: FloatFormat,
+
+    /// The key of the current member of each object being written.
+    keys: Vec<String>,
+
+    /// Whether an object key is being written.
+    in_key: bool,
+}
+
+impl<'a> OptionsFormatter<'a> {
+    pub(crate) fn new(options: &Options) -> Self {
+        Self {
+            compact: CompactFormatter,
+            pretty: if options.pretty {
+                Some(PrettyFormatter::new())
+            } else {
+                None
+            },
+            float_format: options.float_format,
+            keys: Vec::new(),
+            in_key: false,
+        }
+    }
+
+    /// Returns how numbers in the current member are rounded.
+    fn rounding(&self) -> Rounding {
+        match self.keys.last().map(String::as_str) {
+            Some("min") => Rounding::Down,
+            Some("max") => Rounding::Up,
+            _ => Rounding::Nearest,
+        }
+    }
+}
+
+/// Forwards formatter methods to the pretty formatter if there is one.
+macro_rules! forward {
+    ($($name:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $name<W>(&mut self, writer: &mut W $(, $arg: $ty)*) -> io::Result<()>
+            where
+                W: ?Sized + io::Write,
+            {
+                match self.pretty {
+                    Some(ref mut pretty) => pretty.$name(writer $(, $arg)*),
+                    None => self.compact.$name(writer $(, $arg)*),
+                }
+            }
+        )*
+    };
+}
+
+impl<'a> Formatter for OptionsFormatter<'a> {
+    forward! {
+        begin_array();
+        end_array();
+        begin_array_value(first: bool);
+        end_array_value();
+        begin_object_value();
+        end_object_value();
+    }
+
+    fn begin_object<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.keys.push(String::new());
+        match self.pretty {
+            Some(ref mut pretty) => pretty.begin_object(writer),
+            None => self.compact.begin_object(writer),
+        }
+    }
+
+    fn end_object<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.keys.pop();
+        match self.pretty {
+            Some(ref mut pretty) => pretty.end_object(writer),
+            None => self.compact.end_object(writer),
+        }
+    }
+
+    fn begin_object_key<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.in_key = true;
+        if let Some(key) = self.keys.last_mut() {
+            key.clear();
+        }
+        match self.pretty {
+            Some(ref mut pretty) => pretty.begin_object_key(writer, first),
+            None => self.compact.begin_object_key(writer, first),
+        }
+    }
+
+    fn end_object_key<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.in_key = false;
+        match self.pretty {
+            Some(ref mut pretty) => pretty.end_object_key(writer),
+            None => self.compact.end_object_key(writer),
+        }
+    }
+
+    fn write_string_fragment<W>(&mut self, writer: &mut W, fragment: &str) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        if self.in_key {
+            if let Some(key) = self.keys.last_mut() {
+                key.push_str(fragment);
+            }
+        }
+        self.compact.write_string_fragment(writer, fragment)
+    }
+
+    fn write_f32<W>(&mut self, writer: &mut W, value: f32) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        match self.float_format {
+            FloatFormat::Shortest => self.compact.write_f32(writer, value),
+            FloatFormat::Decimals(decimals) => {
+                write_decimals(writer, value as f64, decimals, self.rounding())
+            }
+        }
+    }
+
+    fn write_f64<W>(&mut self, writer: &mut W, value: f64) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        match self.float_format {
+            FloatFormat::Shortest => self.compact.write_f64(writer, value),
+            FloatFormat::Decimals(decimals) => {
+                write_decimals(writer, value, decimals, self.rounding())
+            }
+        }
+    }
+}
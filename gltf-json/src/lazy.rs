@@ -0,0 +1,218 @@
+use crate::{Asset, Root};
+use serde::de;
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// A document whose top-level members are parsed on demand.
+///
+/// Creating a `Lazy` only checks that the input is a JSON object and finds
+/// where each member starts and ends, borrowing from the input rather than
+/// copying it. Members are deserialized when asked for, as `Root` types or
+/// any other type, including types that borrow strings from the input. This
+/// suits tools that inspect a few properties of large documents.
+///
+/// # Examples
+///
+/// ```
+/// use gltf_json::lazy::Lazy;
+/// use serde_derive::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Named<'a> {
+///     #[serde(borrow)]
+///     name: Option<&'a str>,
+/// }
+///
+/// # fn run() -> Result<(), gltf_json::Error> {
+/// let json = r#"{
+///     "asset": {"version": "2.0"},
+///     "nodes": [{"name": "root", "children": [1]}, {"name": "leaf"}]
+/// }"#;
+/// let lazy = Lazy::from_str(json)?;
+/// assert_eq!(lazy.asset()?.version, "2.0");
+/// assert_eq!(lazy.len("nodes")?, 2);
+/// let leaf: Option<Named> = lazy.element("nodes", 1)?;
+/// assert_eq!(leaf.unwrap().name, Some("leaf"));
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Lazy<'a> {
+    /// The whole document.
+    slice: &'a [u8],
+
+    /// The top-level members, by name.
+    members: BTreeMap<Cow<'a, str>, Member<'a>>,
+}
+
+/// A top-level member of a document.
+#[derive(Clone, Debug)]
+struct Member<'a> {
+    /// The JSON text of the member.
+    value: &'a RawValue,
+
+    /// The byte ranges of the elements within the JSON text of the member,
+    /// once it has been split as an array.
+    elements: OnceLock<Vec<Range<usize>>>,
+}
+
+/// The name of a member, borrowed from the input unless it contains escapes.
+#[derive(Eq, Ord, PartialEq, PartialOrd)]
+struct Name<'a>(Cow<'a, str>);
+
+impl<'de> de::Deserialize<'de> for Name<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Name<'de>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "member name")
+            }
+
+            fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E> {
+                Ok(Name(Cow::Borrowed(value)))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(Name(Cow::Owned(value.to_string())))
+            }
+
+            fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+                Ok(Name(Cow::Owned(value)))
+            }
+        }
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+impl<'a> Lazy<'a> {
+    /// Finds the top-level members of a document.
+    pub fn from_slice(slice: &'a [u8]) -> Result<Self, crate::Error> {
+        let members: BTreeMap<Name, &RawValue> = serde_json::from_slice(slice)?;
+        Ok(Self {
+            slice,
+            members: members
+                .into_iter()
+                .map(|(name, value)| {
+                    let member = Member {
+                        value,
+                        elements: OnceLock::new(),
+                    };
+                    (name.0, member)
+                })
+                .collect(),
+        })
+    }
+
+    /// Finds the top-level members of a document.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(str_: &'a str) -> Result<Self, crate::Error> {
+        Self::from_slice(str_.as_bytes())
+    }
+
+    /// Returns the names of the top-level members, in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.members.keys().map(|name| name.as_ref())
+    }
+
+    /// Returns the unparsed JSON text of a top-level member, such as
+    /// `"nodes"`, if present.
+    pub fn raw(&self, name: &str) -> Option<&'a str> {
+        self.members.get(name).map(|member| member.value.get())
+    }
+
+    /// Deserializes a top-level member, such as `"nodes"` into a
+    /// `Vec<json::Node>`, returning `None` if it is absent.
+    pub fn get<T: de::Deserialize<'a>>(&self, name: &str) -> Result<Option<T>, crate::Error> {
+        self.members
+            .get(name)
+            .map(|member| serde_json::from_str(member.value.get()))
+            .transpose()
+    }
+
+    /// Returns the number of elements of a top-level array, such as the
+    /// number of nodes for `"nodes"`.
+    ///
+    /// Returns zero if the member is absent. See `elements` for how the
+    /// array is split.
+    pub fn len(&self, name: &str) -> Result<usize, crate::Error> {
+        Ok(self.split(name)?.len())
+    }
+
+    /// Deserializes one element of a top-level array, returning `None` if
+    /// the member is absent or has no element at `index`.
+    pub fn element<T: de::Deserialize<'a>>(
+        &self,
+        name: &str,
+        index: usize,
+    ) -> Result<Option<T>, crate::Error> {
+        self.elements(name)?
+            .nth(index)
+            .map(serde_json::from_str)
+            .transpose()
+    }
+
+    /// Deserializes the `asset` member, which every document must have.
+    pub fn asset(&self) -> Result<Asset, crate::Error> {
+        match self.get("asset")? {
+            Some(asset) => Ok(asset),
+            None => Err(de::Error::missing_field("asset")),
+        }
+    }
+
+    /// Deserializes the whole document.
+    ///
+    /// The result is not validated.
+    pub fn to_root(&self) -> Result<Root, crate::Error> {
+        serde_json::from_slice(self.slice)
+    }
+
+    /// Returns the unparsed JSON text of the elements of a top-level array,
+    /// or no elements if the member is absent.
+    ///
+    /// The array is split into its elements, without deserializing them, the
+    /// first time one of its elements is asked for, and the split is kept for
+    /// later calls, so that visiting every element takes linear time.
+    pub fn elements(
+        &self,
+        name: &str,
+    ) -> Result<impl ExactSizeIterator<Item = &'a str> + '_, crate::Error> {
+        let text = self.raw(name).unwrap_or("");
+        let elements = self.split(name)?;
+        Ok(elements.iter().map(move |range| &text[range.clone()]))
+    }
+
+    /// Returns the byte ranges of the elements of a top-level array within
+    /// its JSON text, splitting it if it has not been split yet.
+    fn split(&self, name: &str) -> Result<&[Range<usize>], crate::Error> {
+        let member = match self.members.get(name) {
+            Some(member) => member,
+            None => return Ok(&[]),
+        };
+        if let Some(elements) = member.elements.get() {
+            return Ok(elements);
+        }
+        let text = member.value.get();
+        let values: Vec<&RawValue> = serde_json::from_str(text)?;
+        let start = text.as_ptr() as usize;
+        let elements = values
+            .iter()
+            .map(|value| {
+                let offset = value.get().as_ptr() as usize - start;
+                offset..offset + value.get().len()
+            })
+            .collect();
+        Ok(member.elements.get_or_init(|| elements))
+    }
+}
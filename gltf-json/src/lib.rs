@@ -20,9 +20,15 @@ pub mod extensions;
 /// Contains `Extras`.
 pub mod extras;
 
+/// Options for writing JSON.
+mod format;
+
 /// Contains `Image` and other related data structures.
 pub mod image;
 
+/// Contains `Lazy`, for parsing the members of a document on demand.
+pub mod lazy;
+
 /// Contains `Material` and other related data structures.
 pub mod material;
 
@@ -74,6 +80,9 @@ pub use texture::Texture;
 
 #[doc(inline)]
 pub use self::extras::Extras;
+#[doc(inline)]
+pub use self::lazy::Lazy;
+
 #[doc(inline)]
 pub use self::path::Path;
 #[doc(inline)]
@@ -101,6 +110,7 @@ pub mod deserialize {
 /// so that one can serialize data structures other than `Root` without
 /// being bound to a specific version of `serde_json`.
 pub mod serialize {
+    pub use crate::format::{FloatFormat, Options};
     pub use serde_json::{
         to_string, to_string_pretty, to_value, to_vec, to_vec_pretty, to_writer, to_writer_pretty,
     };
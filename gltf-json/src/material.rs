@@ -1,5 +1,5 @@
-use crate::validation::{Checked, Validate};
-use crate::{extensions, texture, Extras, Index};
+use crate::validation::{Checked, Error, Validate};
+use crate::{extensions, texture, Extras, Index, Path, Root};
 use gltf_derive::Validate;
 use serde::{de, ser};
 use serde_derive::{Deserialize, Serialize};
@@ -9,9 +9,10 @@ use std::fmt;
 pub const VALID_ALPHA_MODES: &[&str] = &["OPAQUE", "MASK", "BLEND"];
 
 /// The alpha rendering mode of a material.
-#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
 pub enum AlphaMode {
     /// The alpha value is ignored and the rendered output is fully opaque.
+    #[default]
     Opaque = 1,
 
     /// The rendered output is either fully opaque or fully transparent depending on
@@ -174,7 +175,7 @@ pub struct PbrMetallicRoughness {
 }
 
 /// Defines the normal texture of a material.
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NormalTexture {
     /// The index of the texture.
     pub index: Index<texture::Texture>,
@@ -203,6 +204,28 @@ fn material_normal_texture_scale_default() -> f32 {
     1.0
 }
 
+impl Validate for NormalTexture {
+    fn validate<P, R>(&self, root: &Root, path: P, report: &mut R)
+    where
+        P: Fn() -> Path,
+        R: FnMut(&dyn Fn() -> Path, Error),
+    {
+        // Generated part
+        self.index.validate(root, || path().field("index"), report);
+        self.tex_coord
+            .validate(root, || path().field("texCoord"), report);
+        self.extensions
+            .validate(root, || path().field("extensions"), report);
+        self.extras
+            .validate(root, || path().field("extras"), report);
+
+        // Custom part
+        if !self.scale.is_finite() {
+            report(&|| path().field("scale"), Error::Invalid);
+        }
+    }
+}
+
 /// Defines the occlusion texture of a material.
 #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 pub struct OcclusionTexture {
@@ -251,12 +274,6 @@ impl Default for AlphaCutoff {
 
 impl Validate for AlphaCutoff {}
 
-impl Default for AlphaMode {
-    fn default() -> Self {
-        AlphaMode::Opaque
-    }
-}
-
 impl<'de> de::Deserialize<'de> for Checked<AlphaMode> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -288,7 +305,19 @@ impl<'de> de::Deserialize<'de> for Checked<AlphaMode> {
     }
 }
 
-impl Validate for EmissiveFactor {}
+impl Validate for EmissiveFactor {
+    fn validate<P, R>(&self, _: &Root, path: P, report: &mut R)
+    where
+        P: Fn() -> Path,
+        R: FnMut(&dyn Fn() -> Path, Error),
+    {
+        for (i, &component) in self.0.iter().enumerate() {
+            if !(0.0..=1.0).contains(&component) {
+                report(&|| path().index(i), Error::Invalid);
+            }
+        }
+    }
+}
 
 impl Default for PbrBaseColorFactor {
     fn default() -> Self {
@@ -296,7 +325,19 @@ impl Default for PbrBaseColorFactor {
     }
 }
 
-impl Validate for PbrBaseColorFactor {}
+impl Validate for PbrBaseColorFactor {
+    fn validate<P, R>(&self, _: &Root, path: P, report: &mut R)
+    where
+        P: Fn() -> Path,
+        R: FnMut(&dyn Fn() -> Path, Error),
+    {
+        for (i, &component) in self.0.iter().enumerate() {
+            if !(0.0..=1.0).contains(&component) {
+                report(&|| path().index(i), Error::Invalid);
+            }
+        }
+    }
+}
 
 impl Default for StrengthFactor {
     fn default() -> Self {
@@ -304,4 +345,14 @@ impl Default for StrengthFactor {
     }
 }
 
-impl Validate for StrengthFactor {}
+impl Validate for StrengthFactor {
+    fn validate<P, R>(&self, _: &Root, path: P, report: &mut R)
+    where
+        P: Fn() -> Path,
+        R: FnMut(&dyn Fn() -> Path, Error),
+    {
+        if !(0.0..=1.0).contains(&self.0) {
+            report(&path, Error::Invalid);
+        }
+    }
+}
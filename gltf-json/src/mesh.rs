@@ -43,7 +43,7 @@ pub const VALID_MODES: &[u32] = &[
 pub const VALID_MORPH_TARGETS: &[&str] = &["POSITION", "NORMAL", "TANGENT"];
 
 /// The type of primitives to render.
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Default)]
 pub enum Mode {
     /// Corresponds to `GL_POINTS`.
     Points = 1,
@@ -58,6 +58,7 @@ pub enum Mode {
     LineStrip,
 
     /// Corresponds to `GL_TRIANGLES`.
+    #[default]
     Triangles,
 
     /// Corresponds to `GL_TRIANGLE_STRIP`.
@@ -71,7 +72,7 @@ pub enum Mode {
 ///
 /// A node can contain one or more meshes and its transform places the meshes in
 /// the scene.
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Mesh {
     /// Extension specific data.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -130,6 +131,61 @@ pub struct Primitive {
     pub targets: Option<Vec<MorphTarget>>,
 }
 
+impl Validate for Mesh {
+    fn validate<P, R>(&self, root: &crate::Root, path: P, report: &mut R)
+    where
+        P: Fn() -> crate::Path,
+        R: FnMut(&dyn Fn() -> crate::Path, crate::validation::Error),
+    {
+        // Generated part
+        self.extensions
+            .validate(root, || path().field("extensions"), report);
+        self.extras
+            .validate(root, || path().field("extras"), report);
+        self.primitives
+            .validate(root, || path().field("primitives"), report);
+        self.weights
+            .validate(root, || path().field("weights"), report);
+
+        // Custom part
+        // spec: all primitives **must** have the same number of morph targets
+        // in the same order, and `weights` **must** have one weight per target.
+        if let Some(first) = self.primitives.first() {
+            let count = first.morph_target_count();
+            for (i, primitive) in self.primitives.iter().enumerate().skip(1) {
+                if primitive.morph_target_count() != count {
+                    report(
+                        &|| path().field("primitives").index(i).field("targets"),
+                        Error::Invalid,
+                    );
+                }
+            }
+            if let Some(ref weights) = self.weights {
+                if weights.len() != count {
+                    report(&|| path().field("weights"), Error::Invalid);
+                }
+            }
+        }
+    }
+}
+
+impl Mesh {
+    /// Returns the number of morph targets of the mesh, being that of its
+    /// first primitive.
+    pub fn morph_target_count(&self) -> usize {
+        self.primitives
+            .first()
+            .map_or(0, Primitive::morph_target_count)
+    }
+}
+
+impl Primitive {
+    /// Returns the number of morph targets of the primitive.
+    pub fn morph_target_count(&self) -> usize {
+        self.targets.as_ref().map_or(0, Vec::len)
+    }
+}
+
 fn is_primitive_mode_default(mode: &Checked<Mode>) -> bool {
     *mode == Checked::Valid(Mode::Triangles)
 }
@@ -182,6 +238,45 @@ impl Validate for Primitive {
         } else {
             report(position_path, Error::Missing);
         }
+
+        // spec: indices **must** be scalars of unsigned integers.
+        if let Some(indices) = self.indices.and_then(|index| root.get(index)) {
+            use accessor::ComponentType::{U16, U32, U8};
+            // Invalid types are reported by the accessor.
+            if let (Checked::Valid(component_type), Checked::Valid(type_)) =
+                (indices.component_type, indices.type_)
+            {
+                if !matches!(component_type.0, U8 | U16 | U32) || type_ != accessor::Type::Scalar {
+                    report(&|| path().field("indices"), Error::Invalid);
+                }
+            }
+        }
+
+        // spec: morph target accessors **must** have the same count as the
+        // `POSITION` accessor.
+        let vertex_count = self
+            .attributes
+            .get(&Checked::Valid(Semantic::Positions))
+            .and_then(|&index| root.get(index))
+            .map(|accessor| accessor.count);
+        if let (Some(vertex_count), Some(targets)) = (vertex_count, self.targets.as_ref()) {
+            for (i, target) in targets.iter().enumerate() {
+                let attributes = [
+                    ("POSITION", target.positions),
+                    ("NORMAL", target.normals),
+                    ("TANGENT", target.tangents),
+                ];
+                for (name, index) in attributes {
+                    let accessor = index.and_then(|index| root.get(index));
+                    if accessor.is_some_and(|accessor| accessor.count != vertex_count) {
+                        report(
+                            &|| path().field("targets").index(i).field(name),
+                            Error::Invalid,
+                        );
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -207,8 +302,8 @@ pub struct MorphTarget {
 /// Vertex attribute semantic name.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Semantic {
-    /// Extra attribute name.
-    #[cfg(feature = "extras")]
+    /// Application-specific attribute, such as `_BATCHID`, named without its
+    /// leading underscore.
     Extras(String),
 
     /// XYZ vertex positions.
@@ -234,12 +329,6 @@ pub enum Semantic {
     Weights(u32),
 }
 
-impl Default for Mode {
-    fn default() -> Mode {
-        Mode::Triangles
-    }
-}
-
 impl Mode {
     /// Returns the equivalent `GLenum`.
     pub fn as_gl_enum(self) -> u32 {
@@ -307,29 +396,25 @@ impl Semantic {
             "NORMAL" => Valid(Normals),
             "POSITION" => Valid(Positions),
             "TANGENT" => Valid(Tangents),
-            #[cfg(feature = "extras")]
             _ if s.starts_with('_') => Valid(Extras(s[1..].to_string())),
-            _ if s.starts_with("COLOR_") => match s["COLOR_".len()..].parse() {
-                Ok(set) => Valid(Colors(set)),
-                Err(_) => Invalid,
-            },
-            _ if s.starts_with("TEXCOORD_") => match s["TEXCOORD_".len()..].parse() {
-                Ok(set) => Valid(TexCoords(set)),
-                Err(_) => Invalid,
-            },
-            _ if s.starts_with("JOINTS_") => match s["JOINTS_".len()..].parse() {
-                Ok(set) => Valid(Joints(set)),
-                Err(_) => Invalid,
-            },
-            _ if s.starts_with("WEIGHTS_") => match s["WEIGHTS_".len()..].parse() {
-                Ok(set) => Valid(Weights(set)),
-                Err(_) => Invalid,
-            },
+            _ if s.starts_with("COLOR_") => set(&s["COLOR_".len()..], Colors),
+            _ if s.starts_with("TEXCOORD_") => set(&s["TEXCOORD_".len()..], TexCoords),
+            _ if s.starts_with("JOINTS_") => set(&s["JOINTS_".len()..], Joints),
+            _ if s.starts_with("WEIGHTS_") => set(&s["WEIGHTS_".len()..], Weights),
             _ => Invalid,
         }
     }
 }
 
+/// Parses the set index of a semantic name such as `TEXCOORD_12`, where any
+/// set is allowed.
+fn set(index: &str, semantic: fn(u32) -> Semantic) -> Checked<Semantic> {
+    match index.parse() {
+        Ok(set) => Checked::Valid(semantic(set)),
+        Err(_) => Checked::Invalid,
+    }
+}
+
 impl ser::Serialize for Semantic {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -339,28 +424,27 @@ impl ser::Serialize for Semantic {
     }
 }
 
-impl ToString for Semantic {
-    fn to_string(&self) -> String {
+impl fmt::Display for Semantic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Semantic::*;
         match *self {
-            Positions => "POSITION".into(),
-            Normals => "NORMAL".into(),
-            Tangents => "TANGENT".into(),
-            Colors(set) => format!("COLOR_{}", set),
-            TexCoords(set) => format!("TEXCOORD_{}", set),
-            Joints(set) => format!("JOINTS_{}", set),
-            Weights(set) => format!("WEIGHTS_{}", set),
-            #[cfg(feature = "extras")]
-            Extras(ref name) => format!("_{}", name),
+            Positions => write!(f, "POSITION"),
+            Normals => write!(f, "NORMAL"),
+            Tangents => write!(f, "TANGENT"),
+            Colors(set) => write!(f, "COLOR_{}", set),
+            TexCoords(set) => write!(f, "TEXCOORD_{}", set),
+            Joints(set) => write!(f, "JOINTS_{}", set),
+            Weights(set) => write!(f, "WEIGHTS_{}", set),
+            Extras(ref name) => write!(f, "_{}", name),
         }
     }
 }
 
-impl ToString for Checked<Semantic> {
-    fn to_string(&self) -> String {
+impl fmt::Display for Checked<Semantic> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Checked::Valid(ref semantic) => semantic.to_string(),
-            Checked::Invalid => "<invalid semantic name>".into(),
+            Checked::Valid(ref semantic) => semantic.fmt(f),
+            Checked::Invalid => write!(f, "<invalid semantic name>"),
         }
     }
 }
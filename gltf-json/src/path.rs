@@ -1,7 +1,7 @@
 use std::fmt;
 
 /// An immutable JSON source path.
-#[derive(Default, Clone, Debug, PartialEq)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Path(pub String);
 
 impl Path {
@@ -2,10 +2,10 @@ use crate::buffer;
 use crate::extensions;
 use crate::texture;
 use crate::validation;
-use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
 use std::{self, fmt, io, marker};
 
+use crate::format::{Options, OptionsFormatter};
 use crate::path::Path;
 use crate::{
     Accessor, Animation, Asset, Buffer, Camera, Error, Extras, Image, Material, Mesh, Node, Scene,
@@ -19,11 +19,17 @@ pub trait Get<T> {
     fn get(&self, id: Index<T>) -> Option<&T>;
 }
 
+/// Helper trait for appending top-level objects.
+pub trait Push<T> {
+    /// Appends a value, returning its index.
+    fn push(&mut self, value: T) -> Index<T>;
+}
+
 /// Represents an offset into an array of type `T` owned by the root glTF object.
 pub struct Index<T>(u32, marker::PhantomData<*const T>);
 
 /// The root object of a glTF 2.0 asset.
-#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Root {
     /// An array of accessors.
     #[serde(default)]
@@ -117,6 +123,66 @@ pub struct Root {
     pub textures: Vec<Texture>,
 }
 
+impl Validate for Root {
+    fn validate<P, R>(&self, root: &Root, path: P, report: &mut R)
+    where
+        P: Fn() -> Path,
+        R: FnMut(&dyn Fn() -> Path, validation::Error),
+    {
+        // Generated part, except that accessors are validated knowing which
+        // of them are decoded from Draco compressed geometry.
+        #[cfg(feature = "KHR_draco_mesh_compression")]
+        let decoded = crate::accessor::draco_decoded(root);
+        for (index, accessor) in self.accessors.iter().enumerate() {
+            #[cfg(feature = "KHR_draco_mesh_compression")]
+            let decoded = accessor.buffer_view.is_none() && decoded.contains(&index);
+            #[cfg(not(feature = "KHR_draco_mesh_compression"))]
+            let decoded = false;
+            accessor.validate_decoded(
+                root,
+                decoded,
+                || path().field("accessors").index(index),
+                report,
+            );
+        }
+        self.animations
+            .validate(root, || path().field("animations"), report);
+        self.asset.validate(root, || path().field("asset"), report);
+        self.buffers
+            .validate(root, || path().field("buffers"), report);
+        self.buffer_views
+            .validate(root, || path().field("bufferViews"), report);
+        self.scene.validate(root, || path().field("scene"), report);
+        self.extensions
+            .validate(root, || path().field("extensions"), report);
+        self.extras
+            .validate(root, || path().field("extras"), report);
+        self.extensions_used
+            .validate(root, || path().field("extensionsUsed"), report);
+        self.extensions_required
+            .validate(root, || path().field("extensionsRequired"), report);
+        self.cameras
+            .validate(root, || path().field("cameras"), report);
+        self.images
+            .validate(root, || path().field("images"), report);
+        self.materials
+            .validate(root, || path().field("materials"), report);
+        self.meshes
+            .validate(root, || path().field("meshes"), report);
+        self.nodes.validate(root, || path().field("nodes"), report);
+        self.samplers
+            .validate(root, || path().field("samplers"), report);
+        self.scenes
+            .validate(root, || path().field("scenes"), report);
+        self.skins.validate(root, || path().field("skins"), report);
+        self.textures
+            .validate(root, || path().field("textures"), report);
+
+        // Custom part
+        extensions::validate_placement(self, path, report);
+    }
+}
+
 impl Root {
     /// Returns a single item from the root object.
     pub fn get<T>(&self, index: Index<T>) -> Option<&T>
@@ -126,6 +192,14 @@ impl Root {
         (self as &dyn Get<T>).get(index)
     }
 
+    /// Appends an item to the root object, returning its index.
+    pub fn push<T>(&mut self, value: T) -> Index<T>
+    where
+        Self: Push<T>,
+    {
+        (self as &mut dyn Push<T>).push(value)
+    }
+
     /// Deserialize from a JSON string slice.
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(str_: &str) -> Result<Self, Error> {
@@ -137,6 +211,21 @@ impl Root {
         serde_json::from_slice(slice)
     }
 
+    /// Deserialize from a JSON byte slice, rejecting members of objects that
+    /// are not recognized instead of ignoring them.
+    ///
+    /// Use `validation::Report::with_source` to be warned about these members
+    /// while still accepting the document.
+    pub fn from_slice_strict(slice: &[u8]) -> Result<Self, Error> {
+        use serde::de::Error as _;
+        let root: Self = serde_json::from_slice(slice)?;
+        let source: Value = serde_json::from_slice(slice)?;
+        match validation::unknown_fields(&source).first() {
+            Some(path) => Err(Error::custom(format!("unknown field `{}`", path))),
+            None => Ok(root),
+        }
+    }
+
     /// Deserialize from a stream of JSON.
     pub fn from_reader<R>(reader: R) -> Result<Self, Error>
     where
@@ -185,6 +274,30 @@ impl Root {
     {
         serde_json::to_writer_pretty(writer, self)
     }
+
+    /// Serialize as a JSON byte writer, formatted according to `options`.
+    pub fn to_writer_with<W>(&self, writer: W, options: &Options) -> Result<(), Error>
+    where
+        W: io::Write,
+    {
+        let formatter = OptionsFormatter::new(options);
+        let mut serializer = serde_json::Serializer::with_formatter(writer, formatter);
+        serde::Serialize::serialize(self, &mut serializer)
+    }
+
+    /// Serialize as a JSON byte vector, formatted according to `options`.
+    pub fn to_vec_with(&self, options: &Options) -> Result<Vec<u8>, Error> {
+        let mut vec = Vec::new();
+        self.to_writer_with(&mut vec, options)?;
+        Ok(vec)
+    }
+
+    /// Serialize as a `String` of JSON, formatted according to `options`.
+    pub fn to_string_with(&self, options: &Options) -> Result<String, Error> {
+        let vec = self.to_vec_with(options)?;
+        // The serializer only writes valid UTF-8.
+        Ok(String::from_utf8(vec).expect("serialized JSON is not UTF-8"))
+    }
 }
 
 impl<T> Index<T> {
@@ -277,6 +390,13 @@ macro_rules! impl_get {
                 self.$field.get(index.value())
             }
         }
+
+        impl Push<$ty> for Root {
+            fn push(&mut self, value: $ty) -> Index<$ty> {
+                self.$field.push(value);
+                Index::new(self.$field.len() as u32 - 1)
+            }
+        }
     };
 }
 
@@ -1,5 +1,5 @@
-use crate::validation::Validate;
-use crate::{camera, extensions, mesh, scene, skin, Extras, Index};
+use crate::validation::{Error, Validate};
+use crate::{camera, extensions, mesh, scene, skin, Extras, Index, Path, Root};
 use gltf_derive::Validate;
 use serde_derive::{Deserialize, Serialize};
 
@@ -13,7 +13,7 @@ use serde_derive::{Deserialize, Serialize};
 /// identity. When a node is targeted for animation (referenced by an
 /// animation.channel.target), only TRS properties may be present; `matrix` will not
 /// be present.
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Node {
     /// The index of the camera referenced by this node.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -77,6 +77,51 @@ pub struct Node {
     pub weights: Option<Vec<f32>>,
 }
 
+impl Validate for Node {
+    fn validate<P, R>(&self, root: &Root, path: P, report: &mut R)
+    where
+        P: Fn() -> Path,
+        R: FnMut(&dyn Fn() -> Path, Error),
+    {
+        // Generated part
+        self.camera
+            .validate(root, || path().field("camera"), report);
+        self.children
+            .validate(root, || path().field("children"), report);
+        self.extensions
+            .validate(root, || path().field("extensions"), report);
+        self.extras
+            .validate(root, || path().field("extras"), report);
+        self.matrix
+            .validate(root, || path().field("matrix"), report);
+        self.mesh.validate(root, || path().field("mesh"), report);
+        self.rotation
+            .validate(root, || path().field("rotation"), report);
+        self.scale.validate(root, || path().field("scale"), report);
+        self.translation
+            .validate(root, || path().field("translation"), report);
+        self.skin.validate(root, || path().field("skin"), report);
+        self.weights
+            .validate(root, || path().field("weights"), report);
+
+        // Custom part
+        // spec: when `weights` is defined, `mesh` **must** also be defined and
+        // have as many morph targets as there are weights.
+        if let Some(ref weights) = self.weights {
+            match self.mesh {
+                Some(index) => {
+                    if let Some(mesh) = root.get(index) {
+                        if weights.len() != mesh.morph_target_count() {
+                            report(&|| path().field("weights"), Error::Invalid);
+                        }
+                    }
+                }
+                None => report(&|| path().field("mesh"), Error::Missing),
+            }
+        }
+    }
+}
+
 /// The root `Node`s of a scene.
 #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 pub struct Scene {
@@ -105,7 +105,7 @@ impl MinFilter {
 }
 
 /// Texture co-ordinate wrapping mode.
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Default)]
 pub enum WrappingMode {
     /// Corresponds to `GL_CLAMP_TO_EDGE`.
     ClampToEdge = 1,
@@ -114,6 +114,7 @@ pub enum WrappingMode {
     MirroredRepeat,
 
     /// Corresponds to `GL_REPEAT`.
+    #[default]
     Repeat,
 }
 
@@ -323,12 +324,6 @@ impl ser::Serialize for MagFilter {
     }
 }
 
-impl Default for WrappingMode {
-    fn default() -> Self {
-        WrappingMode::Repeat
-    }
-}
-
 impl ser::Serialize for WrappingMode {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
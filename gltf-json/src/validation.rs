@@ -166,6 +166,283 @@ impl std::fmt::Display for Error {
     }
 }
 
+/// How serious an issue found by validation is.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The asset is invalid and may not be loaded safely.
+    Error,
+
+    /// The asset is valid but may not load as intended.
+    Warning,
+
+    /// The asset is valid but could be improved.
+    Info,
+}
+
+/// Specifies what kind of issue was found by a `Report`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// A violation of the invariants checked by `Validate`.
+    Error(Error),
+
+    /// `extensionsRequired` lists an extension that is not enabled.
+    UnsupportedRequiredExtension(String),
+
+    /// `extensionsUsed` lists an extension that is not enabled, whose data is
+    /// kept but not interpreted.
+    UnsupportedExtension(String),
+
+    /// An object uses an extension that `extensionsUsed` does not list.
+    UndeclaredExtension(String),
+
+    /// `extensionsUsed` lists an extension that no object uses.
+    UnusedExtension(String),
+
+    /// A member of an object is not recognized by this library and is ignored.
+    UnknownField,
+}
+
+impl Kind {
+    /// Returns how serious this kind of issue is.
+    pub fn severity(&self) -> Severity {
+        match *self {
+            Kind::Error(_) | Kind::UnsupportedRequiredExtension(_) => Severity::Error,
+            Kind::UnsupportedExtension(_) | Kind::UndeclaredExtension(_) | Kind::UnknownField => {
+                Severity::Warning
+            }
+            Kind::UnusedExtension(_) => Severity::Info,
+        }
+    }
+}
+
+/// An issue found by a `Report`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Issue {
+    /// The JSON path of the offending value.
+    pub path: Path,
+
+    /// What is wrong with the value.
+    pub kind: Kind,
+}
+
+impl Issue {
+    /// Returns how serious the issue is.
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+}
+
+/// Every issue found in a document, from hard errors that `Validate` reports
+/// to hints about how the document could be improved.
+///
+/// # Examples
+///
+/// ```
+/// use gltf_json::validation::{Report, Severity};
+///
+/// let root = gltf_json::Root::from_str(r#"{
+///     "asset": {"version": "2.0"},
+///     "extensionsUsed": ["VENDOR_unused"]
+/// }"#).unwrap();
+/// let report = Report::new(&root);
+/// assert!(report.is_valid());
+/// assert_eq!(report.count(Severity::Warning), 1);
+/// assert_eq!(report.count(Severity::Info), 1);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// The issues found, errors first.
+    issues: Vec<Issue>,
+}
+
+impl Report {
+    /// Validates `root`, also warning about the members of `source`, the JSON
+    /// `root` was parsed from, that are unknown and were ignored.
+    pub fn with_source(root: &Root, source: &serde_json::Value) -> Self {
+        let mut report = Report::new(root);
+        for path in unknown_fields(source) {
+            report.issues.push(Issue {
+                path,
+                kind: Kind::UnknownField,
+            });
+        }
+        report.issues.sort_by_key(Issue::severity);
+        report
+    }
+
+    /// Validates `root`, collecting every issue found.
+    pub fn new(root: &Root) -> Self {
+        let mut issues = Vec::new();
+        root.validate(root, Path::new, &mut |path, error| {
+            issues.push(Issue {
+                path: path(),
+                kind: Kind::Error(error),
+            })
+        });
+
+        let enabled = crate::extensions::ENABLED_EXTENSIONS;
+        for (i, name) in root.extensions_required.iter().enumerate() {
+            if !enabled.contains(&name.as_str()) {
+                issues.push(Issue {
+                    path: Path::new().field("extensionsRequired").index(i),
+                    kind: Kind::UnsupportedRequiredExtension(name.clone()),
+                });
+            }
+        }
+        for (i, name) in root.extensions_used.iter().enumerate() {
+            if !enabled.contains(&name.as_str()) {
+                issues.push(Issue {
+                    path: Path::new().field("extensionsUsed").index(i),
+                    kind: Kind::UnsupportedExtension(name.clone()),
+                });
+            }
+        }
+
+        // Invalid values cannot be serialized, and have been reported above.
+        if let Ok(value) = serde_json::to_value(root) {
+            let mut used = Vec::new();
+            find_extensions(&value, Path::new(), &mut used);
+            for (path, name) in &used {
+                if !root.extensions_used.contains(name) {
+                    issues.push(Issue {
+                        path: path.clone(),
+                        kind: Kind::UndeclaredExtension(name.clone()),
+                    });
+                }
+            }
+            for (i, name) in root.extensions_used.iter().enumerate() {
+                if !used.iter().any(|(_, used)| used == name) {
+                    issues.push(Issue {
+                        path: Path::new().field("extensionsUsed").index(i),
+                        kind: Kind::UnusedExtension(name.clone()),
+                    });
+                }
+            }
+        }
+
+        issues.sort_by_key(Issue::severity);
+        Report { issues }
+    }
+
+    /// Returns every issue found, ordered by severity.
+    pub fn issues(&self) -> &[Issue] {
+        &self.issues
+    }
+
+    /// Returns the number of issues of the given severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity() == severity)
+            .count()
+    }
+
+    /// Returns `true` if no errors were found.
+    pub fn is_valid(&self) -> bool {
+        self.count(Severity::Error) == 0
+    }
+}
+
+/// Returns the paths of the members of `source` that are not recognized, being
+/// those ignored when `source` is parsed as a `Root`.
+///
+/// Members within `extras`, which may hold anything, are not reported, nor
+/// are `name` members when the `names` feature is disabled.
+pub(crate) fn unknown_fields(source: &serde_json::Value) -> Vec<Path> {
+    /// Converts the path of an ignored member, returning `None` for members
+    /// that are not reported.
+    fn convert(path: &serde_ignored::Path) -> Option<Path> {
+        use serde_ignored::Path as Ignored;
+        match *path {
+            Ignored::Root => Some(Path::new()),
+            Ignored::Seq { parent, index } => convert(parent).map(|path| path.index(index)),
+            Ignored::Map { parent, ref key } => match key.as_str() {
+                "extras" => None,
+                #[cfg(not(feature = "names"))]
+                "name" => None,
+                key => convert(parent).map(|path| path.field(key)),
+            },
+            Ignored::Some { parent }
+            | Ignored::NewtypeStruct { parent }
+            | Ignored::NewtypeVariant { parent } => convert(parent),
+        }
+    }
+
+    let mut unknown = Vec::new();
+    // Invalid values are reported by validation.
+    let _: Result<Root, _> = serde_ignored::deserialize(source, |path| {
+        unknown.extend(convert(&path));
+    });
+    unknown
+}
+
+/// Collects the path and name of every extension object within `value`.
+fn find_extensions(value: &serde_json::Value, path: Path, used: &mut Vec<(Path, String)>) {
+    match *value {
+        serde_json::Value::Object(ref map) => {
+            for (key, value) in map {
+                let path = path.field(key);
+                if key == "extensions" {
+                    if let serde_json::Value::Object(ref extensions) = *value {
+                        for (name, extension) in extensions {
+                            let path = path.field(name);
+                            find_extensions(extension, path.clone(), used);
+                            used.push((path, name.clone()));
+                        }
+                    }
+                } else if key != "extras" {
+                    find_extensions(value, path, used);
+                }
+            }
+        }
+        serde_json::Value::Array(ref values) => {
+            for (index, value) in values.iter().enumerate() {
+                find_extensions(value, path.index(index), used);
+            }
+        }
+        _ => {}
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Info => write!(f, "info"),
+        }
+    }
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Kind::Error(ref error) => error.fmt(f),
+            Kind::UnsupportedRequiredExtension(ref name) => {
+                write!(f, "required extension `{}` is not supported", name)
+            }
+            Kind::UnsupportedExtension(ref name) => {
+                write!(
+                    f,
+                    "extension `{}` is not supported and will be ignored",
+                    name
+                )
+            }
+            Kind::UndeclaredExtension(ref name) => {
+                write!(f, "extension `{}` is not listed in `extensionsUsed`", name)
+            }
+            Kind::UnusedExtension(ref name) => write!(f, "extension `{}` is not used", name),
+            Kind::UnknownField => write!(f, "unknown field is ignored"),
+        }
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.severity(), self.path, self.kind)
+    }
+}
+
 // These types are assumed to be always valid.
 impl Validate for bool {}
 impl Validate for u32 {}
@@ -177,3 +454,4 @@ impl Validate for [f32; 16] {}
 impl Validate for () {}
 impl Validate for String {}
 impl Validate for serde_json::Value {}
+impl Validate for serde_json::Map<String, serde_json::Value> {}
@@ -0,0 +1,77 @@
+use gltf_json::serialize::{FloatFormat, Options};
+
+fn root() -> gltf_json::Root {
+    gltf_json::Root::from_str(
+        r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{"translation": [0.1, 0.33333334, -0.00001], "scale": [2.0, 2.0, 2.0]}]
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_shortest_float_format_round_trips() {
+    let root = root();
+    let json = root.to_string_with(&Options::default()).unwrap();
+    assert_eq!(json, root.to_string().unwrap());
+    assert!(
+        json.contains(r#""translation":[0.1,0.33333334,-0.00001]"#),
+        "{}",
+        json
+    );
+}
+
+#[test]
+fn test_decimals_float_format() {
+    let options = Options {
+        float_format: FloatFormat::Decimals(3),
+        ..Default::default()
+    };
+    let json = root().to_string_with(&options).unwrap();
+    assert!(
+        json.contains(r#""translation":[0.1,0.333,0.0]"#),
+        "{}",
+        json
+    );
+    assert!(json.contains(r#""scale":[2.0,2.0,2.0]"#), "{}", json);
+
+    let options = Options {
+        pretty: true,
+        float_format: FloatFormat::Decimals(0),
+    };
+    let json = root().to_string_with(&options).unwrap();
+    assert!(json.starts_with("{\n  \""), "{}", json);
+    assert!(json.contains("0.0,\n"), "{}", json);
+    assert!(gltf_json::Root::from_str(&json).is_ok());
+}
+
+#[test]
+fn test_decimals_float_format_rounds_bounds_outwards() {
+    let root = gltf_json::Root::from_str(
+        r#"{
+            "asset": {"version": "2.0"},
+            "accessors": [{
+                "componentType": 5126,
+                "count": 2,
+                "type": "VEC3",
+                "min": [0.1236, -0.1234, 1.0],
+                "max": [0.1234, -0.1236, 1.0]
+            }],
+            "nodes": [{"translation": [0.1236, 0.1234, 0.0]}]
+        }"#,
+    )
+    .unwrap();
+    let options = Options {
+        float_format: FloatFormat::Decimals(3),
+        ..Default::default()
+    };
+    let json = root.to_string_with(&options).unwrap();
+    assert!(json.contains(r#""min":[0.123,-0.124,1.0]"#), "{}", json);
+    assert!(json.contains(r#""max":[0.124,-0.123,1.0]"#), "{}", json);
+    assert!(
+        json.contains(r#""translation":[0.124,0.123,0.0]"#),
+        "{}",
+        json
+    );
+}
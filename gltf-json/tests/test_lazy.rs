@@ -0,0 +1,54 @@
+use gltf_json::{Lazy, Node, Root};
+use std::borrow::Cow;
+
+#[test]
+fn test_lazy() {
+    let json = std::fs::read("../examples/Box.gltf").unwrap();
+    let lazy = Lazy::from_slice(&json).unwrap();
+    let root: Root = serde_json::from_slice(&json).unwrap();
+
+    assert_eq!(lazy.asset().unwrap().version, "2.0");
+    assert_eq!(lazy.len("accessors").unwrap(), root.accessors.len());
+    assert_eq!(lazy.len("animations").unwrap(), 0);
+    assert!(lazy.names().any(|name| name == "meshes"));
+
+    let node: Node = lazy.element("nodes", 1).unwrap().unwrap();
+    assert_eq!(
+        node.mesh.map(|mesh| mesh.value()),
+        root.nodes[1].mesh.map(|mesh| mesh.value())
+    );
+    assert!(lazy.element::<Node>("nodes", 99).unwrap().is_none());
+    let nodes: Vec<Node> = lazy.get("nodes").unwrap().unwrap();
+    assert_eq!(nodes.len(), root.nodes.len());
+
+    // The array is split once and kept.
+    let elements: Vec<&str> = lazy.elements("nodes").unwrap().collect();
+    assert_eq!(elements.len(), root.nodes.len());
+    assert_eq!(
+        lazy.elements("nodes").unwrap().collect::<Vec<_>>(),
+        elements
+    );
+    let node: Node = serde_json::from_str(elements[1]).unwrap();
+    assert_eq!(node.mesh.map(|mesh| mesh.value()), Some(0));
+    assert_eq!(lazy.elements("animations").unwrap().len(), 0);
+    assert!(lazy.elements("asset").is_err());
+
+    let root = lazy.to_root().unwrap();
+    assert_eq!(root.meshes.len(), 1);
+}
+
+#[test]
+fn test_lazy_escaped_names() {
+    let json = r#"{"asset": {"version": "2.0"}, "ext\u0072as": {}}"#;
+    let lazy = Lazy::from_str(json).unwrap();
+    assert_eq!(lazy.names().collect::<Vec<_>>(), ["asset", "extras"]);
+    assert_eq!(lazy.raw("extras"), Some("{}"));
+
+    let uri: Option<Cow<str>> = Lazy::from_str(r#"{"uri": "abc"}"#)
+        .unwrap()
+        .get("uri")
+        .unwrap();
+    assert_eq!(uri.as_deref(), Some("abc"));
+    assert!(Lazy::from_str("[]").is_err());
+    assert!(Lazy::from_str("{}").unwrap().asset().is_err());
+}
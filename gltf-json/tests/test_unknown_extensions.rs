@@ -0,0 +1,39 @@
+use gltf_json::Root;
+use serde_json::{json, Value};
+
+#[test]
+fn test_unknown_extensions_roundtrip() {
+    let value = json!({
+        "asset": {"version": "2.0"},
+        "extensionsUsed": ["VENDOR_node", "VENDOR_root", "VENDOR_texture"],
+        "extensions": {"VENDOR_root": {"tool": "modeller", "revision": 3}},
+        "images": [{"uri": "image.png"}],
+        "materials": [{
+            "pbrMetallicRoughness": {
+                "baseColorTexture": {
+                    "index": 0,
+                    "extensions": {"VENDOR_texture": {"layer": 1}}
+                }
+            }
+        }],
+        "nodes": [{"extensions": {"VENDOR_node": [1, 2, 3]}}],
+        "textures": [{"source": 0}]
+    });
+    let root: Root = serde_json::from_value(value).unwrap();
+    let written: Value = serde_json::from_str(&root.to_string().unwrap()).unwrap();
+
+    assert_eq!(
+        written["extensions"]["VENDOR_root"],
+        json!({"tool": "modeller", "revision": 3})
+    );
+    assert_eq!(
+        written["nodes"][0]["extensions"]["VENDOR_node"],
+        json!([1, 2, 3])
+    );
+    assert_eq!(
+        written["materials"][0]["pbrMetallicRoughness"]["baseColorTexture"]["extensions"],
+        json!({"VENDOR_texture": {"layer": 1}})
+    );
+    // Objects without extensions are still written without them.
+    assert!(written["textures"][0].get("extensions").is_none());
+}
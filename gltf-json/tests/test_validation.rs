@@ -1,6 +1,6 @@
 use std::{fs, io};
 
-use gltf_json::validation::{Error, Validate};
+use gltf_json::validation::{Error, Issue, Kind, Report, Severity, Validate};
 use gltf_json::Path;
 
 fn import_json(filename: &str) -> gltf_json::Root {
@@ -44,3 +44,325 @@ fn test_non_sparse_accessor_without_buffer_view_validate() {
         [(Path("accessors[0].bufferView".into()), Error::Missing)]
     );
 }
+
+#[cfg(feature = "KHR_draco_mesh_compression")]
+#[test]
+fn test_draco_accessor_without_buffer_view_validate() {
+    let json = import_json("tests/draco_accessor_without_buffer_view.gltf");
+    let mut errs = vec![];
+    json.validate(&json, gltf_json::Path::new, &mut |path, err| {
+        errs.push((path(), err))
+    });
+    // Only the accessor of the uncompressed primitive needs a buffer view.
+    assert_eq!(
+        errs,
+        [(Path("accessors[1].bufferView".into()), Error::Missing)]
+    );
+
+    let mut errs = vec![];
+    json.accessors[0].validate(&json, gltf_json::Path::new, &mut |path, err| {
+        errs.push((path(), err))
+    });
+    assert!(errs.is_empty());
+}
+
+#[test]
+fn test_material_factors_out_of_range_validate() {
+    let json = import_json("tests/material_factors_out_of_range.gltf");
+    let mut errs = vec![];
+    json.validate(&json, gltf_json::Path::new, &mut |path, err| {
+        errs.push((path(), err))
+    });
+    assert_eq!(
+        errs,
+        [
+            (
+                Path("materials[0].pbrMetallicRoughness.baseColorFactor[1]".into()),
+                Error::Invalid
+            ),
+            (
+                Path("materials[0].pbrMetallicRoughness.metallicFactor".into()),
+                Error::Invalid
+            ),
+            (
+                Path("materials[0].occlusionTexture.strength".into()),
+                Error::Invalid
+            ),
+            (
+                Path("materials[0].emissiveFactor[2]".into()),
+                Error::Invalid
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_extension_misplaced_validate() {
+    let json = import_json("tests/extension_misplaced.gltf");
+    let mut errs = vec![];
+    json.validate(&json, gltf_json::Path::new, &mut |path, err| {
+        errs.push((path(), err))
+    });
+    assert_eq!(
+        errs,
+        [
+            (
+                Path("materials[0].extensions.KHR_lights_punctual".into()),
+                Error::Invalid
+            ),
+            (
+                Path("nodes[0].extensions.KHR_materials_unlit".into()),
+                Error::Invalid
+            ),
+            (
+                Path("textures[0].extensions.KHR_texture_transform".into()),
+                Error::Invalid
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_enums_out_of_spec_validate() {
+    // Unknown enum values are kept as `Checked::Invalid` rather than failing
+    // to parse, so that they can be tolerated or reported.
+    let json = import_json("tests/enums_out_of_spec.gltf");
+    let mut errs = vec![];
+    json.validate(&json, gltf_json::Path::new, &mut |path, err| {
+        errs.push((path(), err))
+    });
+    let invalid = |path: &str| (Path(path.into()), Error::Invalid);
+    assert_eq!(
+        errs,
+        [
+            invalid("accessors[1].componentType"),
+            invalid("accessors[1].type"),
+            invalid("animations[0].samplers[0].interpolation"),
+            invalid("animations[0].channels[0].target.path"),
+            invalid("meshes[0].primitives[0].mode"),
+            invalid("samplers[0].magFilter"),
+            invalid("samplers[0].wrapS"),
+        ]
+    );
+    assert_eq!(
+        json.animations[0].channels[0].target.path,
+        gltf_json::validation::Checked::Invalid
+    );
+}
+
+#[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
+#[test]
+fn test_specular_glossiness_out_of_range_validate() {
+    let json = import_json("tests/specular_glossiness_out_of_range.gltf");
+    let mut errs = vec![];
+    json.validate(&json, gltf_json::Path::new, &mut |path, err| {
+        errs.push((path(), err))
+    });
+    let sg = "materials[0].extensions.KHR_materials_pbrSpecularGlossiness";
+    assert_eq!(
+        errs,
+        [
+            (Path(format!("{}.diffuseFactor[2]", sg)), Error::Invalid),
+            (
+                Path(format!("{}.diffuseTexture.index", sg)),
+                Error::IndexOutOfBounds
+            ),
+            (Path(format!("{}.specularFactor[0]", sg)), Error::Invalid),
+            (Path(format!("{}.glossinessFactor", sg)), Error::Invalid),
+        ]
+    );
+}
+
+#[test]
+fn test_accessor_bounds_invalid_validate() {
+    let json = import_json("tests/accessor_bounds_invalid.gltf");
+    let mut errs = vec![];
+    json.validate(&json, gltf_json::Path::new, &mut |path, err| {
+        errs.push((path(), err))
+    });
+    assert_eq!(
+        errs,
+        [
+            (Path("accessors[0].count".into()), Error::Invalid),
+            (Path("accessors[2].byteOffset".into()), Error::Invalid),
+            (Path("accessors[3].bufferView".into()), Error::Invalid),
+            (Path("accessors[5].count".into()), Error::Invalid),
+            (
+                Path("animations[0].samplers[0].input".into()),
+                Error::Invalid
+            ),
+            (
+                Path("animations[0].samplers[0].input.min".into()),
+                Error::Missing
+            ),
+            (
+                Path("animations[0].samplers[0].input.max".into()),
+                Error::Missing
+            ),
+            (Path("bufferViews[0].byteLength".into()), Error::Invalid),
+            (Path("bufferViews[1].byteStride".into()), Error::Invalid),
+            (
+                Path("meshes[0].primitives[0].indices".into()),
+                Error::Invalid
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_morph_targets_mismatched_validate() {
+    let json = import_json("tests/morph_targets_mismatched.gltf");
+    let mut errs = vec![];
+    json.validate(&json, gltf_json::Path::new, &mut |path, err| {
+        errs.push((path(), err))
+    });
+    assert_eq!(
+        errs,
+        [
+            (
+                Path("meshes[0].primitives[0].targets[1].NORMAL".into()),
+                Error::Invalid
+            ),
+            (
+                Path("meshes[0].primitives[1].targets".into()),
+                Error::Invalid
+            ),
+            (Path("meshes[0].weights".into()), Error::Invalid),
+            (Path("nodes[1].mesh".into()), Error::Missing),
+            (Path("nodes[2].weights".into()), Error::Invalid),
+        ]
+    );
+}
+
+#[cfg(feature = "KHR_lights_punctual")]
+#[test]
+fn test_lights_out_of_range_validate() {
+    let json = import_json("tests/lights_out_of_range.gltf");
+    let mut errs = vec![];
+    json.validate(&json, gltf_json::Path::new, &mut |path, err| {
+        errs.push((path(), err))
+    });
+    let lights = "extensions.KHR_lights_punctual.lights";
+    assert_eq!(
+        errs,
+        [
+            (Path(format!("{}[0].color[1]", lights)), Error::Invalid),
+            (Path(format!("{}[0].intensity", lights)), Error::Invalid),
+            (Path(format!("{}[1].range", lights)), Error::Invalid),
+            (
+                Path(format!("{}[2].spot.innerConeAngle", lights)),
+                Error::Invalid
+            ),
+            (
+                Path(format!("{}[3].spot.innerConeAngle", lights)),
+                Error::Invalid
+            ),
+            (
+                Path(format!("{}[3].spot.outerConeAngle", lights)),
+                Error::Invalid
+            ),
+            (Path(format!("{}[5].spot", lights)), Error::Missing),
+        ]
+    );
+}
+
+#[test]
+fn test_report() {
+    let json = import_json("tests/report.gltf");
+    let report = Report::new(&json);
+    let issue = |path: &str, kind| Issue {
+        path: Path(path.into()),
+        kind,
+    };
+    assert_eq!(
+        report.issues(),
+        [
+            issue("nodes[0].mesh", Kind::Error(Error::IndexOutOfBounds)),
+            issue(
+                "extensionsRequired[0]",
+                Kind::UnsupportedRequiredExtension("VENDOR_node".into())
+            ),
+            issue(
+                "extensionsUsed[0]",
+                Kind::UnsupportedExtension("VENDOR_unused".into())
+            ),
+            issue(
+                "extensionsUsed[1]",
+                Kind::UnsupportedExtension("VENDOR_node".into())
+            ),
+            issue(
+                "nodes[0].extensions.VENDOR_undeclared",
+                Kind::UndeclaredExtension("VENDOR_undeclared".into())
+            ),
+            issue(
+                "extensionsUsed[0]",
+                Kind::UnusedExtension("VENDOR_unused".into())
+            ),
+        ]
+    );
+    assert!(!report.is_valid());
+    assert_eq!(report.count(Severity::Error), 2);
+    assert_eq!(report.count(Severity::Warning), 3);
+    assert_eq!(report.count(Severity::Info), 1);
+    assert_eq!(
+        report.issues()[4].to_string(),
+        "warning: nodes[0].extensions.VENDOR_undeclared: \
+         extension `VENDOR_undeclared` is not listed in `extensionsUsed`"
+    );
+}
+
+#[test]
+fn test_unknown_fields() {
+    let data = fs::read("tests/unknown_fields.gltf").unwrap();
+    let root = gltf_json::Root::from_slice(&data).unwrap();
+    let source = serde_json::from_slice(&data).unwrap();
+    let report = Report::with_source(&root, &source);
+    let unknown: Vec<_> = report
+        .issues()
+        .iter()
+        .filter(|issue| issue.kind == Kind::UnknownField)
+        .map(|issue| issue.path.as_str())
+        .collect();
+    assert_eq!(
+        unknown,
+        [
+            "asset.vendor",
+            "materials[0].pbrMetallicRoughness.colour",
+            "nodes[0].translatoin",
+        ]
+    );
+
+    let err = gltf_json::Root::from_slice_strict(&data).unwrap_err();
+    assert_eq!(err.to_string(), "unknown field `asset.vendor`");
+    let minimal = br#"{"asset": {"version": "2.0"}, "nodes": [{"name": "a", "mesh": 0}]}"#;
+    assert!(gltf_json::Root::from_slice_strict(minimal).is_ok());
+
+    // Unknown members are reported whatever their value.
+    let flag = br#"{"asset": {"version": "2.0"}, "nodes": [{"vendorFlag": false}]}"#;
+    let err = gltf_json::Root::from_slice_strict(flag).unwrap_err();
+    assert_eq!(err.to_string(), "unknown field `nodes[0].vendorFlag`");
+}
+
+#[test]
+fn test_accessor_end_does_not_overflow() {
+    let json = gltf_json::Root::from_str(
+        r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 64}],
+            "bufferViews": [{"buffer": 0, "byteLength": 64}],
+            "accessors": [{
+                "bufferView": 0,
+                "byteOffset": 4294967292,
+                "componentType": 5126,
+                "count": 4294967295,
+                "type": "MAT4"
+            }]
+        }"#,
+    )
+    .unwrap();
+    let mut errs = vec![];
+    json.validate(&json, gltf_json::Path::new, &mut |path, err| {
+        errs.push((path(), err))
+    });
+    assert_eq!(errs, [(Path("accessors[0].count".into()), Error::Invalid)]);
+}
@@ -65,6 +65,11 @@ pub use json::accessor::Type as Dimensions;
 #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
 pub mod util;
 
+/// Writing accessor data.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod writer;
+
 /// Contains data structures for sparse storage.
 pub mod sparse;
 
@@ -72,6 +77,10 @@ pub mod sparse;
 #[doc(inline)]
 pub use self::util::{Item, Iter};
 
+#[cfg(feature = "utils")]
+#[doc(inline)]
+pub use self::writer::{AccessorWriter, WriteItem};
+
 /// A typed view into a buffer view.
 #[derive(Clone, Debug)]
 pub struct Accessor<'a> {
@@ -139,6 +148,18 @@ impl<'a> Accessor<'a> {
         &self.json.extras
     }
 
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new().field("accessors").index(self.index)
+        })
+    }
+
     /// Specifies if the attribute is a scalar, vector, or matrix.
     pub fn dimensions(&self) -> Dimensions {
         self.json.type_.unwrap()
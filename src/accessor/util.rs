@@ -2,15 +2,16 @@ use byteorder::{ByteOrder, LE};
 use std::marker::PhantomData;
 use std::{iter, mem};
 
-use crate::{accessor, buffer};
+use crate::accessor::{self, DataType, Dimensions};
+use crate::buffer;
 
 fn buffer_view_slice<'a, 's>(
     view: buffer::View<'a>,
     get_buffer_data: &dyn Fn(buffer::Buffer<'a>) -> Option<&'s [u8]>,
 ) -> Option<&'s [u8]> {
     let start = view.offset();
-    let end = start + view.length();
-    get_buffer_data(view.buffer()).map(|slice| &slice[start..end])
+    let end = start.checked_add(view.length())?;
+    get_buffer_data(view.buffer()).and_then(|slice| slice.get(start..end))
 }
 
 /// General iterator for an accessor.
@@ -102,6 +103,9 @@ pub struct SparseIter<'a, T: Item> {
 
     /// Iterator counter.
     counter: u32,
+
+    /// The number of items, or `None` to stop at the end of `base`.
+    count: Option<u32>,
 }
 
 impl<'a, T: Item> SparseIter<'a, T> {
@@ -118,6 +122,22 @@ impl<'a, T: Item> SparseIter<'a, T> {
             indices: indices.peekable(),
             values,
             counter: 0,
+            count: None,
+        }
+    }
+
+    /// Constructor for an accessor of `count` items.
+    ///
+    /// Unlike `new`, iteration ends after `count` items when `base` is `None`.
+    pub fn with_count(
+        base: Option<ItemIter<'a, T>>,
+        indices: SparseIndicesIter<'a>,
+        values: ItemIter<'a, T>,
+        count: u32,
+    ) -> Self {
+        SparseIter {
+            count: Some(count),
+            ..Self::new(base, indices, values)
         }
     }
 }
@@ -125,6 +145,9 @@ impl<'a, T: Item> SparseIter<'a, T> {
 impl<'a, T: Item> Iterator for SparseIter<'a, T> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
+        if self.count.is_some_and(|count| self.counter >= count) {
+            return None;
+        }
         let mut next_value = self
             .base
             .as_mut()
@@ -145,7 +168,13 @@ impl<'a, T: Item> Iterator for SparseIter<'a, T> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let hint = self.values.len() - self.counter as usize;
+        let base = self.base.as_ref().map(ItemIter::len);
+        let hint = match (self.count, base) {
+            (Some(count), Some(base)) => base.min((count - self.counter) as usize),
+            (Some(count), None) => (count - self.counter) as usize,
+            (None, Some(base)) => base,
+            (None, None) => usize::MAX,
+        };
         (hint, Some(hint))
     }
 }
@@ -156,6 +185,10 @@ impl<'a, T: Item> ExactSizeIterator for SparseIter<'a, T> {}
 ///
 /// [`Accessor`]: struct.Accessor.html
 pub trait Item {
+    /// The component type and dimensions of accessors holding items of this
+    /// type, or `None` if any accessor with elements of the same size will do.
+    const FORMAT: Option<(DataType, Dimensions)> = None;
+
     /// Create an object of this type from a byte slice.
     fn from_slice(slice: &[u8]) -> Self;
     /// Create an object of this type that represents a zero value.
@@ -173,6 +206,8 @@ pub struct ItemIter<'a, T: Item> {
 }
 
 impl Item for i8 {
+    const FORMAT: Option<(DataType, Dimensions)> = Some((DataType::I8, Dimensions::Scalar));
+
     fn from_slice(slice: &[u8]) -> Self {
         slice[0] as i8
     }
@@ -182,6 +217,8 @@ impl Item for i8 {
 }
 
 impl Item for i16 {
+    const FORMAT: Option<(DataType, Dimensions)> = Some((DataType::I16, Dimensions::Scalar));
+
     fn from_slice(slice: &[u8]) -> Self {
         LE::read_i16(slice)
     }
@@ -191,6 +228,8 @@ impl Item for i16 {
 }
 
 impl Item for u8 {
+    const FORMAT: Option<(DataType, Dimensions)> = Some((DataType::U8, Dimensions::Scalar));
+
     fn from_slice(slice: &[u8]) -> Self {
         slice[0]
     }
@@ -200,6 +239,8 @@ impl Item for u8 {
 }
 
 impl Item for u16 {
+    const FORMAT: Option<(DataType, Dimensions)> = Some((DataType::U16, Dimensions::Scalar));
+
     fn from_slice(slice: &[u8]) -> Self {
         LE::read_u16(slice)
     }
@@ -209,6 +250,8 @@ impl Item for u16 {
 }
 
 impl Item for u32 {
+    const FORMAT: Option<(DataType, Dimensions)> = Some((DataType::U32, Dimensions::Scalar));
+
     fn from_slice(slice: &[u8]) -> Self {
         LE::read_u32(slice)
     }
@@ -218,6 +261,8 @@ impl Item for u32 {
 }
 
 impl Item for f32 {
+    const FORMAT: Option<(DataType, Dimensions)> = Some((DataType::F32, Dimensions::Scalar));
+
     fn from_slice(slice: &[u8]) -> Self {
         LE::read_f32(slice)
     }
@@ -227,6 +272,12 @@ impl Item for f32 {
 }
 
 impl<T: Item + Copy> Item for [T; 2] {
+    const FORMAT: Option<(DataType, Dimensions)> = match T::FORMAT {
+        Some((data_type, Dimensions::Scalar)) => Some((data_type, Dimensions::Vec2)),
+        Some((data_type, Dimensions::Vec2)) => Some((data_type, Dimensions::Mat2)),
+        _ => None,
+    };
+
     fn from_slice(slice: &[u8]) -> Self {
         assert!(slice.len() >= 2 * mem::size_of::<T>());
         [
@@ -240,6 +291,12 @@ impl<T: Item + Copy> Item for [T; 2] {
 }
 
 impl<T: Item + Copy> Item for [T; 3] {
+    const FORMAT: Option<(DataType, Dimensions)> = match T::FORMAT {
+        Some((data_type, Dimensions::Scalar)) => Some((data_type, Dimensions::Vec3)),
+        Some((data_type, Dimensions::Vec3)) => Some((data_type, Dimensions::Mat3)),
+        _ => None,
+    };
+
     fn from_slice(slice: &[u8]) -> Self {
         assert!(slice.len() >= 3 * mem::size_of::<T>());
         [
@@ -254,6 +311,12 @@ impl<T: Item + Copy> Item for [T; 3] {
 }
 
 impl<T: Item + Copy> Item for [T; 4] {
+    const FORMAT: Option<(DataType, Dimensions)> = match T::FORMAT {
+        Some((data_type, Dimensions::Scalar)) => Some((data_type, Dimensions::Vec4)),
+        Some((data_type, Dimensions::Vec4)) => Some((data_type, Dimensions::Mat4)),
+        _ => None,
+    };
+
     fn from_slice(slice: &[u8]) -> Self {
         assert!(slice.len() >= 4 * mem::size_of::<T>());
         [
@@ -279,12 +342,41 @@ impl<'a, T: Item> ItemIter<'a, T> {
     }
 }
 
+/// Returns the bytes of `count` items of `size` bytes placed `stride` bytes
+/// apart from `start`, or `None` if they extend past the end of `slice`.
+fn items(slice: &[u8], start: usize, stride: usize, count: usize, size: usize) -> Option<&[u8]> {
+    let end = match count {
+        0 => start,
+        _ => stride
+            .checked_mul(count - 1)?
+            .checked_add(size)?
+            .checked_add(start)?,
+    };
+    slice.get(start..end)
+}
+
 impl<'a, 's, T: Item> Iter<'s, T> {
     /// Constructor.
+    ///
+    /// Returns `None` if buffer data is missing or too short for the accessor
+    /// and its buffer views, rather than panicking. `None` is also returned if
+    /// the size of the elements of the accessor, or its component type and
+    /// dimensions when `T::FORMAT` names them, do not match `T`, or if its
+    /// buffer view has a stride smaller than `T`.
     pub fn new<F>(accessor: super::Accessor<'a>, get_buffer_data: F) -> Option<Iter<'s, T>>
     where
         F: Clone + Fn(buffer::Buffer<'a>) -> Option<&'s [u8]>,
     {
+        let mut span = crate::trace::span!("read_accessor", accessor, count, bytes);
+        span.record("accessor", accessor.index());
+        span.record("count", accessor.count());
+        span.record("bytes", accessor.count() * accessor.size());
+        let format = (accessor.data_type(), accessor.dimensions());
+        if T::FORMAT.is_some_and(|expected| expected != format)
+            || accessor.size() != mem::size_of::<T>()
+        {
+            return None;
+        }
         let is_sparse = accessor.sparse().is_some();
         if is_sparse {
             let sparse = accessor.sparse();
@@ -294,13 +386,14 @@ impl<'a, 's, T: Item> Iter<'s, T> {
                 // Using `if let` here instead of map to preserve the early return behavior.
                 if let Some(view) = accessor.view() {
                     let stride = view.stride().unwrap_or(mem::size_of::<T>());
-                    let start = accessor.offset();
-                    let end = start + stride * (accessor.count() - 1) + mem::size_of::<T>();
-                    let subslice = if let Some(slice) = buffer_view_slice(view, &get_buffer_data) {
-                        &slice[start..end]
-                    } else {
-                        return None;
-                    };
+                    let slice = buffer_view_slice(view, &get_buffer_data)?;
+                    let subslice = items(
+                        slice,
+                        accessor.offset(),
+                        stride,
+                        accessor.count(),
+                        mem::size_of::<T>(),
+                    )?;
                     Some(ItemIter::new(subslice, stride))
                 } else {
                     None
@@ -311,13 +404,9 @@ impl<'a, 's, T: Item> Iter<'s, T> {
                 let view = indices.view();
                 let index_size = indices.index_type().size();
                 let stride = view.stride().unwrap_or(index_size);
-                let subslice = if let Some(slice) = buffer_view_slice(view, &get_buffer_data) {
-                    let start = indices.offset() as usize;
-                    let end = start + stride * (sparse_count - 1) + index_size;
-                    &slice[start..end]
-                } else {
-                    return None;
-                };
+                let slice = buffer_view_slice(view, &get_buffer_data)?;
+                let start = indices.offset() as usize;
+                let subslice = items(slice, start, stride, sparse_count, index_size)?;
                 match indices.index_type() {
                     accessor::sparse::IndexType::U8 => {
                         SparseIndicesIter::U8(ItemIter::new(subslice, stride))
@@ -333,36 +422,31 @@ impl<'a, 's, T: Item> Iter<'s, T> {
             let value_iter = {
                 let view = values.view();
                 let stride = view.stride().unwrap_or(mem::size_of::<T>());
-                let subslice = if let Some(slice) = buffer_view_slice(view, &get_buffer_data) {
-                    let start = values.offset() as usize;
-                    let end = start + stride * (sparse_count - 1) + mem::size_of::<T>();
-                    &slice[start..end]
-                } else {
-                    return None;
-                };
+                let slice = buffer_view_slice(view, &get_buffer_data)?;
+                let start = values.offset() as usize;
+                let subslice = items(slice, start, stride, sparse_count, mem::size_of::<T>())?;
                 ItemIter::new(subslice, stride)
             };
-            Some(Iter::Sparse(SparseIter::new(
-                base_iter, index_iter, value_iter,
+            Some(Iter::Sparse(SparseIter::with_count(
+                base_iter,
+                index_iter,
+                value_iter,
+                accessor.count() as u32,
             )))
         } else {
-            debug_assert_eq!(mem::size_of::<T>(), accessor.size());
-            debug_assert!(mem::size_of::<T>() > 0);
             if let Some(view) = accessor.view() {
                 let stride = view.stride().unwrap_or(mem::size_of::<T>());
-                debug_assert!(
-                    stride >= mem::size_of::<T>(),
-                    "Mismatch in stride, expected at least {} stride but found {}",
-                    mem::size_of::<T>(),
-                    stride
-                );
-                let start = accessor.offset();
-                let end = start + stride * (accessor.count() - 1) + mem::size_of::<T>();
-                let subslice = if let Some(slice) = buffer_view_slice(view, &get_buffer_data) {
-                    &slice[start..end]
-                } else {
+                if stride < mem::size_of::<T>() {
                     return None;
-                };
+                }
+                let slice = buffer_view_slice(view, &get_buffer_data)?;
+                let subslice = items(
+                    slice,
+                    accessor.offset(),
+                    stride,
+                    accessor.count(),
+                    mem::size_of::<T>(),
+                )?;
                 Some(Iter::Standard(ItemIter {
                     stride,
                     data: subslice,
@@ -431,3 +515,160 @@ impl<'a, T: Item> Iterator for ItemIter<'a, T> {
         (hint, Some(hint))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Document;
+
+    #[test]
+    fn strided_and_out_of_bounds() {
+        let document = Document::from_json_without_validation(
+            json::Root::from_str(
+                r#"{
+                    "asset": {"version": "2.0"},
+                    "buffers": [{"byteLength": 32}],
+                    "bufferViews": [
+                        {"buffer": 0, "byteLength": 32, "byteStride": 8},
+                        {"buffer": 0, "byteOffset": 16, "byteLength": 32}
+                    ],
+                    "accessors": [
+                        {"bufferView": 0, "componentType": 5123, "count": 4, "type": "VEC2"},
+                        {"bufferView": 0, "componentType": 5123, "count": 5, "type": "VEC2"},
+                        {"bufferView": 1, "componentType": 5123, "count": 1, "type": "SCALAR"},
+                        {"bufferView": 0, "componentType": 5123, "count": 0, "type": "VEC4"}
+                    ]
+                }"#,
+            )
+            .unwrap(),
+        );
+        let data: Vec<u8> = (0..32).collect();
+        let get = |_| Some(&data[..]);
+        let accessor = |index| document.accessors().nth(index).unwrap();
+
+        let items: Vec<[u16; 2]> = Iter::new(accessor(0), get).unwrap().collect();
+        assert_eq!(
+            items,
+            vec![
+                [0x0100, 0x0302],
+                [0x0908, 0x0b0a],
+                [0x1110, 0x1312],
+                [0x1918, 0x1b1a]
+            ]
+        );
+        // Accessors and buffer views extending past the data are rejected.
+        assert!(Iter::<[u16; 2]>::new(accessor(1), get).is_none());
+        assert!(Iter::<u16>::new(accessor(2), get).is_none());
+        assert_eq!(Iter::<[u16; 4]>::new(accessor(3), get).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn mismatched_items() {
+        let document = Document::from_json_without_validation(
+            json::Root::from_str(
+                r#"{
+                    "asset": {"version": "2.0"},
+                    "buffers": [{"byteLength": 32}],
+                    "bufferViews": [
+                        {"buffer": 0, "byteLength": 32},
+                        {"buffer": 0, "byteLength": 32, "byteStride": 4}
+                    ],
+                    "accessors": [
+                        {"bufferView": 0, "componentType": 5123, "count": 4, "type": "VEC4"},
+                        {"bufferView": 0, "componentType": 5126, "count": 2, "type": "MAT2"},
+                        {"bufferView": 1, "componentType": 5126, "count": 2, "type": "VEC2"}
+                    ]
+                }"#,
+            )
+            .unwrap(),
+        );
+        let data = [0; 32];
+        let get = |_| Some(&data[..]);
+        let accessor = |index| document.accessors().nth(index).unwrap();
+
+        // The same size, but a different component type or dimensions.
+        assert!(Iter::<[f32; 2]>::new(accessor(0), get).is_none());
+        assert!(Iter::<[i16; 4]>::new(accessor(0), get).is_none());
+        assert!(Iter::<[u16; 4]>::new(accessor(0), get).is_some());
+        assert!(Iter::<[f32; 4]>::new(accessor(1), get).is_none());
+        assert!(Iter::<[[f32; 2]; 2]>::new(accessor(1), get).is_some());
+        // A stride smaller than each item.
+        assert!(Iter::<[f32; 2]>::new(accessor(2), get).is_none());
+
+        // Items without a format are only matched by size.
+        #[derive(Clone, Copy)]
+        struct Packed(u64);
+        impl Item for Packed {
+            fn from_slice(slice: &[u8]) -> Self {
+                Packed(LE::read_u64(slice))
+            }
+            fn zero() -> Self {
+                Packed(0)
+            }
+        }
+        assert_eq!(<[Packed; 2]>::FORMAT, None);
+        assert_eq!(<[[f32; 4]; 2]>::FORMAT, None);
+        let mut packed = Iter::<Packed>::new(accessor(0), get).unwrap();
+        assert_eq!(packed.next().map(|item| item.0), Some(0));
+        assert!(Iter::<[[f32; 4]; 2]>::new(accessor(0), get).is_none());
+    }
+
+    #[test]
+    fn sparse_substitution() {
+        let document = Document::from_json_without_validation(
+            json::Root::from_str(
+                r#"{
+                    "asset": {"version": "2.0"},
+                    "buffers": [{"byteLength": 24}],
+                    "bufferViews": [
+                        {"buffer": 0, "byteLength": 16},
+                        {"buffer": 0, "byteOffset": 16, "byteLength": 2},
+                        {"buffer": 0, "byteOffset": 16, "byteLength": 8}
+                    ],
+                    "accessors": [
+                        {
+                            "componentType": 5126,
+                            "count": 4,
+                            "type": "SCALAR",
+                            "sparse": {
+                                "count": 2,
+                                "indices": {"bufferView": 1, "componentType": 5121},
+                                "values": {"bufferView": 2}
+                            }
+                        },
+                        {
+                            "bufferView": 0,
+                            "componentType": 5126,
+                            "count": 4,
+                            "type": "SCALAR",
+                            "sparse": {
+                                "count": 2,
+                                "indices": {"bufferView": 1, "componentType": 5121},
+                                "values": {"bufferView": 2}
+                            }
+                        }
+                    ]
+                }"#,
+            )
+            .unwrap(),
+        );
+        let mut data = Vec::new();
+        for value in &[1.0f32, 2.0, 3.0, 4.0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        // The indices 1 and 3 reinterpreted as the start of the first value.
+        data.extend_from_slice(&[1, 3, 0x40, 0x40]);
+        data.extend_from_slice(&8.0f32.to_le_bytes());
+        let get = |_| Some(&data[..]);
+        let accessor = |index| document.accessors().nth(index).unwrap();
+        let value = f32::from_le_bytes([1, 3, 0x40, 0x40]);
+
+        // Without a buffer view the base values are zero.
+        let iter = Iter::<f32>::new(accessor(0), get).unwrap();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![0.0, value, 0.0, 8.0]);
+
+        let iter = Iter::<f32>::new(accessor(1), get).unwrap();
+        assert_eq!(iter.collect::<Vec<_>>(), vec![1.0, value, 3.0, 8.0]);
+    }
+}
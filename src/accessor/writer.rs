@@ -0,0 +1,424 @@
+use std::marker::PhantomData;
+
+use json::accessor::GenericComponentType;
+use json::buffer::Target;
+use json::validation::Checked;
+
+use super::{DataType, Dimensions};
+
+/// Pads `data` with zeros up to the next multiple of four bytes.
+pub(crate) fn pad(data: &mut Vec<u8>) {
+    let remainder = data.len() % 4;
+    if remainder != 0 {
+        data.resize(data.len() + 4 - remainder, 0);
+    }
+}
+
+/// Represents the components of items that can be written by an
+/// [`AccessorWriter`].
+///
+/// [`AccessorWriter`]: struct.AccessorWriter.html
+pub trait Component: Copy {
+    /// The component type stored in the accessor.
+    const DATA_TYPE: DataType;
+
+    /// Appends the little-endian representation of this component to `data`.
+    fn write(self, data: &mut Vec<u8>);
+
+    /// Returns the value of this component as a JSON number, for the `min`
+    /// and `max` of the accessor.
+    fn to_json(self) -> json::Value;
+
+    /// Returns `true` if `self` is less than `other`.
+    fn less(self, other: Self) -> bool;
+}
+
+macro_rules! component {
+    ($($ty:ty => $data_type:ident,)*) => {
+        $(
+            impl Component for $ty {
+                const DATA_TYPE: DataType = DataType::$data_type;
+
+                fn write(self, data: &mut Vec<u8>) {
+                    data.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn to_json(self) -> json::Value {
+                    json::Value::from(self)
+                }
+
+                fn less(self, other: Self) -> bool {
+                    self < other
+                }
+            }
+        )*
+    };
+}
+
+component! {
+    i8 => I8,
+    u8 => U8,
+    i16 => I16,
+    u16 => U16,
+    u32 => U32,
+    f32 => F32,
+}
+
+/// Represents items that can be written by an [`AccessorWriter`].
+///
+/// This is the write-side counterpart of [`Item`], implemented for scalars,
+/// arrays of two to four components, and column-major `f32` matrices.
+///
+/// [`AccessorWriter`]: struct.AccessorWriter.html
+/// [`Item`]: ../util/trait.Item.html
+pub trait WriteItem: Copy {
+    /// The component type of the item.
+    type Component: Component;
+
+    /// The dimensions of the item.
+    const DIMENSIONS: Dimensions;
+
+    /// Calls `f` with each component of the item, in storage order.
+    fn for_each(self, f: impl FnMut(Self::Component));
+}
+
+impl<T: Component> WriteItem for T {
+    type Component = T;
+    const DIMENSIONS: Dimensions = Dimensions::Scalar;
+
+    fn for_each(self, mut f: impl FnMut(T)) {
+        f(self)
+    }
+}
+
+macro_rules! vector {
+    ($($n:expr => $dimensions:ident,)*) => {
+        $(
+            impl<T: Component> WriteItem for [T; $n] {
+                type Component = T;
+                const DIMENSIONS: Dimensions = Dimensions::$dimensions;
+
+                fn for_each(self, f: impl FnMut(T)) {
+                    self.iter().copied().for_each(f)
+                }
+            }
+        )*
+    };
+}
+
+vector! {
+    2 => Vec2,
+    3 => Vec3,
+    4 => Vec4,
+}
+
+macro_rules! matrix {
+    ($($n:expr => $dimensions:ident,)*) => {
+        $(
+            impl WriteItem for [[f32; $n]; $n] {
+                type Component = f32;
+                const DIMENSIONS: Dimensions = Dimensions::$dimensions;
+
+                fn for_each(self, f: impl FnMut(f32)) {
+                    self.iter().flatten().copied().for_each(f)
+                }
+            }
+        )*
+    };
+}
+
+matrix! {
+    2 => Mat2,
+    3 => Mat3,
+    4 => Mat4,
+}
+
+/// Collects typed items and writes them out as a buffer view and an accessor.
+///
+/// This is the write-side counterpart of the accessor readers: items are
+/// pushed one at a time, and `finish` appends their bytes to the data of a
+/// buffer and adds a buffer view and an accessor describing them, with the
+/// count, component type, type, `min`, and `max` filled in.
+///
+/// # Examples
+///
+/// ```
+/// use gltf::accessor::AccessorWriter;
+///
+/// let mut root = gltf::json::Root::default();
+/// let mut data = Vec::new();
+/// let mut positions = AccessorWriter::<[f32; 3]>::new();
+/// positions.push([0.0, 0.0, 0.0]);
+/// positions.push([1.0, 0.0, 0.0]);
+/// positions.push([0.0, 1.0, 0.0]);
+/// let index = positions
+///     .finish(&mut root, gltf::json::Index::new(0), &mut data)
+///     .expect("at least one item was written");
+/// assert_eq!(root.accessors[index.value()].count, 3);
+/// assert_eq!(data.len(), 36);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AccessorWriter<T: WriteItem> {
+    /// The item bytes written so far.
+    data: Vec<u8>,
+
+    /// The number of items written so far.
+    count: u32,
+
+    /// The component-wise minimum of the items written so far.
+    min: Vec<T::Component>,
+
+    /// The component-wise maximum of the items written so far.
+    max: Vec<T::Component>,
+
+    /// Whether integer components are normalized.
+    normalized: bool,
+
+    /// The intended target of the buffer view.
+    target: Option<Target>,
+
+    _phantom: PhantomData<T>,
+}
+
+impl<T: WriteItem> AccessorWriter<T> {
+    /// Creates an empty writer.
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            count: 0,
+            min: Vec::new(),
+            max: Vec::new(),
+            normalized: false,
+            target: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Sets whether integer components are normalized when read.
+    pub fn normalized(mut self, normalized: bool) -> Self {
+        self.normalized = normalized;
+        self
+    }
+
+    /// Sets the intended target of the buffer view.
+    ///
+    /// Vertex attributes must start on four byte boundaries, so items of an
+    /// `ArrayBuffer` target whose size is not a multiple of four bytes are
+    /// padded and given a byte stride.
+    pub fn target(mut self, target: Target) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Appends an item.
+    pub fn push(&mut self, item: T) {
+        let first = self.count == 0;
+        let mut i = 0;
+        item.for_each(|component| {
+            component.write(&mut self.data);
+            if first {
+                self.min.push(component);
+                self.max.push(component);
+            } else {
+                if component.less(self.min[i]) {
+                    self.min[i] = component;
+                }
+                if self.max[i].less(component) {
+                    self.max[i] = component;
+                }
+            }
+            i += 1;
+        });
+        if self.padded() {
+            pad(&mut self.data);
+        }
+        self.count += 1;
+    }
+
+    /// Returns the number of items written so far.
+    pub fn len(&self) -> usize {
+        self.count as usize
+    }
+
+    /// Returns `true` if no items have been written.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the size in bytes of an item.
+    fn size() -> usize {
+        T::Component::DATA_TYPE.size() * T::DIMENSIONS.multiplicity()
+    }
+
+    /// Returns `true` if items are padded to four byte boundaries.
+    fn padded(&self) -> bool {
+        self.target == Some(Target::ArrayBuffer) && Self::size() % 4 != 0
+    }
+
+    /// Appends the written bytes to `data`, the contents of `buffer`, and adds
+    /// a buffer view and an accessor for them to `root`, returning the index
+    /// of the new accessor.
+    ///
+    /// `data` is first padded to a four byte boundary. If `buffer` exists in
+    /// `root`, its byte length is updated to the new length of `data`.
+    ///
+    /// Returns `None` without changing anything if no items were written,
+    /// since accessors must have at least one element.
+    pub fn finish(
+        self,
+        root: &mut json::Root,
+        buffer: json::Index<json::Buffer>,
+        data: &mut Vec<u8>,
+    ) -> Option<json::Index<json::Accessor>> {
+        if self.is_empty() {
+            return None;
+        }
+        pad(data);
+        let offset = data.len();
+        data.extend_from_slice(&self.data);
+        if let Some(buffer) = root.buffers.get_mut(buffer.value()) {
+            buffer.byte_length = data.len() as u32;
+        }
+
+        let stride = if self.padded() {
+            Some(self.data.len() as u32 / self.count)
+        } else {
+            None
+        };
+        root.buffer_views.push(json::buffer::View {
+            buffer,
+            byte_length: self.data.len() as u32,
+            byte_offset: Some(offset as u32),
+            byte_stride: stride,
+            #[cfg(feature = "names")]
+            name: None,
+            target: self.target.map(Checked::Valid),
+            extensions: Default::default(),
+            extras: Default::default(),
+        });
+
+        let bounds = |values: Vec<T::Component>| {
+            if values.is_empty() {
+                None
+            } else {
+                Some(json::Value::from(
+                    values
+                        .into_iter()
+                        .map(Component::to_json)
+                        .collect::<Vec<_>>(),
+                ))
+            }
+        };
+        root.accessors.push(json::Accessor {
+            buffer_view: Some(json::Index::new(root.buffer_views.len() as u32 - 1)),
+            byte_offset: 0,
+            count: self.count,
+            component_type: Checked::Valid(GenericComponentType(T::Component::DATA_TYPE)),
+            extensions: Default::default(),
+            extras: Default::default(),
+            type_: Checked::Valid(T::DIMENSIONS),
+            min: bounds(self.min),
+            max: bounds(self.max),
+            #[cfg(feature = "names")]
+            name: None,
+            normalized: self.normalized,
+            sparse: None,
+        });
+        Some(json::Index::new(root.accessors.len() as u32 - 1))
+    }
+}
+
+impl<T: WriteItem> Default for AccessorWriter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: WriteItem> Extend<T> for AccessorWriter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accessor::Iter;
+    use crate::Document;
+
+    #[test]
+    fn write_and_read_back() {
+        let mut root = json::Root::default();
+        root.buffers.push(json::Buffer {
+            byte_length: 0,
+            #[cfg(feature = "names")]
+            name: None,
+            uri: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        });
+        let mut data = vec![1];
+
+        let mut positions = AccessorWriter::new();
+        positions.extend(vec![[1.0f32, -2.0, 0.5], [-1.0, 3.0, 0.25]]);
+        let positions = positions
+            .finish(&mut root, json::Index::new(0), &mut data)
+            .unwrap();
+        let mut colors = AccessorWriter::new()
+            .normalized(true)
+            .target(Target::ArrayBuffer);
+        colors.extend(vec![[255u8, 0, 0], [0, 255, 0]]);
+        let colors = colors
+            .finish(&mut root, json::Index::new(0), &mut data)
+            .unwrap();
+        let mut indices = AccessorWriter::<u32>::new();
+        indices.extend(vec![0, 1, 1]);
+        let indices = indices
+            .finish(&mut root, json::Index::new(0), &mut data)
+            .unwrap();
+
+        assert_eq!(data.len(), 4 + 24 + 8 + 12);
+        assert_eq!(root.buffers[0].byte_length, 48);
+        let accessor = &root.accessors[positions.value()];
+        assert_eq!(accessor.count, 2);
+        assert_eq!(
+            accessor.min,
+            Some(json::Value::from(vec![-1.0, -2.0, 0.25]))
+        );
+        assert_eq!(accessor.max, Some(json::Value::from(vec![1.0, 3.0, 0.5])));
+        assert_eq!(root.buffer_views[0].byte_offset, Some(4));
+        assert_eq!(root.buffer_views[1].byte_stride, Some(4));
+        assert!(root.accessors[colors.value()].normalized);
+        assert_eq!(
+            root.accessors[indices.value()].max,
+            Some(json::Value::from(vec![1]))
+        );
+
+        let document = Document::from_json(root).unwrap();
+        let get = |_| Some(data.as_slice());
+        let accessor = document.accessors().nth(positions.value()).unwrap();
+        let read: Vec<[f32; 3]> = Iter::new(accessor, get).unwrap().collect();
+        assert_eq!(read, vec![[1.0, -2.0, 0.5], [-1.0, 3.0, 0.25]]);
+        let accessor = document.accessors().nth(colors.value()).unwrap();
+        let read: Vec<[u8; 3]> = Iter::new(accessor, get).unwrap().collect();
+        assert_eq!(read, vec![[255, 0, 0], [0, 255, 0]]);
+        let accessor = document.accessors().nth(indices.value()).unwrap();
+        let read: Vec<u32> = Iter::new(accessor, get).unwrap().collect();
+        assert_eq!(read, vec![0, 1, 1]);
+    }
+
+    #[test]
+    fn empty_writer_writes_nothing() {
+        let mut root = json::Root::default();
+        let mut data = vec![1];
+        let colors = AccessorWriter::<[u8; 3]>::new().target(Target::ArrayBuffer);
+        assert!(colors
+            .finish(&mut root, json::Index::new(0), &mut data)
+            .is_none());
+        assert!(root.buffer_views.is_empty());
+        assert!(root.accessors.is_empty());
+        assert_eq!(data, vec![1]);
+    }
+}
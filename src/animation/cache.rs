@@ -0,0 +1,366 @@
+use crate::animation::{Animation, Keyframes, Property, Value};
+use crate::math::*;
+use crate::scene::{Node, Scene, Transform};
+use crate::Buffer;
+
+/// The keyframes of a channel targeting a node transform.
+#[derive(Clone, Debug)]
+struct Track {
+    /// Index of the target node.
+    node: usize,
+
+    /// The keyframes of the translation, rotation, or scale.
+    keyframes: Keyframes,
+}
+
+impl Track {
+    /// Reads the keyframes of the transform channels of an animation.
+    fn read<'a, 's, F>(animation: &Animation<'a>, get_buffer_data: F) -> Vec<Self>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        animation
+            .channels()
+            .filter(|channel| channel.target().property() != Property::MorphTargetWeights)
+            .filter_map(|channel| {
+                Some(Track {
+                    node: channel.target().node().index(),
+                    keyframes: Keyframes::new(&channel, get_buffer_data.clone())?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Node transforms of a scene sampled from its animations at a fixed rate.
+///
+/// Sampling interpolates every translation, rotation, and scale channel once
+/// per frame and multiplies out the node hierarchy, so that playback only
+/// needs to look up the matrices of the current frame. Morph target weights
+/// are not sampled.
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+#[derive(Clone, Debug)]
+pub struct TransformCache {
+    /// Frames per second.
+    rate: f32,
+
+    /// The time of the last keyframe of any sampled channel, in seconds.
+    duration: f32,
+
+    /// The number of cached frames.
+    frames: usize,
+
+    /// The number of nodes in the document.
+    nodes: usize,
+
+    /// Column-major local transforms, by frame and then by node.
+    local: Vec<[[f32; 4]; 4]>,
+
+    /// Column-major world transforms, by frame and then by node, or `None`
+    /// for nodes outside of the scene.
+    world: Vec<Option<[[f32; 4]; 4]>>,
+}
+
+impl TransformCache {
+    /// The largest number of frames sampled, about 4.8 hours at 60 frames
+    /// per second.
+    pub const MAX_FRAMES: usize = 1 << 20;
+
+    /// The largest number of transforms cached, frames times nodes, which
+    /// bounds the memory used to about 540 MiB.
+    pub const MAX_TRANSFORMS: usize = 1 << 22;
+
+    /// Samples `animations`, played together, at `rate` frames per second and
+    /// caches the resulting transforms of every node of the document, with
+    /// world transforms relative to the roots of `scene`.
+    ///
+    /// Frames are sampled at multiples of `1 / rate` seconds from zero up to
+    /// and including the last keyframe. Nodes not targeted by any channel keep
+    /// their rest transform.
+    ///
+    /// Returns `None` if the last keyframe is not finite, or if more than
+    /// `MAX_FRAMES` frames or `MAX_TRANSFORMS` transforms would be sampled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not positive.
+    pub fn new<'a, 's, I, F>(
+        scene: &Scene<'a>,
+        animations: I,
+        rate: f32,
+        get_buffer_data: F,
+    ) -> Option<Self>
+    where
+        I: IntoIterator<Item = Animation<'a>>,
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        assert!(rate > 0.0, "sample rate must be positive");
+        let document = scene.document;
+        let tracks: Vec<Track> = animations
+            .into_iter()
+            .flat_map(|animation| Track::read(&animation, get_buffer_data.clone()))
+            .collect();
+        let duration = tracks
+            .iter()
+            .map(|track| track.keyframes.duration())
+            .fold(0.0, f32::max);
+        let frames = (duration * rate).ceil();
+        if !frames.is_finite() || frames >= Self::MAX_FRAMES as f32 {
+            return None;
+        }
+        let frames = frames as usize + 1;
+        let rest: Vec<([f32; 3], [f32; 4], [f32; 3])> = document
+            .nodes()
+            .map(|node| node.transform().decomposed())
+            .collect();
+        let nodes = rest.len();
+
+        let len = frames
+            .checked_mul(nodes)
+            .filter(|&len| len <= Self::MAX_TRANSFORMS)?;
+        let mut local = Vec::with_capacity(len);
+        let mut world = Vec::with_capacity(len);
+        for frame in 0..frames {
+            let time = (frame as f32 / rate).min(duration);
+            let mut pose = rest.clone();
+            for track in &tracks {
+                let (translation, rotation, scale) = &mut pose[track.node];
+                match track.keyframes.sample(time) {
+                    Value::Translation(value) => *translation = value,
+                    Value::Rotation(value) => *rotation = value,
+                    Value::Scale(value) => *scale = value,
+                    Value::MorphTargetWeights(_) => {}
+                }
+            }
+
+            let begin = local.len();
+            local.extend(pose.into_iter().map(|(translation, rotation, scale)| {
+                Transform::Decomposed {
+                    translation,
+                    rotation,
+                    scale,
+                }
+                .matrix()
+            }));
+            world.resize(begin + nodes, None);
+
+            fn walk(
+                node: Node,
+                parent: Matrix4,
+                depth: usize,
+                local: &[[[f32; 4]; 4]],
+                world: &mut [Option<[[f32; 4]; 4]>],
+            ) {
+                let transform = parent * Matrix4::from_array(local[node.index()]);
+                world[node.index()] = Some(transform.as_array());
+                // A depth of zero means the hierarchy is cyclic, which is invalid glTF.
+                if depth > 0 {
+                    for child in node.children() {
+                        walk(child, transform, depth - 1, local, world);
+                    }
+                }
+            }
+            for root in scene.nodes() {
+                walk(
+                    root,
+                    Matrix4::identity(),
+                    nodes,
+                    &local[begin..],
+                    &mut world[begin..],
+                );
+            }
+        }
+
+        Some(Self {
+            rate,
+            duration,
+            frames,
+            nodes,
+            local,
+            world,
+        })
+    }
+
+    /// Returns the number of frames per second.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Returns the time of the last keyframe, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    /// Returns the number of cached frames.
+    pub fn frame_count(&self) -> usize {
+        self.frames
+    }
+
+    /// Returns the frame nearest to `time` in seconds, clamped to the cached
+    /// frames.
+    pub fn frame_at(&self, time: f32) -> usize {
+        let last = self.frames - 1;
+        ((time * self.rate).round().max(0.0) as usize).min(last)
+    }
+
+    /// Returns the position of the transforms of `node` at `frame`.
+    fn slot(&self, frame: usize, node: &Node) -> Option<usize> {
+        if frame < self.frames && node.index() < self.nodes {
+            Some(frame * self.nodes + node.index())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the column-major local transform of `node` at `frame`.
+    ///
+    /// Returns `None` if `frame` is out of range.
+    pub fn local(&self, frame: usize, node: &Node) -> Option<[[f32; 4]; 4]> {
+        self.slot(frame, node).map(|slot| self.local[slot])
+    }
+
+    /// Returns the column-major world transform of `node` at `frame`.
+    ///
+    /// Returns `None` if `frame` is out of range or `node` is not part of the
+    /// scene.
+    pub fn world(&self, frame: usize, node: &Node) -> Option<[[f32; 4]; 4]> {
+        self.slot(frame, node).and_then(|slot| self.world[slot])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Document;
+
+    #[test]
+    fn sample_translation_and_rotation() {
+        let mut data = Vec::new();
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        let values: &[f32] = &[
+            // Times.
+            0.0, 1.0, // Translations.
+            0.0, 0.0, 0.0, 2.0, 0.0, 0.0, // Rotations about Z by 0 and 90 degrees.
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, half, half,
+        ];
+        for value in values {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 64}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 8},
+                {"buffer": 0, "byteOffset": 8, "byteLength": 24},
+                {"buffer": 0, "byteOffset": 32, "byteLength": 32}
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0,
+                    "componentType": 5126,
+                    "count": 2,
+                    "type": "SCALAR",
+                    "min": [0.0],
+                    "max": [1.0]
+                },
+                {"bufferView": 1, "componentType": 5126, "count": 2, "type": "VEC3"},
+                {"bufferView": 2, "componentType": 5126, "count": 2, "type": "VEC4"}
+            ],
+            "animations": [{
+                "channels": [
+                    {"sampler": 0, "target": {"node": 0, "path": "translation"}},
+                    {"sampler": 1, "target": {"node": 1, "path": "rotation"}}
+                ],
+                "samplers": [
+                    {"input": 0, "output": 1},
+                    {"input": 0, "output": 2, "interpolation": "STEP"}
+                ]
+            }],
+            "nodes": [
+                {"children": [1]},
+                {"translation": [0.0, 1.0, 0.0]},
+                {}
+            ],
+            "scenes": [{"nodes": [0]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let scene = document.scenes().next().unwrap();
+        let cache = TransformCache::new(&scene, document.animations(), 4.0, |_| {
+            Some(data.as_slice())
+        })
+        .unwrap();
+
+        assert_eq!(cache.frame_count(), 5);
+        assert_eq!(cache.duration(), 1.0);
+        assert_eq!(cache.frame_at(0.6), 2);
+        assert_eq!(cache.frame_at(10.0), 4);
+        let nodes: Vec<_> = document.nodes().collect();
+
+        let parent = cache.local(2, &nodes[0]).unwrap();
+        assert_relative_eq!(parent[3][0], 1.0);
+        let child = cache.world(2, &nodes[1]).unwrap();
+        assert_relative_eq!(child[3][0], 1.0);
+        assert_relative_eq!(child[3][1], 1.0);
+        // The step interpolated rotation holds until the last keyframe.
+        assert_relative_eq!(child[0][0], 1.0);
+        let child = cache.world(4, &nodes[1]).unwrap();
+        assert_relative_eq!(child[3][0], 2.0);
+        assert_relative_eq!(child[0][1], 1.0, epsilon = 1e-6);
+
+        assert!(cache.local(4, &nodes[2]).is_some());
+        assert!(cache.world(4, &nodes[2]).is_none());
+        assert!(cache.local(5, &nodes[0]).is_none());
+
+        for rate in [1.0e9, f32::INFINITY] {
+            let cache = TransformCache::new(&scene, document.animations(), rate, |_| {
+                Some(data.as_slice())
+            });
+            assert!(cache.is_none(), "rate {}", rate);
+        }
+    }
+
+    #[test]
+    fn bounds_transforms() {
+        let data: Vec<u8> = [0.0f32, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0]
+            .iter()
+            .flat_map(|value| value.to_le_bytes().to_vec())
+            .collect();
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 32}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 8},
+                {"buffer": 0, "byteOffset": 8, "byteLength": 24}
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0,
+                    "componentType": 5126,
+                    "count": 2,
+                    "type": "SCALAR",
+                    "min": [0.0],
+                    "max": [1.0]
+                },
+                {"bufferView": 1, "componentType": 5126, "count": 2, "type": "VEC3"}
+            ],
+            "animations": [{
+                "channels": [{"sampler": 0, "target": {"node": 0, "path": "translation"}}],
+                "samplers": [{"input": 0, "output": 1}]
+            }],
+            "nodes": [{}, {}, {}, {}, {}],
+            "scenes": [{"nodes": [0, 1, 2, 3, 4]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let scene = document.scenes().next().unwrap();
+        // Within `MAX_FRAMES`, but 900,001 frames of 5 nodes each exceed
+        // `MAX_TRANSFORMS`.
+        let cache = TransformCache::new(&scene, document.animations(), 900_000.0, |_| {
+            Some(data.as_slice())
+        });
+        assert!(cache.is_none());
+        let cache = TransformCache::new(&scene, document.animations(), 60.0, |_| {
+            Some(data.as_slice())
+        });
+        assert_eq!(cache.unwrap().frame_count(), 61);
+    }
+}
@@ -13,10 +13,24 @@ pub mod iter;
 #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
 pub mod util;
 
+/// Precomputed node transforms of animations.
+#[cfg(feature = "utils")]
+mod cache;
+
+/// Sampling animation channels at any time.
+#[cfg(feature = "utils")]
+mod sample;
+
 #[cfg(feature = "utils")]
 #[doc(inline)]
 pub use self::util::Reader;
 
+#[cfg(feature = "utils")]
+pub use self::cache::TransformCache;
+
+#[cfg(feature = "utils")]
+pub use self::sample::{Keyframes, Value};
+
 /// A keyframe animation.
 #[derive(Clone, Debug)]
 pub struct Animation<'a> {
@@ -84,6 +98,18 @@ impl<'a> Animation<'a> {
         &self.json.extras
     }
 
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new().field("animations").index(self.index)
+        })
+    }
+
     /// Returns an `Iterator` over the animation channels.
     ///
     /// Each channel targets an animation's sampler at a node's property.
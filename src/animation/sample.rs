@@ -0,0 +1,368 @@
+use crate::animation::util::ReadOutputs;
+use crate::animation::{Channel, Interpolation, Property};
+use crate::Buffer;
+
+/// The value of an animated property, as sampled by `Keyframes::sample`.
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// XYZ translation.
+    Translation([f32; 3]),
+
+    /// XYZW unit quaternion rotation.
+    Rotation([f32; 4]),
+
+    /// XYZ scale.
+    Scale([f32; 3]),
+
+    /// The weight of every morph target.
+    MorphTargetWeights(Vec<f32>),
+}
+
+/// The keyframes of an animation channel, read into memory so that the
+/// animated value can be sampled at any time.
+///
+/// Linear rotations are interpolated by spherical linear interpolation along
+/// the shortest path, and cubic spline rotations are normalized. The first and
+/// last values are held before and after the keyframe range.
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use gltf::animation::{Keyframes, Value};
+///
+/// let (document, buffers, _) = gltf::import("examples/Box.gltf")?;
+/// for animation in document.animations() {
+///     for channel in animation.channels() {
+///         let keyframes = Keyframes::new(&channel, |buffer| Some(&buffers[buffer.index()]));
+///         if let Some(Value::Translation(translation)) = keyframes.map(|k| k.sample(0.5)) {
+///             println!("{:?}", translation);
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+#[derive(Clone, Debug)]
+pub struct Keyframes {
+    /// The animated property.
+    property: Property,
+
+    /// The keyframe interpolation.
+    interpolation: Interpolation,
+
+    /// Keyframe times in seconds.
+    inputs: Vec<f32>,
+
+    /// Keyframe values, `width` components each. Cubic spline keyframes hold
+    /// an in-tangent, a value, and an out-tangent.
+    outputs: Vec<f32>,
+
+    /// The number of components of a value.
+    width: usize,
+}
+
+/// Adds `b` scaled by `s` to `a`.
+fn add_scaled(a: &mut [f32], b: &[f32], s: f32) {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a += b * s;
+    }
+}
+
+fn normalize(a: &mut [f32]) {
+    let length = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if length > 0.0 {
+        for x in a {
+            *x /= length;
+        }
+    }
+}
+
+/// Spherical linear interpolation between two unit quaternions, taking the
+/// shortest path.
+fn slerp(a: &[f32], b: &[f32], t: f32) -> Vec<f32> {
+    let mut dot: f32 = a.iter().zip(b).map(|(a, b)| a * b).sum();
+    let sign = if dot < 0.0 {
+        dot = -dot;
+        -1.0
+    } else {
+        1.0
+    };
+    let mut value = vec![0.0; a.len()];
+    if dot > 0.9995 {
+        // Nearly parallel; linear interpolation is accurate and stable.
+        add_scaled(&mut value, a, 1.0 - t);
+        add_scaled(&mut value, b, sign * t);
+        normalize(&mut value);
+    } else {
+        let theta = dot.acos();
+        let sin = theta.sin();
+        add_scaled(&mut value, a, ((1.0 - t) * theta).sin() / sin);
+        add_scaled(&mut value, b, sign * (t * theta).sin() / sin);
+    }
+    value
+}
+
+impl Keyframes {
+    /// Reads the keyframes of a channel.
+    ///
+    /// Returns `None` if the buffer data is missing or the channel has no
+    /// keyframes, or fewer outputs than its inputs require.
+    pub fn new<'a, 's, F>(channel: &Channel<'a>, get_buffer_data: F) -> Option<Self>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let reader = channel.reader(get_buffer_data);
+        let inputs: Vec<f32> = reader.read_inputs()?.collect();
+        let (outputs, width): (Vec<f32>, _) = match reader.read_outputs()? {
+            ReadOutputs::Translations(iter) | ReadOutputs::Scales(iter) => {
+                (iter.flatten().collect(), 3)
+            }
+            ReadOutputs::Rotations(iter) => (iter.into_f32().flatten().collect(), 4),
+            ReadOutputs::MorphTargetWeights(iter) => {
+                let outputs: Vec<f32> = iter.into_f32().collect();
+                (outputs, 0)
+            }
+        };
+        let interpolation = channel.sampler().interpolation();
+        let per_key = match interpolation {
+            Interpolation::CubicSpline => 3,
+            _ => 1,
+        };
+        let width = match width {
+            0 if !inputs.is_empty() => outputs.len() / (inputs.len() * per_key),
+            width => width,
+        };
+        if inputs.is_empty() || width == 0 || outputs.len() < inputs.len() * per_key * width {
+            return None;
+        }
+        Some(Self {
+            property: channel.target().property(),
+            interpolation,
+            inputs,
+            outputs,
+            width,
+        })
+    }
+
+    /// Returns the animated property.
+    pub fn property(&self) -> Property {
+        self.property
+    }
+
+    /// Returns the keyframe interpolation.
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// Returns the keyframe times in seconds.
+    pub fn times(&self) -> &[f32] {
+        &self.inputs
+    }
+
+    /// Returns the time of the last keyframe in seconds.
+    pub fn duration(&self) -> f32 {
+        self.inputs[self.inputs.len() - 1]
+    }
+
+    /// Returns output `part` of `key`, where cubic spline keyframes have an
+    /// in-tangent, a value, and an out-tangent part.
+    fn output(&self, key: usize, part: usize) -> &[f32] {
+        let index = match self.interpolation {
+            Interpolation::CubicSpline => 3 * key + part,
+            _ => key,
+        };
+        &self.outputs[index * self.width..][..self.width]
+    }
+
+    /// Returns the value at the given keyframe, skipping cubic spline
+    /// tangents.
+    fn value(&self, key: usize) -> &[f32] {
+        self.output(key, 1)
+    }
+
+    /// Samples the components of the value at `time`.
+    fn components(&self, time: f32) -> Vec<f32> {
+        let last = self.inputs.len() - 1;
+        let next = self.inputs.partition_point(|&input| input <= time);
+        if next == 0 {
+            return self.value(0).to_vec();
+        }
+        if next > last {
+            return self.value(last).to_vec();
+        }
+        let previous = next - 1;
+        let delta = self.inputs[next] - self.inputs[previous];
+        let t = if delta > 0.0 {
+            (time - self.inputs[previous]) / delta
+        } else {
+            0.0
+        };
+        let rotation = self.property == Property::Rotation;
+
+        let mut value = vec![0.0; self.width];
+        match self.interpolation {
+            Interpolation::Step => value.copy_from_slice(self.value(previous)),
+            Interpolation::Linear if rotation => {
+                value = slerp(self.value(previous), self.value(next), t)
+            }
+            Interpolation::Linear => {
+                add_scaled(&mut value, self.value(previous), 1.0 - t);
+                add_scaled(&mut value, self.value(next), t);
+            }
+            Interpolation::CubicSpline => {
+                let (t2, t3) = (t * t, t * t * t);
+                add_scaled(&mut value, self.value(previous), 2.0 * t3 - 3.0 * t2 + 1.0);
+                add_scaled(
+                    &mut value,
+                    self.output(previous, 2),
+                    delta * (t3 - 2.0 * t2 + t),
+                );
+                add_scaled(&mut value, self.value(next), -2.0 * t3 + 3.0 * t2);
+                add_scaled(&mut value, self.output(next, 0), delta * (t3 - t2));
+                if rotation {
+                    normalize(&mut value);
+                }
+            }
+        }
+        value
+    }
+
+    /// Samples the animated value at `time` in seconds.
+    pub fn sample(&self, time: f32) -> Value {
+        let value = self.components(time);
+        match self.property {
+            Property::Translation => Value::Translation([value[0], value[1], value[2]]),
+            Property::Rotation => Value::Rotation([value[0], value[1], value[2], value[3]]),
+            Property::Scale => Value::Scale([value[0], value[1], value[2]]),
+            Property::MorphTargetWeights => Value::MorphTargetWeights(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Document;
+
+    #[test]
+    fn sample_channels() {
+        let mut data = Vec::new();
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+        let values: &[f32] = &[
+            // Times.
+            0.0, 2.0, // Translations.
+            0.0, 0.0, 0.0, 2.0, 4.0, 0.0, // Rotations about Z by 0 and 90 degrees.
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, half, half, // Weights of two targets.
+            0.0, 1.0, 1.0, 0.0, // Cubic spline scales, with tangents.
+            0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 3.0, 3.0, 3.0, 0.0, 0.0,
+            0.0,
+        ];
+        for value in values {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 144}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 8},
+                {"buffer": 0, "byteOffset": 8, "byteLength": 24},
+                {"buffer": 0, "byteOffset": 32, "byteLength": 32},
+                {"buffer": 0, "byteOffset": 64, "byteLength": 16},
+                {"buffer": 0, "byteOffset": 80, "byteLength": 72}
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0,
+                    "componentType": 5126,
+                    "count": 2,
+                    "type": "SCALAR",
+                    "min": [0.0],
+                    "max": [2.0]
+                },
+                {"bufferView": 1, "componentType": 5126, "count": 2, "type": "VEC3"},
+                {"bufferView": 2, "componentType": 5126, "count": 2, "type": "VEC4"},
+                {"bufferView": 3, "componentType": 5126, "count": 4, "type": "SCALAR"},
+                {"bufferView": 4, "componentType": 5126, "count": 6, "type": "VEC3"}
+            ],
+            "meshes": [{
+                "primitives": [{"attributes": {}, "targets": [{}, {}]}],
+                "weights": [0.0, 0.0]
+            }],
+            "animations": [{
+                "channels": [
+                    {"sampler": 0, "target": {"node": 0, "path": "translation"}},
+                    {"sampler": 1, "target": {"node": 0, "path": "rotation"}},
+                    {"sampler": 2, "target": {"node": 0, "path": "weights"}},
+                    {"sampler": 3, "target": {"node": 0, "path": "scale"}},
+                    {"sampler": 4, "target": {"node": 0, "path": "translation"}}
+                ],
+                "samplers": [
+                    {"input": 0, "output": 1},
+                    {"input": 0, "output": 2},
+                    {"input": 0, "output": 3},
+                    {"input": 0, "output": 4, "interpolation": "CUBICSPLINE"},
+                    {"input": 0, "output": 1, "interpolation": "STEP"}
+                ]
+            }],
+            "nodes": [{"mesh": 0}]
+        }"#;
+        let document = Document::from_json_without_validation(json::Root::from_str(json).unwrap());
+        let keyframes: Vec<Keyframes> = document
+            .animations()
+            .next()
+            .unwrap()
+            .channels()
+            .map(|channel| Keyframes::new(&channel, |_| Some(&data[..])).unwrap())
+            .collect();
+
+        assert_eq!(keyframes[0].times(), &[0.0, 2.0]);
+        assert_eq!(keyframes[0].duration(), 2.0);
+        assert_eq!(
+            keyframes[0].sample(0.5),
+            Value::Translation([0.5, 1.0, 0.0])
+        );
+        assert_eq!(
+            keyframes[0].sample(-1.0),
+            Value::Translation([0.0, 0.0, 0.0])
+        );
+        assert_eq!(
+            keyframes[0].sample(3.0),
+            Value::Translation([2.0, 4.0, 0.0])
+        );
+
+        // Halfway between 0 and 90 degrees about Z is 45 degrees.
+        let (sin, cos) = (std::f32::consts::FRAC_PI_8).sin_cos();
+        match keyframes[1].sample(1.0) {
+            Value::Rotation(rotation) => {
+                assert_relative_eq!(rotation[2], sin, epsilon = 1e-6);
+                assert_relative_eq!(rotation[3], cos, epsilon = 1e-6);
+            }
+            other => panic!("unexpected value: {:?}", other),
+        }
+
+        assert_eq!(
+            keyframes[2].sample(1.5),
+            Value::MorphTargetWeights(vec![0.75, 0.25])
+        );
+
+        assert_eq!(keyframes[3].interpolation(), Interpolation::CubicSpline);
+        assert_eq!(keyframes[3].sample(0.0), Value::Scale([1.0, 1.0, 1.0]));
+        // Equal tangents cancel out at the midpoint, leaving the average.
+        assert_eq!(keyframes[3].sample(1.0), Value::Scale([2.0, 2.0, 2.0]));
+
+        assert_eq!(
+            keyframes[4].sample(1.9),
+            Value::Translation([0.0, 0.0, 0.0])
+        );
+        assert_eq!(
+            keyframes[4].sample(2.0),
+            Value::Translation([2.0, 4.0, 0.0])
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::task::Poll;
+
+use crate::import::{import_impl, Import, Scheme};
+use crate::{buffer, image, Error, Gltf, Limits, Result};
+
+/// Reads the external resources of a glTF document without blocking.
+///
+/// This is the asynchronous counterpart of [`Fetch`], for resources that live
+/// on the network, such as on a CDN. It is independent of any particular
+/// runtime, so the returned futures may be driven by tokio, by the browser
+/// through `wasm-bindgen-futures`, or by any other executor.
+///
+/// ```
+/// use gltf::asynchronous::Fetch;
+///
+/// /// Fetches resources relative to a base URL.
+/// struct Cdn {
+///     base: String,
+/// }
+///
+/// impl Fetch for Cdn {
+///     async fn fetch(&self, path: &str) -> gltf::Result<Vec<u8>> {
+///         let url = format!("{}/{}", self.base, path);
+///         // An HTTP client would request `url` here.
+///         # let _ = url;
+///         std::fs::read(format!("examples/{}", path)).map_err(gltf::Error::Io)
+///     }
+/// }
+///
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     let mut future = Box::pin(future);
+/// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+/// #     loop {
+/// #         if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// # fn run() -> Result<(), gltf::Error> {
+/// let cdn = Cdn {
+///     base: "https://example.com/models".to_string(),
+/// };
+/// let json = std::fs::read("examples/Box.gltf").map_err(gltf::Error::Io)?;
+/// let (document, buffers, _) = block_on(gltf::asynchronous::import_slice(json, &cdn))?;
+/// assert_eq!(buffers.len(), document.buffers().len());
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+///
+/// [`Fetch`]: ../trait.Fetch.html
+pub trait Fetch {
+    /// Returns the contents of the resource at `path`.
+    ///
+    /// `path` is the percent-decoded URI of the resource, without its `file:`
+    /// scheme if it has one.
+    fn fetch(&self, path: &str) -> impl Future<Output = Result<Vec<u8>>>;
+}
+
+/// Waits for every future in `futures`, polling them concurrently, and
+/// returns their outputs in order.
+async fn join_all<F: Future>(futures: Vec<F>) -> Vec<F::Output> {
+    let mut futures: Vec<Pin<Box<F>>> = futures.into_iter().map(Box::pin).collect();
+    let mut outputs: Vec<Option<F::Output>> = futures.iter().map(|_| None).collect();
+    future::poll_fn(|context| {
+        let mut pending = false;
+        for (future, output) in futures.iter_mut().zip(&mut outputs) {
+            if output.is_none() {
+                match future.as_mut().poll(context) {
+                    Poll::Ready(value) => *output = Some(value),
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await;
+    outputs.into_iter().map(Option::unwrap).collect()
+}
+
+/// Returns the paths of the external resources of a document, as passed to
+/// `Fetch::fetch`, without duplicates.
+fn external_paths(gltf: &Gltf) -> Result<Vec<String>> {
+    let buffers = gltf.buffers().filter_map(|buffer| match buffer.source() {
+        buffer::Source::Uri(uri) => Some(uri),
+        buffer::Source::Bin => None,
+    });
+    let images = gltf.images().filter_map(|image| match image.source() {
+        image::Source::Uri { uri, .. } => Some(uri),
+        image::Source::View { .. } => None,
+    });
+
+    let mut paths = Vec::new();
+    for uri in buffers.chain(images) {
+        let path = match Scheme::parse(uri) {
+            Scheme::Data(..) => continue,
+            Scheme::File(path) => path.to_string(),
+            Scheme::Relative(path) => path.into_owned(),
+            Scheme::Unsupported => return Err(Error::UnsupportedScheme),
+        };
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+/// Fetches the external resources of a document concurrently, then imports
+/// its buffers and images once all of them have arrived.
+async fn import_gltf<F: Fetch>(gltf: Gltf, fetch: &F, limits: &Limits) -> Result<Import> {
+    let paths = external_paths(&gltf)?;
+    let data = join_all(paths.iter().map(|path| fetch.fetch(path)).collect()).await;
+    let mut fetched = HashMap::new();
+    for (path, data) in paths.into_iter().zip(data) {
+        fetched.insert(path, data?);
+    }
+    import_impl(gltf, &fetched, limits)
+}
+
+/// Import some glTF 2.0 from a slice, reading external buffers and images with
+/// `fetch`.
+///
+/// Every external resource is requested up front and the requests run
+/// concurrently. The document is parsed and validated before anything is
+/// fetched, and the buffers and images are imported as by
+/// [`import_slice_with`] once all of them have arrived.
+///
+/// See [`Fetch`] for an example.
+///
+/// [`import_slice_with`]: ../fn.import_slice_with.html
+/// [`Fetch`]: trait.Fetch.html
+pub async fn import_slice<S, F>(slice: S, fetch: &F) -> Result<Import>
+where
+    S: AsRef<[u8]>,
+    F: Fetch,
+{
+    import_gltf(Gltf::from_slice(slice.as_ref())?, fetch, &Limits::default()).await
+}
+
+/// Import some glTF 2.0 from a slice, reading external buffers and images with
+/// `fetch` and rejecting documents that exceed `limits` with `Error::Limit`.
+///
+/// See [`import_with_limits`] for when the limits are checked.
+///
+/// [`import_with_limits`]: ../fn.import_with_limits.html
+pub async fn import_slice_with_limits<S, F>(slice: S, fetch: &F, limits: &Limits) -> Result<Import>
+where
+    S: AsRef<[u8]>,
+    F: Fetch,
+{
+    import_gltf(
+        Gltf::from_slice_with_limits(slice.as_ref(), limits)?,
+        fetch,
+        limits,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::task::{Context, Waker};
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    /// Serves files from memory, each only after being polled once, and
+    /// records the order in which requests start and finish.
+    struct Files {
+        files: HashMap<&'static str, Vec<u8>>,
+        log: RefCell<Vec<String>>,
+    }
+
+    impl Fetch for Files {
+        async fn fetch(&self, path: &str) -> Result<Vec<u8>> {
+            self.log.borrow_mut().push(format!("start {}", path));
+            let mut yielded = false;
+            future::poll_fn(|_| {
+                if std::mem::replace(&mut yielded, true) {
+                    Poll::Ready(())
+                } else {
+                    Poll::Pending
+                }
+            })
+            .await;
+            self.log.borrow_mut().push(format!("end {}", path));
+            self.files
+                .get(path)
+                .cloned()
+                .ok_or(Error::ExternalReferenceInSliceImport)
+        }
+    }
+
+    const JSON: &[u8] = br#"{
+        "asset": {"version": "2.0"},
+        "buffers": [
+            {"byteLength": 4, "uri": "a%20b.bin"},
+            {"byteLength": 4, "uri": "data:application/octet-stream;base64,AAAAAA=="},
+            {"byteLength": 4, "uri": "c.bin"},
+            {"byteLength": 4, "uri": "a b.bin"}
+        ]
+    }"#;
+
+    #[test]
+    fn fetches_concurrently() {
+        let mut files = HashMap::new();
+        files.insert("a b.bin", vec![1, 2, 3, 4]);
+        files.insert("c.bin", vec![5, 6, 7, 8]);
+        let files = Files {
+            files,
+            log: RefCell::new(Vec::new()),
+        };
+        let (_, buffers, _) = block_on(import_slice(JSON, &files)).unwrap();
+        assert_eq!(buffers[0].0, [1, 2, 3, 4]);
+        assert_eq!(buffers[1].0, [0, 0, 0, 0]);
+        assert_eq!(buffers[2].0, [5, 6, 7, 8]);
+        assert_eq!(buffers[3].0, [1, 2, 3, 4]);
+        assert_eq!(
+            *files.log.borrow(),
+            ["start a b.bin", "start c.bin", "end a b.bin", "end c.bin"]
+        );
+    }
+
+    #[test]
+    fn fetch_errors() {
+        let files = Files {
+            files: HashMap::new(),
+            log: RefCell::new(Vec::new()),
+        };
+        assert!(matches!(
+            block_on(import_slice(JSON, &files)),
+            Err(Error::ExternalReferenceInSliceImport)
+        ));
+    }
+}
@@ -0,0 +1,154 @@
+use std::boxed::Box;
+use std::collections::BTreeSet;
+use std::error::Error as StdError;
+use std::process;
+
+use gltf::mesh::Mode;
+
+const USAGE: &str = "usage: gltf-info <FILE>";
+
+/// Returns the number of triangles rasterized for `count` vertices.
+fn triangle_count(mode: Mode, count: usize) -> usize {
+    match mode {
+        Mode::Triangles => count / 3,
+        Mode::TriangleStrip | Mode::TriangleFan => count.saturating_sub(2),
+        _ => 0,
+    }
+}
+
+/// Returns the number of vertices submitted by a primitive.
+fn vertex_count(primitive: &gltf::Primitive) -> usize {
+    match primitive.indices() {
+        Some(indices) => indices.count(),
+        None => primitive
+            .get(&gltf::Semantic::Positions)
+            .map(|accessor| accessor.count())
+            .unwrap_or(0),
+    }
+}
+
+/// Returns the length of an animation in seconds, derived from the `max`
+/// bounds of its sampler inputs.
+fn duration(animation: &gltf::Animation) -> f64 {
+    animation
+        .samplers()
+        .filter_map(|sampler| sampler.input().max())
+        .filter_map(|max| max.get(0).and_then(|value| value.as_f64()))
+        .fold(0.0, f64::max)
+}
+
+fn label(index: usize, _name: Option<&str>) -> String {
+    #[cfg(feature = "names")]
+    {
+        if let Some(name) = _name {
+            return format!("{} ({})", index, name);
+        }
+    }
+    index.to_string()
+}
+
+fn run(path: &str) -> Result<(), Box<dyn StdError>> {
+    let (document, _, images) = gltf::import(path)?;
+
+    println!("file: {}", path);
+
+    println!("scenes: {}", document.scenes().len());
+    for scene in document.scenes() {
+        #[cfg(feature = "names")]
+        let name = scene.name();
+        #[cfg(not(feature = "names"))]
+        let name = None;
+        let default = document
+            .default_scene()
+            .map(|default| default.index() == scene.index())
+            .unwrap_or(false);
+        println!(
+            "  scene {}: {} root node(s){}",
+            label(scene.index(), name),
+            scene.nodes().len(),
+            if default { " [default]" } else { "" },
+        );
+    }
+
+    println!("nodes: {}", document.nodes().len());
+
+    let mut primitives = 0;
+    let mut triangles = 0;
+    for mesh in document.meshes() {
+        for primitive in mesh.primitives() {
+            primitives += 1;
+            triangles += triangle_count(primitive.mode(), vertex_count(&primitive));
+        }
+    }
+    println!(
+        "meshes: {} ({} primitive(s), {} triangle(s))",
+        document.meshes().len(),
+        primitives,
+        triangles,
+    );
+
+    let accessor_bytes: usize = document
+        .accessors()
+        .map(|accessor| accessor.count() * accessor.size())
+        .sum();
+    println!(
+        "accessors: {} ({} byte(s))",
+        document.accessors().len(),
+        accessor_bytes,
+    );
+
+    println!("textures: {}", document.textures().len());
+    for texture in document.textures() {
+        #[cfg(feature = "names")]
+        let name = texture.name();
+        #[cfg(not(feature = "names"))]
+        let name = None;
+        let image = &images[texture.source().index()];
+        println!(
+            "  texture {}: image {}, {}x{} {:?}",
+            label(texture.index(), name),
+            texture.source().index(),
+            image.width,
+            image.height,
+            image.format,
+        );
+    }
+
+    println!("animations: {}", document.animations().len());
+    for animation in document.animations() {
+        #[cfg(feature = "names")]
+        let name = animation.name();
+        #[cfg(not(feature = "names"))]
+        let name = None;
+        println!(
+            "  animation {}: {} channel(s), {:.3}s",
+            label(animation.index(), name),
+            animation.channels().count(),
+            duration(&animation),
+        );
+    }
+
+    let used: BTreeSet<&str> = document.extensions_used().collect();
+    let required: BTreeSet<&str> = document.extensions_required().collect();
+    println!("extensions used: {}", used.len());
+    for name in used {
+        if required.contains(name) {
+            println!("  {} [required]", name);
+        } else {
+            println!("  {}", name);
+        }
+    }
+
+    Ok(())
+}
+
+fn main() {
+    if let Some(path) = std::env::args().nth(1) {
+        if let Err(err) = run(&path) {
+            eprintln!("{}: {}", path, err);
+            process::exit(1);
+        }
+    } else {
+        println!("{}", USAGE);
+    }
+}
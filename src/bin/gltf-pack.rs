@@ -0,0 +1,192 @@
+use std::borrow::Cow;
+use std::boxed::Box;
+use std::error::Error as StdError;
+use std::path::{Path, PathBuf};
+use std::{fs, process};
+
+use gltf::{binary, transform};
+
+const USAGE: &str = "\
+usage: gltf-pack [OPTIONS] <INPUT> <OUTPUT>
+
+Converts between .gltf and .glb, choosing the output format from the
+extension of OUTPUT. Accessor bounds required by the specification are
+recomputed from the buffer data.
+
+options:
+    --embed         embed buffers and images in the output file (default)
+    --externalize   write buffers and images to files beside the output
+    --prune         remove objects that are not referenced
+    --dedup         merge identical objects
+    --interleave    interleave the vertex attributes of each primitive; combine
+                    with --prune to remove the replaced accessors
+    --deinterleave  store each vertex attribute in a buffer view of its own
+    --sparse-targets
+                    store morph targets that are mostly zero as sparse accessors;
+                    combine with --prune to remove the replaced buffer views
+    --quantize      store positions, normals, and texture co-ordinates as
+                    16-bit normalized integers using KHR_mesh_quantization";
+
+/// Command line options.
+#[derive(Debug, Default)]
+struct Options {
+    externalize: bool,
+    prune: bool,
+    dedup: bool,
+    interleave: bool,
+    deinterleave: bool,
+    sparse_targets: bool,
+    quantize: bool,
+    input: PathBuf,
+    output: PathBuf,
+}
+
+impl Options {
+    fn parse() -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut paths = Vec::new();
+        for arg in std::env::args().skip(1) {
+            match arg.as_str() {
+                "--embed" => options.externalize = false,
+                "--externalize" => options.externalize = true,
+                "--prune" => options.prune = true,
+                "--dedup" => options.dedup = true,
+                "--interleave" => options.interleave = true,
+                "--deinterleave" => options.deinterleave = true,
+                "--sparse-targets" => options.sparse_targets = true,
+                "--quantize" => options.quantize = true,
+                _ if arg.starts_with("--") => return Err(format!("unknown option `{}`", arg)),
+                _ => paths.push(PathBuf::from(arg)),
+            }
+        }
+        if paths.len() != 2 {
+            return Err("expected an input and an output file".to_string());
+        }
+        options.output = paths.pop().unwrap();
+        options.input = paths.pop().unwrap();
+        Ok(options)
+    }
+}
+
+/// Returns the file extension conventionally used for the given image MIME
+/// type.
+fn image_extension(mime_type: Option<&str>) -> &'static str {
+    match mime_type {
+        Some("image/png") => "png",
+        Some("image/jpeg") => "jpg",
+        _ => "bin",
+    }
+}
+
+fn run(options: &Options) -> Result<(), Box<dyn StdError>> {
+    let input_dir = options.input.parent().unwrap_or_else(|| Path::new("./"));
+    let output_dir = options.output.parent().unwrap_or_else(|| Path::new("./"));
+    let stem = options
+        .output
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or("invalid output file name")?
+        .to_string();
+    let glb = options
+        .output
+        .extension()
+        .map(|extension| extension.eq_ignore_ascii_case("glb"))
+        .unwrap_or(false);
+
+    let gltf::Gltf { document, blob } = gltf::Gltf::open(&options.input)?;
+    let mut buffers = gltf::import_buffers(&document, Some(input_dir), blob)?;
+    let mut root = document.into_json();
+
+    transform::embed_images(&mut root, &mut buffers, Some(input_dir))?;
+    transform::check(&root, &buffers)?;
+    if options.interleave {
+        transform::interleave(&mut root, &mut buffers)?;
+    }
+    if options.deinterleave {
+        transform::deinterleave(&mut root, &mut buffers)?;
+    }
+    if options.sparse_targets {
+        transform::sparsify_morph_targets(
+            &mut root,
+            &mut buffers,
+            transform::DEFAULT_SPARSE_DENSITY,
+        )?;
+    }
+    if options.quantize {
+        let formats = transform::VertexFormats {
+            positions: true,
+            normals: true,
+            tex_coords: true,
+        };
+        transform::convert_vertex_formats(&mut root, &mut buffers, formats)?;
+    }
+    if options.prune {
+        transform::prune(&mut root);
+    }
+    if options.dedup {
+        transform::dedup(&mut root);
+    }
+
+    if options.externalize {
+        let (images, _) = transform::extract_images(&mut root, &buffers, |index, mime_type| {
+            format!("{}_{}.{}", stem, index, image_extension(mime_type))
+        })?;
+        for (uri, data) in images {
+            fs::write(output_dir.join(uri), data)?;
+        }
+    }
+
+    transform::compute_bounds(&mut root, &buffers)?;
+    let (mut bin, _) = transform::pack_buffers(&mut root, &buffers)?;
+    if !bin.is_empty() && !glb {
+        let uri = if options.externalize {
+            let uri = format!("{}.bin", stem);
+            fs::write(output_dir.join(&uri), &bin)?;
+            uri
+        } else {
+            format!(
+                "data:application/octet-stream;base64,{}",
+                base64::encode(&bin)
+            )
+        };
+        root.buffers[0].uri = Some(uri);
+        bin.clear();
+    }
+
+    if glb {
+        let json = root.to_vec()?;
+        let glb = binary::Glb {
+            header: binary::Header {
+                magic: *b"glTF",
+                version: 2,
+                // `to_writer` computes the final length.
+                length: 0,
+            },
+            json: Cow::Owned(json),
+            bin: if bin.is_empty() {
+                None
+            } else {
+                Some(Cow::Owned(bin))
+            },
+        };
+        let writer = fs::File::create(&options.output)?;
+        glb.to_writer(writer)?;
+    } else {
+        fs::write(&options.output, root.to_vec_pretty()?)?;
+    }
+    Ok(())
+}
+
+fn main() {
+    let options = match Options::parse() {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+    if let Err(err) = run(&options) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,158 @@
+use std::{fmt, fs, process};
+
+use gltf::json;
+use json::validation::{Report, Severity};
+
+const USAGE: &str = "usage: gltf-validate [--json-report] <FILE>...";
+
+/// A single problem found in a file.
+#[derive(Clone, Debug)]
+struct Diagnostic {
+    severity: Severity,
+    path: Option<json::Path>,
+    message: String,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, path: Option<json::Path>, message: String) -> Self {
+        Self {
+            severity,
+            path,
+            message,
+        }
+    }
+
+    fn to_json(&self) -> json::Value {
+        let path = match self.path {
+            Some(ref path) => json::Value::from(path.as_str()),
+            None => json::Value::Null,
+        };
+        json::Value::Object(
+            vec![
+                ("severity".to_string(), self.severity.to_string().into()),
+                ("path".to_string(), path),
+                ("message".to_string(), self.message.clone().into()),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.path {
+            Some(ref path) => write!(f, "{}: {}: {}", self.severity, path, self.message),
+            None => write!(f, "{}: {}", self.severity, self.message),
+        }
+    }
+}
+
+/// Validates the file at `path`, returning every diagnostic found.
+fn validate(path: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            diagnostics.push(Diagnostic::new(Severity::Error, None, err.to_string()));
+            return diagnostics;
+        }
+    };
+
+    let gltf = match gltf::Gltf::from_slice_without_validation(&bytes) {
+        Ok(gltf) => gltf,
+        Err(err) => {
+            diagnostics.push(Diagnostic::new(Severity::Error, None, err.to_string()));
+            return diagnostics;
+        }
+    };
+
+    // Compare against the JSON itself to warn about unknown members.
+    let source = if bytes.starts_with(b"glTF") {
+        gltf::Glb::from_slice(&bytes)
+            .ok()
+            .and_then(|glb| json::deserialize::from_slice(&glb.json).ok())
+    } else {
+        json::deserialize::from_slice(&bytes).ok()
+    };
+    let root = gltf.document.into_json();
+    let report = match source {
+        Some(source) => Report::with_source(&root, &source),
+        None => Report::new(&root),
+    };
+    for issue in report.issues() {
+        diagnostics.push(Diagnostic::new(
+            issue.severity(),
+            Some(issue.path.clone()),
+            issue.kind.to_string(),
+        ));
+    }
+    diagnostics
+}
+
+fn main() {
+    let mut json_report = false;
+    let mut paths = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json-report" => json_report = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ if arg.starts_with("--") => {
+                eprintln!("unknown option `{}`\n{}", arg, USAGE);
+                process::exit(2);
+            }
+            _ => paths.push(arg),
+        }
+    }
+    if paths.is_empty() {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    }
+
+    let mut failed = false;
+    let mut files = Vec::new();
+    for path in &paths {
+        let diagnostics = validate(path);
+        let count = |severity| {
+            diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity == severity)
+                .count()
+        };
+        let errors = count(Severity::Error);
+        let warnings = count(Severity::Warning);
+        failed |= errors > 0;
+
+        if json_report {
+            files.push(json::Value::Object(
+                vec![
+                    ("file".to_string(), path.clone().into()),
+                    ("errors".to_string(), errors.into()),
+                    ("warnings".to_string(), warnings.into()),
+                    (
+                        "diagnostics".to_string(),
+                        diagnostics.iter().map(Diagnostic::to_json).collect(),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            ));
+        } else {
+            for diagnostic in &diagnostics {
+                println!("{}: {}", path, diagnostic);
+            }
+            println!("{}: {} error(s), {} warning(s)", path, errors, warnings);
+        }
+    }
+
+    if json_report {
+        let report = json::Value::Array(files);
+        println!("{}", json::serialize::to_string_pretty(&report).unwrap());
+    }
+    if failed {
+        process::exit(1);
+    }
+}
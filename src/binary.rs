@@ -1,9 +1,14 @@
+use crate::trace;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::borrow::Cow;
 use std::{fmt, io, mem};
 
 /// Represents a Glb loader error.
+///
+/// New kinds of malformed binary glTF may be reported in later releases, so
+/// matches on this type must include a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Io error occured.
     Io(::std::io::Error),
@@ -31,9 +36,62 @@ pub enum Error {
     ChunkType(ChunkType),
     /// Unknown chunk type.
     UnknownChunkType([u8; 4]),
+    /// Stream ended before we could read a chunk of unknown type.
+    UnknownChunkLength {
+        /// chunkType error happened at.
+        ty: [u8; 4],
+        /// chunkLength.
+        length: u32,
+        /// Actual length of data read.
+        length_read: usize,
+    },
+    /// The first chunk is not the JSON chunk.
+    FirstChunk([u8; 4]),
+    /// A chunk length is not a multiple of 4, so the chunk after it would be
+    /// misaligned.
+    UnalignedChunk {
+        /// chunkType error happened at.
+        ty: [u8; 4],
+        /// chunkLength.
+        length: u32,
+    },
+    /// Stream ended before we could read the BIN chunk, leaving some buffer
+    /// views of the JSON chunk unreadable.
+    TruncatedBin {
+        /// chunkLength.
+        length: u32,
+        /// Actual length of data read.
+        length_read: usize,
+        /// The indices of the buffer views extending past the data read.
+        views: Vec<usize>,
+    },
 }
 
 /// Binary glTF contents.
+///
+/// `Glb::from_slice` checks the header and the lengths of the JSON and BIN
+/// chunks, skipping chunks of unknown type. Loading through `Gltf::from_slice`
+/// or `import_slice` does this too, and uses the BIN chunk as the data of the
+/// first buffer when it has no URI.
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let data = std::fs::read("examples/Box.glb")?;
+/// let glb = gltf::binary::Glb::from_slice(&data)?;
+/// let root = gltf::json::Root::from_slice(&glb.json)?;
+/// assert_eq!(glb.bin.as_ref().map(|bin| bin.len()), Some(648));
+///
+/// let (document, buffers, _) = gltf::import_slice(&data)?;
+/// assert_eq!(document.buffers().len(), root.buffers.len());
+/// assert_eq!(&buffers[0][..], &glb.bin.unwrap()[..]);
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
 #[derive(Clone, Debug)]
 pub struct Glb<'a> {
     /// The header section of the `.glb` file.
@@ -65,6 +123,27 @@ pub enum ChunkType {
     Bin,
 }
 
+/// A chunk of binary glTF, as yielded by `Glb::chunks`.
+#[derive(Copy, Clone, Debug)]
+pub struct Chunk<'a> {
+    /// The chunk type, such as `b"JSON"` or `b"BIN\0"`.
+    pub ty: [u8; 4],
+    /// The byte offset of the chunk data from the start of the `.glb` file.
+    pub offset: usize,
+    /// The chunk data, excluding its header.
+    pub data: &'a [u8],
+}
+
+/// An `Iterator` that visits every chunk of binary glTF, including chunks of
+/// unknown type.
+#[derive(Clone, Debug)]
+pub struct Chunks<'a> {
+    /// The remaining chunk data.
+    data: &'a [u8],
+    /// The byte offset of `data` from the start of the `.glb` file.
+    offset: usize,
+}
+
 /// Chunk header with no data read yet.
 #[derive(Copy, Clone, Debug)]
 #[repr(C)]
@@ -99,96 +178,224 @@ impl Header {
     }
 }
 
-impl ChunkHeader {
-    fn from_reader<R: io::Read>(mut reader: R) -> Result<Self, Error> {
-        use self::Error::Io;
-        let length = reader.read_u32::<LittleEndian>().map_err(Io)?;
-        let mut ty = [0; 4];
-        reader.read_exact(&mut ty).map_err(Io)?;
-        let ty = match &ty {
-            b"JSON" => Ok(ChunkType::Json),
-            b"BIN\0" => Ok(ChunkType::Bin),
-            _ => Err(Error::UnknownChunkType(ty)),
-        }?;
-        Ok(Self { length, ty })
+impl<'a> Iterator for Chunks<'a> {
+    type Item = Result<Chunk<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let mut data = self.data;
+        let result = (|| {
+            let length = data.read_u32::<LittleEndian>().map_err(Error::Io)?;
+            let mut ty = [0; 4];
+            io::Read::read_exact(&mut data, &mut ty).map_err(Error::Io)?;
+            if length as usize > data.len() {
+                return Err(match &ty {
+                    b"JSON" => Error::ChunkLength {
+                        ty: ChunkType::Json,
+                        length,
+                        length_read: data.len(),
+                    },
+                    b"BIN\0" => Error::ChunkLength {
+                        ty: ChunkType::Bin,
+                        length,
+                        length_read: data.len(),
+                    },
+                    _ => Error::UnknownChunkLength {
+                        ty,
+                        length,
+                        length_read: data.len(),
+                    },
+                });
+            }
+            let (chunk, rest) = data.split_at(length as usize);
+            Ok((ty, chunk, rest))
+        })();
+        match result {
+            Ok((ty, chunk, rest)) => {
+                let offset = self.offset + mem::size_of::<ChunkHeader>();
+                self.offset = offset + chunk.len();
+                self.data = rest;
+                Some(Ok(Chunk {
+                    ty,
+                    offset,
+                    data: chunk,
+                }))
+            }
+            Err(error) => {
+                self.data = &[];
+                Some(Err(error))
+            }
+        }
     }
 }
 
-fn align_to_multiple_of_four(n: &mut usize) {
-    *n = (*n + 3) & !3;
+/// Returns the length of the data following the header, as declared by the
+/// header, checking it against the `available` number of bytes.
+fn contents(header: &Header, available: usize) -> Result<usize, Error> {
+    let length = (header.length as usize).saturating_sub(Header::size_of());
+    if length <= available {
+        Ok(length)
+    } else {
+        Err(Error::Length {
+            length: length as u32,
+            length_read: available,
+        })
+    }
 }
 
-fn split_binary_gltf(mut data: &[u8]) -> Result<(&[u8], Option<&[u8]>), Error> {
-    let (json, mut data) = ChunkHeader::from_reader(&mut data)
-        .and_then(|json_h| {
-            if let ChunkType::Json = json_h.ty {
-                Ok(json_h)
-            } else {
-                Err(Error::ChunkType(json_h.ty))
-            }
-        })
-        .and_then(|json_h| {
-            if json_h.length as usize <= data.len() {
-                Ok(json_h)
-            } else {
-                Err(Error::ChunkLength {
-                    ty: json_h.ty,
-                    length: json_h.length,
-                    length_read: data.len(),
+/// Explains an error splitting `data`, the bytes following the header, by
+/// finding the chunk that is cut short, and for the BIN chunk the buffer views
+/// left unreadable.
+///
+/// Errors other than truncation are returned as is.
+fn diagnose(data: &[u8], error: Error) -> Error {
+    match error {
+        Error::Length { .. } | Error::ChunkLength { .. } | Error::UnknownChunkLength { .. } => {}
+        _ => return error,
+    }
+    let mut chunks = Chunks { data, offset: 0 };
+    let json = match chunks.next() {
+        Some(Ok(chunk)) => chunk.data,
+        Some(Err(truncated)) => return truncated,
+        None => return error,
+    };
+    match chunks.find_map(Result::err) {
+        Some(Error::ChunkLength {
+            ty: ChunkType::Bin,
+            length,
+            length_read,
+        }) => {
+            let root: json::Root = match json::deserialize::from_slice(json) {
+                Ok(root) => root,
+                Err(_) => {
+                    return Error::ChunkLength {
+                        ty: ChunkType::Bin,
+                        length,
+                        length_read,
+                    }
+                }
+            };
+            // The BIN chunk holds the data of the first buffer, if it has no
+            // URI.
+            let embedded = root
+                .buffers
+                .first()
+                .is_some_and(|buffer| buffer.uri.is_none());
+            let views = root
+                .buffer_views
+                .iter()
+                .enumerate()
+                .filter(|(_, view)| embedded && view.buffer.value() == 0)
+                .filter(|(_, view)| {
+                    let offset = view.byte_offset.unwrap_or(0) as usize;
+                    offset.saturating_add(view.byte_length as usize) > length_read
                 })
+                .map(|(index, _)| index)
+                .collect();
+            Error::TruncatedBin {
+                length,
+                length_read,
+                views,
             }
-        })
-        // We have verified that json_h.length is no greater than that of
-        // data.len().
-        .map(|json_h| data.split_at(json_h.length as usize))?;
-
-    let bin = if !data.is_empty() {
-        ChunkHeader::from_reader(&mut data)
-            .and_then(|bin_h| {
-                if let ChunkType::Bin = bin_h.ty {
-                    Ok(bin_h)
-                } else {
-                    Err(Error::ChunkType(bin_h.ty))
-                }
-            })
-            .and_then(|bin_h| {
-                if bin_h.length as usize <= data.len() {
-                    Ok(bin_h)
-                } else {
-                    Err(Error::ChunkLength {
-                        ty: bin_h.ty,
-                        length: bin_h.length,
-                        length_read: data.len(),
-                    })
-                }
-            })
-            // We have verified that bin_h.length is no greater than that
-            // of data.len().
-            .map(|bin_h| data.split_at(bin_h.length as usize))
-            .map(|(x, _)| Some(x))?
-    } else {
-        None
+        }
+        Some(truncated) => truncated,
+        None => error,
+    }
+}
+
+fn align_to_multiple_of_four(n: &mut usize) {
+    *n = (*n + 3) & !3;
+}
+
+fn split_binary_gltf<'a>(data: &'a [u8]) -> Result<(&'a [u8], Option<&'a [u8]>), Error> {
+    let mut chunks = Chunks {
+        data,
+        offset: Header::size_of(),
+    };
+    // Chunks start and end on 4-byte boundaries.
+    let aligned = |chunk: Chunk<'a>| match chunk.data.len() % 4 {
+        0 => Ok(chunk),
+        _ => Err(Error::UnalignedChunk {
+            ty: chunk.ty,
+            length: chunk.data.len() as u32,
+        }),
     };
+    let json = match chunks.next() {
+        Some(Ok(chunk)) => match &chunk.ty {
+            b"JSON" => aligned(chunk)?.data,
+            _ => return Err(Error::FirstChunk(chunk.ty)),
+        },
+        Some(Err(error)) => return Err(error),
+        None => return Err(Error::Io(io::ErrorKind::UnexpectedEof.into())),
+    };
+
+    // Chunks of unknown type are skipped, as required by the specification.
+    let mut bin = None;
+    for chunk in chunks {
+        let chunk = aligned(chunk?)?;
+        match &chunk.ty {
+            b"JSON" => return Err(Error::ChunkType(ChunkType::Json)),
+            b"BIN\0" if bin.is_none() => bin = Some(chunk.data),
+            b"BIN\0" => return Err(Error::ChunkType(ChunkType::Bin)),
+            _ => {}
+        }
+    }
     Ok((json, bin))
 }
 
 impl<'a> Glb<'a> {
+    /// Returns the length in bytes of the `.glb` file, including padding.
+    fn length(&self, chunks: &[([u8; 4], &[u8])]) -> usize {
+        let mut length = mem::size_of::<Header>() + mem::size_of::<ChunkHeader>() + self.json.len();
+        align_to_multiple_of_four(&mut length);
+        if let Some(bin) = self.bin.as_ref() {
+            length += mem::size_of::<ChunkHeader>() + bin.len();
+            align_to_multiple_of_four(&mut length);
+        }
+        for (_, data) in chunks {
+            length += mem::size_of::<ChunkHeader>() + data.len();
+            align_to_multiple_of_four(&mut length);
+        }
+        length
+    }
+
     /// Writes binary glTF to a writer.
-    pub fn to_writer<W>(&self, mut writer: W) -> Result<(), crate::Error>
+    pub fn to_writer<W>(&self, writer: W) -> Result<(), crate::Error>
+    where
+        W: io::Write,
+    {
+        self.to_writer_with_chunks(&[], writer)
+    }
+
+    /// Writes binary glTF to a writer, followed by additional chunks of the
+    /// given types.
+    ///
+    /// The chunks are written after the BIN chunk in order, each padded with
+    /// zeros to a multiple of four bytes. Chunks of type `JSON` or `BIN\0` are
+    /// rejected.
+    pub fn to_writer_with_chunks<W>(
+        &self,
+        chunks: &[([u8; 4], &[u8])],
+        mut writer: W,
+    ) -> Result<(), crate::Error>
     where
         W: io::Write,
     {
+        for (ty, _) in chunks {
+            match ty {
+                b"JSON" => return Err(crate::Error::Binary(Error::ChunkType(ChunkType::Json))),
+                b"BIN\0" => return Err(crate::Error::Binary(Error::ChunkType(ChunkType::Bin))),
+                _ => {}
+            }
+        }
+
         // Write GLB header
         {
             let magic = b"glTF";
             let version = 2;
-            let mut length =
-                mem::size_of::<Header>() + mem::size_of::<ChunkHeader>() + self.json.len();
-            align_to_multiple_of_four(&mut length);
-            if let Some(bin) = self.bin.as_ref() {
-                length += mem::size_of::<ChunkHeader>() + bin.len();
-                align_to_multiple_of_four(&mut length);
-            }
+            let length = self.length(chunks);
 
             writer.write_all(&magic[..])?;
             writer.write_u32::<LittleEndian>(version)?;
@@ -210,15 +417,15 @@ impl<'a> Glb<'a> {
             }
         }
 
-        if let Some(bin) = self.bin.as_ref() {
-            let magic = b"BIN\0";
-            let mut length = bin.len();
+        let bin = self.bin.as_ref().map(|bin| (*b"BIN\0", &bin[..]));
+        for (magic, data) in bin.iter().chain(chunks) {
+            let mut length = data.len();
             align_to_multiple_of_four(&mut length);
-            let padding = length - bin.len();
+            let padding = length - data.len();
 
             writer.write_u32::<LittleEndian>(length as u32)?;
             writer.write_all(&magic[..])?;
-            writer.write_all(bin)?;
+            writer.write_all(data)?;
             for _ in 0..padding {
                 writer.write_u8(0)?;
             }
@@ -229,15 +436,16 @@ impl<'a> Glb<'a> {
 
     /// Writes binary glTF to a byte vector.
     pub fn to_vec(&self) -> Result<Vec<u8>, crate::Error> {
-        let mut length = mem::size_of::<Header>() + mem::size_of::<ChunkHeader>() + self.json.len();
-        align_to_multiple_of_four(&mut length);
-        if let Some(bin) = self.bin.as_ref() {
-            length += mem::size_of::<ChunkHeader>() + bin.len();
-            align_to_multiple_of_four(&mut length);
-        }
+        self.to_vec_with_chunks(&[])
+    }
 
-        let mut vec = Vec::with_capacity(length);
-        self.to_writer(&mut vec as &mut dyn io::Write)?;
+    /// Writes binary glTF to a byte vector, followed by additional chunks of
+    /// the given types.
+    ///
+    /// See `to_writer_with_chunks` for details.
+    pub fn to_vec_with_chunks(&self, chunks: &[([u8; 4], &[u8])]) -> Result<Vec<u8>, crate::Error> {
+        let mut vec = Vec::with_capacity(self.length(chunks));
+        self.to_writer_with_chunks(chunks, &mut vec as &mut dyn io::Write)?;
         Ok(vec)
     }
 
@@ -247,31 +455,43 @@ impl<'a> Glb<'a> {
     /// * Mandatory JSON chunk.
     /// * Optional BIN chunk.
     pub fn from_slice(mut data: &'a [u8]) -> Result<Self, crate::Error> {
-        let header = Header::from_reader(&mut data)
-            .and_then(|header| {
-                let contents_length = header.length as usize - Header::size_of();
-                if contents_length <= data.len() {
-                    Ok(header)
-                } else {
-                    Err(Error::Length {
-                        length: contents_length as u32,
-                        length_read: data.len(),
-                    })
-                }
-            })
-            .map_err(crate::Error::Binary)?;
+        let mut span = trace::span!("glb", json_bytes, bin_bytes);
+        let header = Header::from_reader(&mut data).map_err(crate::Error::Binary)?;
         match header.version {
-            2 => split_binary_gltf(data)
-                .map(|(json, bin)| Glb {
+            2 => {
+                // Chunks may not extend past the length declared by the header.
+                let contents = contents(&header, data.len())
+                    .map_err(|error| crate::Error::Binary(diagnose(data, error)))?;
+                let data = &data[..contents];
+                let (json, bin) = split_binary_gltf(data)
+                    .map_err(|error| crate::Error::Binary(diagnose(data, error)))?;
+                span.record("json_bytes", json.len());
+                span.record("bin_bytes", bin.map_or(0, <[u8]>::len));
+                Ok(Glb {
                     header,
                     json: json.into(),
                     bin: bin.map(Into::into),
                 })
-                .map_err(crate::Error::Binary),
+            }
             x => Err(crate::Error::Binary(Error::Version(x))),
         }
     }
 
+    /// Returns an `Iterator` that visits every chunk of a loaded GLB in
+    /// order, including the JSON and BIN chunks and any chunks of unknown
+    /// type.
+    pub fn chunks(mut data: &'a [u8]) -> Result<Chunks<'a>, crate::Error> {
+        let header = Header::from_reader(&mut data).map_err(crate::Error::Binary)?;
+        if header.version != 2 {
+            return Err(crate::Error::Binary(Error::Version(header.version)));
+        }
+        let contents_length = contents(&header, data.len()).map_err(crate::Error::Binary)?;
+        Ok(Chunks {
+            data: &data[..contents_length],
+            offset: Header::size_of(),
+        })
+    }
+
     /// Reads binary glTF from a generic stream of data.
     ///
     /// # Note
@@ -279,22 +499,28 @@ impl<'a> Glb<'a> {
     /// Reading terminates early if the stream does not contain valid binary
     /// glTF.
     pub fn from_reader<R: io::Read>(mut reader: R) -> Result<Self, crate::Error> {
+        let mut span = trace::span!("glb", json_bytes, bin_bytes);
         let header = Header::from_reader(&mut reader).map_err(crate::Error::Binary)?;
         match header.version {
             2 => {
-                let glb_len = header.length - Header::size_of() as u32;
-                let mut buf = vec![0; glb_len as usize];
-                if let Err(e) = reader.read_exact(&mut buf).map_err(Error::Io) {
-                    Err(crate::Error::Binary(e))
-                } else {
-                    split_binary_gltf(&buf)
-                        .map(|(json, bin)| Glb {
-                            header,
-                            json: json.to_vec().into(),
-                            bin: bin.map(<[u8]>::to_vec).map(Into::into),
-                        })
-                        .map_err(crate::Error::Binary)
-                }
+                // Read as much as the stream holds rather than trusting the
+                // declared length for allocation.
+                let glb_len = (header.length as usize).saturating_sub(Header::size_of());
+                let mut buf = Vec::new();
+                io::Read::read_to_end(&mut reader.take(glb_len as u64), &mut buf)
+                    .map_err(|e| crate::Error::Binary(Error::Io(e)))?;
+                let contents = contents(&header, buf.len())
+                    .map_err(|error| crate::Error::Binary(diagnose(&buf, error)))?;
+                let data = &buf[..contents];
+                let (json, bin) = split_binary_gltf(data)
+                    .map_err(|error| crate::Error::Binary(diagnose(data, error)))?;
+                span.record("json_bytes", json.len());
+                span.record("bin_bytes", bin.map_or(0, <[u8]>::len));
+                Ok(Glb {
+                    header,
+                    json: json.to_vec().into(),
+                    bin: bin.map(<[u8]>::to_vec).map(Into::into),
+                })
             }
             x => Err(crate::Error::Binary(Error::Version(x))),
         }
@@ -303,26 +529,250 @@ impl<'a> Glb<'a> {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match *self {
-                Error::Io(ref e) => return e.fmt(f),
-                Error::Version(_) => "unsupported version",
-                Error::Magic(_) => "not glTF magic",
-                Error::Length { .. } => "could not completely read the object",
-                Error::ChunkLength { ty, .. } => match ty {
-                    ChunkType::Json => "JSON chunk length exceeds that of slice",
-                    ChunkType::Bin => "BIN\\0 chunk length exceeds that of slice",
-                },
-                Error::ChunkType(ty) => match ty {
-                    ChunkType::Json => "was not expecting JSON chunk",
-                    ChunkType::Bin => "was not expecting BIN\\0 chunk",
-                },
-                Error::UnknownChunkType(_) => "unknown chunk type",
+        let truncated = |f: &mut fmt::Formatter, what: &str, length: u32, length_read: usize| {
+            write!(
+                f,
+                "{} declares {} bytes but only {} are present, {} bytes missing",
+                what,
+                length,
+                length_read,
+                length as usize - length_read.min(length as usize)
+            )
+        };
+        match *self {
+            Error::Io(ref e) => e.fmt(f),
+            Error::Version(_) => write!(f, "unsupported version"),
+            Error::Magic(_) => write!(f, "not glTF magic"),
+            Error::Length {
+                length,
+                length_read,
+            } => truncated(f, "header", length, length_read),
+            Error::ChunkLength {
+                ty,
+                length,
+                length_read,
+            } => match ty {
+                ChunkType::Json => truncated(f, "JSON chunk", length, length_read),
+                ChunkType::Bin => truncated(f, "BIN\\0 chunk", length, length_read),
+            },
+            Error::ChunkType(ty) => match ty {
+                ChunkType::Json => write!(f, "was not expecting JSON chunk"),
+                ChunkType::Bin => write!(f, "was not expecting BIN\\0 chunk"),
+            },
+            Error::UnknownChunkType(_) => write!(f, "unknown chunk type"),
+            Error::FirstChunk(ty) => write!(
+                f,
+                "first chunk is {} rather than JSON",
+                String::from_utf8_lossy(&ty)
+            ),
+            Error::UnalignedChunk { ty, length } => write!(
+                f,
+                "{} chunk length {} is not a multiple of 4",
+                String::from_utf8_lossy(&ty),
+                length
+            ),
+            Error::UnknownChunkLength {
+                ty,
+                length,
+                length_read,
+            } => {
+                let what = format!("{} chunk", String::from_utf8_lossy(&ty));
+                truncated(f, &what, length, length_read)
+            }
+            Error::TruncatedBin {
+                length,
+                length_read,
+                ref views,
+            } => {
+                truncated(f, "BIN\\0 chunk", length, length_read)?;
+                match views.len() {
+                    0 => Ok(()),
+                    1 => write!(f, ", leaving buffer view {} unreadable", views[0]),
+                    _ => write!(f, ", leaving buffer views {:?} unreadable", views),
+                }
             }
-        )
+        }
     }
 }
 
 impl ::std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(ty: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut bytes = (data.len() as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(ty);
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn chunks_of_unknown_type() {
+        let mut contents = chunk(b"JSON", br#"{"asset":{"version":"2.0"}}     "#);
+        contents.extend(chunk(b"XTRA", &[1, 2, 3, 4]));
+        contents.extend(chunk(b"BIN\0", &[5, 6, 7, 8]));
+        let mut data = b"glTF".to_vec();
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&(12 + contents.len() as u32).to_le_bytes());
+        data.extend(contents);
+
+        let chunks: Vec<Chunk> = Glb::chunks(&data)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(&chunks[0].ty, b"JSON");
+        assert_eq!(chunks[0].offset, 20);
+        assert_eq!(&chunks[1].ty, b"XTRA");
+        assert_eq!(chunks[1].offset, 60);
+        assert_eq!(chunks[1].data, &[1, 2, 3, 4]);
+        assert_eq!(&chunks[2].ty, b"BIN\0");
+        assert_eq!(&data[chunks[2].offset..][..4], &[5, 6, 7, 8]);
+
+        let glb = Glb::from_slice(&data).unwrap();
+        assert_eq!(glb.bin.as_deref(), Some(&[5, 6, 7, 8][..]));
+    }
+
+    #[test]
+    fn write_custom_chunks() {
+        let glb = Glb {
+            header: Header {
+                magic: *b"glTF",
+                version: 2,
+                length: 0,
+            },
+            json: br#"{"asset":{"version":"2.0"}}"#[..].into(),
+            bin: Some(vec![1, 2, 3, 4, 5].into()),
+        };
+        let data = glb
+            .to_vec_with_chunks(&[(*b"PHYS", &[6, 7]), (*b"LMAP", &[8])])
+            .unwrap();
+        assert_eq!(data.len() % 4, 0);
+        assert_eq!(
+            u32::from_le_bytes([data[8], data[9], data[10], data[11]]) as usize,
+            data.len()
+        );
+
+        let chunks: Vec<Chunk> = Glb::chunks(&data)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let types: Vec<_> = chunks.iter().map(|chunk| &chunk.ty).collect();
+        assert_eq!(types, vec![b"JSON", b"BIN\0", b"PHYS", b"LMAP"]);
+        assert_eq!(chunks[2].data, &[6, 7, 0, 0]);
+        assert_eq!(chunks[3].data, &[8, 0, 0, 0]);
+
+        assert!(glb.to_vec_with_chunks(&[(*b"BIN\0", &[])]).is_err());
+    }
+
+    #[test]
+    fn declared_lengths_are_checked() {
+        let contents = chunk(b"JSON", br#"{"asset":{"version":"2.0"}}     "#);
+        let mut data = b"glTF".to_vec();
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+        data.extend(contents.iter().copied());
+
+        match Glb::from_reader(data.as_slice()) {
+            Err(crate::Error::Binary(Error::Length { length_read, .. })) => {
+                assert_eq!(length_read, contents.len())
+            }
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+        assert!(Glb::from_slice(&data).is_err());
+
+        // A chunk extending past the declared file length is truncated.
+        data[8..12].copy_from_slice(&(12 + contents.len() as u32 - 4).to_le_bytes());
+        match Glb::from_slice(&data) {
+            Err(crate::Error::Binary(Error::ChunkLength { .. })) => {}
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+        match Glb::from_reader(data.as_slice()) {
+            Err(crate::Error::Binary(Error::ChunkLength { .. })) => {}
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    fn glb(contents: Vec<u8>) -> Vec<u8> {
+        let mut data = b"glTF".to_vec();
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&(12 + contents.len() as u32).to_le_bytes());
+        data.extend(contents);
+        data
+    }
+
+    #[test]
+    fn chunks_are_aligned() {
+        let json = br#"{"asset":{"version":"2.0"}}     "#;
+        let data = glb(chunk(b"JSON", &json[..30]));
+        match Glb::from_slice(&data) {
+            Err(crate::Error::Binary(Error::UnalignedChunk { ty, length })) => {
+                assert_eq!((&ty, length), (b"JSON", 30));
+            }
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+
+        let mut contents = chunk(b"JSON", json);
+        contents.extend(chunk(b"BIN\0", &[1, 2, 3]));
+        match Glb::from_reader(glb(contents).as_slice()) {
+            Err(crate::Error::Binary(error @ Error::UnalignedChunk { .. })) => {
+                assert_eq!(
+                    error.to_string(),
+                    "BIN\0 chunk length 3 is not a multiple of 4"
+                );
+            }
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn first_chunk_is_json() {
+        for ty in [b"BIN\0", b"XTRA"] {
+            let mut contents = chunk(ty, &[1, 2, 3, 4]);
+            contents.extend(chunk(b"JSON", br#"{"asset":{"version":"2.0"}}     "#));
+            match Glb::from_slice(&glb(contents)) {
+                Err(crate::Error::Binary(Error::FirstChunk(first))) => assert_eq!(&first, ty),
+                other => panic!("unexpected result: {:?}", other.map(|_| ())),
+            }
+        }
+    }
+
+    #[test]
+    fn truncated_bin_reports_unreadable_views() {
+        let json = br#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 16}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 8},
+                {"buffer": 0, "byteOffset": 8, "byteLength": 8},
+                {"buffer": 0, "byteLength": 4}
+            ]
+        }  "#;
+        let mut contents = chunk(b"JSON", json);
+        contents.extend(chunk(b"BIN\0", &[0; 16]));
+        let mut data = b"glTF".to_vec();
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&(12 + contents.len() as u32).to_le_bytes());
+        data.extend(contents);
+        let truncated = &data[..data.len() - 6];
+
+        for result in [Glb::from_slice(truncated), Glb::from_reader(truncated)] {
+            match result {
+                Err(crate::Error::Binary(error)) => {
+                    assert_eq!(
+                        error.to_string(),
+                        "BIN\\0 chunk declares 16 bytes but only 10 are present, \
+                         6 bytes missing, leaving buffer view 1 unreadable"
+                    );
+                    match error {
+                        Error::TruncatedBin { views, .. } => assert_eq!(views, vec![1]),
+                        other => panic!("unexpected error: {:?}", other),
+                    }
+                }
+                other => panic!("unexpected result: {:?}", other.map(|_| ())),
+            }
+        }
+    }
+}
@@ -1,4 +1,3 @@
-#[cfg(feature = "import")]
 use std::ops;
 
 use crate::Document;
@@ -47,13 +46,9 @@ pub enum Source<'a> {
 }
 
 /// Buffer data belonging to an imported glTF asset.
-#[cfg(feature = "import")]
-#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
 #[derive(Clone, Debug)]
 pub struct Data(pub Vec<u8>);
 
-#[cfg(feature = "import")]
-#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
 impl ops::Deref for Data {
     type Target = [u8];
     fn deref(&self) -> &Self::Target {
@@ -105,6 +100,18 @@ impl<'a> Buffer<'a> {
     pub fn extras(&self) -> &'a json::Extras {
         &self.json.extras
     }
+
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new().field("buffers").index(self.index)
+        })
+    }
 }
 
 impl<'a> View<'a> {
@@ -172,4 +179,16 @@ impl<'a> View<'a> {
     pub fn extras(&self) -> &'a json::Extras {
         &self.json.extras
     }
+
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new().field("bufferViews").index(self.index)
+        })
+    }
 }
@@ -0,0 +1,309 @@
+use std::borrow::Cow;
+use std::path::Path;
+use std::{fs, io};
+
+use crate::accessor::writer::pad;
+use crate::accessor::{AccessorWriter, WriteItem};
+use crate::{binary, buffer, Document, Error, Result};
+use json::root::Push;
+use json::validation::Checked;
+
+/// Builds a document from scratch, keeping its binary data in one buffer.
+///
+/// Objects pushed into the document are given the next index of their kind,
+/// and the bytes of accessors and buffer views are appended to the first
+/// buffer, which the builder adds and keeps the length of. The document is
+/// validated before it is written out, either as standard glTF with the buffer
+/// in a separate `.bin` file, or as binary glTF with the buffer in its `BIN`
+/// chunk.
+///
+/// # Examples
+///
+/// ```
+/// use gltf::accessor::AccessorWriter;
+/// use gltf::build::Builder;
+/// use gltf::json;
+/// use json::validation::Checked::Valid;
+///
+/// # fn run() -> Result<(), gltf::Error> {
+/// let mut builder = Builder::new();
+/// let mut positions = AccessorWriter::<[f32; 3]>::new();
+/// positions.extend(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+/// let positions = builder
+///     .push_accessor(positions)
+///     .expect("at least one position was written");
+///
+/// let mut attributes = std::collections::HashMap::new();
+/// attributes.insert(Valid(json::mesh::Semantic::Positions), positions);
+/// let mesh = builder.push(json::Mesh {
+///     extensions: Default::default(),
+///     extras: Default::default(),
+///     name: None,
+///     primitives: vec![json::mesh::Primitive {
+///         attributes,
+///         extensions: Default::default(),
+///         extras: Default::default(),
+///         indices: None,
+///         material: None,
+///         mode: Valid(json::mesh::Mode::Triangles),
+///         targets: None,
+///     }],
+///     weights: None,
+/// });
+/// let node = builder.push(json::Node {
+///     camera: None,
+///     children: None,
+///     extensions: Default::default(),
+///     extras: Default::default(),
+///     matrix: None,
+///     mesh: Some(mesh),
+///     name: None,
+///     rotation: None,
+///     scale: None,
+///     translation: None,
+///     skin: None,
+///     weights: None,
+/// });
+/// let scene = builder.push(json::Scene {
+///     extensions: Default::default(),
+///     extras: Default::default(),
+///     name: None,
+///     nodes: vec![node],
+/// });
+/// builder.root_mut().scene = Some(scene);
+///
+/// let glb = builder.to_glb()?;
+/// let (document, buffers, _) = gltf::import_slice(&glb)?;
+/// assert_eq!(document.meshes().len(), 1);
+/// assert_eq!(buffers[0].len(), 36);
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Builder {
+    /// The document being built.
+    root: json::Root,
+
+    /// The contents of the first buffer.
+    data: Vec<u8>,
+}
+
+impl Builder {
+    /// Creates a document holding only an empty buffer.
+    pub fn new() -> Self {
+        let mut root = json::Root::default();
+        root.buffers.push(json::Buffer {
+            byte_length: 0,
+            #[cfg(feature = "names")]
+            name: None,
+            uri: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        });
+        Self {
+            root,
+            data: Vec::new(),
+        }
+    }
+
+    /// Returns the document built so far.
+    pub fn root(&self) -> &json::Root {
+        &self.root
+    }
+
+    /// Returns the document built so far, for setting properties that have no
+    /// dedicated method, such as the default scene.
+    pub fn root_mut(&mut self) -> &mut json::Root {
+        &mut self.root
+    }
+
+    /// Returns the contents of the first buffer written so far.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Appends a top-level object, such as a mesh or a node, returning its
+    /// index.
+    pub fn push<T>(&mut self, value: T) -> json::Index<T>
+    where
+        json::Root: Push<T>,
+    {
+        self.root.push(value)
+    }
+
+    /// Appends `bytes` to the first buffer, starting on a four byte boundary,
+    /// and adds a buffer view of them, returning its index.
+    pub fn push_bytes(
+        &mut self,
+        bytes: &[u8],
+        target: Option<json::buffer::Target>,
+    ) -> json::Index<json::buffer::View> {
+        pad(&mut self.data);
+        let offset = self.data.len();
+        self.data.extend_from_slice(bytes);
+        self.root.buffers[0].byte_length = self.data.len() as u32;
+        self.root.push(json::buffer::View {
+            buffer: json::Index::new(0),
+            byte_length: bytes.len() as u32,
+            byte_offset: Some(offset as u32),
+            byte_stride: None,
+            #[cfg(feature = "names")]
+            name: None,
+            target: target.map(Checked::Valid),
+            extensions: Default::default(),
+            extras: Default::default(),
+        })
+    }
+
+    /// Appends the items of `writer` to the first buffer and adds a buffer view
+    /// and an accessor for them, returning the index of the accessor, or
+    /// `None` if `writer` is empty.
+    ///
+    /// See [`AccessorWriter::finish`].
+    ///
+    /// [`AccessorWriter::finish`]: ../accessor/struct.AccessorWriter.html#method.finish
+    pub fn push_accessor<T: WriteItem>(
+        &mut self,
+        writer: AccessorWriter<T>,
+    ) -> Option<json::Index<json::Accessor>> {
+        writer.finish(&mut self.root, json::Index::new(0), &mut self.data)
+    }
+
+    /// Checks the document built so far, returning `Error::Validation` with
+    /// every problem found.
+    pub fn validate(&self) -> Result<()> {
+        crate::validate(&self.root)
+    }
+
+    /// Validates the document and returns it together with its buffer data,
+    /// as if it had been imported.
+    ///
+    /// Buffers other than the first, which the builder does not hold the data
+    /// of, are left empty.
+    pub fn into_document(self) -> Result<(Document, Vec<buffer::Data>)> {
+        let Builder { root, mut data } = self;
+        let document = Document::from_json(root)?;
+        pad(&mut data);
+        let mut buffers = vec![buffer::Data(data)];
+        buffers.resize_with(document.buffers().len(), || buffer::Data(Vec::new()));
+        Ok((document, buffers))
+    }
+
+    /// Validates the document and writes it as binary glTF.
+    pub fn to_glb(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+        let json = json::serialize::to_vec(&self.root).map_err(Error::Serialize)?;
+        let glb = binary::Glb {
+            header: binary::Header {
+                magic: *b"glTF",
+                version: 2,
+                // `to_vec` computes the final length.
+                length: 0,
+            },
+            json: Cow::Owned(json),
+            bin: Some(Cow::Borrowed(&self.data)),
+        };
+        glb.to_vec()
+    }
+
+    /// Validates the document and writes it to the file system as binary
+    /// glTF.
+    pub fn write_glb<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let glb = self.to_glb()?;
+        fs::write(path, glb).map_err(Error::Io)
+    }
+
+    /// Validates the document and writes it to the file system as standard
+    /// glTF, with the first buffer in a file next to it of the same name and
+    /// the `.bin` extension.
+    pub fn write_gltf<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.validate()?;
+        let path = path.as_ref();
+        let bin = path.with_extension("bin");
+        let name = bin
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "path has no file name",
+                ))
+            })?;
+
+        let mut root = self.root.clone();
+        root.buffers[0].uri = Some(urlencoding::encode(name).into_owned());
+        let json = json::serialize::to_vec_pretty(&root).map_err(Error::Serialize)?;
+        fs::write(path, json).map_err(Error::Io)?;
+        fs::write(&bin, &self.data).map_err(Error::Io)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, feature = "import"))]
+mod tests {
+    use super::*;
+
+    fn builder() -> Builder {
+        let mut builder = Builder::new();
+        let mut indices = AccessorWriter::<u16>::new();
+        indices.extend(vec![0, 1, 2]);
+        let indices = builder.push_accessor(indices).unwrap();
+        let view = builder.push_bytes(&[1, 2, 3, 4, 5], None);
+        let mut positions = AccessorWriter::<[f32; 3]>::new();
+        positions.extend(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+        let positions = builder.push_accessor(positions).unwrap();
+
+        assert_eq!(indices.value(), 0);
+        assert_eq!(view.value(), 1);
+        assert_eq!(positions.value(), 1);
+        assert_eq!(builder.root().buffer_views[1].byte_offset, Some(8));
+        assert_eq!(builder.root().buffer_views[2].byte_offset, Some(16));
+        assert_eq!(builder.root().buffers[0].byte_length, 52);
+        builder
+    }
+
+    #[test]
+    fn glb_roundtrip() {
+        let builder = builder();
+        let glb = builder.to_glb().unwrap();
+        assert_eq!(glb.len() % 4, 0);
+        let (document, buffers, _) = crate::import_slice(&glb).unwrap();
+        assert_eq!(document.accessors().len(), 2);
+        assert_eq!(buffers[0].0, builder.data());
+    }
+
+    #[test]
+    fn gltf_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("gltf-build-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("built model.gltf");
+        let builder = builder();
+        builder.write_gltf(&path).unwrap();
+
+        let (document, buffers, _) = crate::import(&path).unwrap();
+        let buffer = document.buffers().next().unwrap();
+        assert!(matches!(
+            buffer.source(),
+            buffer::Source::Uri("built%20model.bin")
+        ));
+        assert_eq!(buffers[0].0, builder.data());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn invalid() {
+        let mut builder = Builder::new();
+        let node: json::Node = json::deserialize::from_str(r#"{"mesh": 0}"#).unwrap();
+        builder.push(node);
+        assert!(matches!(builder.to_glb(), Err(Error::Validation(_))));
+        assert!(matches!(builder.into_document(), Err(Error::Validation(_))));
+    }
+}
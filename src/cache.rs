@@ -0,0 +1,289 @@
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{self, Read};
+use std::{fmt, result};
+
+use crate::{buffer, image, Document, Result};
+
+/// The magic bytes at the start of every cache.
+const MAGIC: [u8; 4] = *b"glTC";
+
+/// The version of the cache format written by `write`.
+const VERSION: u32 = 2;
+
+/// Every image format, in the order of their tags in the cache.
+const FORMATS: &[image::Format] = &[
+    image::Format::R8,
+    image::Format::R8G8,
+    image::Format::R8G8B8,
+    image::Format::R8G8B8A8,
+    image::Format::R16,
+    image::Format::R16G16,
+    image::Format::R16G16B16,
+    image::Format::R16G16B16A16,
+    image::Format::R32G32B32FLOAT,
+    image::Format::R32G32B32A32FLOAT,
+];
+
+/// Represents an error reading a cache.
+#[derive(Debug)]
+pub enum Error {
+    /// Io error occured.
+    Io(io::Error),
+
+    /// Magic says that the data is not a cache.
+    Magic([u8; 4]),
+
+    /// The cache was written by an unsupported version of the format.
+    Version(u32),
+
+    /// The data ended before the end of the cache.
+    Truncated,
+
+    /// The buffer data does not match the buffers of the document.
+    Buffers,
+
+    /// Unknown image format tag.
+    ImageFormat(u8),
+
+    /// The document could not be encoded.
+    Encode(rmp_serde::encode::Error),
+
+    /// The cached document could not be decoded.
+    Decode(rmp_serde::decode::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => e.fmt(f),
+            Error::Magic(_) => write!(f, "not glTF cache magic"),
+            Error::Version(version) => write!(f, "unsupported cache version {}", version),
+            Error::Truncated => write!(f, "cache data ended unexpectedly"),
+            Error::Buffers => write!(f, "cached buffer data does not match the document"),
+            Error::ImageFormat(tag) => write!(f, "unknown image format tag {}", tag),
+            Error::Encode(ref e) => e.fmt(f),
+            Error::Decode(ref e) => e.fmt(f),
+        }
+    }
+}
+
+impl ::std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => Error::Truncated,
+            _ => Error::Io(error),
+        }
+    }
+}
+
+/// Writes a length-prefixed byte string.
+fn write_bytes<W: io::Write>(writer: &mut W, bytes: &[u8]) -> io::Result<()> {
+    writer.write_u64::<LittleEndian>(bytes.len() as u64)?;
+    writer.write_all(bytes)
+}
+
+/// Reads a length-prefixed byte string, without trusting the length for
+/// allocation.
+fn read_bytes<R: Read>(reader: &mut R) -> result::Result<Vec<u8>, Error> {
+    let length = reader.read_u64::<LittleEndian>()?;
+    let mut bytes = Vec::new();
+    reader.take(length).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != length {
+        return Err(Error::Truncated);
+    }
+    Ok(bytes)
+}
+
+/// Writes an imported document, with its buffer and decoded image data, to a
+/// cache that `read` loads quickly.
+///
+/// The cache holds the document in MessagePack, a binary encoding that keeps
+/// the arbitrary JSON values of bounds and extras, along with the buffer data
+/// and the image data already decoded, so reading it back skips JSON parsing,
+/// validation, and base64 and image decoding. It is meant for build caches
+/// and warm starts, not for distribution; use glTF or GLB for that.
+///
+/// The document is validated before anything is written, so that `read` can
+/// trust it; validation errors are reported as `Error::Validation`.
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let (document, buffers, images) = gltf::import("examples/Box.gltf")?;
+/// let mut cache = Vec::new();
+/// gltf::cache::write(&mut cache, &document, &buffers, &images)?;
+/// let (document, buffers, images) = gltf::cache::read(cache.as_slice())?;
+/// # let _ = (document, buffers, images);
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+pub fn write<W: io::Write>(
+    mut writer: W,
+    document: &Document,
+    buffers: &[buffer::Data],
+    images: &[image::Data],
+) -> Result<()> {
+    document.validate()?;
+    let encoded = rmp_serde::to_vec_named(&document.0)
+        .map_err(|error| crate::Error::Cache(Error::Encode(error)))?;
+    let io = |error| crate::Error::Cache(Error::Io(error));
+    writer.write_all(&MAGIC).map_err(io)?;
+    writer.write_u32::<LittleEndian>(VERSION).map_err(io)?;
+    write_bytes(&mut writer, &encoded).map_err(io)?;
+    writer
+        .write_u32::<LittleEndian>(buffers.len() as u32)
+        .map_err(io)?;
+    for buffer in buffers {
+        write_bytes(&mut writer, &buffer.0).map_err(io)?;
+    }
+    writer
+        .write_u32::<LittleEndian>(images.len() as u32)
+        .map_err(io)?;
+    for image in images {
+        let tag = FORMATS.iter().position(|&format| format == image.format);
+        writer.write_u8(tag.unwrap_or(0) as u8).map_err(io)?;
+        writer.write_u32::<LittleEndian>(image.width).map_err(io)?;
+        writer.write_u32::<LittleEndian>(image.height).map_err(io)?;
+        write_bytes(&mut writer, &image.pixels).map_err(io)?;
+    }
+    Ok(())
+}
+
+/// Reads a document with its buffer and image data from a cache written by
+/// `write`.
+///
+/// The document is not validated again, since `write` only stores valid
+/// documents; only its buffers are checked against the buffer data. A cache
+/// must therefore come from `write`, as a hand-made one may produce a
+/// document that panics when used.
+pub fn read<R: Read>(mut reader: R) -> Result<(Document, Vec<buffer::Data>, Vec<image::Data>)> {
+    let mut magic = [0; 4];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|error| crate::Error::Cache(error.into()))?;
+    if magic != MAGIC {
+        return Err(crate::Error::Cache(Error::Magic(magic)));
+    }
+    let (encoded, buffers, images) = read_body(&mut reader).map_err(crate::Error::Cache)?;
+    let root = rmp_serde::from_slice(&encoded)
+        .map_err(|error| crate::Error::Cache(Error::Decode(error)))?;
+    let document = Document::from_json_without_validation(root);
+    let matches = document.buffers().len() == buffers.len()
+        && document
+            .buffers()
+            .zip(&buffers)
+            .all(|(buffer, data)| data.len() >= buffer.length());
+    if !matches {
+        return Err(crate::Error::Cache(Error::Buffers));
+    }
+    Ok((document, buffers, images))
+}
+
+/// Reads everything after the magic bytes of a cache.
+#[allow(clippy::type_complexity)]
+fn read_body<R: Read>(
+    reader: &mut R,
+) -> result::Result<(Vec<u8>, Vec<buffer::Data>, Vec<image::Data>), Error> {
+    let version = reader.read_u32::<LittleEndian>()?;
+    if version != VERSION {
+        return Err(Error::Version(version));
+    }
+    let encoded = read_bytes(reader)?;
+    let count = reader.read_u32::<LittleEndian>()?;
+    let mut buffers = Vec::new();
+    for _ in 0..count {
+        buffers.push(buffer::Data(read_bytes(reader)?));
+    }
+    let count = reader.read_u32::<LittleEndian>()?;
+    let mut images = Vec::new();
+    for _ in 0..count {
+        let tag = reader.read_u8()?;
+        let format = *FORMATS.get(tag as usize).ok_or(Error::ImageFormat(tag))?;
+        let width = reader.read_u32::<LittleEndian>()?;
+        let height = reader.read_u32::<LittleEndian>()?;
+        images.push(image::Data {
+            pixels: read_bytes(reader)?,
+            format,
+            width,
+            height,
+        });
+    }
+    Ok((encoded, buffers, images))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let json = r#"{"asset": {"version": "2.0"}, "buffers": [{"byteLength": 4}]}"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let buffers = vec![buffer::Data(vec![1, 2, 3, 4])];
+        let images = vec![image::Data {
+            pixels: vec![0, 1, 2, 3, 4, 5],
+            format: image::Format::R8G8B8,
+            width: 2,
+            height: 1,
+        }];
+
+        let mut cache = Vec::new();
+        write(&mut cache, &document, &buffers, &images).unwrap();
+        let (read_document, read_buffers, read_images) = read(cache.as_slice()).unwrap();
+        assert_eq!(read_document.buffers().next().unwrap().length(), 4);
+        assert_eq!(read_buffers[0].0, buffers[0].0);
+        assert_eq!(read_images[0].pixels, images[0].pixels);
+        assert_eq!(read_images[0].format, image::Format::R8G8B8);
+        assert_eq!((read_images[0].width, read_images[0].height), (2, 1));
+
+        match read(&cache[..cache.len() - 1]) {
+            Err(crate::Error::Cache(Error::Truncated)) => {}
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+        match read(&b"glTF"[..]) {
+            Err(crate::Error::Cache(Error::Magic(_))) => {}
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn keeps_json_values() {
+        let (document, buffers, images) = crate::import("examples/Box.gltf").unwrap();
+        let mut cache = Vec::new();
+        write(&mut cache, &document, &buffers, &images).unwrap();
+        let (read_document, _, _) = read(cache.as_slice()).unwrap();
+        assert_eq!(
+            json::serialize::to_value(&read_document.0).unwrap(),
+            json::serialize::to_value(&document.0).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_contents() {
+        let json = r#"{"asset": {"version": "2.0"}, "buffers": [{"byteLength": 4}]}"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+
+        let mut cache = Vec::new();
+        write(&mut cache, &document, &[buffer::Data(vec![1, 2])], &[]).unwrap();
+        match read(cache.as_slice()) {
+            Err(crate::Error::Cache(Error::Buffers)) => {}
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+
+        let invalid = r#"{"asset": {"version": "2.0"}, "scene": 0}"#;
+        let document =
+            Document::from_json_without_validation(json::Root::from_str(invalid).unwrap());
+        let mut cache = Vec::new();
+        match write(&mut cache, &document, &[], &[]) {
+            Err(crate::Error::Validation(_)) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(cache.is_empty());
+    }
+}
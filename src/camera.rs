@@ -73,7 +73,7 @@ impl<'a> Camera<'a> {
     }
 
     /// Returns the camera's projection.
-    pub fn projection(&self) -> Projection {
+    pub fn projection(&self) -> Projection<'_> {
         match self.json.type_.unwrap() {
             json::camera::Type::Orthographic => {
                 let json = self.json.orthographic.as_ref().unwrap();
@@ -86,10 +86,34 @@ impl<'a> Camera<'a> {
         }
     }
 
+    /// Returns the column-major projection matrix of the camera.
+    ///
+    /// `aspect_ratio` overrides the aspect ratio stored in the camera, which
+    /// is typically the aspect ratio of the viewport being rendered to. See
+    /// [`Perspective::matrix`] and [`Orthographic::matrix`] for details.
+    pub fn projection_matrix(&self, aspect_ratio: Option<f32>) -> [[f32; 4]; 4] {
+        match self.projection() {
+            Projection::Orthographic(orthographic) => orthographic.matrix(aspect_ratio),
+            Projection::Perspective(perspective) => perspective.matrix(aspect_ratio),
+        }
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &'a json::Extras {
         &self.json.extras
     }
+
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new().field("cameras").index(self.index)
+        })
+    }
 }
 
 impl<'a> Orthographic<'a> {
@@ -118,6 +142,22 @@ impl<'a> Orthographic<'a> {
         self.json.znear
     }
 
+    /// Returns the column-major orthographic projection matrix.
+    ///
+    /// When `aspect_ratio` is provided the horizontal magnification is
+    /// replaced by `ymag * aspect_ratio`, preserving the vertical extent.
+    pub fn matrix(&self, aspect_ratio: Option<f32>) -> [[f32; 4]; 4] {
+        let r = aspect_ratio.map_or(self.xmag(), |aspect_ratio| self.ymag() * aspect_ratio);
+        let t = self.ymag();
+        let (n, f) = (self.znear(), self.zfar());
+        [
+            [1.0 / r, 0.0, 0.0, 0.0],
+            [0.0, 1.0 / t, 0.0, 0.0],
+            [0.0, 0.0, 2.0 / (n - f), 0.0],
+            [0.0, 0.0, (f + n) / (n - f), 1.0],
+        ]
+    }
+
     ///  Optional application specific data.
     pub fn extras(&self) -> &'a json::Extras {
         &self.json.extras
@@ -150,8 +190,99 @@ impl<'a> Perspective<'a> {
         self.json.znear
     }
 
+    /// Returns the column-major perspective projection matrix.
+    ///
+    /// The aspect ratio used is `aspect_ratio` if provided, otherwise the
+    /// aspect ratio of the camera, otherwise 1. An infinite projection is
+    /// returned when the camera has no far clipping plane.
+    pub fn matrix(&self, aspect_ratio: Option<f32>) -> [[f32; 4]; 4] {
+        let a = aspect_ratio.or_else(|| self.aspect_ratio()).unwrap_or(1.0);
+        let t = (0.5 * self.yfov()).tan();
+        let n = self.znear();
+        match self.zfar() {
+            Some(f) => [
+                [1.0 / (a * t), 0.0, 0.0, 0.0],
+                [0.0, 1.0 / t, 0.0, 0.0],
+                [0.0, 0.0, (f + n) / (n - f), -1.0],
+                [0.0, 0.0, 2.0 * f * n / (n - f), 0.0],
+            ],
+            None => [
+                [1.0 / (a * t), 0.0, 0.0, 0.0],
+                [0.0, 1.0 / t, 0.0, 0.0],
+                [0.0, 0.0, -1.0, -1.0],
+                [0.0, 0.0, -2.0 * n, 0.0],
+            ],
+        }
+    }
+
     ///  Optional application specific data.
     pub fn extras(&self) -> &'a json::Extras {
         &self.json.extras
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::math::*;
+    use crate::Document;
+
+    fn document(cameras: &str) -> Document {
+        let json = format!(
+            r#"{{"asset": {{"version": "2.0"}}, "cameras": {}}}"#,
+            cameras
+        );
+        Document::from_json(json::Root::from_str(&json).unwrap()).unwrap()
+    }
+
+    /// Projects a point in view space into normalized device coordinates.
+    fn project(matrix: [[f32; 4]; 4], [x, y, z]: [f32; 3]) -> [f32; 3] {
+        let v = Matrix4::from_array(matrix) * Vector4::new(x, y, z, 1.0);
+        [v.x / v.w, v.y / v.w, v.z / v.w]
+    }
+
+    #[test]
+    fn perspective_projection() {
+        let document = document(
+            r#"[
+                {"type": "perspective", "perspective": {"yfov": 1.0, "znear": 0.5, "zfar": 10.0, "aspectRatio": 2.0}},
+                {"type": "perspective", "perspective": {"yfov": 1.0, "znear": 0.5}}
+            ]"#,
+        );
+        let finite = document.cameras().next().unwrap();
+        let matrix = finite.projection_matrix(None);
+        assert_relative_eq!(project(matrix, [0.0, 0.0, -0.5])[2], -1.0, epsilon = 1e-5);
+        assert_relative_eq!(project(matrix, [0.0, 0.0, -10.0])[2], 1.0, epsilon = 1e-5);
+        let top = 0.5_f32.tan();
+        assert_relative_eq!(
+            project(matrix, [2.0 * top, top, -1.0])[0],
+            1.0,
+            epsilon = 1e-5
+        );
+        assert_relative_eq!(
+            project(matrix, [2.0 * top, top, -1.0])[1],
+            1.0,
+            epsilon = 1e-5
+        );
+
+        let overridden = finite.projection_matrix(Some(1.0));
+        assert_relative_eq!(
+            project(overridden, [top, top, -1.0])[0],
+            1.0,
+            epsilon = 1e-5
+        );
+
+        let infinite = document.cameras().nth(1).unwrap().projection_matrix(None);
+        assert_relative_eq!(project(infinite, [0.0, 0.0, -0.5])[2], -1.0, epsilon = 1e-5);
+        assert_relative_eq!(project(infinite, [0.0, 0.0, -1e6])[2], 1.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn orthographic_projection() {
+        let document = document(
+            r#"[{"type": "orthographic", "orthographic": {"xmag": 2.0, "ymag": 1.0, "znear": 1.0, "zfar": 11.0}}]"#,
+        );
+        let matrix = document.cameras().next().unwrap().projection_matrix(None);
+        assert_eq!(project(matrix, [2.0, 1.0, -1.0]), [1.0, 1.0, -1.0]);
+        assert_eq!(project(matrix, [-2.0, -1.0, -11.0]), [-1.0, -1.0, 1.0]);
+    }
+}
@@ -0,0 +1,982 @@
+use std::sync::Mutex;
+use std::{fmt, result};
+
+use crate::visit::{self, Kind};
+use crate::Document;
+use json::{Path, Value};
+
+/// Represents an error applying an edit.
+#[derive(Debug)]
+pub enum Error {
+    /// There is no object of the given kind and index.
+    Index(Kind, usize),
+
+    /// The JSON pointer does not lead into the object.
+    Pointer(String),
+
+    /// The object or the edited JSON does not convert between JSON and an
+    /// object of its kind.
+    Json(json::Error),
+
+    /// The object may not be removed while other objects refer to it.
+    Referenced(Kind, usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Index(kind, index) => write!(f, "no {:?} at index {}", kind, index),
+            Error::Pointer(ref pointer) => write!(f, "invalid JSON pointer {:?}", pointer),
+            Error::Json(ref e) => e.fmt(f),
+            Error::Referenced(kind, index) => {
+                write!(f, "{:?} {} is still referenced", kind, index)
+            }
+        }
+    }
+}
+
+impl ::std::error::Error for Error {}
+
+/// Result type for convenience.
+pub type Result<T = ()> = result::Result<T, Error>;
+
+/// An edit of the root-level objects of a document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Edit {
+    /// Sets the value at a JSON pointer, such as `/translation`, within an
+    /// object. Missing object members along the pointer are created.
+    Set {
+        /// The kind of the object.
+        kind: Kind,
+        /// The index of the object.
+        index: usize,
+        /// The JSON pointer of the value within the object.
+        pointer: String,
+        /// The new value.
+        value: Value,
+    },
+
+    /// Inserts an object, given as JSON, shifting later objects of the same
+    /// kind up by one and rewriting every index into the array accordingly.
+    ///
+    /// Indices within `value` refer to objects after the insertion.
+    Insert {
+        /// The kind of the object.
+        kind: Kind,
+        /// The index of the new object, at most the number of objects.
+        index: usize,
+        /// The JSON of the new object.
+        value: Value,
+    },
+
+    /// Removes an object that no other object refers to, shifting later
+    /// objects of the same kind down by one and rewriting every index into
+    /// the array accordingly.
+    Remove {
+        /// The kind of the object.
+        kind: Kind,
+        /// The index of the object.
+        index: usize,
+    },
+
+    /// Redirects every reference to one object to another of the same kind.
+    Remap {
+        /// The kind of the objects.
+        kind: Kind,
+        /// The index of the object currently referred to.
+        from: usize,
+        /// The index of the object to refer to instead.
+        to: usize,
+    },
+}
+
+/// An applied edit, with what is needed to revert it.
+#[derive(Clone, Debug)]
+enum Entry {
+    /// An object was replaced, changing the value at `path`.
+    Replace {
+        kind: Kind,
+        index: usize,
+        path: Path,
+        old: Value,
+        new: Value,
+    },
+
+    /// An object was inserted.
+    Insert {
+        kind: Kind,
+        index: usize,
+        value: Value,
+    },
+
+    /// An object was removed.
+    Remove {
+        kind: Kind,
+        index: usize,
+        value: Value,
+    },
+
+    /// The references at the given positions in visiting order were
+    /// redirected.
+    Remap {
+        from: usize,
+        to: usize,
+        slots: Vec<usize>,
+    },
+}
+
+/// The undo and redo stacks of an `Editor`.
+#[derive(Clone, Debug, Default)]
+struct Journal {
+    undo: Vec<Entry>,
+    redo: Vec<Entry>,
+}
+
+/// Mutable glTF JSON wrapper, applying edits to the root-level objects while
+/// keeping the indices between them consistent.
+///
+/// With a journal, every edit is recorded so that it can be reverted with
+/// `undo` and reapplied with `redo`, as editor applications need. Edits are
+/// checked for indices and JSON structure but the document as a whole is only
+/// validated by `into_document`.
+///
+/// Observers registered with `observe` are called with the JSON path of every
+/// value changed by an edit, its undo, or its redo, so that user interfaces
+/// can refresh only what changed. Inserting or removing an object reports the
+/// whole array holding it, such as `nodes`, as its later objects move, along
+/// with every index rewritten as a result. A clone of an editor starts without
+/// observers.
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use gltf::edit::{Edit, Editor};
+/// use gltf::usage::Kind;
+///
+/// let gltf = gltf::Gltf::open("examples/Box.gltf")?;
+/// let mut editor = Editor::with_journal(gltf.document.into_json());
+/// editor.apply(Edit::Set {
+///     kind: Kind::Node,
+///     index: 0,
+///     pointer: "/translation".to_string(),
+///     value: gltf::json::Value::from(vec![0.0, 1.0, 0.0]),
+/// })?;
+/// assert_eq!(editor.root().nodes[0].translation, Some([0.0, 1.0, 0.0]));
+/// assert!(editor.undo()?);
+/// assert_eq!(editor.root().nodes[0].translation, None);
+/// assert!(editor.redo()?);
+/// let document = editor.into_document()?;
+/// # let _ = document;
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+pub struct Editor {
+    /// The JSON being edited.
+    root: json::Root,
+
+    /// The journal, if edits are recorded.
+    journal: Option<Journal>,
+
+    /// The registered observers, in a mutex only so that the editor is `Sync`.
+    /// They are reached through `get_mut` and never locked.
+    observers: Mutex<Vec<(ObserverId, Box<Observer>)>>,
+
+    /// The identifier of the next observer.
+    next_observer: usize,
+
+    /// The paths changed by the edit being applied.
+    changes: Vec<Path>,
+}
+
+/// A callback receiving the JSON path of a changed value.
+pub type Observer = dyn FnMut(&Path) + Send;
+
+/// Identifies an observer registered with `Editor::observe`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ObserverId(usize);
+
+impl fmt::Debug for Editor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Editor")
+            .field("root", &self.root)
+            .field("journal", &self.journal)
+            .finish()
+    }
+}
+
+impl Clone for Editor {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            journal: self.journal.clone(),
+            observers: Mutex::new(Vec::new()),
+            next_observer: self.next_observer,
+            changes: self.changes.clone(),
+        }
+    }
+}
+
+/// Evaluates `$body` with `$items` bound to the array of objects of `$kind`.
+/// Extension arrays that do not exist are bound to an empty array.
+macro_rules! with_items {
+    ($root:expr, $kind:expr, |$items:ident| $body:expr) => {
+        match $kind {
+            Kind::Accessor => {
+                let $items = &mut $root.accessors;
+                $body
+            }
+            Kind::Animation => {
+                let $items = &mut $root.animations;
+                $body
+            }
+            Kind::Buffer => {
+                let $items = &mut $root.buffers;
+                $body
+            }
+            Kind::BufferView => {
+                let $items = &mut $root.buffer_views;
+                $body
+            }
+            Kind::Camera => {
+                let $items = &mut $root.cameras;
+                $body
+            }
+            Kind::Image => {
+                let $items = &mut $root.images;
+                $body
+            }
+            #[cfg(feature = "KHR_lights_punctual")]
+            Kind::Light => {
+                let mut empty = Vec::<json::extensions::scene::khr_lights_punctual::Light>::new();
+                let $items = match $root
+                    .extensions
+                    .as_mut()
+                    .and_then(|extensions| extensions.khr_lights_punctual.as_mut())
+                {
+                    Some(extension) => &mut extension.lights,
+                    None => &mut empty,
+                };
+                $body
+            }
+            Kind::Material => {
+                let $items = &mut $root.materials;
+                $body
+            }
+            Kind::Mesh => {
+                let $items = &mut $root.meshes;
+                $body
+            }
+            Kind::Node => {
+                let $items = &mut $root.nodes;
+                $body
+            }
+            Kind::Sampler => {
+                let $items = &mut $root.samplers;
+                $body
+            }
+            Kind::Scene => {
+                let $items = &mut $root.scenes;
+                $body
+            }
+            Kind::Skin => {
+                let $items = &mut $root.skins;
+                $body
+            }
+            Kind::Texture => {
+                let $items = &mut $root.textures;
+                $body
+            }
+            #[cfg(feature = "KHR_materials_variants")]
+            Kind::Variant => {
+                let mut empty =
+                    Vec::<json::extensions::scene::khr_materials_variants::Variant>::new();
+                let $items = match $root
+                    .extensions
+                    .as_mut()
+                    .and_then(|extensions| extensions.khr_materials_variants.as_mut())
+                {
+                    Some(extension) => &mut extension.variants,
+                    None => &mut empty,
+                };
+                $body
+            }
+        }
+    };
+}
+
+/// Sets the value at a JSON pointer, creating missing object members.
+fn set_pointer(target: &mut Value, pointer: &str, value: Value) -> Result {
+    let invalid = || Error::Pointer(pointer.to_string());
+    if pointer.is_empty() {
+        *target = value;
+        return Ok(());
+    }
+    if !pointer.starts_with('/') {
+        return Err(invalid());
+    }
+    let mut current = target;
+    let tokens: Vec<String> = pointer[1..]
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    let (last, parents) = tokens.split_last().unwrap();
+    for token in parents {
+        current = match current {
+            Value::Object(map) => map
+                .entry(token.clone())
+                .or_insert_with(|| Value::Object(Default::default())),
+            Value::Array(array) => token
+                .parse::<usize>()
+                .ok()
+                .and_then(move |index| array.get_mut(index))
+                .ok_or_else(invalid)?,
+            _ => return Err(invalid()),
+        };
+    }
+    match current {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+        }
+        Value::Array(array) => {
+            let slot = last
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| array.get_mut(index))
+                .ok_or_else(invalid)?;
+            *slot = value;
+        }
+        _ => return Err(invalid()),
+    }
+    Ok(())
+}
+
+impl Editor {
+    /// Creates an editor without a journal.
+    pub fn new(root: json::Root) -> Self {
+        Self {
+            root,
+            journal: None,
+            observers: Mutex::new(Vec::new()),
+            next_observer: 0,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Creates an editor that records every edit for `undo` and `redo`.
+    pub fn with_journal(root: json::Root) -> Self {
+        Self {
+            journal: Some(Journal::default()),
+            ..Self::new(root)
+        }
+    }
+
+    /// Starts or stops recording edits. Stopping discards the journal.
+    pub fn set_journal(&mut self, enabled: bool) {
+        match (enabled, self.journal.is_some()) {
+            (true, false) => self.journal = Some(Journal::default()),
+            (false, true) => self.journal = None,
+            _ => {}
+        }
+    }
+
+    /// Registers a callback to be called with the JSON path of every value
+    /// changed from now on.
+    pub fn observe<F>(&mut self, observer: F) -> ObserverId
+    where
+        F: FnMut(&Path) + Send + 'static,
+    {
+        let id = ObserverId(self.next_observer);
+        self.next_observer += 1;
+        self.observers_mut().push((id, Box::new(observer)));
+        id
+    }
+
+    /// Unregisters an observer, returning `false` if it is not registered.
+    pub fn unobserve(&mut self, id: ObserverId) -> bool {
+        let observers = self.observers_mut();
+        let len = observers.len();
+        observers.retain(|(other, _)| *other != id);
+        observers.len() != len
+    }
+
+    /// Returns the registered observers.
+    fn observers_mut(&mut self) -> &mut Vec<(ObserverId, Box<Observer>)> {
+        // The mutex is never locked, so it is never poisoned.
+        self.observers
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Calls the observers with the paths changed since the last call.
+    fn notify(&mut self) {
+        let mut changes = std::mem::take(&mut self.changes);
+        changes.dedup();
+        for (_, observer) in self.observers_mut() {
+            for path in &changes {
+                observer(path);
+            }
+        }
+    }
+
+    /// Returns the JSON being edited.
+    pub fn root(&self) -> &json::Root {
+        &self.root
+    }
+
+    /// Unwraps the JSON being edited.
+    pub fn into_root(self) -> json::Root {
+        self.root
+    }
+
+    /// Validates the edited JSON and loads it as a document.
+    pub fn into_document(self) -> crate::Result<Document> {
+        Document::from_json(self.root)
+    }
+
+    /// Applies an edit, recording it in the journal if there is one and
+    /// discarding any edits that could be redone.
+    ///
+    /// The document is left unchanged if an error is returned.
+    pub fn apply(&mut self, edit: Edit) -> Result {
+        let entry = match edit {
+            Edit::Set {
+                kind,
+                index,
+                pointer,
+                value,
+            } => {
+                let old = self.object(kind, index)?;
+                let mut new = old.clone();
+                set_pointer(&mut new, &pointer, value)?;
+                let entry = Entry::Replace {
+                    kind,
+                    index,
+                    path: pointer_path(kind, index, &pointer),
+                    old,
+                    new,
+                };
+                self.perform(&entry)?;
+                entry
+            }
+            Edit::Insert { kind, index, value } => {
+                if index > visit::len(&self.root, kind) {
+                    return Err(Error::Index(kind, index));
+                }
+                let entry = Entry::Insert { kind, index, value };
+                self.perform(&entry)?;
+                entry
+            }
+            Edit::Remove { kind, index } => {
+                let mut referenced = false;
+                visit::walk(&self.root, &mut |k, i, owner, _| {
+                    if k == kind && i as usize == index && owner != Some((kind, index)) {
+                        referenced = true;
+                    }
+                });
+                if referenced {
+                    return Err(Error::Referenced(kind, index));
+                }
+                let value = self.object(kind, index)?;
+                self.remove(kind, index);
+                Entry::Remove { kind, index, value }
+            }
+            Edit::Remap { kind, from, to } => {
+                self.object(kind, from)?;
+                self.object(kind, to)?;
+                let mut slots = Vec::new();
+                let mut slot = 0;
+                let changes = &mut self.changes;
+                visit::rewrite_indices(&mut self.root, &mut |k, i, path| {
+                    slot += 1;
+                    if k == kind && i as usize == from {
+                        slots.push(slot - 1);
+                        if from != to {
+                            changes.push(path());
+                        }
+                        to as u32
+                    } else {
+                        i
+                    }
+                });
+                Entry::Remap { from, to, slots }
+            }
+        };
+        if let Some(journal) = self.journal.as_mut() {
+            journal.undo.push(entry);
+            journal.redo.clear();
+        }
+        self.notify();
+        Ok(())
+    }
+
+    /// Returns `true` if there is an edit to undo.
+    pub fn can_undo(&self) -> bool {
+        self.journal
+            .as_ref()
+            .is_some_and(|journal| !journal.undo.is_empty())
+    }
+
+    /// Returns `true` if there is an undone edit to redo.
+    pub fn can_redo(&self) -> bool {
+        self.journal
+            .as_ref()
+            .is_some_and(|journal| !journal.redo.is_empty())
+    }
+
+    /// Reverts the last edit, returning `false` if there is none.
+    ///
+    /// If reverting fails, the edit is kept to be undone later.
+    pub fn undo(&mut self) -> Result<bool> {
+        let entry = match self.journal.as_mut().and_then(|journal| journal.undo.pop()) {
+            Some(entry) => entry,
+            None => return Ok(false),
+        };
+        let result = self.revert(&entry);
+        let journal = self.journal.as_mut().unwrap();
+        if let Err(error) = result {
+            journal.undo.push(entry);
+            return Err(error);
+        }
+        journal.redo.push(entry);
+        self.notify();
+        Ok(true)
+    }
+
+    /// Reapplies the last undone edit, returning `false` if there is none.
+    ///
+    /// If reapplying fails, the edit is kept to be redone later.
+    pub fn redo(&mut self) -> Result<bool> {
+        let entry = match self.journal.as_mut().and_then(|journal| journal.redo.pop()) {
+            Some(entry) => entry,
+            None => return Ok(false),
+        };
+        let result = self.perform(&entry);
+        let journal = self.journal.as_mut().unwrap();
+        if let Err(error) = result {
+            journal.redo.push(entry);
+            return Err(error);
+        }
+        journal.undo.push(entry);
+        self.notify();
+        Ok(true)
+    }
+
+    /// Returns the JSON of an object.
+    fn object(&mut self, kind: Kind, index: usize) -> Result<Value> {
+        // Objects holding invalid values, such as an unknown component type,
+        // fail to serialize.
+        with_items!(self.root, kind, |items| items
+            .get(index)
+            .map(|item| json::serialize::to_value(item).map_err(Error::Json)))
+        .unwrap_or(Err(Error::Index(kind, index)))
+    }
+
+    /// Applies a journal entry.
+    fn perform(&mut self, entry: &Entry) -> Result {
+        match *entry {
+            Entry::Replace {
+                kind,
+                index,
+                ref path,
+                ref new,
+                ..
+            } => {
+                replace(&mut self.root, kind, index, new)?;
+                self.changes.push(path.clone());
+                Ok(())
+            }
+            Entry::Insert {
+                kind,
+                index,
+                ref value,
+            } => self.insert(kind, index, value),
+            Entry::Remove { kind, index, .. } => {
+                self.remove(kind, index);
+                Ok(())
+            }
+            Entry::Remap { to, ref slots, .. } => {
+                set_slots(&mut self.root, slots, to, &mut self.changes);
+                Ok(())
+            }
+        }
+    }
+
+    /// Reverts a journal entry.
+    fn revert(&mut self, entry: &Entry) -> Result {
+        match *entry {
+            Entry::Replace {
+                kind,
+                index,
+                ref path,
+                ref old,
+                ..
+            } => {
+                replace(&mut self.root, kind, index, old)?;
+                self.changes.push(path.clone());
+                Ok(())
+            }
+            Entry::Insert { kind, index, .. } => {
+                self.remove(kind, index);
+                Ok(())
+            }
+            Entry::Remove {
+                kind,
+                index,
+                ref value,
+            } => self.insert(kind, index, value),
+            Entry::Remap {
+                from, ref slots, ..
+            } => {
+                set_slots(&mut self.root, slots, from, &mut self.changes);
+                Ok(())
+            }
+        }
+    }
+
+    /// Inserts an object, shifting the indices of later objects first so
+    /// that indices within the inserted object are kept as given.
+    fn insert(&mut self, kind: Kind, index: usize, value: &Value) -> Result {
+        // Deserialize before changing anything, so that errors leave the
+        // document unchanged.
+        with_items!(self.root, kind, |items| {
+            let mut staged = items[..0].to_vec();
+            staged.push(json::deserialize::from_value(value.clone()).map_err(Error::Json)?);
+        });
+        match kind {
+            #[cfg(feature = "KHR_lights_punctual")]
+            Kind::Light => {
+                self.root
+                    .extensions
+                    .get_or_insert_with(Default::default)
+                    .khr_lights_punctual
+                    .get_or_insert_with(Default::default);
+            }
+            #[cfg(feature = "KHR_materials_variants")]
+            Kind::Variant => {
+                self.root
+                    .extensions
+                    .get_or_insert_with(Default::default)
+                    .khr_materials_variants
+                    .get_or_insert_with(Default::default);
+            }
+            _ => {}
+        }
+        shift(&mut self.root, kind, index, true, &mut self.changes);
+        with_items!(self.root, kind, |items| {
+            let item = json::deserialize::from_value(value.clone()).map_err(Error::Json)?;
+            items.insert(index, item);
+        });
+        self.changes.push(visit::array_path(kind));
+        Ok(())
+    }
+
+    /// Removes an object, shifting the indices of later objects.
+    fn remove(&mut self, kind: Kind, index: usize) {
+        take(&mut self.root, kind, index);
+        self.changes.push(visit::array_path(kind));
+        shift(&mut self.root, kind, index, false, &mut self.changes);
+    }
+}
+
+/// Replaces an object with the one deserialized from `value`.
+fn replace(root: &mut json::Root, kind: Kind, index: usize, value: &Value) -> Result {
+    with_items!(root, kind, |items| {
+        items[index] = json::deserialize::from_value(value.clone()).map_err(Error::Json)?;
+    });
+    Ok(())
+}
+
+/// Removes an object, returning its JSON.
+fn take(root: &mut json::Root, kind: Kind, index: usize) -> Value {
+    with_items!(root, kind, |items| json::serialize::to_value(
+        items.remove(index)
+    )
+    .unwrap())
+}
+
+/// Returns the JSON path of the value at a JSON pointer within an object,
+/// taking numeric tokens to be array indices.
+fn pointer_path(kind: Kind, index: usize, pointer: &str) -> Path {
+    let mut path = visit::array_path(kind).index(index);
+    for token in pointer.split('/').skip(1) {
+        path = match token.parse() {
+            Ok(index) => path.index(index),
+            Err(_) => path.field(&token.replace("~1", "/").replace("~0", "~")),
+        };
+    }
+    path
+}
+
+/// Shifts every index of the given kind at or past `index` up by one, or
+/// every index past it down by one, recording the paths of the indices
+/// changed.
+fn shift(root: &mut json::Root, kind: Kind, index: usize, up: bool, changes: &mut Vec<Path>) {
+    let index = index as u32;
+    visit::rewrite_indices(root, &mut |k, i, path| {
+        let shifted = match (k == kind, up) {
+            (true, true) if i >= index => i + 1,
+            (true, false) if i > index => i - 1,
+            _ => return i,
+        };
+        changes.push(path());
+        shifted
+    });
+}
+
+/// Sets the indices at the given positions in visiting order, recording the
+/// paths of the indices changed.
+fn set_slots(root: &mut json::Root, slots: &[usize], value: usize, changes: &mut Vec<Path>) {
+    let mut slot = 0;
+    let mut slots = slots.iter().peekable();
+    visit::rewrite_indices(root, &mut |_, i, path| {
+        slot += 1;
+        if slots.peek() == Some(&&(slot - 1)) {
+            slots.next();
+            if i as usize != value {
+                changes.push(path());
+            }
+            value as u32
+        } else {
+            i
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor() -> Editor {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "materials": [{"name": "a"}, {"name": "b"}],
+            "nodes": [{"children": [1]}, {"name": "child"}, {}],
+            "scenes": [{"nodes": [0, 2]}],
+            "scene": 0
+        }"#;
+        Editor::with_journal(json::Root::from_str(json).unwrap())
+    }
+
+    fn canonical(editor: &Editor) -> Value {
+        json::serialize::to_value(editor.root()).unwrap()
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut editor = editor();
+        let original = canonical(&editor);
+        let edits = vec![
+            Edit::Set {
+                kind: Kind::Node,
+                index: 1,
+                pointer: "/translation".to_string(),
+                value: Value::from(vec![1.0, 2.0, 3.0]),
+            },
+            Edit::Insert {
+                kind: Kind::Node,
+                index: 0,
+                value: json::deserialize::from_str(r#"{"children": [2]}"#).unwrap(),
+            },
+            Edit::Remap {
+                kind: Kind::Node,
+                from: 1,
+                to: 3,
+            },
+            Edit::Remove {
+                kind: Kind::Node,
+                index: 1,
+            },
+        ];
+        let mut states = vec![original.clone()];
+        for edit in edits {
+            editor.apply(edit).unwrap();
+            states.push(canonical(&editor));
+        }
+
+        // The inserted node shifted the others, the root node was replaced
+        // in the scene by the last node, then removed.
+        let root = editor.root();
+        assert_eq!(root.nodes.len(), 3);
+        assert_eq!(root.nodes[0].children.as_ref().unwrap()[0].value(), 1);
+        assert_eq!(root.nodes[1].translation, Some([1.0, 2.0, 3.0]));
+        let scene: Vec<usize> = root.scenes[0].nodes.iter().map(|n| n.value()).collect();
+        assert_eq!(scene, vec![2, 2]);
+
+        for state in states.iter().rev().skip(1) {
+            assert!(editor.undo().unwrap());
+            assert_eq!(&canonical(&editor), state);
+        }
+        assert!(!editor.undo().unwrap());
+        for state in states.iter().skip(1) {
+            assert!(editor.redo().unwrap());
+            assert_eq!(&canonical(&editor), state);
+        }
+        assert!(!editor.redo().unwrap());
+        assert!(editor.into_document().is_ok());
+    }
+
+    #[test]
+    fn errors_leave_the_document_unchanged() {
+        let mut editor = editor();
+        let original = canonical(&editor);
+        let errors = vec![
+            Edit::Remove {
+                kind: Kind::Node,
+                index: 1,
+            },
+            Edit::Set {
+                kind: Kind::Material,
+                index: 0,
+                pointer: "/alphaCutoff".to_string(),
+                value: Value::from("high"),
+            },
+            Edit::Set {
+                kind: Kind::Material,
+                index: 0,
+                pointer: "name".to_string(),
+                value: Value::from("c"),
+            },
+            Edit::Remap {
+                kind: Kind::Material,
+                from: 0,
+                to: 2,
+            },
+        ];
+        for edit in errors {
+            assert!(editor.apply(edit).is_err());
+            assert_eq!(canonical(&editor), original);
+        }
+        assert!(!editor.can_undo());
+    }
+
+    #[test]
+    fn failed_undo_and_redo_keep_the_edit() {
+        let mut editor = editor();
+        let set = Edit::Set {
+            kind: Kind::Material,
+            index: 1,
+            pointer: "/doubleSided".to_string(),
+            value: Value::from(true),
+        };
+        editor.apply(set).unwrap();
+        // Corrupt the recorded values, so that applying them fails.
+        let corrupt = |editor: &mut Editor, undo: bool| {
+            let journal = editor.journal.as_mut().unwrap();
+            let stack = if undo {
+                &mut journal.undo
+            } else {
+                &mut journal.redo
+            };
+            match stack.last_mut() {
+                Some(Entry::Replace { old, new, .. }) => {
+                    let old = std::mem::replace(old, Value::from("invalid"));
+                    let new = std::mem::replace(new, Value::from("invalid"));
+                    (old, new)
+                }
+                _ => unreachable!(),
+            }
+        };
+        let restore = |editor: &mut Editor, undo: bool, (old, new): (Value, Value)| {
+            let journal = editor.journal.as_mut().unwrap();
+            let stack = if undo {
+                &mut journal.undo
+            } else {
+                &mut journal.redo
+            };
+            if let Some(Entry::Replace {
+                old: entry_old,
+                new: entry_new,
+                ..
+            }) = stack.last_mut()
+            {
+                *entry_old = old;
+                *entry_new = new;
+            }
+        };
+
+        let values = corrupt(&mut editor, true);
+        assert!(editor.undo().is_err());
+        assert!(editor.can_undo());
+        assert!(!editor.can_redo());
+        restore(&mut editor, true, values);
+        assert!(editor.undo().unwrap());
+        assert!(!editor.root().materials[1].double_sided);
+
+        let values = corrupt(&mut editor, false);
+        assert!(editor.redo().is_err());
+        assert!(editor.can_redo());
+        assert!(!editor.can_undo());
+        restore(&mut editor, false, values);
+        assert!(editor.redo().unwrap());
+        assert!(editor.root().materials[1].double_sided);
+    }
+
+    #[test]
+    fn new_edits_discard_redo() {
+        let mut editor = editor();
+        let set = |name: &str| Edit::Set {
+            kind: Kind::Material,
+            index: 1,
+            pointer: "/name".to_string(),
+            value: Value::from(name),
+        };
+        editor.apply(set("c")).unwrap();
+        assert!(editor.undo().unwrap());
+        assert!(editor.can_redo());
+        editor.apply(set("d")).unwrap();
+        assert!(!editor.can_redo());
+    }
+
+    #[test]
+    fn observers() {
+        use std::sync::Arc;
+
+        let mut editor = editor();
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let id = editor.observe({
+            let changes = changes.clone();
+            move |path| changes.lock().unwrap().push(path.to_string())
+        });
+        let take = || std::mem::take(&mut *changes.lock().unwrap());
+
+        editor
+            .apply(Edit::Set {
+                kind: Kind::Material,
+                index: 0,
+                pointer: "/pbrMetallicRoughness/baseColorFactor/1".to_string(),
+                value: Value::from(0.5),
+            })
+            .unwrap();
+        assert_eq!(
+            take(),
+            vec!["materials[0].pbrMetallicRoughness.baseColorFactor[1]"]
+        );
+
+        let insert = Edit::Insert {
+            kind: Kind::Node,
+            index: 2,
+            value: Value::Object(Default::default()),
+        };
+        editor.apply(insert).unwrap();
+        assert_eq!(take(), vec!["scenes[0].nodes[1]", "nodes"]);
+        editor.undo().unwrap();
+        assert_eq!(take(), vec!["nodes", "scenes[0].nodes[1]"]);
+
+        let remap = Edit::Remap {
+            kind: Kind::Node,
+            from: 2,
+            to: 0,
+        };
+        editor.apply(remap).unwrap();
+        assert_eq!(take(), vec!["scenes[0].nodes[1]"]);
+
+        assert!(editor.unobserve(id));
+        assert!(!editor.unobserve(id));
+        editor.undo().unwrap();
+        assert!(take().is_empty());
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+
+use crate::visit::{self, Kind, ARRAYS};
+use crate::{buffer, Document};
+use json::Value;
+
+/// The kinds of object that `transform::prune` always keeps, in the order in
+/// which they contribute to the hash of a document.
+const ROOTS: &[Kind] = &[
+    Kind::Scene,
+    Kind::Node,
+    Kind::Animation,
+    Kind::Buffer,
+    #[cfg(feature = "KHR_lights_punctual")]
+    Kind::Light,
+    #[cfg(feature = "KHR_materials_variants")]
+    Kind::Variant,
+];
+
+/// The 64-bit FNV-1a hash function, chosen for being stable across platforms
+/// and compiler versions.
+#[derive(Clone, Copy, Debug)]
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// Writes a length-prefixed string, so that consecutive strings cannot run
+    /// into each other.
+    fn write_str(&mut self, string: &str) {
+        self.write(&(string.len() as u64).to_le_bytes());
+        self.write(string.as_bytes());
+    }
+}
+
+/// A segment of a JSON path.
+enum Segment<'a> {
+    Field(&'a str),
+    Index(usize),
+}
+
+/// Splits a JSON path, as built by `json::Path`, into its segments.
+fn segments(path: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = path;
+    while !rest.is_empty() {
+        if let Some(key) = rest.strip_prefix("[\"") {
+            let end = key.find("\"]").unwrap_or(key.len());
+            segments.push(Segment::Field(&key[..end]));
+            rest = key.get(end + 2..).unwrap_or("");
+        } else if let Some(index) = rest.strip_prefix('[') {
+            let end = index.find(']').unwrap_or(index.len());
+            segments.push(Segment::Index(index[..end].parse().unwrap_or(usize::MAX)));
+            rest = index.get(end + 1..).unwrap_or("");
+        } else {
+            let field = rest.strip_prefix('.').unwrap_or(rest);
+            let end = field.find(['.', '[']).unwrap_or(field.len());
+            segments.push(Segment::Field(&field[..end]));
+            rest = &field[end..];
+        }
+    }
+    segments
+}
+
+/// Returns the value at a JSON path.
+fn get<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+    segments(path)
+        .into_iter()
+        .try_fold(value, |value, segment| match segment {
+            Segment::Field(field) => value.get(field),
+            Segment::Index(index) => value.get(index),
+        })
+}
+
+/// Returns the value at a JSON path for writing.
+fn get_mut<'v>(value: &'v mut Value, path: &str) -> Option<&'v mut Value> {
+    segments(path)
+        .into_iter()
+        .try_fold(value, |value, segment| match segment {
+            Segment::Field(field) => value.get_mut(field),
+            Segment::Index(index) => value.get_mut(index),
+        })
+}
+
+/// Returns the JSON path of an object.
+fn object_path(kind: Kind, index: usize) -> String {
+    visit::array_path(kind).index(index).0
+}
+
+/// The JSON path relative to its holding object, kind, and value of an index.
+type Reference = (String, Kind, usize);
+
+/// The state of the hash of an object.
+#[derive(Clone, Copy)]
+enum State {
+    /// The hash is being computed, so the object is part of a cycle.
+    Pending,
+
+    /// The hash has been computed.
+    Done(u64),
+}
+
+/// Computes the hashes of the objects of a document.
+struct Hasher<'a> {
+    root: Value,
+    buffers: &'a [buffer::Data],
+    lengths: Vec<usize>,
+    references: HashMap<(Kind, usize), Vec<Reference>>,
+    states: HashMap<(Kind, usize), State>,
+}
+
+impl<'a> Hasher<'a> {
+    /// Returns the hash of an object, with every index it holds replaced by
+    /// the hash of the object referred to.
+    fn hash(&mut self, kind: Kind, index: usize) -> u64 {
+        let mut hasher = Fnv::new();
+        hasher.write_str(&format!("{:?}", kind));
+        match self.states.get(&(kind, index)) {
+            Some(State::Done(hash)) => return *hash,
+            // Objects in a cycle hash as their kind alone wherever the cycle
+            // closes.
+            Some(State::Pending) => return hasher.0,
+            None => {}
+        }
+
+        let prefix = object_path(kind, index);
+        let mut value = match get(&self.root, &prefix) {
+            Some(value) => value.clone(),
+            None => {
+                hasher.write_str("missing");
+                return hasher.0;
+            }
+        };
+        self.states.insert((kind, index), State::Pending);
+        let references = self
+            .references
+            .get(&(kind, index))
+            .cloned()
+            .unwrap_or_default();
+        for (path, target, target_index) in references {
+            let hash = self.hash(target, target_index);
+            if let Some(slot) = get_mut(&mut value, &path) {
+                *slot = Value::String(format!("{:016x}", hash));
+            }
+        }
+
+        if kind == Kind::Buffer {
+            // Where the data is stored does not change the content.
+            if let Some(object) = value.as_object_mut() {
+                object.remove("uri");
+            }
+            let data = self.buffers.get(index).map_or(&[][..], |data| &data.0[..]);
+            let length = self.lengths[index].min(data.len());
+            hasher.write(&data[..length]);
+        }
+        hasher.write_str(&json::serialize::to_string(&value).unwrap());
+        self.states.insert((kind, index), State::Done(hasher.0));
+        hasher.0
+    }
+}
+
+impl Document {
+    /// Returns a hash of the content of this document and its buffer data.
+    ///
+    /// The hash is computed over the canonical serialization of the JSON, in
+    /// which every index is replaced by the hash of the object it refers to,
+    /// and over the bytes of the buffers. It is therefore unaffected by the
+    /// order of fields in the source JSON and by the removal of unreferenced
+    /// objects or the merging of identical ones, as done by `transform::prune`
+    /// and `transform::dedup`. The URIs of buffers are not hashed, so neither
+    /// does embedding or externalizing buffer data change the hash.
+    ///
+    /// The hash function is 64-bit FNV-1a, whose output is stable across
+    /// platforms and releases, making the hash suitable as a key for asset
+    /// caches. It is not a cryptographic hash.
+    pub fn content_hash(&self, buffers: &[buffer::Data]) -> u64 {
+        let mut references: HashMap<_, Vec<_>> = HashMap::new();
+        visit::walk(&self.0, &mut |kind, index, owner, path| {
+            if let Some((owner_kind, owner_index)) = owner {
+                let prefix = object_path(owner_kind, owner_index);
+                let path = path();
+                let relative = path.as_str()[prefix.len()..].to_string();
+                references
+                    .entry((owner_kind, owner_index))
+                    .or_default()
+                    .push((relative, kind, index as usize));
+            }
+        });
+        let root = json::serialize::to_value(&self.0).unwrap();
+        let lengths = self.buffers().map(|buffer| buffer.length()).collect();
+
+        // The root object itself, without the arrays of objects.
+        let mut top = root.clone();
+        for (_, array) in ARRAYS {
+            let (parent, field) = match array.rfind('.') {
+                Some(dot) => (&array[..dot], &array[dot + 1..]),
+                None => ("", *array),
+            };
+            if let Some(object) = get_mut(&mut top, parent).and_then(Value::as_object_mut) {
+                object.remove(field);
+            }
+        }
+        if let Some(object) = top.as_object_mut() {
+            object.remove("scene");
+        }
+
+        let mut hasher = Hasher {
+            root,
+            buffers,
+            lengths,
+            references,
+            states: HashMap::new(),
+        };
+        let mut hash = Fnv::new();
+        hash.write_str(&json::serialize::to_string(&top).unwrap());
+        if let Some(scene) = self.default_scene() {
+            hash.write(&hasher.hash(Kind::Scene, scene.index()).to_le_bytes());
+        }
+        for &kind in ROOTS {
+            let count = visit::len(&self.0, kind);
+            hash.write_str(&format!("{:?}", kind));
+            hash.write(&(count as u64).to_le_bytes());
+            for index in 0..count {
+                hash.write(&hasher.hash(kind, index).to_le_bytes());
+            }
+        }
+        hash.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{
+        "asset": {"version": "2.0"},
+        "buffers": [{"byteLength": 12, "uri": "a.bin"}],
+        "bufferViews": [
+            {"buffer": 0, "byteLength": 12},
+            {"buffer": 0, "byteLength": 12}
+        ],
+        "accessors": [
+            {"bufferView": 1, "componentType": 5126, "count": 1, "type": "VEC3", "min": [1.0, 0.0, 0.0], "max": [1.0, 0.0, 0.0]},
+            {"bufferView": 0, "componentType": 5126, "count": 1, "type": "VEC3", "min": [1.0, 0.0, 0.0], "max": [1.0, 0.0, 0.0]},
+            {"bufferView": 0, "componentType": 5126, "count": 1, "type": "VEC3", "min": [1.0, 0.0, 0.0], "max": [1.0, 0.0, 0.0]}
+        ],
+        "materials": [{"name": "unused"}, {"doubleSided": true}],
+        "meshes": [{"primitives": [
+            {"attributes": {"POSITION": 2}, "material": 1},
+            {"attributes": {"POSITION": 0}, "material": 1}
+        ]}],
+        "nodes": [{"mesh": 0}],
+        "scenes": [{"nodes": [0]}],
+        "scene": 0
+    }"#;
+
+    #[test]
+    fn content_hash() {
+        let buffers = vec![buffer::Data(vec![0, 0, 128, 63, 0, 0, 0, 0, 0, 0, 0, 0])];
+        let document = Document::from_json(json::Root::from_str(JSON).unwrap()).unwrap();
+        let hash = document.content_hash(&buffers);
+
+        let mut root = json::Root::from_str(JSON).unwrap();
+        crate::transform::dedup(&mut root);
+        crate::transform::prune(&mut root);
+        assert_eq!(root.accessors.len(), 1);
+        assert_eq!(root.materials.len(), 1);
+        root.buffers[0].uri = Some("b.bin".to_string());
+        let pruned = Document::from_json(root).unwrap();
+        assert_eq!(pruned.content_hash(&buffers), hash);
+
+        let reordered = r#"{
+            "scene": 0,
+            "scenes": [{"nodes": [0]}],
+            "nodes": [{"mesh": 0}],
+            "meshes": [{"primitives": [
+                {"material": 1, "attributes": {"POSITION": 2}},
+                {"material": 1, "attributes": {"POSITION": 0}}
+            ]}],
+            "materials": [{"name": "unused"}, {"doubleSided": true}],
+            "accessors": [
+                {"max": [1.0, 0.0, 0.0], "min": [1.0, 0.0, 0.0], "type": "VEC3", "count": 1, "componentType": 5126, "bufferView": 1},
+                {"max": [1.0, 0.0, 0.0], "min": [1.0, 0.0, 0.0], "type": "VEC3", "count": 1, "componentType": 5126, "bufferView": 0},
+                {"max": [1.0, 0.0, 0.0], "min": [1.0, 0.0, 0.0], "type": "VEC3", "count": 1, "componentType": 5126, "bufferView": 0}
+            ],
+            "bufferViews": [
+                {"byteLength": 12, "buffer": 0},
+                {"byteLength": 12, "buffer": 0}
+            ],
+            "buffers": [{"uri": "a.bin", "byteLength": 12}],
+            "asset": {"version": "2.0"}
+        }"#;
+        let reordered = Document::from_json(json::Root::from_str(reordered).unwrap()).unwrap();
+        assert_eq!(reordered.content_hash(&buffers), hash);
+
+        let changed = vec![buffer::Data(vec![0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 0])];
+        assert_ne!(document.content_hash(&changed), hash);
+        let mut root = json::Root::from_str(JSON).unwrap();
+        root.materials[1].double_sided = false;
+        let changed = Document::from_json(root).unwrap();
+        assert_ne!(changed.content_hash(&buffers), hash);
+    }
+}
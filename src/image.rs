@@ -1,4 +1,8 @@
-use crate::{buffer, Document, Error, Result};
+use crate::usage::Usage;
+use crate::{buffer, Document};
+
+#[cfg(feature = "import")]
+use crate::{Error, Result};
 
 #[cfg(feature = "import")]
 #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
@@ -86,10 +90,10 @@ pub struct Data {
     /// The image pixel data format.
     pub format: Format,
 
-    /// The image height in pixels.
+    /// The image width in pixels.
     pub width: u32,
 
-    /// The image width in pixels.
+    /// The image height in pixels.
     pub height: u32,
 }
 
@@ -128,10 +132,33 @@ impl<'a> Image<'a> {
         }
     }
 
+    /// Returns the textures, materials, primitives, and nodes that use this
+    /// image.
+    pub fn usage(&self) -> Usage<'a> {
+        let textures = self
+            .document
+            .textures()
+            .filter(|texture| texture.source().index() == self.index)
+            .collect();
+        Usage::of_textures(self.document, textures)
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &'a json::Extras {
         &self.json.extras
     }
+
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new().field("images").index(self.index)
+        })
+    }
 }
 
 #[cfg(feature = "import")]
@@ -162,4 +189,65 @@ impl Data {
             pixels,
         })
     }
+
+    /// Decodes an encoded PNG or JPEG image into pixels.
+    ///
+    /// The encoding is given by `mime_type`, being `"image/png"` or
+    /// `"image/jpeg"`. For other MIME types or none, the encoding is guessed
+    /// from the data when the `guess_mime_type` feature is enabled, or else
+    /// `Error::UnsupportedImageEncoding` is returned.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// // A single red pixel.
+    /// let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x01\0\0\0\x01\x08\x02\0\0\0\x90wS\xde\0\0\0\x0cIDATx\x9cc\xf8\xcf\xc0\0\0\x03\x01\x01\0\xc9\xfe\x92\xef\0\0\0\0IEND\xaeB`\x82";
+    /// let image = gltf::image::Data::decode(png, Some("image/png"))?;
+    /// assert_eq!((image.width, image.height), (1, 1));
+    /// assert_eq!(image.format, gltf::image::Format::R8G8B8);
+    /// assert_eq!(image.pixels, [255, 0, 0]);
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("test failure");
+    /// # }
+    /// ```
+    pub fn decode(encoded: &[u8], mime_type: Option<&str>) -> Result<Self> {
+        use image_crate::ImageFormat::{Jpeg, Png};
+        let format = match mime_type {
+            Some("image/png") => Png,
+            Some("image/jpeg") => Jpeg,
+            _ => guess_format(encoded).ok_or(Error::UnsupportedImageEncoding)?,
+        };
+        Data::new(image_crate::load_from_memory_with_format(encoded, format)?)
+    }
+}
+
+/// Guesses the encoding of an image, if the `guess_mime_type` feature is
+/// enabled.
+#[cfg(feature = "import")]
+fn guess_format(encoded: &[u8]) -> Option<image_crate::ImageFormat> {
+    #[cfg(feature = "guess_mime_type")]
+    match image_crate::guess_format(encoded) {
+        Ok(format @ image_crate::ImageFormat::Png)
+        | Ok(format @ image_crate::ImageFormat::Jpeg) => Some(format),
+        _ => None,
+    }
+    #[cfg(not(feature = "guess_mime_type"))]
+    {
+        let _ = encoded;
+        None
+    }
+}
+
+#[cfg(all(test, feature = "import"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_unsupported() {
+        let result = Data::decode(b"GIF89a", Some("image/gif"));
+        assert!(matches!(result, Err(Error::UnsupportedImageEncoding)));
+        let result = Data::decode(b"not an image", None);
+        assert!(matches!(result, Err(Error::UnsupportedImageEncoding)));
+    }
 }
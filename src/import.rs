@@ -1,18 +1,21 @@
 use crate::buffer;
 use crate::image;
+use crate::trace;
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::{fs, io};
 
-use crate::{Document, Error, Gltf, Result};
-use image_crate::ImageFormat::{Jpeg, Png};
+use crate::usage::Kind;
+use crate::{Document, Error, Gltf, Limits, Result};
 use std::path::Path;
 
 /// Return type of `import`.
-type Import = (Document, Vec<buffer::Data>, Vec<image::Data>);
+pub(crate) type Import = (Document, Vec<buffer::Data>, Vec<image::Data>);
 
 /// Represents the set of URI schemes the importer supports.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
-enum Scheme<'a> {
+pub(crate) enum Scheme<'a> {
     /// `data:[<media type>];base64,<data>`.
     Data(Option<&'a str>, &'a str),
 
@@ -29,7 +32,7 @@ enum Scheme<'a> {
 }
 
 impl<'a> Scheme<'a> {
-    fn parse(uri: &str) -> Scheme<'_> {
+    pub(crate) fn parse(uri: &str) -> Scheme<'_> {
         if uri.contains(':') {
             if let Some(rest) = uri.strip_prefix("data:") {
                 let mut it = rest.split(";base64,");
@@ -51,15 +54,94 @@ impl<'a> Scheme<'a> {
         }
     }
 
-    fn read(base: Option<&Path>, uri: &str) -> Result<Vec<u8>> {
-        match Scheme::parse(uri) {
+    pub(crate) fn read(base: Option<&Path>, uri: &str) -> Result<Vec<u8>> {
+        Scheme::fetch(&Base(base), uri)
+    }
+
+    /// Decodes a data URI, or asks `fetch` for any other supported URI.
+    fn fetch<F: Fetch + ?Sized>(fetch: &F, uri: &str) -> Result<Vec<u8>> {
+        let mut span = trace::span!("fetch", bytes);
+        let data = match Scheme::parse(uri) {
             // The path may be unused in the Scheme::Data case
             // Example: "uri" : "data:application/octet-stream;base64,wsVHPgA...."
-            Scheme::Data(_, base64) => base64::decode(&base64).map_err(Error::Base64),
-            Scheme::File(path) if base.is_some() => read_to_end(path),
-            Scheme::Relative(path) if base.is_some() => read_to_end(base.unwrap().join(&*path)),
+            Scheme::Data(_, base64) => base64::decode(base64).map_err(Error::Base64),
+            Scheme::File(path) => fetch.fetch(path),
+            Scheme::Relative(path) => fetch.fetch(&path),
             Scheme::Unsupported => Err(Error::UnsupportedScheme),
-            _ => Err(Error::ExternalReferenceInSliceImport),
+        }?;
+        span.record("bytes", data.len());
+        Ok(data)
+    }
+}
+
+/// Reads the external resources of a glTF document.
+///
+/// The importer decodes `data:` URIs and takes the binary chunk of binary glTF
+/// itself, and asks a `Fetch` for everything else: paths relative to the
+/// document and `file:` URIs. It is implemented for [`Path`] and [`PathBuf`],
+/// which read files relative to a directory, and for a `HashMap` of paths to
+/// their contents. Implement it to read from an archive or the network.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// # fn run() -> Result<(), gltf::Error> {
+/// # let path = "examples/Box.gltf";
+/// let json = std::fs::read(path).map_err(gltf::Error::Io)?;
+/// let bin = std::fs::read("examples/Box0.bin").map_err(gltf::Error::Io)?;
+/// let mut files = HashMap::new();
+/// files.insert("Box0.bin".to_string(), bin);
+/// let (document, buffers, _) = gltf::import_slice_with(json, &files)?;
+/// assert_eq!(buffers.len(), document.buffers().len());
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+///
+/// [`Path`]: https://doc.rust-lang.org/std/path/struct.Path.html
+/// [`PathBuf`]: https://doc.rust-lang.org/std/path/struct.PathBuf.html
+pub trait Fetch {
+    /// Returns the contents of the resource at `path`.
+    ///
+    /// `path` is the percent-decoded URI of the resource, without its `file:`
+    /// scheme if it has one.
+    fn fetch(&self, path: &str) -> Result<Vec<u8>>;
+}
+
+impl Fetch for Path {
+    fn fetch(&self, path: &str) -> Result<Vec<u8>> {
+        read_to_end(self.join(path))
+    }
+}
+
+impl Fetch for PathBuf {
+    fn fetch(&self, path: &str) -> Result<Vec<u8>> {
+        self.as_path().fetch(path)
+    }
+}
+
+impl Fetch for HashMap<String, Vec<u8>> {
+    fn fetch(&self, path: &str) -> Result<Vec<u8>> {
+        self.get(path).cloned().ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no resource named {:?}", path),
+            ))
+        })
+    }
+}
+
+/// Reads files relative to a base directory, or refuses to read any when there
+/// is none, as for slice imports.
+struct Base<'a>(Option<&'a Path>);
+
+impl<'a> Fetch for Base<'a> {
+    fn fetch(&self, path: &str) -> Result<Vec<u8>> {
+        match self.0 {
+            Some(base) => base.fetch(path),
+            None => Err(Error::ExternalReferenceInSliceImport),
         }
     }
 }
@@ -81,17 +163,62 @@ where
 }
 
 /// Import the buffer data referenced by a glTF document.
-pub fn import_buffer_data(
+pub fn import_buffers(
     document: &Document,
     base: Option<&Path>,
+    blob: Option<Vec<u8>>,
+) -> Result<Vec<buffer::Data>> {
+    import_buffers_impl(document, &Base(base), blob, &|_| true, &Limits::default())
+}
+
+/// Import the buffer data referenced by a glTF document, reading external
+/// buffers with `fetch`.
+pub fn import_buffers_with<F>(
+    document: &Document,
+    fetch: &F,
+    blob: Option<Vec<u8>>,
+) -> Result<Vec<buffer::Data>>
+where
+    F: Fetch + ?Sized,
+{
+    import_buffers_impl(document, fetch, blob, &|_| true, &Limits::default())
+}
+
+/// Imports the buffers for which `keep` returns `true`, leaving the others
+/// empty, and stops once their total size exceeds `limits`.
+fn import_buffers_impl<F: Fetch + ?Sized>(
+    document: &Document,
+    fetch: &F,
     mut blob: Option<Vec<u8>>,
+    keep: &dyn Fn(usize) -> bool,
+    limits: &Limits,
 ) -> Result<Vec<buffer::Data>> {
     let mut buffers = Vec::new();
+    let mut total = 0usize;
     for buffer in document.buffers() {
+        if !keep(buffer.index()) {
+            buffers.push(buffer::Data(Vec::new()));
+            continue;
+        }
         let mut data = match buffer.source() {
-            buffer::Source::Uri(uri) => Scheme::read(base, uri),
+            buffer::Source::Uri(uri) => {
+                if let Scheme::Data(_, base64) = Scheme::parse(uri) {
+                    // Refuse to decode far more data than the buffer declares.
+                    let decoded = (base64.len() / 4 * 3).saturating_sub(2);
+                    if decoded > buffer.length().div_ceil(4) * 4 {
+                        return Err(Error::BufferLength {
+                            buffer: buffer.index(),
+                            expected: buffer.length(),
+                            actual: decoded,
+                        });
+                    }
+                }
+                Scheme::fetch(fetch, uri)
+            }
             buffer::Source::Bin => blob.take().ok_or(Error::MissingBlob),
         }?;
+        total = total.saturating_add(data.len());
+        limits.check_buffer_bytes(total)?;
         if data.len() < buffer.length() {
             return Err(Error::BufferLength {
                 buffer: buffer.index(),
@@ -108,56 +235,63 @@ pub fn import_buffer_data(
 }
 
 /// Import the image data referenced by a glTF document.
-pub fn import_image_data(
+pub fn import_images(
     document: &Document,
     base: Option<&Path>,
     buffer_data: &[buffer::Data],
+) -> Result<Vec<image::Data>> {
+    import_images_impl(document, &Base(base), buffer_data, &|_| true)
+}
+
+/// Import the image data referenced by a glTF document, reading external
+/// images with `fetch`.
+pub fn import_images_with<F>(
+    document: &Document,
+    fetch: &F,
+    buffer_data: &[buffer::Data],
+) -> Result<Vec<image::Data>>
+where
+    F: Fetch + ?Sized,
+{
+    import_images_impl(document, fetch, buffer_data, &|_| true)
+}
+
+/// Imports the images for which `keep` returns `true`, leaving the others
+/// empty.
+fn import_images_impl<F: Fetch + ?Sized>(
+    document: &Document,
+    fetch: &F,
+    buffer_data: &[buffer::Data],
+    keep: &dyn Fn(usize) -> bool,
 ) -> Result<Vec<image::Data>> {
     let mut images = Vec::new();
-    #[cfg(feature = "guess_mime_type")]
-    let guess_format = |encoded_image: &[u8]| match image_crate::guess_format(encoded_image) {
-        Ok(image_crate::ImageFormat::Png) => Some(Png),
-        Ok(image_crate::ImageFormat::Jpeg) => Some(Jpeg),
-        _ => None,
-    };
-    #[cfg(not(feature = "guess_mime_type"))]
-    let guess_format = |_encoded_image: &[u8]| None;
     for image in document.images() {
-        let decoded_image = match image.source() {
-            image::Source::Uri { uri, mime_type } if base.is_some() => match Scheme::parse(uri) {
-                Scheme::Data(Some(annoying_case), base64) => {
-                    let encoded_image = base64::decode(&base64).map_err(Error::Base64)?;
-                    let encoded_format = match annoying_case {
-                        "image/png" => Png,
-                        "image/jpeg" => Jpeg,
-                        _ => match guess_format(&encoded_image) {
-                            Some(format) => format,
-                            None => return Err(Error::UnsupportedImageEncoding),
-                        },
-                    };
-
-                    image_crate::load_from_memory_with_format(&encoded_image, encoded_format)?
+        if !keep(image.index()) {
+            images.push(image::Data {
+                pixels: Vec::new(),
+                format: image::Format::R8G8B8A8,
+                width: 0,
+                height: 0,
+            });
+            continue;
+        }
+        let mut span = trace::span!("decode_image", image, width, height, bytes);
+        span.record("image", image.index());
+        let data = match image.source() {
+            image::Source::Uri { uri, mime_type } => match Scheme::parse(uri) {
+                Scheme::Data(Some(mime_type), base64) => {
+                    let encoded_image = base64::decode(base64).map_err(Error::Base64)?;
+                    image::Data::decode(&encoded_image, Some(mime_type))?
                 }
                 Scheme::Unsupported => return Err(Error::UnsupportedScheme),
                 _ => {
-                    let encoded_image = Scheme::read(base, uri)?;
-                    let encoded_format = match mime_type {
-                        Some("image/png") => Png,
-                        Some("image/jpeg") => Jpeg,
-                        Some(_) => match guess_format(&encoded_image) {
-                            Some(format) => format,
-                            None => return Err(Error::UnsupportedImageEncoding),
-                        },
-                        None => match uri.rsplit('.').next() {
-                            Some("png") => Png,
-                            Some("jpg") | Some("jpeg") => Jpeg,
-                            _ => match guess_format(&encoded_image) {
-                                Some(format) => format,
-                                None => return Err(Error::UnsupportedImageEncoding),
-                            },
-                        },
-                    };
-                    image_crate::load_from_memory_with_format(&encoded_image, encoded_format)?
+                    let encoded_image = Scheme::fetch(fetch, uri)?;
+                    let mime_type = mime_type.or_else(|| match uri.rsplit('.').next() {
+                        Some("png") => Some("image/png"),
+                        Some("jpg") | Some("jpeg") => Some("image/jpeg"),
+                        _ => None,
+                    });
+                    image::Data::decode(&encoded_image, mime_type)?
                 }
             },
             image::Source::View { view, mime_type } => {
@@ -165,37 +299,58 @@ pub fn import_image_data(
                 let begin = view.offset();
                 let end = begin + view.length();
                 let encoded_image = &parent_buffer_data[begin..end];
-                let encoded_format = match mime_type {
-                    "image/png" => Png,
-                    "image/jpeg" => Jpeg,
-                    _ => match guess_format(encoded_image) {
-                        Some(format) => format,
-                        None => return Err(Error::UnsupportedImageEncoding),
-                    },
-                };
-                image_crate::load_from_memory_with_format(encoded_image, encoded_format)?
+                image::Data::decode(encoded_image, Some(mime_type))?
             }
-            _ => return Err(Error::ExternalReferenceInSliceImport),
         };
 
-        images.push(image::Data::new(decoded_image)?);
+        span.record("width", data.width as usize);
+        span.record("height", data.height as usize);
+        span.record("bytes", data.pixels.len());
+        images.push(data);
     }
 
     Ok(images)
 }
 
-fn import_impl(Gltf { document, blob }: Gltf, base: Option<&Path>) -> Result<Import> {
-    let buffer_data = import_buffer_data(&document, base, blob)?;
-    let image_data = import_image_data(&document, base, &buffer_data)?;
+pub(crate) fn import_impl<F: Fetch + ?Sized>(
+    Gltf { document, blob }: Gltf,
+    fetch: &F,
+    limits: &Limits,
+) -> Result<Import> {
+    let buffer_data = import_buffers_impl(&document, fetch, blob, &|_| true, limits)?;
+    let image_data = import_images_impl(&document, fetch, &buffer_data, &|_| true)?;
     let import = (document, buffer_data, image_data);
     Ok(import)
 }
 
-fn import_path(path: &Path) -> Result<Import> {
+fn import_scene_impl(
+    Gltf { document, blob }: Gltf,
+    base: Option<&Path>,
+    scene: usize,
+) -> Result<Import> {
+    let reachable = document
+        .scenes()
+        .nth(scene)
+        .ok_or(Error::MissingScene(scene))?
+        .reachable();
+    let buffer_data = import_buffers_impl(
+        &document,
+        &Base(base),
+        blob,
+        &|index| reachable.contains(Kind::Buffer, index),
+        &Limits::default(),
+    )?;
+    let image_data = import_images_impl(&document, &Base(base), &buffer_data, &|index| {
+        reachable.contains(Kind::Image, index)
+    })?;
+    Ok((document, buffer_data, image_data))
+}
+
+fn import_path(path: &Path, limits: &Limits) -> Result<Import> {
     let base = path.parent().unwrap_or_else(|| Path::new("./"));
     let file = fs::File::open(path).map_err(Error::Io)?;
     let reader = io::BufReader::new(file);
-    import_impl(Gltf::from_reader(reader)?, Some(base))
+    import_impl(Gltf::from_reader_with_limits(reader, limits)?, base, limits)
 }
 
 /// Import some glTF 2.0 from the file system.
@@ -212,6 +367,9 @@ fn import_path(path: &Path) -> Result<Import> {
 /// # }
 /// ```
 ///
+/// Standard and binary glTF are told apart automatically; use [`detect`] to
+/// find the format and version of some data without loading it.
+///
 /// ### Note
 ///
 /// This function is provided as a convenience for loading glTF and associated
@@ -225,19 +383,92 @@ fn import_path(path: &Path) -> Result<Import> {
 ///
 /// [`Gltf`]: struct.Gltf.html
 /// [`Glb`]: struct.Glb.html
+/// [`detect`]: fn.detect.html
 pub fn import<P>(path: P) -> Result<Import>
 where
     P: AsRef<Path>,
 {
-    import_path(path.as_ref())
+    import_path(path.as_ref(), &Limits::default())
+}
+
+/// Import some glTF 2.0 from the file system, rejecting documents that exceed
+/// `limits` with `Error::Limit`.
+///
+/// The JSON and the declared sizes of the document are checked before any
+/// buffer or image is loaded, and the total size of the buffers is checked
+/// again as each one is loaded.
+pub fn import_with_limits<P>(path: P, limits: &Limits) -> Result<Import>
+where
+    P: AsRef<Path>,
+{
+    import_path(path.as_ref(), limits)
+}
+
+/// Import some glTF 2.0 from the file system, rejecting documents with members
+/// of objects that are not recognized.
+///
+/// See [`Gltf::from_slice_strict`] for which members are rejected.
+///
+/// [`Gltf::from_slice_strict`]: struct.Gltf.html#method.from_slice_strict
+pub fn import_strict<P>(path: P) -> Result<Import>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let base = path.parent().unwrap_or_else(|| Path::new("./"));
+    let gltf = Gltf::from_slice_strict(&read_to_end(path)?)?;
+    import_impl(gltf, base, &Limits::default())
+}
+
+/// Import some glTF 2.0 from the file system, loading only the buffers and
+/// images reachable from one of its scenes.
+///
+/// Buffers and images that are not reachable from the scene, as found by
+/// [`Scene::reachable`], are not read and are left empty so that their data
+/// still lines up with the indices of the document. Accessors of the scene only
+/// ever refer to buffers that have been loaded.
+///
+/// Only the loading of data is limited to the scene: the whole document is
+/// still parsed and validated, including the accessors, buffer views, and
+/// other objects that the scene does not reach, so that the returned document
+/// can be used in full. An invalid object outside of the scene therefore
+/// still fails the import.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// # let path = "examples/Box.gltf";
+/// # #[allow(unused)]
+/// let (document, buffers, images) = gltf::import_scene(path, 0)?;
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+///
+/// Returns `Error::MissingScene` if the document has no scene of the given
+/// index.
+///
+/// [`Scene::reachable`]: struct.Scene.html#method.reachable
+pub fn import_scene<P>(path: P, scene: usize) -> Result<Import>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    let base = path.parent().unwrap_or_else(|| Path::new("./"));
+    let file = fs::File::open(path).map_err(Error::Io)?;
+    let reader = io::BufReader::new(file);
+    import_scene_impl(Gltf::from_reader(reader)?, Some(base), scene)
 }
 
 pub fn import_slice_impl(slice: &[u8]) -> Result<Import> {
-    import_impl(Gltf::from_slice(slice)?, None)
+    import_impl(Gltf::from_slice(slice)?, &Base(None), &Limits::default())
 }
 
 /// Import some glTF 2.0 from a slice
 ///
+/// Standard and binary glTF are told apart automatically.
+///
 /// ```
 /// # extern crate gltf;
 /// # use std::fs;
@@ -261,3 +492,130 @@ where
 {
     import_slice_impl(slice.as_ref())
 }
+
+/// Import some glTF 2.0 from a slice, rejecting documents that exceed
+/// `limits` with `Error::Limit`.
+///
+/// See [`import_with_limits`] for when the limits are checked.
+///
+/// [`import_with_limits`]: fn.import_with_limits.html
+pub fn import_slice_with_limits<S>(slice: S, limits: &Limits) -> Result<Import>
+where
+    S: AsRef<[u8]>,
+{
+    import_impl(
+        Gltf::from_slice_with_limits(slice.as_ref(), limits)?,
+        &Base(None),
+        limits,
+    )
+}
+
+/// Import some glTF 2.0 from a slice, rejecting documents with members of
+/// objects that are not recognized.
+///
+/// See [`Gltf::from_slice_strict`] for which members are rejected.
+///
+/// [`Gltf::from_slice_strict`]: struct.Gltf.html#method.from_slice_strict
+pub fn import_slice_strict<S>(slice: S) -> Result<Import>
+where
+    S: AsRef<[u8]>,
+{
+    import_impl(
+        Gltf::from_slice_strict(slice.as_ref())?,
+        &Base(None),
+        &Limits::default(),
+    )
+}
+
+/// Import some glTF 2.0 from a slice, reading external buffers and images with
+/// `fetch`.
+///
+/// See [`Fetch`] for an example.
+///
+/// [`Fetch`]: trait.Fetch.html
+pub fn import_slice_with<S, F>(slice: S, fetch: &F) -> Result<Import>
+where
+    S: AsRef<[u8]>,
+    F: Fetch + ?Sized,
+{
+    import_impl(Gltf::from_slice(slice.as_ref())?, fetch, &Limits::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_longer_than_declared() {
+        let json = br#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 4, "uri": "data:application/octet-stream;base64,AAAAAAAAAAAAAAAA"}]
+        }"#;
+        match import_slice(&json[..]) {
+            Err(Error::BufferLength {
+                expected, actual, ..
+            }) => {
+                assert_eq!(expected, 4);
+                assert_eq!(actual, 10);
+            }
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+
+        let json = br#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 4, "uri": "data:application/octet-stream;base64,AAAAAA=="}]
+        }"#;
+        assert!(import_slice(&json[..]).is_ok());
+    }
+
+    #[test]
+    fn fetch_decoded_paths() {
+        use std::cell::RefCell;
+
+        struct Record(RefCell<Vec<String>>);
+
+        impl Fetch for Record {
+            fn fetch(&self, path: &str) -> Result<Vec<u8>> {
+                self.0.borrow_mut().push(path.to_string());
+                Ok(vec![0; 4])
+            }
+        }
+
+        let json = br#"{
+            "asset": {"version": "2.0"},
+            "buffers": [
+                {"byteLength": 4, "uri": "data/box%200.bin"},
+                {"byteLength": 4, "uri": "data:application/octet-stream;base64,AAAAAA=="},
+                {"byteLength": 4, "uri": "file:///tmp/box.bin"}
+            ]
+        }"#;
+        let record = Record(RefCell::new(Vec::new()));
+        let (_, buffers, _) = import_slice_with(&json[..], &record).unwrap();
+        assert_eq!(buffers.len(), 3);
+        assert_eq!(*record.0.borrow(), ["data/box 0.bin", "/tmp/box.bin"]);
+
+        let mut files = HashMap::new();
+        files.insert("data/box 0.bin".to_string(), vec![0; 4]);
+        match import_slice_with(&json[..], &files) {
+            Err(Error::Io(error)) => assert_eq!(error.kind(), io::ErrorKind::NotFound),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+        assert!(matches!(
+            import_slice(&json[..]),
+            Err(Error::ExternalReferenceInSliceImport)
+        ));
+    }
+
+    #[test]
+    fn slice_strict() {
+        let json = br#"{"asset": {"version": "2.0"}, "nodes": [{"vendorFlag": false}]}"#;
+        assert!(import_slice(&json[..]).is_ok());
+        match import_slice_strict(&json[..]) {
+            Err(Error::Deserialize(error)) => {
+                assert_eq!(error.to_string(), "unknown field `nodes[0].vendorFlag`")
+            }
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+        assert!(import_strict("examples/Box.gltf").is_ok());
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::hash_map;
+
+use crate::mesh::Semantic;
+use crate::{buffer, Document};
+
+/// The geometry of a primitive, compressed with the Draco library.
+///
+/// With the `draco_decoder` feature, [`transform::decode_draco`] decodes the
+/// compressed data so that [`Reader`] reads it like that of any other
+/// primitive. Otherwise, pass the bytes of [`view`] to a Draco decoder, then
+/// look up each decoded attribute by the id that [`attributes`] gives for its
+/// semantic. The accessors of the primitive still describe the type and count
+/// of the decoded data.
+///
+/// [`transform::decode_draco`]: ../transform/fn.decode_draco.html
+/// [`Reader`]: ../mesh/struct.Reader.html
+/// [`view`]: #method.view
+/// [`attributes`]: #method.attributes
+#[derive(Clone, Debug)]
+pub struct DracoMeshCompression<'a> {
+    /// The parent `Document` struct.
+    document: &'a Document,
+
+    /// The corresponding JSON struct.
+    json: &'a json::extensions::mesh::KhrDracoMeshCompression,
+}
+
+impl<'a> DracoMeshCompression<'a> {
+    /// Constructs a `DracoMeshCompression`.
+    pub(crate) fn new(
+        document: &'a Document,
+        json: &'a json::extensions::mesh::KhrDracoMeshCompression,
+    ) -> Self {
+        Self { document, json }
+    }
+
+    /// Returns the buffer view containing the compressed data.
+    pub fn view(&self) -> buffer::View<'a> {
+        self.document
+            .views()
+            .nth(self.json.buffer_view.value())
+            .unwrap()
+    }
+
+    /// Returns the id of the compressed attribute with the given semantic.
+    pub fn get(&self, semantic: &Semantic) -> Option<u32> {
+        self.json
+            .attributes
+            .get(&json::validation::Checked::Valid(semantic.clone()))
+            .cloned()
+    }
+
+    /// Returns an `Iterator` that visits the semantic and id of each
+    /// compressed attribute.
+    pub fn attributes(&self) -> Attributes<'a> {
+        Attributes {
+            iter: self.json.attributes.iter(),
+        }
+    }
+}
+
+/// An `Iterator` that visits the compressed attributes of a primitive.
+#[derive(Clone, Debug)]
+pub struct Attributes<'a> {
+    /// The internal attribute iterator.
+    iter: hash_map::Iter<'a, json::validation::Checked<Semantic>, u32>,
+}
+
+impl<'a> ExactSizeIterator for Attributes<'a> {}
+impl<'a> Iterator for Attributes<'a> {
+    type Item = (Semantic, u32);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(key, &id)| (key.as_ref().unwrap().clone(), id))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mesh::Semantic;
+
+    #[test]
+    fn draco_mesh_compression() {
+        let gltf = crate::Gltf::from_slice(
+            br#"{
+                "asset": { "version": "2.0" },
+                "extensionsUsed": ["KHR_draco_mesh_compression"],
+                "extensionsRequired": ["KHR_draco_mesh_compression"],
+                "buffers": [{ "byteLength": 8 }],
+                "bufferViews": [{ "buffer": 0, "byteLength": 8 }],
+                "accessors": [{
+                    "componentType": 5126,
+                    "count": 3,
+                    "type": "VEC3",
+                    "min": [0, 0, 0],
+                    "max": [1, 1, 0]
+                }],
+                "meshes": [{
+                    "primitives": [{
+                        "attributes": { "POSITION": 0 },
+                        "extensions": {
+                            "KHR_draco_mesh_compression": {
+                                "bufferView": 0,
+                                "attributes": { "POSITION": 1 }
+                            }
+                        }
+                    }]
+                }]
+            }"#,
+        )
+        .unwrap();
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let draco = primitive.draco_mesh_compression().unwrap();
+        assert_eq!(draco.view().index(), 0);
+        assert_eq!(draco.get(&Semantic::Positions), Some(1));
+        assert_eq!(draco.get(&Semantic::Normals), None);
+        assert_eq!(
+            draco.attributes().collect::<Vec<_>>(),
+            [(Semantic::Positions, 1)]
+        );
+        let view = json::Index::<json::buffer::View>::new(0);
+        assert_eq!(gltf.references_to(view).len(), 1);
+    }
+}
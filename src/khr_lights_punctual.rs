@@ -2,6 +2,7 @@ use crate::Document;
 use gltf_json::Extras;
 
 /// A light in the scene.
+#[derive(Clone, Debug)]
 pub struct Light<'a> {
     /// The parent `Document` struct.
     #[allow(dead_code)]
@@ -49,6 +50,22 @@ impl<'a> Light<'a> {
         &self.json.extras
     }
 
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new()
+                .field("extensions")
+                .field("KHR_lights_punctual")
+                .field("lights")
+                .index(self.index)
+        })
+    }
+
     /// Intensity of the light source. `point` and `spot` lights use luminous intensity
     /// in candela (lm/sr) while `directional` lights use illuminance in lux (lm/m^2).
     pub fn intensity(&self) -> f32 {
@@ -79,6 +96,7 @@ impl<'a> Light<'a> {
 }
 
 /// Light subcategory.
+#[derive(Clone, Debug)]
 pub enum Kind {
     /// Directional lights are light sources that act as though they are infinitely far away
     /// and emit light in the direction of the local -z axis. This light type inherits the
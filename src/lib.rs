@@ -71,11 +71,30 @@
 //! You can read glTF without loading resources by constructing the [`Gltf`]
 //! (standard glTF) or [`Glb`] (binary glTF) data structures explicitly.
 //!
+//! ## Tracing
+//!
+//! With the `tracing` feature, loading emits spans through the [`tracing`]
+//! crate at the debug level, named by the operation they cover:
+//!
+//! * `"parse_json"`, with the `bytes` of JSON parsed.
+//! * `"glb"`, splitting binary glTF into chunks, with the `json_bytes` and
+//!   `bin_bytes` of the chunks found.
+//! * `"validate"`, with the number of `errors` found.
+//! * `"fetch"`, reading a buffer or image from a URI, with the `bytes` read.
+//! * `"decode_image"`, fetching and decoding an image, with its `image` index,
+//!   and the `width`, `height`, and `bytes` of the decoded pixels.
+//! * `"read_accessor"`, setting up the reading of an accessor, with its
+//!   `accessor` index, element `count`, and the `bytes` it spans.
+//!
+//! Fields are recorded as the work proceeds, so some are only set by the time
+//! the span is exited.
+//!
 //! [glTF 2.0]: https://www.khronos.org/gltf
 //! [`Gltf`]: struct.Gltf.html
 //! [`Glb`]: struct.Glb.html
 //! [`Node`]: struct.Node.html
 //! [`Scene`]: struct.Scene.html
+//! [`tracing`]: https://docs.rs/tracing
 
 #[cfg(test)]
 #[macro_use]
@@ -91,6 +110,11 @@ pub extern crate gltf_json as json;
 /// Accessors for reading vertex attributes from buffer views.
 pub mod accessor;
 
+/// Importing glTF with resources fetched asynchronously.
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub mod asynchronous;
+
 /// Animations, their channels, targets, and samplers.
 pub mod animation;
 
@@ -100,9 +124,25 @@ pub mod binary;
 /// Buffers and buffer views.
 pub mod buffer;
 
+/// Building documents and writing them as standard or binary glTF.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub mod build;
+
+/// A cache of imported documents with their decoded data for fast reloads.
+#[cfg(feature = "cache")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+pub mod cache;
+
 /// Cameras and their projections.
 pub mod camera;
 
+/// Editing documents with undo and redo.
+pub mod edit;
+
+/// Stable hashing of document content.
+mod hash;
+
 /// Images that may be used by textures.
 pub mod image;
 
@@ -114,6 +154,11 @@ mod import;
 /// Iterators for walking the glTF node hierarchy.
 pub mod iter;
 
+/// Support for the `KHR_draco_mesh_compression` extension.
+#[cfg(feature = "KHR_draco_mesh_compression")]
+#[cfg_attr(docsrs, doc(cfg(feature = "KHR_draco_mesh_compression")))]
+pub mod khr_draco_mesh_compression;
+
 /// Support for the `KHR_lights_punctual` extension.
 #[cfg(feature = "KHR_lights_punctual")]
 #[cfg_attr(docsrs, doc(cfg(feature = "KHR_lights_punctual")))]
@@ -124,12 +169,22 @@ pub mod khr_lights_punctual;
 #[cfg_attr(docsrs, doc(cfg(feature = "KHR_materials_variants")))]
 pub mod khr_materials_variants;
 
+/// Limits on the size of documents being loaded.
+pub mod limits;
+
 /// Material properties of primitives.
 pub mod material;
 
 /// For internal use.
 mod math;
 
+/// For internal use.
+#[cfg(feature = "utils")]
+mod normalize;
+
+/// Estimating the GPU memory used by the images of a document.
+pub mod memory;
+
 /// Meshes and their primitives.
 pub mod mesh;
 
@@ -139,9 +194,33 @@ pub mod scene;
 /// Mesh skinning primitives.
 pub mod skin;
 
+/// Generating random valid documents for property testing.
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
 /// Textures and their samplers.
 pub mod texture;
 
+/// Instrumentation of loading with `tracing` spans.
+mod trace;
+
+/// Transformations that restructure glTF documents.
+///
+/// Passes reading vertex data leave primitives compressed by
+/// `KHR_draco_mesh_compression`, and the bounds of their attributes,
+/// unchanged; run `decode_draco`, behind the `draco_decoder` feature, first.
+pub mod transform;
+
+/// Upgrading glTF 1.0 documents to glTF 2.0.
+pub mod upgrade;
+
+/// Reverse lookups of the objects using an image, texture, or material.
+pub mod usage;
+
+/// Walking and rewriting the indices of a glTF document.
+mod visit;
+
 #[doc(inline)]
 pub use self::accessor::Accessor;
 #[doc(inline)]
@@ -159,7 +238,24 @@ pub use self::image::Image;
 pub use self::import::import;
 #[cfg(feature = "import")]
 #[doc(inline)]
+pub use self::import::import_scene;
+#[cfg(feature = "import")]
+#[doc(inline)]
 pub use self::import::import_slice;
+#[cfg(feature = "import")]
+#[doc(inline)]
+pub use self::import::{import_buffers, import_images};
+#[cfg(feature = "import")]
+#[doc(inline)]
+pub use self::import::{import_buffers_with, import_images_with, import_slice_with, Fetch};
+#[cfg(feature = "import")]
+#[doc(inline)]
+pub use self::import::{import_slice_strict, import_strict};
+#[cfg(feature = "import")]
+#[doc(inline)]
+pub use self::import::{import_slice_with_limits, import_with_limits};
+#[doc(inline)]
+pub use self::limits::Limits;
 #[doc(inline)]
 pub use self::material::Material;
 #[doc(inline)]
@@ -172,17 +268,21 @@ pub use self::skin::Skin;
 pub use self::texture::Texture;
 
 use std::path::Path;
+use std::sync::Arc;
 use std::{fs, io, ops, result};
 
-pub(crate) trait Normalize<T> {
-    fn normalize(self) -> T;
-}
+#[cfg(feature = "utils")]
+use self::normalize::Normalize;
 
 /// Result type for convenience.
 pub type Result<T> = result::Result<T, Error>;
 
 /// Represents a runtime error.
+///
+/// New kinds of failure may be reported in later releases, so matches on
+/// this type must include a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// Base 64 decoding error.
     #[cfg(feature = "import")]
@@ -192,6 +292,11 @@ pub enum Error {
     /// GLB parsing error.
     Binary(binary::Error),
 
+    /// Cache reading or writing error.
+    #[cfg(feature = "cache")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "cache")))]
+    Cache(cache::Error),
+
     /// Buffer length does not match expected length.
     #[cfg(feature = "import")]
     #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
@@ -209,9 +314,27 @@ pub enum Error {
     /// JSON deserialization error.
     Deserialize(json::Error),
 
+    /// JSON serialization error.
+    Serialize(json::Error),
+
+    /// Application specific data could not be deserialized into the
+    /// requested type.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    Extras {
+        /// The JSON path of the data.
+        path: json::Path,
+
+        /// The deserialization error.
+        error: json::Error,
+    },
+
     /// Standard I/O error.
     Io(std::io::Error),
 
+    /// The document exceeds one of the given `Limits`.
+    Limit(limits::Exceeded),
+
     /// Image decoding error.
     #[cfg(feature = "import")]
     #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
@@ -222,6 +345,11 @@ pub enum Error {
     #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
     MissingBlob,
 
+    /// The document has no scene of the given index.
+    #[cfg(feature = "import")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+    MissingScene(usize),
+
     /// An external file is referenced in a slice only import without path
     #[cfg(feature = "import")]
     #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
@@ -242,10 +370,172 @@ pub enum Error {
     #[cfg_attr(docsrs, doc(cfg(feature = "import")))]
     UnsupportedScheme,
 
+    /// A transform pass cannot read the data of the document.
+    Transform(transform::Error),
+
+    /// The document is of a glTF version other than 2.x, such as glTF 1.0.
+    ///
+    /// glTF 1.0 documents can be converted with `upgrade::from_slice`.
+    UnsupportedVersion(String),
+
     /// glTF validation error.
     Validation(Vec<(json::Path, json::validation::Error)>),
 }
 
+/// The UTF-8 byte order mark, which some exporters write before glTF JSON.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Strips a leading byte order mark and any trailing whitespace or NUL padding
+/// from glTF JSON.
+fn trim_json(mut json: &[u8]) -> &[u8] {
+    if json.starts_with(UTF8_BOM) {
+        json = &json[UTF8_BOM.len()..];
+    }
+    while let Some((&last, rest)) = json.split_last() {
+        if !matches!(last, b' ' | b'\t' | b'\n' | b'\r' | 0) {
+            break;
+        }
+        json = rest;
+    }
+    json
+}
+
+/// Top-level properties that are arrays in glTF 2.0 but objects keyed by ID in
+/// glTF 1.0.
+const ARRAY_PROPERTIES: &[&str] = &[
+    "accessors",
+    "animations",
+    "buffers",
+    "bufferViews",
+    "cameras",
+    "images",
+    "materials",
+    "meshes",
+    "nodes",
+    "samplers",
+    "scenes",
+    "skins",
+    "textures",
+];
+
+/// Returns an error unless the given `asset.version` has major version 2.
+fn check_version(version: &str) -> Result<()> {
+    if version.split('.').next() == Some("2") {
+        Ok(())
+    } else {
+        Err(Error::UnsupportedVersion(version.to_string()))
+    }
+}
+
+/// Guesses the glTF version of JSON that is not valid glTF 2.0.
+fn sniff_version(json: &json::Value) -> Option<String> {
+    let asset = json.get("asset");
+    if let Some(version) = asset.and_then(|asset| asset.get("version")?.as_str()) {
+        return Some(version.to_string());
+    }
+    let profile = asset.and_then(|asset| asset.get("profile")).is_some();
+    let keyed = ARRAY_PROPERTIES
+        .iter()
+        .any(|property| json.get(property).is_some_and(json::Value::is_object));
+    if profile || keyed {
+        Some("1.0".to_string())
+    } else {
+        None
+    }
+}
+
+/// Deserializes glTF JSON, rejecting documents of other glTF versions.
+fn parse_json(json: &[u8], strict: bool) -> Result<json::Root> {
+    let json = trim_json(json);
+    let mut span = trace::span!("parse_json", bytes);
+    span.record("bytes", json.len());
+    let root = if strict {
+        json::Root::from_slice_strict(json)
+    } else {
+        json::deserialize::from_slice::<json::Root>(json)
+    };
+    match root {
+        Ok(root) => {
+            check_version(&root.asset.version)?;
+            Ok(root)
+        }
+        Err(error) => {
+            // Documents of other versions rarely deserialize, so look for
+            // their version before reporting the error.
+            let value = json::deserialize::from_slice::<json::Value>(json).ok();
+            if let Some(version) = value.as_ref().and_then(sniff_version) {
+                check_version(&version)?;
+            }
+            Err(error.into())
+        }
+    }
+}
+
+/// The encoding of glTF data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// Standard glTF, as JSON text.
+    Json,
+
+    /// Binary glTF.
+    Binary,
+}
+
+/// The encoding and glTF version of some data, as found by `detect`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Detected {
+    /// The encoding of the data.
+    pub format: Format,
+
+    /// The glTF version, such as `"2.0"` or `"1.0"`, if one could be found.
+    pub version: Option<String>,
+}
+
+impl Detected {
+    /// Returns `true` if the data can be loaded by this crate.
+    pub fn is_supported(&self) -> bool {
+        self.version
+            .as_ref()
+            .is_some_and(|version| check_version(version).is_ok())
+    }
+}
+
+/// Sniffs whether some data is standard or binary glTF, and of which glTF
+/// version.
+///
+/// Binary glTF 1.0, as defined by the `KHR_binary_glTF` extension, is
+/// recognized by its header. Otherwise the JSON is parsed, though neither
+/// validated nor loaded as a document, and the version is taken from
+/// `asset.version`, or guessed from its structure for glTF 1.0 documents that
+/// omit it.
+///
+/// To load data of either version, use `upgrade::from_slice`, which upgrades
+/// glTF 1.0 and returns glTF 2.0 as is.
+pub fn detect(slice: &[u8]) -> Result<Detected> {
+    let (format, json) = if slice.starts_with(b"glTF") {
+        if slice.get(4..8) == Some(&1u32.to_le_bytes()[..]) {
+            return Ok(Detected {
+                format: Format::Binary,
+                version: Some("1.0".to_string()),
+            });
+        }
+        let glb = binary::Glb::from_slice(slice)?;
+        (
+            Format::Binary,
+            json::deserialize::from_slice(trim_json(&glb.json))?,
+        )
+    } else {
+        (
+            Format::Json,
+            json::deserialize::from_slice(trim_json(slice))?,
+        )
+    };
+    Ok(Detected {
+        format,
+        version: sniff_version(&json),
+    })
+}
+
 /// glTF JSON wrapper plus binary payload.
 #[derive(Clone, Debug)]
 pub struct Gltf {
@@ -273,7 +563,20 @@ impl Gltf {
     }
 
     /// Loads glTF from a reader without performing validation checks.
-    pub fn from_reader_without_validation<R>(mut reader: R) -> Result<Self>
+    ///
+    /// A leading UTF-8 byte order mark is skipped, as is trailing whitespace
+    /// or NUL padding. Documents of a glTF version other than 2.x, such as
+    /// glTF 1.0, are rejected with `Error::UnsupportedVersion`.
+    pub fn from_reader_without_validation<R>(reader: R) -> Result<Self>
+    where
+        R: io::Read + io::Seek,
+    {
+        Self::read_with_limits(reader, &Limits::default())
+    }
+
+    /// Loads glTF from a reader without performing validation checks,
+    /// rejecting documents that exceed `limits`.
+    fn read_with_limits<R>(mut reader: R, limits: &Limits) -> Result<Self>
     where
         R: io::Read + io::Seek,
     {
@@ -283,14 +586,26 @@ impl Gltf {
         let (json, blob): (json::Root, Option<Vec<u8>>);
         if magic.starts_with(b"glTF") {
             let mut glb = binary::Glb::from_reader(reader)?;
+            limits.check_json(glb.json.len())?;
             // TODO: use `json::from_reader` instead of `json::from_slice`
-            json = json::deserialize::from_slice(&glb.json)?;
+            json = parse_json(&glb.json, false)?;
             blob = glb.bin.take().map(|x| x.into_owned());
         } else {
-            json = json::deserialize::from_reader(reader)?;
+            let mut data = Vec::new();
+            match limits.json_size {
+                // Read one byte past the limit to tell whether it is exceeded.
+                Some(max) => {
+                    let mut limited = io::Read::take(&mut reader, max as u64 + 1);
+                    io::Read::read_to_end(&mut limited, &mut data)?
+                }
+                None => reader.read_to_end(&mut data)?,
+            };
+            limits.check_json(data.len())?;
+            json = parse_json(&data, false)?;
             blob = None;
         };
         let document = Document::from_json_without_validation(json);
+        limits.check_document(&document)?;
         Ok(Gltf { document, blob })
     }
 
@@ -300,30 +615,77 @@ impl Gltf {
         R: io::Read + io::Seek,
     {
         let gltf = Self::from_reader_without_validation(reader)?;
-        let _ = gltf.document.validate()?;
+        gltf.document.validate()?;
+        Ok(gltf)
+    }
+
+    /// Loads glTF from a reader, rejecting documents that exceed `limits`
+    /// with `Error::Limit` before validating them.
+    pub fn from_reader_with_limits<R>(reader: R, limits: &Limits) -> Result<Self>
+    where
+        R: io::Read + io::Seek,
+    {
+        let gltf = Self::read_with_limits(reader, limits)?;
+        gltf.document.validate()?;
         Ok(gltf)
     }
 
     /// Loads glTF from a slice of bytes without performing validation
     /// checks.
+    ///
+    /// A leading UTF-8 byte order mark is skipped, as is trailing whitespace
+    /// or NUL padding. Documents of a glTF version other than 2.x, such as
+    /// glTF 1.0, are rejected with `Error::UnsupportedVersion`.
     pub fn from_slice_without_validation(slice: &[u8]) -> Result<Self> {
+        Self::slice_with_limits(slice, &Limits::default(), false)
+    }
+
+    /// Loads glTF from a slice of bytes without performing validation
+    /// checks, rejecting documents that exceed `limits`, and if `strict`,
+    /// documents with unknown members.
+    fn slice_with_limits(slice: &[u8], limits: &Limits, strict: bool) -> Result<Self> {
         let (json, blob): (json::Root, Option<Vec<u8>>);
         if slice.starts_with(b"glTF") {
             let mut glb = binary::Glb::from_slice(slice)?;
-            json = json::deserialize::from_slice(&glb.json)?;
+            limits.check_json(glb.json.len())?;
+            json = parse_json(&glb.json, strict)?;
             blob = glb.bin.take().map(|x| x.into_owned());
         } else {
-            json = json::deserialize::from_slice(slice)?;
+            limits.check_json(slice.len())?;
+            json = parse_json(slice, strict)?;
             blob = None;
         };
         let document = Document::from_json_without_validation(json);
+        limits.check_document(&document)?;
         Ok(Gltf { document, blob })
     }
 
     /// Loads glTF from a slice of bytes.
     pub fn from_slice(slice: &[u8]) -> Result<Self> {
         let gltf = Self::from_slice_without_validation(slice)?;
-        let _ = gltf.document.validate()?;
+        gltf.document.validate()?;
+        Ok(gltf)
+    }
+
+    /// Loads glTF from a slice of bytes, rejecting documents that exceed
+    /// `limits` with `Error::Limit` before validating them.
+    pub fn from_slice_with_limits(slice: &[u8], limits: &Limits) -> Result<Self> {
+        let gltf = Self::slice_with_limits(slice, limits, false)?;
+        gltf.document.validate()?;
+        Ok(gltf)
+    }
+
+    /// Loads glTF from a slice of bytes, rejecting documents with members of
+    /// objects that are not recognized.
+    ///
+    /// Other loading functions are lenient, ignoring such members so that
+    /// documents from exporters that add fields of their own still load. This
+    /// function instead fails with `Error::Deserialize`, naming the path of
+    /// the first unknown member. Use `json::validation::Report::with_source`
+    /// to list every unknown member as a warning instead.
+    pub fn from_slice_strict(slice: &[u8]) -> Result<Self> {
+        let gltf = Self::slice_with_limits(slice, &Limits::default(), true)?;
+        gltf.document.validate()?;
         Ok(gltf)
     }
 }
@@ -341,11 +703,27 @@ impl ops::DerefMut for Gltf {
     }
 }
 
+/// Perform validation checks on glTF JSON.
+pub(crate) fn validate(root: &json::Root) -> Result<()> {
+    use json::validation::Validate;
+    let mut span = trace::span!("validate", errors);
+    let mut errors = Vec::new();
+    root.validate(root, json::Path::new, &mut |path, error| {
+        errors.push((path(), error))
+    });
+    span.record("errors", errors.len());
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Validation(errors))
+    }
+}
+
 impl Document {
     /// Loads glTF from pre-deserialized JSON.
     pub fn from_json(json: json::Root) -> Result<Self> {
         let document = Self::from_json_without_validation(json);
-        let _ = document.validate()?;
+        document.validate()?;
         Ok(document)
     }
 
@@ -360,23 +738,58 @@ impl Document {
         self.0
     }
 
+    /// Moves the document behind an `Arc`, so that many threads can read it
+    /// at once without cloning it.
+    ///
+    /// `Document`, the buffer and image data, and every wrapper type such as
+    /// `Node` or `Accessor` are `Send + Sync`, so wrappers borrowed from the
+    /// shared document may be used on any thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let (document, buffers, _) = gltf::import("examples/Box.gltf")?;
+    /// let document = document.into_shared();
+    /// let buffers = Arc::new(buffers);
+    /// let threads: Vec<_> = (0..4)
+    ///     .map(|_| {
+    ///         let (document, buffers) = (document.clone(), buffers.clone());
+    ///         thread::spawn(move || {
+    ///             document
+    ///                 .meshes()
+    ///                 .flat_map(|mesh| mesh.primitives())
+    ///                 .map(|primitive| {
+    ///                     let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+    ///                     reader.read_positions().map_or(0, |positions| positions.count())
+    ///                 })
+    ///                 .sum::<usize>()
+    ///         })
+    ///     })
+    ///     .collect();
+    /// for thread in threads {
+    ///     assert_eq!(thread.join().unwrap(), 24);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("test failure");
+    /// # }
+    /// ```
+    pub fn into_shared(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+
     /// Perform validation checks on loaded glTF.
     pub(crate) fn validate(&self) -> Result<()> {
-        use json::validation::Validate;
-        let mut errors = Vec::new();
-        self.0
-            .validate(&self.0, json::Path::new, &mut |path, error| {
-                errors.push((path(), error))
-            });
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(Error::Validation(errors))
-        }
+        validate(&self.0)
     }
 
     /// Returns an `Iterator` that visits the accessors of the glTF asset.
-    pub fn accessors(&self) -> iter::Accessors {
+    pub fn accessors(&self) -> iter::Accessors<'_> {
         iter::Accessors {
             iter: self.0.accessors.iter().enumerate(),
             document: self,
@@ -384,7 +797,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the animations of the glTF asset.
-    pub fn animations(&self) -> iter::Animations {
+    pub fn animations(&self) -> iter::Animations<'_> {
         iter::Animations {
             iter: self.0.animations.iter().enumerate(),
             document: self,
@@ -392,7 +805,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the pre-loaded buffers of the glTF asset.
-    pub fn buffers(&self) -> iter::Buffers {
+    pub fn buffers(&self) -> iter::Buffers<'_> {
         iter::Buffers {
             iter: self.0.buffers.iter().enumerate(),
             document: self,
@@ -400,7 +813,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the cameras of the glTF asset.
-    pub fn cameras(&self) -> iter::Cameras {
+    pub fn cameras(&self) -> iter::Cameras<'_> {
         iter::Cameras {
             iter: self.0.cameras.iter().enumerate(),
             document: self,
@@ -408,7 +821,7 @@ impl Document {
     }
 
     /// Returns the default scene, if provided.
-    pub fn default_scene(&self) -> Option<Scene> {
+    pub fn default_scene(&self) -> Option<Scene<'_>> {
         self.0
             .scene
             .as_ref()
@@ -416,17 +829,17 @@ impl Document {
     }
 
     /// Returns the extensions referenced in this .document file.
-    pub fn extensions_used(&self) -> iter::ExtensionsUsed {
+    pub fn extensions_used(&self) -> iter::ExtensionsUsed<'_> {
         iter::ExtensionsUsed(self.0.extensions_used.iter())
     }
 
     /// Returns the extensions required to load and render this asset.
-    pub fn extensions_required(&self) -> iter::ExtensionsRequired {
+    pub fn extensions_required(&self) -> iter::ExtensionsRequired<'_> {
         iter::ExtensionsRequired(self.0.extensions_required.iter())
     }
 
     /// Returns an `Iterator` that visits the pre-loaded images of the glTF asset.
-    pub fn images(&self) -> iter::Images {
+    pub fn images(&self) -> iter::Images<'_> {
         iter::Images {
             iter: self.0.images.iter().enumerate(),
             document: self,
@@ -437,7 +850,7 @@ impl Document {
     /// `KHR_lights_punctual` extension.
     #[cfg(feature = "KHR_lights_punctual")]
     #[cfg_attr(docsrs, doc(cfg(feature = "KHR_lights_punctual")))]
-    pub fn lights(&self) -> Option<iter::Lights> {
+    pub fn lights(&self) -> Option<iter::Lights<'_>> {
         let iter = self
             .0
             .extensions
@@ -458,7 +871,7 @@ impl Document {
     /// `KHR_materials_variants` extension.
     #[cfg(feature = "KHR_materials_variants")]
     #[cfg_attr(docsrs, doc(cfg(feature = "KHR_materials_variants")))]
-    pub fn variants(&self) -> Option<iter::Variants> {
+    pub fn variants(&self) -> Option<iter::Variants<'_>> {
         let iter = self
             .0
             .extensions
@@ -476,7 +889,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the materials of the glTF asset.
-    pub fn materials(&self) -> iter::Materials {
+    pub fn materials(&self) -> iter::Materials<'_> {
         iter::Materials {
             iter: self.0.materials.iter().enumerate(),
             document: self,
@@ -484,7 +897,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the meshes of the glTF asset.
-    pub fn meshes(&self) -> iter::Meshes {
+    pub fn meshes(&self) -> iter::Meshes<'_> {
         iter::Meshes {
             iter: self.0.meshes.iter().enumerate(),
             document: self,
@@ -492,7 +905,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the nodes of the glTF asset.
-    pub fn nodes(&self) -> iter::Nodes {
+    pub fn nodes(&self) -> iter::Nodes<'_> {
         iter::Nodes {
             iter: self.0.nodes.iter().enumerate(),
             document: self,
@@ -500,7 +913,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the samplers of the glTF asset.
-    pub fn samplers(&self) -> iter::Samplers {
+    pub fn samplers(&self) -> iter::Samplers<'_> {
         iter::Samplers {
             iter: self.0.samplers.iter().enumerate(),
             document: self,
@@ -508,7 +921,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the scenes of the glTF asset.
-    pub fn scenes(&self) -> iter::Scenes {
+    pub fn scenes(&self) -> iter::Scenes<'_> {
         iter::Scenes {
             iter: self.0.scenes.iter().enumerate(),
             document: self,
@@ -516,7 +929,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the skins of the glTF asset.
-    pub fn skins(&self) -> iter::Skins {
+    pub fn skins(&self) -> iter::Skins<'_> {
         iter::Skins {
             iter: self.0.skins.iter().enumerate(),
             document: self,
@@ -524,7 +937,7 @@ impl Document {
     }
 
     /// Returns an `Iterator` that visits the textures of the glTF asset.
-    pub fn textures(&self) -> iter::Textures {
+    pub fn textures(&self) -> iter::Textures<'_> {
         iter::Textures {
             iter: self.0.textures.iter().enumerate(),
             document: self,
@@ -533,7 +946,7 @@ impl Document {
 
     /// Returns an `Iterator` that visits the pre-loaded buffer views of the glTF
     /// asset.
-    pub fn views(&self) -> iter::Views {
+    pub fn views(&self) -> iter::Views<'_> {
         iter::Views {
             iter: self.0.buffer_views.iter().enumerate(),
             document: self,
@@ -541,12 +954,55 @@ impl Document {
     }
 }
 
+/// Fails to compile if any of the listed types stop being `Send + Sync`, which
+/// `Document::into_shared` promises.
+#[allow(dead_code)]
+fn assert_send_sync() {
+    fn check<T: Send + Sync>() {}
+    check::<Document>();
+    check::<Gltf>();
+    check::<Glb<'static>>();
+    check::<Error>();
+    check::<buffer::Data>();
+    check::<edit::Editor>();
+    #[cfg(feature = "import")]
+    check::<image::Data>();
+    check::<Accessor<'static>>();
+    check::<Animation<'static>>();
+    check::<animation::Channel<'static>>();
+    check::<animation::Sampler<'static>>();
+    #[cfg(feature = "utils")]
+    check::<animation::TransformCache>();
+    check::<Buffer<'static>>();
+    check::<buffer::View<'static>>();
+    check::<Camera<'static>>();
+    check::<Image<'static>>();
+    check::<Material<'static>>();
+    check::<Mesh<'static>>();
+    check::<Primitive<'static>>();
+    check::<mesh::MorphTarget<'static>>();
+    check::<Node<'static>>();
+    check::<Scene<'static>>();
+    check::<Skin<'static>>();
+    check::<Texture<'static>>();
+    check::<texture::Sampler<'static>>();
+    check::<usage::Usage<'static>>();
+    #[cfg(feature = "KHR_lights_punctual")]
+    check::<khr_lights_punctual::Light<'static>>();
+    #[cfg(feature = "KHR_materials_variants")]
+    check::<khr_materials_variants::Variant<'static>>();
+    check::<iter::Nodes<'static>>();
+    check::<scene::iter::Children<'static>>();
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             #[cfg(feature = "import")]
             Error::Base64(ref e) => e.fmt(f),
             Error::Binary(ref e) => e.fmt(f),
+            #[cfg(feature = "cache")]
+            Error::Cache(ref e) => e.fmt(f),
             #[cfg(feature = "import")]
             Error::BufferLength {
                 buffer,
@@ -560,12 +1016,21 @@ impl std::fmt::Display for Error {
                 )
             }
             Error::Deserialize(ref e) => e.fmt(f),
+            Error::Serialize(ref e) => e.fmt(f),
+            #[cfg(feature = "extras")]
+            Error::Extras {
+                ref path,
+                ref error,
+            } => write!(f, "{}: {}", path, error),
             Error::Io(ref e) => e.fmt(f),
+            Error::Limit(ref e) => e.fmt(f),
             #[cfg(feature = "import")]
             Error::Image(ref e) => e.fmt(f),
             #[cfg(feature = "import")]
             Error::MissingBlob => write!(f, "missing binary portion of binary glTF"),
             #[cfg(feature = "import")]
+            Error::MissingScene(scene) => write!(f, "missing scene {}", scene),
+            #[cfg(feature = "import")]
             Error::ExternalReferenceInSliceImport => {
                 write!(f, "external reference in slice only import")
             }
@@ -577,9 +1042,13 @@ impl std::fmt::Display for Error {
             }
             #[cfg(feature = "import")]
             Error::UnsupportedScheme => write!(f, "unsupported URI scheme"),
+            Error::Transform(ref e) => e.fmt(f),
+            Error::UnsupportedVersion(ref version) => {
+                write!(f, "unsupported glTF version {}", version)
+            }
             Error::Validation(ref xs) => {
                 write!(f, "invalid glTF:")?;
-                for &(ref path, ref error) in xs {
+                for (path, error) in xs {
                     write!(f, " {}: {};", path, error)?;
                 }
                 Ok(())
@@ -590,12 +1059,37 @@ impl std::fmt::Display for Error {
 
 impl std::error::Error for Error {}
 
+/// Deserializes application specific data, reporting errors with the JSON path
+/// of the object holding it.
+#[cfg(feature = "extras")]
+fn extras_as<'a, T, P>(extras: &'a json::Extras, path: P) -> Result<Option<T>>
+where
+    T: serde::Deserialize<'a>,
+    P: Fn() -> json::Path,
+{
+    match extras {
+        Some(raw) => json::deserialize::from_str(raw.get())
+            .map(Some)
+            .map_err(|error| Error::Extras {
+                path: path().field("extras"),
+                error,
+            }),
+        None => Ok(None),
+    }
+}
+
 impl From<binary::Error> for Error {
     fn from(err: binary::Error) -> Self {
         Error::Binary(err)
     }
 }
 
+impl From<transform::Error> for Error {
+    fn from(err: transform::Error) -> Self {
+        Error::Transform(err)
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
         Error::Io(err)
@@ -620,189 +1114,3 @@ impl From<Vec<(json::Path, json::validation::Error)>> for Error {
         Error::Validation(errs)
     }
 }
-
-impl Normalize<i8> for i8 {
-    fn normalize(self) -> i8 {
-        self
-    }
-}
-
-impl Normalize<u8> for i8 {
-    fn normalize(self) -> u8 {
-        self.max(0) as u8 * 2
-    }
-}
-
-impl Normalize<i16> for i8 {
-    fn normalize(self) -> i16 {
-        self as i16 * 0x100
-    }
-}
-
-impl Normalize<u16> for i8 {
-    fn normalize(self) -> u16 {
-        self.max(0) as u16 * 0x200
-    }
-}
-
-impl Normalize<f32> for i8 {
-    fn normalize(self) -> f32 {
-        (self as f32 * 127.0_f32.recip()).max(-1.0)
-    }
-}
-
-impl Normalize<i8> for u8 {
-    fn normalize(self) -> i8 {
-        (self / 2) as i8
-    }
-}
-
-impl Normalize<u8> for u8 {
-    fn normalize(self) -> u8 {
-        self
-    }
-}
-
-impl Normalize<i16> for u8 {
-    fn normalize(self) -> i16 {
-        self as i16 * 0x80
-    }
-}
-
-impl Normalize<u16> for u8 {
-    fn normalize(self) -> u16 {
-        self as u16 * 0x100
-    }
-}
-
-impl Normalize<f32> for u8 {
-    fn normalize(self) -> f32 {
-        self as f32 * 255.0_f32.recip()
-    }
-}
-
-impl Normalize<i8> for i16 {
-    fn normalize(self) -> i8 {
-        (self / 0x100) as i8
-    }
-}
-
-impl Normalize<u8> for i16 {
-    fn normalize(self) -> u8 {
-        (self.max(0) / 0x80) as u8
-    }
-}
-
-impl Normalize<i16> for i16 {
-    fn normalize(self) -> i16 {
-        self
-    }
-}
-
-impl Normalize<u16> for i16 {
-    fn normalize(self) -> u16 {
-        self.max(0) as u16 * 2
-    }
-}
-
-impl Normalize<f32> for i16 {
-    fn normalize(self) -> f32 {
-        (self as f32 * 32767.0_f32.recip()).max(-1.0)
-    }
-}
-
-impl Normalize<i8> for u16 {
-    fn normalize(self) -> i8 {
-        (self / 0x200) as i8
-    }
-}
-
-impl Normalize<u8> for u16 {
-    fn normalize(self) -> u8 {
-        (self / 0x100) as u8
-    }
-}
-
-impl Normalize<i16> for u16 {
-    fn normalize(self) -> i16 {
-        (self / 2) as i16
-    }
-}
-
-impl Normalize<u16> for u16 {
-    fn normalize(self) -> u16 {
-        self
-    }
-}
-
-impl Normalize<f32> for u16 {
-    fn normalize(self) -> f32 {
-        self as f32 * 65535.0_f32.recip()
-    }
-}
-
-impl Normalize<i8> for f32 {
-    fn normalize(self) -> i8 {
-        (self * 127.0) as i8
-    }
-}
-
-impl Normalize<u8> for f32 {
-    fn normalize(self) -> u8 {
-        (self.max(0.0) * 255.0) as u8
-    }
-}
-
-impl Normalize<i16> for f32 {
-    fn normalize(self) -> i16 {
-        (self * 32767.0) as i16
-    }
-}
-
-impl Normalize<u16> for f32 {
-    fn normalize(self) -> u16 {
-        (self.max(0.0) * 65535.0) as u16
-    }
-}
-
-impl Normalize<f32> for f32 {
-    fn normalize(self) -> f32 {
-        self
-    }
-}
-
-impl<U, T> Normalize<[T; 2]> for [U; 2]
-where
-    U: Normalize<T> + Copy,
-{
-    fn normalize(self) -> [T; 2] {
-        [self[0].normalize(), self[1].normalize()]
-    }
-}
-
-impl<U, T> Normalize<[T; 3]> for [U; 3]
-where
-    U: Normalize<T> + Copy,
-{
-    fn normalize(self) -> [T; 3] {
-        [
-            self[0].normalize(),
-            self[1].normalize(),
-            self[2].normalize(),
-        ]
-    }
-}
-
-impl<U, T> Normalize<[T; 4]> for [U; 4]
-where
-    U: Normalize<T> + Copy,
-{
-    fn normalize(self) -> [T; 4] {
-        [
-            self[0].normalize(),
-            self[1].normalize(),
-            self[2].normalize(),
-            self[3].normalize(),
-        ]
-    }
-}
@@ -0,0 +1,314 @@
+use std::fmt;
+
+use crate::visit::{self, Kind, KINDS};
+use crate::{Document, Error, Result};
+
+/// Upper bounds on the size of a document, checked while it is loaded.
+///
+/// Every limit is `None` by default, meaning unlimited. Services loading
+/// untrusted files should set all of them, so that a small file declaring
+/// huge buffers or deeply nested nodes is rejected up front with
+/// `Error::Limit` rather than exhausting memory.
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use gltf::Limits;
+///
+/// let limits = Limits {
+///     json_size: Some(16 << 20),
+///     buffer_bytes: Some(256 << 20),
+///     objects: Some(100_000),
+///     node_depth: Some(256),
+///     accessor_expansion: Some(1024),
+/// };
+/// # #[allow(unused)]
+/// let (document, buffers, images) = gltf::import_with_limits("examples/Box.gltf", &limits)?;
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Limits {
+    /// The maximum size in bytes of the JSON, standalone or in the JSON chunk
+    /// of binary glTF.
+    pub json_size: Option<usize>,
+
+    /// The maximum total size in bytes of the buffers, both as declared by
+    /// their `byteLength` and as loaded.
+    pub buffer_bytes: Option<usize>,
+
+    /// The maximum number of objects of any one kind, such as nodes or
+    /// accessors.
+    pub objects: Option<usize>,
+
+    /// The maximum depth of the node hierarchy, where nodes without a parent
+    /// are at depth 1.
+    pub node_depth: Option<usize>,
+
+    /// The maximum number of elements an accessor without a buffer view may
+    /// hold per element stored in its sparse substitution, which bounds how
+    /// far such accessors expand in memory when read.
+    pub accessor_expansion: Option<usize>,
+}
+
+/// A limit of `Limits`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Limit {
+    /// `Limits::json_size`.
+    JsonSize,
+
+    /// `Limits::buffer_bytes`.
+    BufferBytes,
+
+    /// `Limits::objects`, for objects of the given kind.
+    Objects(Kind),
+
+    /// `Limits::node_depth`.
+    NodeDepth,
+
+    /// `Limits::accessor_expansion`, for the accessor of the given index.
+    AccessorExpansion(usize),
+}
+
+/// A limit that a document exceeds, as reported by `Error::Limit`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Exceeded {
+    /// The limit that is exceeded.
+    pub limit: Limit,
+
+    /// The value of the limit.
+    pub max: usize,
+
+    /// The value found, or for `Limit::NodeDepth` the depth at which checking
+    /// stopped.
+    pub actual: usize,
+}
+
+impl fmt::Display for Exceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let limit = match self.limit {
+            Limit::JsonSize => "JSON size".to_string(),
+            Limit::BufferBytes => "buffer size".to_string(),
+            Limit::Objects(kind) => format!("{:?} count", kind),
+            Limit::NodeDepth => "node depth".to_string(),
+            Limit::AccessorExpansion(index) => format!("expansion of accessor {}", index),
+        };
+        write!(
+            f,
+            "{} of {} exceeds the limit of {}",
+            limit, self.actual, self.max
+        )
+    }
+}
+
+/// Returns an error if `actual` exceeds `max`.
+fn check(limit: Limit, max: Option<usize>, actual: usize) -> Result<()> {
+    match max {
+        Some(max) if actual > max => Err(Error::Limit(Exceeded { limit, max, actual })),
+        _ => Ok(()),
+    }
+}
+
+impl Limits {
+    /// Checks the size of some JSON before it is parsed.
+    pub(crate) fn check_json(&self, size: usize) -> Result<()> {
+        check(Limit::JsonSize, self.json_size, size)
+    }
+
+    /// Checks the total size of the buffers loaded so far.
+    pub(crate) fn check_buffer_bytes(&self, size: usize) -> Result<()> {
+        check(Limit::BufferBytes, self.buffer_bytes, size)
+    }
+
+    /// Checks a parsed document before any of its resources are loaded.
+    pub(crate) fn check_document(&self, document: &Document) -> Result<()> {
+        for &kind in KINDS {
+            check(
+                Limit::Objects(kind),
+                self.objects,
+                visit::len(&document.0, kind),
+            )?;
+        }
+        let declared = document.buffers().fold(0usize, |total, buffer| {
+            total.saturating_add(buffer.length())
+        });
+        self.check_buffer_bytes(declared)?;
+        if let Some(max) = self.node_depth {
+            check_node_depth(document, max)?;
+        }
+        for (index, accessor) in document.0.accessors.iter().enumerate() {
+            if accessor.buffer_view.is_none() {
+                let stored = accessor.sparse.as_ref().map_or(0, |sparse| sparse.count);
+                let expansion = accessor.count as usize / stored.max(1) as usize;
+                check(
+                    Limit::AccessorExpansion(index),
+                    self.accessor_expansion,
+                    expansion,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Checks the depth of the node hierarchy, guarding against cycles.
+fn check_node_depth(document: &Document, max: usize) -> Result<()> {
+    let nodes = &document.0.nodes;
+    let mut parents = vec![None; nodes.len()];
+    for (index, node) in nodes.iter().enumerate() {
+        for child in node.children.iter().flatten() {
+            if let Some(parent) = parents.get_mut(child.value()) {
+                *parent = Some(index);
+            }
+        }
+    }
+
+    let mut depths: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut chain = Vec::new();
+    for start in 0..nodes.len() {
+        // Walk up until a node of known depth, giving up past the limit,
+        // which also ends cycles.
+        chain.clear();
+        let mut next = Some(start);
+        let mut base = 0;
+        while let Some(node) = next {
+            if let Some(depth) = depths[node] {
+                base = depth;
+                break;
+            }
+            chain.push(node);
+            if chain.len() > max {
+                break;
+            }
+            next = parents[node];
+        }
+        check(Limit::NodeDepth, Some(max), base + chain.len())?;
+        for (i, &node) in chain.iter().rev().enumerate() {
+            depths[node] = Some(base + i + 1);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(json: &str) -> Document {
+        Document::from_json_without_validation(json::Root::from_str(json).unwrap())
+    }
+
+    fn exceeded(result: Result<()>) -> Limit {
+        match result {
+            Err(Error::Limit(exceeded)) => exceeded.limit,
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn limits() {
+        let document = document(
+            r#"{
+                "asset": {"version": "2.0"},
+                "buffers": [{"byteLength": 1000}, {"byteLength": 24}],
+                "nodes": [{"children": [1]}, {"children": [2]}, {}, {}]
+            }"#,
+        );
+        assert!(Limits::default().check_document(&document).is_ok());
+
+        let limits = Limits {
+            buffer_bytes: Some(1024),
+            objects: Some(4),
+            node_depth: Some(3),
+            ..Default::default()
+        };
+        assert!(limits.check_document(&document).is_ok());
+
+        let limits = Limits {
+            buffer_bytes: Some(1023),
+            ..Default::default()
+        };
+        assert_eq!(
+            exceeded(limits.check_document(&document)),
+            Limit::BufferBytes
+        );
+        let limits = Limits {
+            objects: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            exceeded(limits.check_document(&document)),
+            Limit::Objects(Kind::Node)
+        );
+        let limits = Limits {
+            node_depth: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(exceeded(limits.check_document(&document)), Limit::NodeDepth);
+        let limits = Limits {
+            json_size: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(exceeded(limits.check_json(11)), Limit::JsonSize);
+    }
+
+    #[test]
+    fn accessor_expansion() {
+        let document = document(
+            r#"{
+                "asset": {"version": "2.0"},
+                "buffers": [{"byteLength": 16}],
+                "bufferViews": [{"buffer": 0, "byteLength": 16}],
+                "accessors": [
+                    {"bufferView": 0, "componentType": 5126, "count": 4, "type": "SCALAR"},
+                    {
+                        "componentType": 5126,
+                        "count": 1000,
+                        "type": "SCALAR",
+                        "sparse": {
+                            "count": 2,
+                            "indices": {"bufferView": 0, "componentType": 5125},
+                            "values": {"bufferView": 0, "byteOffset": 8}
+                        }
+                    },
+                    {"componentType": 5126, "count": 1000000000, "type": "VEC4"}
+                ]
+            }"#,
+        );
+        let limits = Limits {
+            accessor_expansion: Some(500),
+            ..Default::default()
+        };
+        assert_eq!(
+            exceeded(limits.check_document(&document)),
+            Limit::AccessorExpansion(2)
+        );
+        let limits = Limits {
+            accessor_expansion: Some(499),
+            ..Default::default()
+        };
+        assert_eq!(
+            exceeded(limits.check_document(&document)),
+            Limit::AccessorExpansion(1)
+        );
+    }
+
+    #[test]
+    fn node_cycles_exceed_any_depth() {
+        let document = document(
+            r#"{
+                "asset": {"version": "2.0"},
+                "nodes": [{"children": [1]}, {"children": [0]}]
+            }"#,
+        );
+        let limits = Limits {
+            node_depth: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(exceeded(limits.check_document(&document)), Limit::NodeDepth);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::usage::Usage;
 use crate::{texture, Document};
 
 pub use json::material::AlphaMode;
@@ -216,13 +217,79 @@ impl<'a> Material<'a> {
         self.json
             .extensions
             .as_ref()
-            .map_or(false, |extensions| extensions.unlit.is_some())
+            .is_some_and(|extensions| extensions.unlit.is_some())
+    }
+
+    /// Returns every texture referred to by this material, including those of
+    /// enabled material extensions, in no particular order.
+    ///
+    /// A texture referred to more than once is listed more than once.
+    pub fn textures(&self) -> Vec<texture::Texture<'a>> {
+        let pbr = self.pbr_metallic_roughness();
+        #[allow(unused_mut)]
+        let mut textures: Vec<_> = vec![
+            pbr.base_color_texture().map(|info| info.texture()),
+            pbr.metallic_roughness_texture().map(|info| info.texture()),
+            self.normal_texture().map(|normal| normal.texture()),
+            self.occlusion_texture()
+                .map(|occlusion| occlusion.texture()),
+            self.emissive_texture().map(|info| info.texture()),
+        ];
+        #[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
+        if let Some(spec_gloss) = self.pbr_specular_glossiness() {
+            textures.push(spec_gloss.diffuse_texture().map(|info| info.texture()));
+            textures.push(
+                spec_gloss
+                    .specular_glossiness_texture()
+                    .map(|info| info.texture()),
+            );
+        }
+        #[cfg(feature = "KHR_materials_transmission")]
+        if let Some(transmission) = self.transmission() {
+            textures.push(
+                transmission
+                    .transmission_texture()
+                    .map(|info| info.texture()),
+            );
+        }
+        #[cfg(feature = "KHR_materials_volume")]
+        if let Some(volume) = self.volume() {
+            textures.push(volume.thickness_texture().map(|info| info.texture()));
+        }
+        #[cfg(feature = "KHR_materials_specular")]
+        if let Some(specular) = self.specular() {
+            textures.push(specular.specular_texture().map(|info| info.texture()));
+            textures.push(specular.specular_color_texture().map(|info| info.texture()));
+        }
+        textures.into_iter().flatten().collect()
+    }
+
+    /// Returns the primitives and nodes that use this material.
+    ///
+    /// The `textures` of the returned usage is empty and its `materials`
+    /// contains only this material. The default material is reported as
+    /// unused.
+    pub fn usage(&self) -> Usage<'a> {
+        Usage::of_materials(self.document, Vec::new(), vec![self.clone()])
     }
 
     /// Optional application specific data.
     pub fn extras(&self) -> &'a json::Extras {
         &self.json.extras
     }
+
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            let path = json::Path::new().field("materials");
+            self.index.map_or(path.clone(), |index| path.index(index))
+        })
+    }
 }
 
 /// A set of parameter values that are used to define the metallic-roughness
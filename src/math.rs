@@ -124,10 +124,55 @@ impl Vector3 {
         self.z *= s;
     }
 
-    #[cfg(test)]
+    #[cfg(feature = "utils")]
     pub fn normalize(self) -> Vector3 {
         self * (1.0 / self.magnitude())
     }
+
+    pub fn dot(self, other: Vector3) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Vector3) -> Vector3 {
+        Vector3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn as_array(&self) -> [f32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    #[cfg(feature = "utils")]
+    pub fn from_array([x, y, z]: [f32; 3]) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl ops::Add for Vector3 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl ops::Sub for Vector3 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
 }
 
 impl ops::Mul<f32> for Vector3 {
@@ -163,7 +208,6 @@ impl Vector4 {
         [self.x, self.y, self.z, self.w]
     }
 
-    #[cfg(test)]
     pub fn from_array([x, y, z, w]: [f32; 4]) -> Self {
         Self { x, y, z, w }
     }
@@ -222,6 +266,38 @@ impl Matrix3 {
     pub fn trace(&self) -> f32 {
         self.x.x + self.y.y + self.z.z
     }
+
+    pub fn as_array(&self) -> [[f32; 3]; 3] {
+        [self.x.as_array(), self.y.as_array(), self.z.as_array()]
+    }
+
+    /// Returns `true` if the columns are of unit length and mutually
+    /// orthogonal, within `epsilon`.
+    pub fn is_orthonormal(&self, epsilon: f32) -> bool {
+        let unit = |v: Vector3| (v.dot(v) - 1.0).abs() <= epsilon;
+        let orthogonal = |a: Vector3, b: Vector3| a.dot(b).abs() <= epsilon;
+        unit(self.x)
+            && unit(self.y)
+            && unit(self.z)
+            && orthogonal(self.x, self.y)
+            && orthogonal(self.y, self.z)
+            && orthogonal(self.z, self.x)
+    }
+
+    /// Returns the transpose of the inverse, or `None` if the matrix is
+    /// singular.
+    pub fn inverse_transpose(&self) -> Option<Matrix3> {
+        let determinant = self.determinant();
+        if determinant == 0.0 {
+            return None;
+        }
+        let inverse = 1.0 / determinant;
+        Some(Matrix3 {
+            x: self.y.cross(self.z) * inverse,
+            y: self.z.cross(self.x) * inverse,
+            z: self.x.cross(self.y) * inverse,
+        })
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -250,7 +326,6 @@ impl Matrix4 {
         }
     }
 
-    #[cfg(test)]
     pub fn from_array([x, y, z, w]: [[f32; 4]; 4]) -> Matrix4 {
         Matrix4 {
             x: Vector4::from_array(x),
@@ -260,6 +335,16 @@ impl Matrix4 {
         }
     }
 
+    #[rustfmt::skip]
+    pub fn identity() -> Matrix4 {
+        Matrix4::new(
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+        )
+    }
+
     /// Create a homogeneous transformation matrix from a translation vector.
     #[rustfmt::skip]
     pub fn from_translation(v: Vector3) -> Matrix4 {
@@ -308,6 +393,97 @@ impl Matrix4 {
         }
     }
 
+    /// Returns the inverse of the matrix, or `None` if the matrix is singular.
+    pub fn invert(&self) -> Option<Matrix4> {
+        let [a, b, c, d] = self.as_array();
+        let m = [
+            a[0], a[1], a[2], a[3], b[0], b[1], b[2], b[3], c[0], c[1], c[2], c[3], d[0], d[1],
+            d[2], d[3],
+        ];
+
+        // Cofactor expansion. The result holds for either storage order since
+        // the inverse of the transpose is the transpose of the inverse.
+        let mut inv = [0.0; 16];
+        inv[0] = m[5] * m[10] * m[15] - m[5] * m[11] * m[14] - m[9] * m[6] * m[15]
+            + m[9] * m[7] * m[14]
+            + m[13] * m[6] * m[11]
+            - m[13] * m[7] * m[10];
+        inv[4] = -m[4] * m[10] * m[15] + m[4] * m[11] * m[14] + m[8] * m[6] * m[15]
+            - m[8] * m[7] * m[14]
+            - m[12] * m[6] * m[11]
+            + m[12] * m[7] * m[10];
+        inv[8] = m[4] * m[9] * m[15] - m[4] * m[11] * m[13] - m[8] * m[5] * m[15]
+            + m[8] * m[7] * m[13]
+            + m[12] * m[5] * m[11]
+            - m[12] * m[7] * m[9];
+        inv[12] = -m[4] * m[9] * m[14] + m[4] * m[10] * m[13] + m[8] * m[5] * m[14]
+            - m[8] * m[6] * m[13]
+            - m[12] * m[5] * m[10]
+            + m[12] * m[6] * m[9];
+        inv[1] = -m[1] * m[10] * m[15] + m[1] * m[11] * m[14] + m[9] * m[2] * m[15]
+            - m[9] * m[3] * m[14]
+            - m[13] * m[2] * m[11]
+            + m[13] * m[3] * m[10];
+        inv[5] = m[0] * m[10] * m[15] - m[0] * m[11] * m[14] - m[8] * m[2] * m[15]
+            + m[8] * m[3] * m[14]
+            + m[12] * m[2] * m[11]
+            - m[12] * m[3] * m[10];
+        inv[9] = -m[0] * m[9] * m[15] + m[0] * m[11] * m[13] + m[8] * m[1] * m[15]
+            - m[8] * m[3] * m[13]
+            - m[12] * m[1] * m[11]
+            + m[12] * m[3] * m[9];
+        inv[13] = m[0] * m[9] * m[14] - m[0] * m[10] * m[13] - m[8] * m[1] * m[14]
+            + m[8] * m[2] * m[13]
+            + m[12] * m[1] * m[10]
+            - m[12] * m[2] * m[9];
+        inv[2] = m[1] * m[6] * m[15] - m[1] * m[7] * m[14] - m[5] * m[2] * m[15]
+            + m[5] * m[3] * m[14]
+            + m[13] * m[2] * m[7]
+            - m[13] * m[3] * m[6];
+        inv[6] = -m[0] * m[6] * m[15] + m[0] * m[7] * m[14] + m[4] * m[2] * m[15]
+            - m[4] * m[3] * m[14]
+            - m[12] * m[2] * m[7]
+            + m[12] * m[3] * m[6];
+        inv[10] = m[0] * m[5] * m[15] - m[0] * m[7] * m[13] - m[4] * m[1] * m[15]
+            + m[4] * m[3] * m[13]
+            + m[12] * m[1] * m[7]
+            - m[12] * m[3] * m[5];
+        inv[14] = -m[0] * m[5] * m[14] + m[0] * m[6] * m[13] + m[4] * m[1] * m[14]
+            - m[4] * m[2] * m[13]
+            - m[12] * m[1] * m[6]
+            + m[12] * m[2] * m[5];
+        inv[3] = -m[1] * m[6] * m[11] + m[1] * m[7] * m[10] + m[5] * m[2] * m[11]
+            - m[5] * m[3] * m[10]
+            - m[9] * m[2] * m[7]
+            + m[9] * m[3] * m[6];
+        inv[7] = m[0] * m[6] * m[11] - m[0] * m[7] * m[10] - m[4] * m[2] * m[11]
+            + m[4] * m[3] * m[10]
+            + m[8] * m[2] * m[7]
+            - m[8] * m[3] * m[6];
+        inv[11] = -m[0] * m[5] * m[11] + m[0] * m[7] * m[9] + m[4] * m[1] * m[11]
+            - m[4] * m[3] * m[9]
+            - m[8] * m[1] * m[7]
+            + m[8] * m[3] * m[5];
+        inv[15] = m[0] * m[5] * m[10] - m[0] * m[6] * m[9] - m[4] * m[1] * m[10]
+            + m[4] * m[2] * m[9]
+            + m[8] * m[1] * m[6]
+            - m[8] * m[2] * m[5];
+
+        let det = m[0] * inv[0] + m[1] * inv[4] + m[2] * inv[8] + m[3] * inv[12];
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let r = 1.0 / det;
+        #[rustfmt::skip]
+        let inverse = Matrix4::new(
+            inv[0] * r, inv[1] * r, inv[2] * r, inv[3] * r,
+            inv[4] * r, inv[5] * r, inv[6] * r, inv[7] * r,
+            inv[8] * r, inv[9] * r, inv[10] * r, inv[11] * r,
+            inv[12] * r, inv[13] * r, inv[14] * r, inv[15] * r,
+        );
+        Some(inverse)
+    }
+
     pub fn as_array(&self) -> [[f32; 4]; 4] {
         [
             self.x.as_array(),
@@ -334,6 +510,13 @@ impl ops::Mul<Matrix4> for Matrix4 {
     }
 }
 
+impl ops::Mul<Vector4> for Matrix4 {
+    type Output = Vector4;
+    fn mul(self, rhs: Vector4) -> Self::Output {
+        self.x * rhs.x + self.y * rhs.y + self.z * rhs.z + self.w * rhs.w
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[repr(C)]
 pub struct Quaternion {
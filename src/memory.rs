@@ -0,0 +1,240 @@
+use std::collections::BTreeSet;
+
+use crate::{Document, Image, Material, Scene, Texture};
+
+/// A GPU texture format that images are assumed to be uploaded in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// Uncompressed, four bytes per texel.
+    Rgba8,
+
+    /// BC7 block compression, sixteen bytes per 4x4 block.
+    Bc7,
+
+    /// ASTC block compression, sixteen bytes per block of the given size.
+    Astc {
+        /// The width of a block in texels, from 4 to 12.
+        block_width: u32,
+
+        /// The height of a block in texels, from 4 to 12.
+        block_height: u32,
+    },
+}
+
+impl Format {
+    /// Returns the size in bytes of a single image level.
+    pub fn level_size(self, width: u32, height: u32) -> u64 {
+        let blocks = |texels: u32, block: u32| texels.div_ceil(block.max(1)) as u64;
+        match self {
+            Format::Rgba8 => width as u64 * height as u64 * 4,
+            Format::Bc7 => blocks(width, 4) * blocks(height, 4) * 16,
+            Format::Astc {
+                block_width,
+                block_height,
+            } => blocks(width, block_width) * blocks(height, block_height) * 16,
+        }
+    }
+}
+
+/// How images are assumed to be uploaded to the GPU.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Policy {
+    /// The texture format of every image.
+    pub format: Format,
+
+    /// Whether a full mip chain is generated for every image.
+    pub mipmaps: bool,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            format: Format::Rgba8,
+            mipmaps: true,
+        }
+    }
+}
+
+impl Policy {
+    /// Returns the size in bytes of an image of the given dimensions,
+    /// including its mip chain if enabled.
+    pub fn image_size(&self, width: u32, height: u32) -> u64 {
+        let (mut width, mut height) = (width.max(1), height.max(1));
+        let mut size = self.format.level_size(width, height);
+        while self.mipmaps && (width > 1 || height > 1) {
+            width = (width / 2).max(1);
+            height = (height / 2).max(1);
+            size += self.format.level_size(width, height);
+        }
+        size
+    }
+}
+
+/// Estimates of the GPU memory needed to upload the images of a document.
+///
+/// Every image is counted once per estimate, however many textures or
+/// materials use it.
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// use gltf::memory::{Format, MemoryEstimate, Policy};
+///
+/// let (document, _, images) = gltf::import("examples/Box.gltf")?;
+/// let policy = Policy {
+///     format: Format::Bc7,
+///     mipmaps: true,
+/// };
+/// let estimate = MemoryEstimate::new(&document, policy, |image| {
+///     images.get(image.index()).map(|data| (data.width, data.height))
+/// });
+/// for scene in document.scenes() {
+///     println!("scene #{}: {} bytes", scene.index(), estimate.scene(&scene));
+/// }
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #    let _ = run().expect("runtime error");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct MemoryEstimate {
+    /// The estimated size of each image, or `None` if its dimensions are
+    /// unknown.
+    images: Vec<Option<u64>>,
+}
+
+impl MemoryEstimate {
+    /// Estimates the size of every image of `document` under `policy`.
+    ///
+    /// `dimensions` returns the width and height in texels of an image, such
+    /// as those of the decoded `image::Data`, or `None` if they are unknown.
+    /// Images of unknown dimensions count as zero bytes.
+    pub fn new<F>(document: &Document, policy: Policy, dimensions: F) -> Self
+    where
+        F: Fn(Image) -> Option<(u32, u32)>,
+    {
+        let images = document
+            .images()
+            .map(|image| dimensions(image).map(|(width, height)| policy.image_size(width, height)))
+            .collect();
+        Self { images }
+    }
+
+    /// Returns the estimated size in bytes of an image, or `None` if its
+    /// dimensions are unknown.
+    pub fn image(&self, image: &Image) -> Option<u64> {
+        self.images.get(image.index()).copied().flatten()
+    }
+
+    /// Returns the estimated size in bytes of the image of a texture.
+    pub fn texture(&self, texture: &Texture) -> u64 {
+        self.total(Some(texture.source().index()))
+    }
+
+    /// Returns the estimated size in bytes of the images used by a material.
+    pub fn material(&self, material: &Material) -> u64 {
+        self.total(
+            material
+                .textures()
+                .iter()
+                .map(|texture| texture.source().index()),
+        )
+    }
+
+    /// Returns the estimated size in bytes of the images used by the
+    /// materials of the primitives drawn by a scene.
+    pub fn scene(&self, scene: &Scene) -> u64 {
+        self.total(scene.draw_calls().iter().flat_map(|call| {
+            call.material
+                .textures()
+                .into_iter()
+                .map(|texture| texture.source().index())
+        }))
+    }
+
+    /// Returns the total size of the given images, counting each image once.
+    fn total<I: IntoIterator<Item = usize>>(&self, images: I) -> u64 {
+        let images: BTreeSet<usize> = images.into_iter().collect();
+        images
+            .into_iter()
+            .filter_map(|index| self.images.get(index).copied().flatten())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level_sizes() {
+        assert_eq!(Format::Rgba8.level_size(256, 128), 256 * 128 * 4);
+        assert_eq!(Format::Bc7.level_size(6, 4), 2 * 16);
+        let astc = Format::Astc {
+            block_width: 8,
+            block_height: 8,
+        };
+        assert_eq!(astc.level_size(9, 8), 2 * 16);
+
+        let policy = Policy::default();
+        assert_eq!(policy.image_size(4, 2), 32 + 8 + 4);
+        let policy = Policy {
+            format: Format::Bc7,
+            mipmaps: false,
+        };
+        assert_eq!(policy.image_size(4, 2), 16);
+    }
+
+    #[test]
+    fn scene_estimate() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 36}],
+            "bufferViews": [{"buffer": 0, "byteLength": 36}],
+            "accessors": [{
+                "bufferView": 0,
+                "componentType": 5126,
+                "count": 3,
+                "type": "VEC3",
+                "min": [0.0, 0.0, 0.0],
+                "max": [1.0, 1.0, 0.0]
+            }],
+            "images": [{"uri": "a.png"}, {"uri": "b.png"}, {"uri": "c.png"}],
+            "textures": [{"source": 0}, {"source": 1}, {"source": 0}, {"source": 2}],
+            "materials": [
+                {
+                    "pbrMetallicRoughness": {"baseColorTexture": {"index": 0}},
+                    "normalTexture": {"index": 2}
+                },
+                {"emissiveTexture": {"index": 1}},
+                {"emissiveTexture": {"index": 3}}
+            ],
+            "meshes": [{"primitives": [
+                {"attributes": {"POSITION": 0}, "material": 0},
+                {"attributes": {"POSITION": 0}, "material": 1}
+            ]}],
+            "nodes": [{"mesh": 0}],
+            "scenes": [{"nodes": [0]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let policy = Policy {
+            format: Format::Rgba8,
+            mipmaps: false,
+        };
+        let estimate = MemoryEstimate::new(&document, policy, |image| match image.index() {
+            0 => Some((16, 16)),
+            1 => Some((8, 8)),
+            _ => None,
+        });
+
+        let materials: Vec<_> = document.materials().collect();
+        assert_eq!(estimate.material(&materials[0]), 1024);
+        assert_eq!(estimate.material(&materials[2]), 0);
+        let scene = document.scenes().next().unwrap();
+        assert_eq!(estimate.scene(&scene), 1024 + 256);
+        let texture = document.textures().nth(1).unwrap();
+        assert_eq!(estimate.texture(&texture), 256);
+    }
+}
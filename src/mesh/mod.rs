@@ -56,10 +56,10 @@ pub mod iter;
 #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
 pub mod util;
 
-use crate::{Accessor, Buffer, Document, Material};
+use crate::{Accessor, Document, Material};
 
 #[cfg(feature = "utils")]
-use crate::accessor;
+use crate::{accessor, Buffer};
 
 pub use json::mesh::{Mode, Semantic};
 use json::validation::Checked;
@@ -120,12 +120,14 @@ pub struct Primitive<'a> {
 }
 
 /// Mesh primitive reader.
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
 #[derive(Clone, Debug)]
 pub struct Reader<'a, 's, F>
 where
     F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
 {
-    pub(crate) primitive: &'a Primitive<'a>,
+    pub(crate) primitive: Primitive<'a>,
     pub(crate) get_buffer_data: F,
 }
 
@@ -149,6 +151,18 @@ impl<'a> Mesh<'a> {
         &self.json.extras
     }
 
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new().field("meshes").index(self.index)
+        })
+    }
+
     /// Optional user-defined name for this object.
     #[cfg(feature = "names")]
     #[cfg_attr(docsrs, doc(cfg(feature = "names")))]
@@ -200,6 +214,22 @@ impl<'a> Primitive<'a> {
         &self.json.extras
     }
 
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new()
+                .field("meshes")
+                .index(self.mesh.index())
+                .field("primitives")
+                .index(self.index)
+        })
+    }
+
     /// Return the accessor with the given semantic.
     pub fn get(&self, semantic: &Semantic) -> Option<Accessor<'a>> {
         self.json
@@ -230,7 +260,11 @@ impl<'a> Primitive<'a> {
         }
     }
 
-    /// Returns the material to apply to this primitive when rendering
+    /// Returns the material to apply to this primitive when rendering.
+    ///
+    /// When the primitive does not specify a material, a default material
+    /// with every property set to its specification default is returned.
+    /// The default material can be recognized by its `index()` being `None`.
     pub fn material(&self) -> Material<'a> {
         self.json
             .material
@@ -254,11 +288,29 @@ impl<'a> Primitive<'a> {
         } else {
             iter::MorphTargets {
                 document: self.mesh.document,
-                iter: (&[]).iter(),
+                iter: [].iter(),
             }
         }
     }
 
+    /// Returns the Draco compressed geometry of the primitive, if any.
+    #[cfg(feature = "KHR_draco_mesh_compression")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "KHR_draco_mesh_compression")))]
+    pub fn draco_mesh_compression(
+        &self,
+    ) -> Option<crate::khr_draco_mesh_compression::DracoMeshCompression<'a>> {
+        self.json
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.khr_draco_mesh_compression.as_ref())
+            .map(|json| {
+                crate::khr_draco_mesh_compression::DracoMeshCompression::new(
+                    self.mesh.document,
+                    json,
+                )
+            })
+    }
+
     /// Get the material variants.
     #[cfg(feature = "KHR_materials_variants")]
     #[cfg_attr(docsrs, doc(cfg(feature = "KHR_materials_variants")))]
@@ -269,7 +321,7 @@ impl<'a> Primitive<'a> {
             .as_ref()
             .and_then(|extensions| extensions.khr_materials_variants.as_ref())
             .map(|variants| variants.mappings.iter())
-            .unwrap_or_else(|| (&[]).iter());
+            .unwrap_or_else(|| [].iter());
 
         iter::Mappings {
             document: self.mesh.document,
@@ -277,15 +329,42 @@ impl<'a> Primitive<'a> {
         }
     }
 
+    /// Returns the vertex indices of each triangle of this primitive.
+    ///
+    /// Triangle strips and fans are expanded into separate triangles, and
+    /// non-indexed primitives yield consecutive vertices. Primitives that are
+    /// not made of triangles yield nothing. Returns `None` if the index data
+    /// is not available.
+    #[cfg(feature = "utils")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn triangles<'s, F>(&self, get_buffer_data: F) -> Option<util::triangles::Triangles<'s>>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        use self::util::triangles::Triangles;
+        match self.indices() {
+            Some(_) => self
+                .reader(get_buffer_data)
+                .read_indices()
+                .map(|indices| Triangles::indexed(self.mode(), indices.into_u32())),
+            None => {
+                let count = self
+                    .get(&Semantic::Positions)
+                    .map_or(0, |accessor| accessor.count());
+                Some(Triangles::sequential(self.mode(), count as u32))
+            }
+        }
+    }
+
     /// Constructs the primitive reader.
     #[cfg(feature = "utils")]
     #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
-    pub fn reader<'s, F>(&'a self, get_buffer_data: F) -> Reader<'a, 's, F>
+    pub fn reader<'s, F>(&self, get_buffer_data: F) -> Reader<'a, 's, F>
     where
         F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
     {
         Reader {
-            primitive: self,
+            primitive: self.clone(),
             get_buffer_data,
         }
     }
@@ -300,21 +379,21 @@ where
     pub fn read_positions(&self) -> Option<util::ReadPositions<'s>> {
         self.primitive
             .get(&Semantic::Positions)
-            .and_then(|accessor| accessor::Iter::new(accessor, self.get_buffer_data.clone()))
+            .and_then(|accessor| util::ReadPositions::new(accessor, self.get_buffer_data.clone()))
     }
 
     /// Visits the vertex normals of a primitive.
     pub fn read_normals(&self) -> Option<util::ReadNormals<'s>> {
         self.primitive
             .get(&Semantic::Normals)
-            .and_then(|accessor| accessor::Iter::new(accessor, self.get_buffer_data.clone()))
+            .and_then(|accessor| util::ReadNormals::new(accessor, self.get_buffer_data.clone()))
     }
 
     /// Visits the vertex tangents of a primitive.
     pub fn read_tangents(&self) -> Option<util::ReadTangents<'s>> {
         self.primitive
             .get(&Semantic::Tangents)
-            .and_then(|accessor| accessor::Iter::new(accessor, self.get_buffer_data.clone()))
+            .and_then(|accessor| util::ReadTangents::new(accessor, self.get_buffer_data.clone()))
     }
 
     /// Visits the vertex colors of a primitive.
@@ -395,6 +474,32 @@ where
             })
     }
 
+    /// Visits the vertex texture co-ordinates sampled by a texture, with its
+    /// `KHR_texture_transform` applied.
+    ///
+    /// The co-ordinate set is chosen by the transform's `texCoord` override,
+    /// falling back to `info.tex_coord()`. Co-ordinates are returned unchanged
+    /// when the texture has no transform.
+    #[cfg(feature = "KHR_texture_transform")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "KHR_texture_transform")))]
+    pub fn read_tex_coords_transformed(
+        &self,
+        info: &crate::texture::Info,
+    ) -> Option<util::tex_coords::Transformed<'s>> {
+        let transform = info.texture_transform();
+        let set = transform
+            .as_ref()
+            .and_then(|transform| transform.tex_coord())
+            .unwrap_or_else(|| info.tex_coord());
+        let matrix = transform.map(|transform| transform.matrix()).unwrap_or([
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+        ]);
+        self.read_tex_coords(set)
+            .map(|iter| util::tex_coords::Transformed::new(iter, matrix))
+    }
+
     /// Visits the joint weights of the primitive.
     pub fn read_weights(&self, set: u32) -> Option<util::ReadWeights<'s>> {
         use self::accessor::DataType;
@@ -413,6 +518,21 @@ where
             })
     }
 
+    /// Visits the values of any vertex attribute as items of type `T`, such as
+    /// `u32` for an application-specific `_BATCHID` attribute of unsigned
+    /// integers.
+    ///
+    /// Returns `None` if the primitive has no such attribute or if its
+    /// component type and dimensions are not those of `T`.
+    pub fn read_attribute<T: accessor::Item>(
+        &self,
+        semantic: &Semantic,
+    ) -> Option<accessor::Iter<'s, T>> {
+        self.primitive
+            .get(semantic)
+            .and_then(|accessor| accessor::Iter::new(accessor, self.get_buffer_data.clone()))
+    }
+
     /// Visits the morph targets of the primitive.
     pub fn read_morph_targets(&self) -> util::ReadMorphTargets<'a, 's, F> {
         util::ReadMorphTargets {
@@ -438,3 +558,215 @@ impl<'a> MorphTarget<'a> {
         self.tangents.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::material::AlphaMode;
+    use crate::Document;
+
+    #[test]
+    fn missing_material_is_default() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 36}],
+            "bufferViews": [{"buffer": 0, "byteLength": 36}],
+            "accessors": [{
+                "bufferView": 0,
+                "componentType": 5126,
+                "count": 3,
+                "type": "VEC3",
+                "min": [0.0, 0.0, 0.0],
+                "max": [1.0, 1.0, 0.0]
+            }],
+            "materials": [{"doubleSided": true}],
+            "meshes": [{
+                "primitives": [
+                    {"attributes": {"POSITION": 0}},
+                    {"attributes": {"POSITION": 0}, "material": 0}
+                ]
+            }]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let mesh = document.meshes().next().unwrap();
+        let mut primitives = mesh.primitives();
+
+        let default = primitives.next().unwrap().material();
+        assert_eq!(default.index(), None);
+        assert_eq!(default.alpha_mode(), AlphaMode::Opaque);
+        assert_eq!(default.alpha_cutoff(), None);
+        assert!(!default.double_sided());
+        assert_eq!(default.emissive_factor(), [0.0, 0.0, 0.0]);
+        let pbr = default.pbr_metallic_roughness();
+        assert_eq!(pbr.base_color_factor(), [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(pbr.metallic_factor(), 1.0);
+        assert_eq!(pbr.roughness_factor(), 1.0);
+        assert!(pbr.base_color_texture().is_none());
+
+        let explicit = primitives.next().unwrap().material();
+        assert_eq!(explicit.index(), Some(0));
+        assert!(explicit.double_sided());
+    }
+
+    #[cfg(feature = "utils")]
+    #[test]
+    fn dequantizes_vertices() {
+        let mut data = Vec::new();
+        for position in &[[1u16, 2, 3], [0, 0, 0], [65535, 0, 7]] {
+            for component in position {
+                data.extend_from_slice(&component.to_le_bytes());
+            }
+            data.extend_from_slice(&[0, 0]);
+        }
+        for normal in &[[0i16, 0, 32767], [0, -32767, 0], [-32768, 0, 0]] {
+            for component in normal {
+                data.extend_from_slice(&component.to_le_bytes());
+            }
+            data.extend_from_slice(&[0, 0]);
+        }
+        for tangent in &[[127i8, 0, 0, 127], [0, 127, 0, -127], [0, 0, -127, 127]] {
+            data.extend(tangent.iter().map(|&component| component as u8));
+        }
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "extensionsUsed": ["KHR_mesh_quantization"],
+            "extensionsRequired": ["KHR_mesh_quantization"],
+            "buffers": [{"byteLength": 60}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 24, "byteStride": 8},
+                {"buffer": 0, "byteOffset": 24, "byteLength": 24, "byteStride": 8},
+                {"buffer": 0, "byteOffset": 48, "byteLength": 12}
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0,
+                    "componentType": 5123,
+                    "count": 3,
+                    "type": "VEC3",
+                    "min": [0, 0, 0],
+                    "max": [65535, 2, 7]
+                },
+                {
+                    "bufferView": 1,
+                    "componentType": 5122,
+                    "normalized": true,
+                    "count": 3,
+                    "type": "VEC3"
+                },
+                {
+                    "bufferView": 2,
+                    "componentType": 5120,
+                    "normalized": true,
+                    "count": 3,
+                    "type": "VEC4"
+                }
+            ],
+            "meshes": [{
+                "primitives": [{"attributes": {"POSITION": 0, "NORMAL": 1, "TANGENT": 2}}]
+            }]
+        }"#;
+        let document = Document::from_json_without_validation(json::Root::from_str(json).unwrap());
+        let primitive = document
+            .meshes()
+            .next()
+            .unwrap()
+            .primitives()
+            .next()
+            .unwrap();
+        let reader = primitive.reader(|_| Some(&data[..]));
+
+        let positions: Vec<[f32; 3]> = reader.read_positions().unwrap().collect();
+        assert_eq!(
+            positions,
+            vec![[1.0, 2.0, 3.0], [0.0, 0.0, 0.0], [65535.0, 0.0, 7.0]]
+        );
+        let normals: Vec<[f32; 3]> = reader.read_normals().unwrap().collect();
+        assert_eq!(
+            normals,
+            vec![[0.0, 0.0, 1.0], [0.0, -1.0, 0.0], [-1.0, 0.0, 0.0]]
+        );
+        let tangents = reader.read_tangents().unwrap();
+        assert_eq!(tangents.len(), 3);
+        assert_eq!(
+            tangents.collect::<Vec<_>>(),
+            vec![
+                [1.0, 0.0, 0.0, 1.0],
+                [0.0, 1.0, 0.0, -1.0],
+                [0.0, 0.0, -1.0, 1.0]
+            ]
+        );
+    }
+
+    #[cfg(feature = "utils")]
+    #[test]
+    fn custom_attributes_and_high_sets() {
+        use super::Semantic;
+
+        let mut data = Vec::new();
+        for value in &[0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for batch in &[7u32, 7, 9] {
+            data.extend_from_slice(&batch.to_le_bytes());
+        }
+        for value in &[0.0f32, 0.0, 1.0, 0.0, 0.0, 1.0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 72}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 36},
+                {"buffer": 0, "byteOffset": 36, "byteLength": 12},
+                {"buffer": 0, "byteOffset": 48, "byteLength": 24}
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0,
+                    "componentType": 5126,
+                    "count": 3,
+                    "type": "VEC3",
+                    "min": [0.0, 0.0, 0.0],
+                    "max": [1.0, 1.0, 0.0]
+                },
+                {"bufferView": 1, "componentType": 5125, "count": 3, "type": "SCALAR"},
+                {"bufferView": 2, "componentType": 5126, "count": 3, "type": "VEC2"}
+            ],
+            "meshes": [{
+                "primitives": [{
+                    "attributes": {"POSITION": 0, "_BATCHID": 1, "_FEATURE_ID_0": 1, "TEXCOORD_12": 2}
+                }]
+            }]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let primitive = document
+            .meshes()
+            .next()
+            .unwrap()
+            .primitives()
+            .next()
+            .unwrap();
+        assert_eq!(primitive.attributes().count(), 4);
+        let reader = primitive.reader(|_| Some(&data[..]));
+
+        let batch = Semantic::Extras("BATCHID".to_string());
+        assert_eq!(batch.to_string(), "_BATCHID");
+        let ids: Vec<u32> = reader.read_attribute(&batch).unwrap().collect();
+        assert_eq!(ids, vec![7, 7, 9]);
+        assert!(reader.read_attribute::<u16>(&batch).is_none());
+        // Items of the same size but not the same component type or
+        // dimensions are rejected as well.
+        assert!(reader.read_attribute::<f32>(&batch).is_none());
+        assert!(reader.read_attribute::<[u16; 2]>(&batch).is_none());
+        let tex_coords = Semantic::TexCoords(12);
+        assert!(reader.read_attribute::<[f32; 2]>(&tex_coords).is_some());
+        assert!(reader.read_attribute::<[u32; 2]>(&tex_coords).is_none());
+        assert!(reader.read_attribute::<[u16; 4]>(&tex_coords).is_none());
+        assert!(primitive
+            .get(&Semantic::Extras("FEATURE_ID_0".to_string()))
+            .is_some());
+
+        let tex_coords: Vec<[f32; 2]> = reader.read_tex_coords(12).unwrap().into_f32().collect();
+        assert_eq!(tex_coords[1], [1.0, 0.0]);
+        assert!(reader.read_tex_coords(1).is_none());
+    }
+}
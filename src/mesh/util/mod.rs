@@ -10,23 +10,21 @@ pub mod joints;
 /// Casting iterator adapters for texture co-ordinates.
 pub mod tex_coords;
 
+/// Iterator adapter assembling triangles from vertex indices.
+pub mod triangles;
+
 /// Casting iterator adapters for node weights.
 pub mod weights;
 
+/// Dequantizing iterators for positions, normals, and tangents.
+mod vectors;
+
 use crate::mesh;
 
 use crate::accessor::Iter;
 use crate::Buffer;
 
-/// XYZ vertex positions of type `[f32; 3]`.
-pub type ReadPositions<'a> = Iter<'a, [f32; 3]>;
-
-/// XYZ vertex normals of type `[f32; 3]`.
-pub type ReadNormals<'a> = Iter<'a, [f32; 3]>;
-
-/// XYZW vertex tangents of type `[f32; 4]` where the `w` component is a
-/// sign value (-1 or +1) indicating the handedness of the tangent basis.
-pub type ReadTangents<'a> = Iter<'a, [f32; 4]>;
+pub use self::vectors::{ReadNormals, ReadPositions, ReadTangents};
 
 /// XYZ vertex position displacements of type `[f32; 3]`.
 pub type ReadPositionDisplacements<'a> = Iter<'a, [f32; 3]>;
@@ -90,6 +90,55 @@ impl<'a, A: Cast> Iterator for CastingIter<'a, A> {
     }
 }
 
+/// Iterator over texture co-ordinates with `KHR_texture_transform` applied.
+#[cfg(feature = "KHR_texture_transform")]
+#[cfg_attr(docsrs, doc(cfg(feature = "KHR_texture_transform")))]
+#[derive(Clone, Debug)]
+pub struct Transformed<'a> {
+    iter: CastingIter<'a, F32>,
+    matrix: [[f32; 3]; 3],
+}
+
+#[cfg(feature = "KHR_texture_transform")]
+impl<'a> Transformed<'a> {
+    pub(crate) fn new(iter: ReadTexCoords<'a>, matrix: [[f32; 3]; 3]) -> Self {
+        Transformed {
+            iter: CastingIter::new(iter),
+            matrix,
+        }
+    }
+
+    fn transform(&self, [u, v]: [f32; 2]) -> [f32; 2] {
+        let m = &self.matrix;
+        [
+            m[0][0] * u + m[1][0] * v + m[2][0],
+            m[0][1] * u + m[1][1] * v + m[2][1],
+        ]
+    }
+}
+
+#[cfg(feature = "KHR_texture_transform")]
+impl<'a> ExactSizeIterator for Transformed<'a> {}
+#[cfg(feature = "KHR_texture_transform")]
+impl<'a> Iterator for Transformed<'a> {
+    type Item = [f32; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|uv| self.transform(uv))
+    }
+
+    #[inline]
+    fn nth(&mut self, x: usize) -> Option<Self::Item> {
+        self.iter.nth(x).map(|uv| self.transform(uv))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
 impl Cast for U8 {
     type Output = [u8; 2];
 
@@ -0,0 +1,138 @@
+use std::{ops, vec};
+
+use crate::mesh::Mode;
+
+use super::indices::{CastingIter, U32};
+
+/// The vertex sequence from which triangles are assembled.
+#[derive(Clone, Debug)]
+enum Vertices<'a> {
+    /// Vertices given by an index accessor.
+    Indexed(CastingIter<'a, U32>),
+
+    /// Consecutive vertices of a non-indexed primitive.
+    Sequential(ops::Range<u32>),
+
+    /// Vertex indices already read from an index accessor.
+    Owned(vec::IntoIter<u32>),
+}
+
+impl<'a> Iterator for Vertices<'a> {
+    type Item = u32;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Vertices::Indexed(iter) => iter.next(),
+            Vertices::Sequential(iter) => iter.next(),
+            Vertices::Owned(iter) => iter.next(),
+        }
+    }
+}
+
+/// Iterator over the vertex indices of each triangle of a primitive.
+///
+/// Triangle strips and fans are expanded into separate triangles with the
+/// winding order given in the specification. Primitives that are not made of
+/// triangles yield nothing.
+#[derive(Clone, Debug)]
+pub struct Triangles<'a> {
+    /// The vertex sequence.
+    vertices: Vertices<'a>,
+
+    /// The primitive mode.
+    mode: Mode,
+
+    /// The two vertices shared with the next triangle of a strip or fan.
+    shared: Option<[u32; 2]>,
+
+    /// Whether the next triangle of a strip has odd parity.
+    odd: bool,
+}
+
+impl<'a> Triangles<'a> {
+    /// Assembles the triangles of an indexed primitive.
+    pub(crate) fn indexed(mode: Mode, indices: CastingIter<'a, U32>) -> Self {
+        Self::new(mode, Vertices::Indexed(indices))
+    }
+
+    /// Assembles the triangles of a non-indexed primitive with `count`
+    /// vertices.
+    pub(crate) fn sequential(mode: Mode, count: u32) -> Self {
+        Self::new(mode, Vertices::Sequential(0..count))
+    }
+
+    /// Assembles the triangles of a primitive from vertex indices read
+    /// beforehand.
+    pub(crate) fn owned(mode: Mode, indices: Vec<u32>) -> Self {
+        Self::new(mode, Vertices::Owned(indices.into_iter()))
+    }
+
+    fn new(mode: Mode, vertices: Vertices<'a>) -> Self {
+        Self {
+            vertices,
+            mode,
+            shared: None,
+            odd: false,
+        }
+    }
+}
+
+impl<'a> Iterator for Triangles<'a> {
+    type Item = [u32; 3];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let triangle = match self.mode {
+            Mode::Triangles => [
+                self.vertices.next()?,
+                self.vertices.next()?,
+                self.vertices.next()?,
+            ],
+            Mode::TriangleStrip | Mode::TriangleFan => {
+                let [a, b] = match self.shared {
+                    Some(shared) => shared,
+                    None => [self.vertices.next()?, self.vertices.next()?],
+                };
+                let c = self.vertices.next()?;
+                if self.mode == Mode::TriangleStrip {
+                    self.shared = Some([b, c]);
+                    self.odd = !self.odd;
+                    if self.odd {
+                        [a, b, c]
+                    } else {
+                        [a, c, b]
+                    }
+                } else {
+                    self.shared = Some([a, c]);
+                    [b, c, a]
+                }
+            }
+            _ => return None,
+        };
+        Some(triangle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(mode: Mode, count: u32) -> Vec<[u32; 3]> {
+        Triangles::sequential(mode, count).collect()
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(collect(Mode::Triangles, 7), vec![[0, 1, 2], [3, 4, 5]]);
+        assert_eq!(
+            collect(Mode::TriangleStrip, 5),
+            vec![[0, 1, 2], [1, 3, 2], [2, 3, 4]]
+        );
+        assert_eq!(
+            collect(Mode::TriangleFan, 5),
+            vec![[1, 2, 0], [2, 3, 0], [3, 4, 0]]
+        );
+        assert!(collect(Mode::TriangleFan, 2).is_empty());
+        assert!(collect(Mode::Lines, 6).is_empty());
+    }
+}
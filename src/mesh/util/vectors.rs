@@ -0,0 +1,125 @@
+use crate::accessor::{Accessor, DataType, Iter};
+use crate::{Buffer, Normalize};
+
+/// Converts integer components to `f32`, normalizing them if the accessor is
+/// marked as normalized.
+fn dequantize<T, const N: usize>(vector: [T; N], normalized: bool) -> [f32; N]
+where
+    T: Normalize<f32> + Into<f32>,
+{
+    vector.map(|component| match normalized {
+        true => component.normalize(),
+        false => component.into(),
+    })
+}
+
+macro_rules! vectors {
+    ($(#[$attr:meta])* $name:ident, $storage:ident, $n:expr) => {
+        /// The component type of stored vectors.
+        #[derive(Clone, Debug)]
+        enum $storage<'a> {
+            I8(Iter<'a, [i8; $n]>),
+            U8(Iter<'a, [u8; $n]>),
+            I16(Iter<'a, [i16; $n]>),
+            U16(Iter<'a, [u16; $n]>),
+            F32(Iter<'a, [f32; $n]>),
+        }
+
+        $(#[$attr])*
+        #[derive(Clone, Debug)]
+        pub struct $name<'a> {
+            storage: $storage<'a>,
+            normalized: bool,
+        }
+
+        impl<'a> $name<'a> {
+            pub(crate) fn new<'d, F>(accessor: Accessor<'d>, get_buffer_data: F) -> Option<Self>
+            where
+                F: Clone + Fn(Buffer<'d>) -> Option<&'a [u8]>,
+            {
+                let normalized = accessor.normalized();
+                let storage = match accessor.data_type() {
+                    DataType::I8 => $storage::I8(Iter::new(accessor, get_buffer_data)?),
+                    DataType::U8 => $storage::U8(Iter::new(accessor, get_buffer_data)?),
+                    DataType::I16 => $storage::I16(Iter::new(accessor, get_buffer_data)?),
+                    DataType::U16 => $storage::U16(Iter::new(accessor, get_buffer_data)?),
+                    DataType::F32 => $storage::F32(Iter::new(accessor, get_buffer_data)?),
+                    DataType::U32 => return None,
+                };
+                Some($name {
+                    storage,
+                    normalized,
+                })
+            }
+        }
+
+        impl<'a> ExactSizeIterator for $name<'a> {}
+        impl<'a> Iterator for $name<'a> {
+            type Item = [f32; $n];
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let normalized = self.normalized;
+                match self.storage {
+                    $storage::I8(ref mut i) => i.next().map(|v| dequantize(v, normalized)),
+                    $storage::U8(ref mut i) => i.next().map(|v| dequantize(v, normalized)),
+                    $storage::I16(ref mut i) => i.next().map(|v| dequantize(v, normalized)),
+                    $storage::U16(ref mut i) => i.next().map(|v| dequantize(v, normalized)),
+                    $storage::F32(ref mut i) => i.next(),
+                }
+            }
+
+            #[inline]
+            fn nth(&mut self, x: usize) -> Option<Self::Item> {
+                let normalized = self.normalized;
+                match self.storage {
+                    $storage::I8(ref mut i) => i.nth(x).map(|v| dequantize(v, normalized)),
+                    $storage::U8(ref mut i) => i.nth(x).map(|v| dequantize(v, normalized)),
+                    $storage::I16(ref mut i) => i.nth(x).map(|v| dequantize(v, normalized)),
+                    $storage::U16(ref mut i) => i.nth(x).map(|v| dequantize(v, normalized)),
+                    $storage::F32(ref mut i) => i.nth(x),
+                }
+            }
+
+            fn count(self) -> usize {
+                self.size_hint().0
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match self.storage {
+                    $storage::I8(ref i) => i.size_hint(),
+                    $storage::U8(ref i) => i.size_hint(),
+                    $storage::I16(ref i) => i.size_hint(),
+                    $storage::U16(ref i) => i.size_hint(),
+                    $storage::F32(ref i) => i.size_hint(),
+                }
+            }
+        }
+    };
+}
+
+vectors! {
+    /// XYZ vertex positions of type `[f32; 3]`.
+    ///
+    /// Positions stored as integers, as allowed by `KHR_mesh_quantization`,
+    /// are converted to `f32`, and normalized if their accessor is.
+    ReadPositions, Positions, 3
+}
+
+vectors! {
+    /// XYZ vertex normals of type `[f32; 3]`.
+    ///
+    /// Normals stored as normalized integers, as allowed by
+    /// `KHR_mesh_quantization`, are converted to `f32`.
+    ReadNormals, Normals, 3
+}
+
+vectors! {
+    /// XYZW vertex tangents of type `[f32; 4]` where the `w` component is a
+    /// sign value (-1 or +1) indicating the handedness of the tangent basis.
+    ///
+    /// Tangents stored as normalized integers, as allowed by
+    /// `KHR_mesh_quantization`, are converted to `f32`.
+    ReadTangents, Tangents, 4
+}
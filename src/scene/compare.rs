@@ -0,0 +1,411 @@
+use crate::math::*;
+use crate::transform::data;
+use crate::{buffer, Document, Primitive, Scene, Semantic};
+
+/// A way in which the geometry of two documents differs, as found by
+/// `compare_geometry`.
+///
+/// Draw calls are identified by their position in `Scene::draw_calls`.
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Difference {
+    /// The documents have different numbers of scenes.
+    SceneCount {
+        /// The number of scenes of the first document.
+        a: usize,
+        /// The number of scenes of the second document.
+        b: usize,
+    },
+
+    /// A scene has different numbers of draw calls.
+    DrawCallCount {
+        /// The index of the scene.
+        scene: usize,
+        /// The number of draw calls in the first document.
+        a: usize,
+        /// The number of draw calls in the second document.
+        b: usize,
+    },
+
+    /// A draw call has different numbers of triangles, so its vertices are
+    /// not compared.
+    TriangleCount {
+        /// The index of the scene.
+        scene: usize,
+        /// The position of the draw call.
+        draw_call: usize,
+        /// The number of triangles in the first document.
+        a: usize,
+        /// The number of triangles in the second document.
+        b: usize,
+    },
+
+    /// A vertex attribute is present in only one of the documents.
+    Attribute {
+        /// The index of the scene.
+        scene: usize,
+        /// The position of the draw call.
+        draw_call: usize,
+        /// The attribute.
+        semantic: Semantic,
+    },
+
+    /// A vertex attribute deviates by more than the tolerance.
+    Deviation {
+        /// The index of the scene.
+        scene: usize,
+        /// The position of the draw call.
+        draw_call: usize,
+        /// The attribute.
+        semantic: Semantic,
+        /// The largest distance between corresponding values.
+        deviation: f32,
+    },
+}
+
+/// The result of `compare_geometry`.
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GeometryDiff {
+    /// The largest distance between corresponding vertex positions, in world
+    /// space.
+    pub max_position: f32,
+
+    /// The largest distance between corresponding unit normals, in world
+    /// space.
+    pub max_normal: f32,
+
+    /// The largest distance between corresponding texture co-ordinates of
+    /// the first set.
+    pub max_tex_coord: f32,
+
+    /// Every difference found, including deviations past the tolerance.
+    pub differences: Vec<Difference>,
+}
+
+impl GeometryDiff {
+    /// Returns `true` if no differences were found.
+    pub fn is_match(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+/// The vertex data of the corners of the triangles of a draw call, in world
+/// space.
+#[derive(Default)]
+struct Corners {
+    triangles: usize,
+    positions: Option<Vec<Vector3>>,
+    normals: Option<Vec<Vector3>>,
+    tex_coords: Option<Vec<Vector3>>,
+}
+
+/// Reads a vertex attribute of any component type as floating point vectors,
+/// normalizing integer components if the accessor is marked as normalized.
+///
+/// Returns `None` if the primitive has no such attribute or its data cannot
+/// be read.
+fn vectors(
+    document: &Document,
+    buffers: &[buffer::Data],
+    primitive: &Primitive,
+    semantic: Semantic,
+    width: usize,
+) -> Option<Vec<Vector3>> {
+    let accessor = primitive.get(&semantic)?;
+    let values = data::read_normalized(&document.0, buffers, accessor.index()).ok()?;
+    let vector = |v: &[f64]| match *v {
+        [x, y] => Vector3::new(x as f32, y as f32, 0.0),
+        [x, y, z] => Vector3::new(x as f32, y as f32, z as f32),
+        _ => unreachable!(),
+    };
+    Some(values.chunks_exact(width).map(vector).collect())
+}
+
+/// Reads the corners of every draw call of a scene.
+fn corners(document: &Document, scene: &Scene, buffers: &[buffer::Data]) -> Vec<Corners> {
+    let get = |buffer: crate::Buffer| buffers.get(buffer.index()).map(|data| &data.0[..]);
+    scene
+        .draw_calls()
+        .into_iter()
+        .map(|call| {
+            let triangles: Vec<[u32; 3]> = match call.primitive.triangles(get) {
+                Some(triangles) => triangles.collect(),
+                None => return Corners::default(),
+            };
+            let read =
+                |semantic, width| vectors(document, buffers, &call.primitive, semantic, width);
+            let world = Matrix4::from_array(call.transform);
+            let normal_matrix = super::normal_matrix(call.transform);
+            Corners {
+                triangles: triangles.len(),
+                positions: read(Semantic::Positions, 3).and_then(|positions| {
+                    expand(
+                        &triangles,
+                        positions
+                            .into_iter()
+                            .map(|v| {
+                                let p = world * Vector4::new(v.x, v.y, v.z, 1.0);
+                                Vector3::new(p.x, p.y, p.z)
+                            })
+                            .collect(),
+                    )
+                }),
+                normals: read(Semantic::Normals, 3).and_then(|normals| {
+                    let [x, y, z] = normal_matrix?;
+                    let (x, y, z) = (
+                        Vector3::from_array(x),
+                        Vector3::from_array(y),
+                        Vector3::from_array(z),
+                    );
+                    expand(
+                        &triangles,
+                        normals
+                            .into_iter()
+                            .map(|v| (x * v.x + y * v.y + z * v.z).normalize())
+                            .collect(),
+                    )
+                }),
+                tex_coords: read(Semantic::TexCoords(0), 2)
+                    .and_then(|tex_coords| expand(&triangles, tex_coords)),
+            }
+        })
+        .collect()
+}
+
+/// Looks up the values at the corners of the triangles, returning `None` if
+/// any is missing.
+fn expand(triangles: &[[u32; 3]], values: Vec<Vector3>) -> Option<Vec<Vector3>> {
+    triangles
+        .iter()
+        .flatten()
+        .map(|&index| values.get(index as usize).copied())
+        .collect()
+}
+
+/// Compares the geometry that the scenes of two documents draw, for testing
+/// that passes such as quantization or vertex welding preserve it.
+///
+/// Scenes are matched by index and their draw calls by position in
+/// `Scene::draw_calls`. Vertices are compared at corresponding corners of
+/// corresponding triangles, so reordering or merging vertices makes no
+/// difference while reordering triangles does. Positions and normals are
+/// compared in world space, so flattening the node hierarchy makes no
+/// difference either. Vertex attributes of any component type are read, so
+/// the output of `transform::convert_vertex_formats` can be compared with its
+/// input. Morph targets and skinning are not applied.
+///
+/// A deviation of a position, normal, or first set of texture co-ordinates
+/// larger than `tolerance` is reported as a difference, as is any mismatch in
+/// the number of scenes, draw calls, or triangles, or in the presence of these
+/// attributes.
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let (document, buffers, _) = gltf::import("examples/Box.gltf")?;
+/// let diff = gltf::scene::compare_geometry(&document, &buffers, &document, &buffers, 1e-6);
+/// assert!(diff.is_match());
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub fn compare_geometry(
+    a: &Document,
+    a_buffers: &[buffer::Data],
+    b: &Document,
+    b_buffers: &[buffer::Data],
+    tolerance: f32,
+) -> GeometryDiff {
+    let mut diff = GeometryDiff::default();
+    if a.scenes().len() != b.scenes().len() {
+        diff.differences.push(Difference::SceneCount {
+            a: a.scenes().len(),
+            b: b.scenes().len(),
+        });
+    }
+
+    for (scene, (a_scene, b_scene)) in a.scenes().zip(b.scenes()).enumerate() {
+        let a_calls = corners(a, &a_scene, a_buffers);
+        let b_calls = corners(b, &b_scene, b_buffers);
+        if a_calls.len() != b_calls.len() {
+            diff.differences.push(Difference::DrawCallCount {
+                scene,
+                a: a_calls.len(),
+                b: b_calls.len(),
+            });
+        }
+
+        for (draw_call, (a, b)) in a_calls.iter().zip(&b_calls).enumerate() {
+            if a.triangles != b.triangles {
+                diff.differences.push(Difference::TriangleCount {
+                    scene,
+                    draw_call,
+                    a: a.triangles,
+                    b: b.triangles,
+                });
+                continue;
+            }
+
+            let attributes = [
+                (
+                    Semantic::Positions,
+                    &a.positions,
+                    &b.positions,
+                    &mut diff.max_position,
+                ),
+                (
+                    Semantic::Normals,
+                    &a.normals,
+                    &b.normals,
+                    &mut diff.max_normal,
+                ),
+                (
+                    Semantic::TexCoords(0),
+                    &a.tex_coords,
+                    &b.tex_coords,
+                    &mut diff.max_tex_coord,
+                ),
+            ];
+            for (semantic, a, b, max) in attributes {
+                match (a, b) {
+                    (Some(a), Some(b)) => {
+                        let deviation = a
+                            .iter()
+                            .zip(b)
+                            .map(|(&a, &b)| (a - b).magnitude())
+                            .fold(0.0, f32::max);
+                        *max = max.max(deviation);
+                        if deviation > tolerance {
+                            diff.differences.push(Difference::Deviation {
+                                scene,
+                                draw_call,
+                                semantic,
+                                deviation,
+                            });
+                        }
+                    }
+                    (None, None) => {}
+                    _ => diff.differences.push(Difference::Attribute {
+                        scene,
+                        draw_call,
+                        semantic,
+                    }),
+                }
+            }
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A triangle, optionally indexed and with its vertices rotated, placed
+    /// by a translated node.
+    fn document(indexed: bool, offset: f32) -> (Document, Vec<buffer::Data>) {
+        let mut data = Vec::new();
+        let positions: &[f32] = if indexed {
+            &[0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0]
+        } else {
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]
+        };
+        for value in positions {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in &[0.0f32, 0.6, 0.8, 0.0, 0.6, 0.8, 0.0, 0.6, 0.8] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        for index in &[1u16, 2, 0, 0] {
+            data.extend_from_slice(&index.to_le_bytes());
+        }
+        let indices = if indexed { r#", "indices": 1"# } else { "" };
+        let json = format!(
+            r#"{{
+                "asset": {{"version": "2.0"}},
+                "buffers": [{{"byteLength": 80}}],
+                "bufferViews": [
+                    {{"buffer": 0, "byteLength": 72, "byteStride": 12}},
+                    {{"buffer": 0, "byteOffset": 72, "byteLength": 6}}
+                ],
+                "accessors": [
+                    {{
+                        "bufferView": 0,
+                        "componentType": 5126,
+                        "count": 3,
+                        "type": "VEC3",
+                        "min": [0.0, 0.0, 0.0],
+                        "max": [1.0, 1.0, 0.0]
+                    }},
+                    {{"bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR"}},
+                    {{
+                        "bufferView": 0,
+                        "byteOffset": 36,
+                        "componentType": 5126,
+                        "count": 3,
+                        "type": "VEC3"
+                    }}
+                ],
+                "meshes": [{{
+                    "primitives": [{{"attributes": {{"POSITION": 0, "NORMAL": 2}}{}}}]
+                }}],
+                "nodes": [{{"translation": [{}, 0.0, 0.0], "mesh": 0}}],
+                "scenes": [{{"nodes": [0]}}]
+            }}"#,
+            indices, offset
+        );
+        let document = Document::from_json(json::Root::from_str(&json).unwrap()).unwrap();
+        (document, vec![buffer::Data(data)])
+    }
+
+    #[test]
+    fn compare_geometry() {
+        let (a, a_buffers) = document(false, 0.0);
+        let (b, b_buffers) = document(true, 0.0);
+        let diff = super::compare_geometry(&a, &a_buffers, &b, &b_buffers, 0.0);
+        assert!(diff.is_match(), "{:?}", diff);
+        assert_eq!(diff.max_position, 0.0);
+
+        let (c, c_buffers) = document(true, 0.5);
+        let diff = super::compare_geometry(&a, &a_buffers, &c, &c_buffers, 0.1);
+        assert_relative_eq!(diff.max_position, 0.5);
+        assert_eq!(
+            diff.differences,
+            vec![Difference::Deviation {
+                scene: 0,
+                draw_call: 0,
+                semantic: Semantic::Positions,
+                deviation: 0.5,
+            }]
+        );
+        assert!(super::compare_geometry(&a, &a_buffers, &c, &c_buffers, 0.5).is_match());
+    }
+
+    #[test]
+    fn compare_quantized_geometry() {
+        let (a, a_buffers) = document(true, 0.5);
+        let mut root = a.clone().into_json();
+        let mut buffers = a_buffers.clone();
+        let formats = crate::transform::VertexFormats {
+            positions: true,
+            normals: true,
+            tex_coords: true,
+        };
+        crate::transform::convert_vertex_formats(&mut root, &mut buffers, formats).unwrap();
+        // Every attribute was converted to normalized shorts.
+        assert_eq!(root.accessors.len(), 5);
+        assert!(root.accessors[3..]
+            .iter()
+            .all(|accessor| accessor.normalized));
+        let b = Document::from_json(root).unwrap();
+
+        let diff = super::compare_geometry(&a, &a_buffers, &b, &buffers, 1e-4);
+        assert!(diff.is_match(), "{:?}", diff);
+        assert!(diff.max_position > 0.0 || diff.max_normal > 0.0);
+    }
+}
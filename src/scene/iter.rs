@@ -1,5 +1,6 @@
 use std::slice;
 
+use crate::math::*;
 use crate::{Document, Node};
 
 /// An `Iterator` that visits the nodes in a scene.
@@ -22,6 +23,15 @@ pub struct Children<'a> {
     pub(crate) iter: slice::Iter<'a, json::Index<json::scene::Node>>,
 }
 
+/// An `Iterator` that visits every node of a scene in depth-first order, with
+/// its column-major world transform.
+#[derive(Clone, Debug)]
+pub struct Traverse<'a> {
+    /// The nodes left to visit, with the world transforms of their parents
+    /// and how many more levels of the hierarchy may be descended.
+    pub(crate) stack: Vec<(Node<'a>, Matrix4, usize)>,
+}
+
 impl<'a> ExactSizeIterator for Nodes<'a> {}
 impl<'a> Iterator for Nodes<'a> {
     type Item = Node<'a>;
@@ -62,3 +72,19 @@ impl<'a> Iterator for Children<'a> {
             .map(|index| self.document.nodes().nth(index.value()).unwrap())
     }
 }
+
+impl<'a> Iterator for Traverse<'a> {
+    type Item = (Node<'a>, [[f32; 4]; 4]);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, parent, depth) = self.stack.pop()?;
+        let world = parent * Matrix4::from_array(node.transform().matrix());
+        // A depth of zero means the hierarchy is cyclic, which is invalid glTF.
+        if depth > 0 {
+            let start = self.stack.len();
+            let children = node.children().map(|child| (child, world, depth - 1));
+            self.stack.extend(children);
+            self.stack[start..].reverse();
+        }
+        Some((node, world.as_array()))
+    }
+}
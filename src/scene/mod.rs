@@ -1,9 +1,22 @@
 use crate::math::*;
-use crate::{Camera, Document, Mesh, Skin};
+use crate::{Camera, Document, Material, Mesh, Primitive, Skin};
+
+/// Comparing the geometry of documents.
+#[cfg(feature = "utils")]
+mod compare;
 
 /// Iterators.
 pub mod iter;
 
+/// Ray intersection with scenes.
+#[cfg(feature = "utils")]
+mod pick;
+
+#[cfg(feature = "utils")]
+pub use self::compare::{compare_geometry, Difference, GeometryDiff};
+#[cfg(feature = "utils")]
+pub use self::pick::Hit;
+
 /// The transform for a `Node`.
 #[derive(Clone, Debug)]
 pub enum Transform {
@@ -47,6 +60,26 @@ impl Transform {
         }
     }
 
+    /// Returns the transform of a node.
+    pub(crate) fn from_json(json: &json::Node) -> Self {
+        if let Some(m) = json.matrix {
+            Transform::Matrix {
+                matrix: [
+                    [m[0], m[1], m[2], m[3]],
+                    [m[4], m[5], m[6], m[7]],
+                    [m[8], m[9], m[10], m[11]],
+                    [m[12], m[13], m[14], m[15]],
+                ],
+            }
+        } else {
+            Transform::Decomposed {
+                translation: json.translation.unwrap_or([0.0, 0.0, 0.0]),
+                rotation: json.rotation.unwrap_or_default().0,
+                scale: json.scale.unwrap_or([1.0, 1.0, 1.0]),
+            }
+        }
+    }
+
     /// Returns a decomposed representation of this transform.
     ///
     /// If the transform is `Matrix`, then the decomposition is extracted from the
@@ -97,12 +130,86 @@ pub struct Node<'a> {
     json: &'a json::scene::Node,
 }
 
+/// A single primitive to be drawn, as produced by `Scene::draw_calls`.
+#[derive(Clone, Debug)]
+pub struct DrawCall<'a> {
+    /// The column-major world transform of the node instancing the mesh.
+    pub transform: [[f32; 4]; 4],
+
+    /// The node instancing the mesh.
+    pub node: Node<'a>,
+
+    /// The primitive to draw.
+    pub primitive: Primitive<'a>,
+
+    /// The material to draw the primitive with.
+    pub material: Material<'a>,
+
+    /// The skin deforming the primitive, if any.
+    pub skin: Option<Skin<'a>>,
+}
+
+/// A camera placed in the world, as produced by `Scene::cameras`.
+#[derive(Clone, Debug)]
+pub struct SceneCamera<'a> {
+    /// The column-major world transform of the node holding the camera.
+    pub transform: [[f32; 4]; 4],
+
+    /// The column-major world-to-view matrix, being the inverse of
+    /// `transform`, or `None` if `transform` is not invertible.
+    pub view: Option<[[f32; 4]; 4]>,
+
+    /// The column-major projection matrix of the camera.
+    pub projection: [[f32; 4]; 4],
+
+    /// The node holding the camera.
+    pub node: Node<'a>,
+
+    /// The camera.
+    pub camera: Camera<'a>,
+}
+
+/// A light placed in the world, as produced by `Scene::lights`.
+#[cfg(feature = "KHR_lights_punctual")]
+#[cfg_attr(docsrs, doc(cfg(feature = "KHR_lights_punctual")))]
+#[derive(Clone, Debug)]
+pub struct SceneLight<'a> {
+    /// The column-major world transform of the node holding the light.
+    pub transform: [[f32; 4]; 4],
+
+    /// The node holding the light.
+    pub node: Node<'a>,
+
+    /// The light.
+    pub light: crate::khr_lights_punctual::Light<'a>,
+
+    /// The world-space position of the light.
+    pub position: [f32; 3],
+
+    /// The normalized world-space direction the light points in, being the
+    /// local -Z axis of the node. Ignored by point lights.
+    pub direction: [f32; 3],
+
+    /// The linear RGB color of the light.
+    pub color: [f32; 3],
+
+    /// The intensity of the light, in candela for point and spot lights and
+    /// in lux for directional lights.
+    pub intensity: f32,
+
+    /// The distance at which the light may be considered to have no effect.
+    pub range: Option<f32>,
+
+    /// The inner and outer cone angles of a spot light in radians, or `None`
+    /// for other kinds of light.
+    pub cone: Option<(f32, f32)>,
+}
+
 /// The root nodes of a scene.
 #[derive(Clone, Debug)]
 pub struct Scene<'a> {
     /// The parent `Document` struct.
-    #[allow(dead_code)]
-    document: &'a Document,
+    pub(crate) document: &'a Document,
 
     /// The corresponding JSON index.
     index: usize,
@@ -142,11 +249,33 @@ impl<'a> Node<'a> {
         }
     }
 
+    /// Returns the node that has this node as a child, or `None` for the root
+    /// nodes of a scene.
+    ///
+    /// The parent is not stored, so this searches every node of the document.
+    pub fn parent(&self) -> Option<Node<'a>> {
+        self.document
+            .nodes()
+            .find(|node| node.children().any(|child| child.index == self.index))
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &'a json::Extras {
         &self.json.extras
     }
 
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new().field("nodes").index(self.index)
+        })
+    }
+
     /// Returns the light at this node as defined by the `KHR_lights_punctual` extension.
     #[cfg(feature = "KHR_lights_punctual")]
     #[cfg_attr(docsrs, doc(cfg(feature = "KHR_lights_punctual")))]
@@ -179,22 +308,7 @@ impl<'a> Node<'a> {
 
     /// Returns the node's transform.
     pub fn transform(&self) -> Transform {
-        if let Some(m) = self.json.matrix {
-            Transform::Matrix {
-                matrix: [
-                    [m[0], m[1], m[2], m[3]],
-                    [m[4], m[5], m[6], m[7]],
-                    [m[8], m[9], m[10], m[11]],
-                    [m[12], m[13], m[14], m[15]],
-                ],
-            }
-        } else {
-            Transform::Decomposed {
-                translation: self.json.translation.unwrap_or_else(|| [0.0, 0.0, 0.0]),
-                rotation: self.json.rotation.unwrap_or_default().0,
-                scale: self.json.scale.unwrap_or_else(|| [1.0, 1.0, 1.0]),
-            }
-        }
+        Transform::from_json(self.json)
     }
 
     /// Returns the skin referenced by this node.
@@ -231,6 +345,18 @@ impl<'a> Scene<'a> {
         &self.json.extras
     }
 
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new().field("scenes").index(self.index)
+        })
+    }
+
     /// Optional user-defined name for this object.
     #[cfg(feature = "names")]
     pub fn name(&self) -> Option<&'a str> {
@@ -244,14 +370,360 @@ impl<'a> Scene<'a> {
             iter: self.json.nodes.iter(),
         }
     }
+
+    /// Returns an `Iterator` that visits every node of the scene in depth-first
+    /// order, together with its column-major world transform.
+    ///
+    /// The world transform of a node is the product of the local transforms on
+    /// the path from the scene root to the node, as given by
+    /// [`Node::transform`], which prefers `matrix` over TRS properties when both
+    /// are present. Nodes instanced more than once in the scene are visited once
+    /// per instance.
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// let (document, _, _) = gltf::import("examples/Box.gltf")?;
+    /// let scene = document.scenes().next().unwrap();
+    /// for (node, world) in scene.traverse() {
+    ///     println!("node {}: translation {:?}", node.index(), &world[3][..3]);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("test failure");
+    /// # }
+    /// ```
+    ///
+    /// [`Node::transform`]: struct.Node.html#method.transform
+    pub fn traverse(&self) -> iter::Traverse<'a> {
+        let depth = self.document.nodes().len();
+        let mut stack: Vec<_> = self
+            .nodes()
+            .map(|root| (root, Matrix4::identity(), depth))
+            .collect();
+        stack.reverse();
+        iter::Traverse { stack }
+    }
+
+    /// Returns the column-major world transform of `node`, being the product
+    /// of the local transforms on the path from the scene root to `node`.
+    ///
+    /// Returns `None` if `node` is not part of this scene.
+    pub fn world_transform(&self, node: &Node) -> Option<[[f32; 4]; 4]> {
+        fn find(node: Node, parent: Matrix4, target: usize, depth: usize) -> Option<Matrix4> {
+            let world = parent * Matrix4::from_array(node.transform().matrix());
+            if node.index() == target {
+                Some(world)
+            } else if depth == 0 {
+                // The hierarchy is cyclic, which is invalid glTF.
+                None
+            } else {
+                node.children()
+                    .find_map(|child| find(child, world, target, depth - 1))
+            }
+        }
+
+        let depth = self.document.nodes().len();
+        self.nodes()
+            .find_map(|root| find(root, Matrix4::identity(), node.index(), depth))
+            .map(|world| world.as_array())
+    }
+
+    /// Returns every mesh primitive instanced by the nodes of this scene,
+    /// together with its world transform, material, and skin.
+    ///
+    /// Draw calls are listed in depth-first order of the node hierarchy.
+    pub fn draw_calls(&self) -> Vec<DrawCall<'a>> {
+        let mut calls = Vec::new();
+        self.walk(&mut |node, world| {
+            if let Some(mesh) = node.mesh() {
+                for primitive in mesh.primitives() {
+                    calls.push(DrawCall {
+                        transform: world.as_array(),
+                        node: node.clone(),
+                        material: primitive.material(),
+                        primitive,
+                        skin: node.skin(),
+                    });
+                }
+            }
+        });
+        calls
+    }
+
+    /// Returns every `KHR_lights_punctual` light of this scene, resolved to
+    /// world space.
+    ///
+    /// Lights are listed in depth-first order of the node hierarchy.
+    #[cfg(feature = "KHR_lights_punctual")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "KHR_lights_punctual")))]
+    pub fn lights(&self) -> Vec<SceneLight<'a>> {
+        use crate::khr_lights_punctual::Kind;
+
+        let mut lights = Vec::new();
+        self.walk(&mut |node, world| {
+            if let Some(light) = node.light() {
+                let direction = Vector3::new(-world.z.x, -world.z.y, -world.z.z);
+                let direction = if direction.magnitude() > 0.0 {
+                    direction * (1.0 / direction.magnitude())
+                } else {
+                    direction
+                };
+                let cone = match light.kind() {
+                    Kind::Spot {
+                        inner_cone_angle,
+                        outer_cone_angle,
+                    } => Some((inner_cone_angle, outer_cone_angle)),
+                    _ => None,
+                };
+                lights.push(SceneLight {
+                    transform: world.as_array(),
+                    position: [world.w.x, world.w.y, world.w.z],
+                    direction: direction.as_array(),
+                    color: light.color(),
+                    intensity: light.intensity(),
+                    range: light.range(),
+                    cone,
+                    node: node.clone(),
+                    light,
+                });
+            }
+        });
+        lights
+    }
+
+    /// Returns every camera of this scene with its world and view transforms
+    /// and its projection matrix.
+    ///
+    /// The projection uses the authored aspect ratio of perspective cameras,
+    /// or 1 if there is none; call `Camera::projection_matrix` with the
+    /// viewport aspect ratio to override it. Cameras are listed in depth-first
+    /// order of the node hierarchy.
+    pub fn cameras(&self) -> Vec<SceneCamera<'a>> {
+        let mut cameras = Vec::new();
+        self.walk(&mut |node, world| {
+            if let Some(camera) = node.camera() {
+                cameras.push(SceneCamera {
+                    transform: world.as_array(),
+                    view: world.invert().map(|view| view.as_array()),
+                    projection: camera.projection_matrix(None),
+                    node: node.clone(),
+                    camera,
+                });
+            }
+        });
+        cameras
+    }
+
+    /// Calls `f` with every node of this scene and its world transform, in
+    /// depth-first order.
+    fn walk(&self, f: &mut dyn FnMut(&Node<'a>, Matrix4)) {
+        for (node, world) in self.traverse() {
+            f(&node, Matrix4::from_array(world));
+        }
+    }
+
+    /// Returns the column-major 3x3 matrix that transforms normals of `node`
+    /// from its local space to world space.
+    ///
+    /// This is the inverse transpose of the upper 3x3 part of the world
+    /// transform, which keeps normals perpendicular to their surfaces under
+    /// non-uniform scaling. For rigid transforms it equals the rotation part
+    /// of the world transform, which is returned as is.
+    ///
+    /// Returns `None` if `node` is not part of this scene or its world
+    /// transform is not invertible.
+    pub fn normal_matrix(&self, node: &Node) -> Option<[[f32; 3]; 3]> {
+        normal_matrix(self.world_transform(node)?)
+    }
+
+    /// Returns the column-major world-to-view matrix for rendering from a
+    /// camera attached to `node`, being the inverse of its world transform.
+    ///
+    /// Returns `None` if `node` is not part of this scene or its world
+    /// transform is not invertible.
+    pub fn view_matrix(&self, node: &Node) -> Option<[[f32; 4]; 4]> {
+        self.world_transform(node)
+            .and_then(|world| Matrix4::from_array(world).invert())
+            .map(|view| view.as_array())
+    }
+}
+
+/// Returns the normal matrix of a column-major transform, as described by
+/// `Scene::normal_matrix`.
+fn normal_matrix([x, y, z, _]: [[f32; 4]; 4]) -> Option<[[f32; 3]; 3]> {
+    #[rustfmt::skip]
+    let m = Matrix3::new(
+        x[0], x[1], x[2],
+        y[0], y[1], y[2],
+        z[0], z[1], z[2],
+    );
+    if m.is_orthonormal(1e-5) {
+        Some(m.as_array())
+    } else {
+        m.inverse_transpose().map(|m| m.as_array())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::math::*;
     use crate::scene::Transform;
+    use crate::Document;
     use std::f32::consts::PI;
 
+    #[test]
+    fn world_transform_and_view_matrix() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "cameras": [{"type": "perspective", "perspective": {"yfov": 1.0, "znear": 0.1}}],
+            "nodes": [
+                {"translation": [1.0, 0.0, 0.0], "children": [1]},
+                {"translation": [0.0, 2.0, 0.0], "scale": [2.0, 2.0, 2.0], "children": [2]},
+                {"translation": [0.0, 0.0, 3.0], "camera": 0},
+                {}
+            ],
+            "scenes": [{"nodes": [0]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let scene = document.scenes().next().unwrap();
+        let camera = document.nodes().nth(2).unwrap();
+
+        let world = scene.world_transform(&camera).unwrap();
+        assert_eq!(world[3], [1.0, 2.0, 6.0, 1.0]);
+
+        let view = Matrix4::from_array(scene.view_matrix(&camera).unwrap());
+        let origin = view * Vector4::new(1.0, 2.0, 6.0, 1.0);
+        assert_relative_eq!(origin, Vector4::new(0.0, 0.0, 0.0, 1.0));
+
+        let detached = document.nodes().nth(3).unwrap();
+        assert!(scene.world_transform(&detached).is_none());
+        assert!(scene.view_matrix(&detached).is_none());
+    }
+
+    #[test]
+    fn traverse() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [
+                {"translation": [1.0, 0.0, 0.0], "children": [1, 3]},
+                {
+                    "matrix": [2, 0, 0, 0, 0, 2, 0, 0, 0, 0, 2, 0, 0, 1, 0, 1],
+                    "translation": [5.0, 5.0, 5.0],
+                    "children": [2]
+                },
+                {"translation": [0.0, 0.0, 1.0]},
+                {"scale": [3.0, 3.0, 3.0]},
+                {"translation": [0.0, 4.0, 0.0]}
+            ],
+            "scenes": [{"nodes": [0, 4]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let scene = document.scenes().next().unwrap();
+        let visited: Vec<_> = scene
+            .traverse()
+            .map(|(node, world)| (node.index(), world[3]))
+            .collect();
+        assert_eq!(
+            visited,
+            [
+                (0, [1.0, 0.0, 0.0, 1.0]),
+                // The matrix takes precedence over the translation.
+                (1, [1.0, 1.0, 0.0, 1.0]),
+                (2, [1.0, 1.0, 2.0, 1.0]),
+                (3, [1.0, 0.0, 0.0, 1.0]),
+                (4, [0.0, 4.0, 0.0, 1.0]),
+            ]
+        );
+        for (node, world) in scene.traverse() {
+            assert_eq!(scene.world_transform(&node), Some(world));
+        }
+    }
+
+    #[test]
+    fn parent() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{"children": [1, 2]}, {"children": [3]}, {}, {}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let parents: Vec<_> = document
+            .nodes()
+            .map(|node| node.parent().map(|parent| parent.index()))
+            .collect();
+        assert_eq!(parents, [None, Some(0), Some(0), Some(1)]);
+    }
+
+    #[test]
+    fn normal_matrix() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [
+                {"scale": [2.0, 1.0, 1.0]},
+                {"rotation": [0.0, 0.0, 0.7071068, 0.7071068], "children": [2]},
+                {"scale": [0.0, 1.0, 1.0]}
+            ],
+            "scenes": [{"nodes": [0, 1]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let scene = document.scenes().next().unwrap();
+        let nodes: Vec<_> = document.nodes().collect();
+
+        // Normals are scaled inversely to the geometry.
+        let scaled = scene.normal_matrix(&nodes[0]).unwrap();
+        assert_eq!(scaled, [[0.5, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+
+        let rigid = scene.normal_matrix(&nodes[1]).unwrap();
+        let world = scene.world_transform(&nodes[1]).unwrap();
+        for i in 0..3 {
+            assert_eq!(rigid[i], [world[i][0], world[i][1], world[i][2]]);
+        }
+
+        assert!(scene.normal_matrix(&nodes[2]).is_none());
+    }
+
+    #[test]
+    fn draw_calls() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 36}],
+            "bufferViews": [{"buffer": 0, "byteLength": 36}],
+            "accessors": [{
+                "bufferView": 0,
+                "componentType": 5126,
+                "count": 3,
+                "type": "VEC3",
+                "min": [0.0, 0.0, 0.0],
+                "max": [1.0, 1.0, 0.0]
+            }],
+            "materials": [{}],
+            "meshes": [{
+                "primitives": [
+                    {"attributes": {"POSITION": 0}, "material": 0},
+                    {"attributes": {"POSITION": 0}}
+                ]
+            }],
+            "nodes": [
+                {"translation": [1.0, 0.0, 0.0], "children": [1], "mesh": 0},
+                {"translation": [0.0, 2.0, 0.0], "mesh": 0}
+            ],
+            "scenes": [{"nodes": [0]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let scene = document.scenes().next().unwrap();
+
+        let calls = scene.draw_calls();
+        assert_eq!(calls.len(), 4);
+        assert_eq!(calls[0].node.index(), 0);
+        assert_eq!(calls[0].transform[3], [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(calls[0].material.index(), Some(0));
+        assert_eq!(calls[1].primitive.index(), 1);
+        assert_eq!(calls[1].material.index(), None);
+        assert_eq!(calls[2].node.index(), 1);
+        assert_eq!(calls[2].transform[3], [1.0, 2.0, 0.0, 1.0]);
+        assert!(calls.iter().all(|call| call.skin.is_none()));
+    }
+
     fn rotate(x: f32, y: f32, z: f32, r: f32) -> [f32; 4] {
         let r = Quaternion::from_axis_angle(Vector3::new(x, y, z).normalize(), r);
         [r.v.x, r.v.y, r.v.z, r.s]
@@ -591,4 +1063,99 @@ mod tests {
         let scale = [10.0, 0.1, -0.1];
         test_decompose_scale(scale);
     }
+
+    #[cfg(feature = "extras")]
+    #[test]
+    fn extras_as() {
+        use std::collections::BTreeMap;
+
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [{}, {"extras": {"lod": 2, "tag": 7}}, {"extras": {"lod": "high"}}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let nodes: Vec<_> = document.nodes().collect();
+
+        assert_eq!(nodes[0].extras_as::<BTreeMap<String, u32>>().unwrap(), None);
+        let extras: BTreeMap<String, u32> = nodes[1].extras_as().unwrap().unwrap();
+        assert_eq!(extras["lod"], 2);
+        match nodes[2].extras_as::<BTreeMap<String, u32>>() {
+            Err(crate::Error::Extras { path, .. }) => assert_eq!(path.as_str(), "nodes[2].extras"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "KHR_lights_punctual")]
+    #[test]
+    fn lights() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "extensionsUsed": ["KHR_lights_punctual"],
+            "extensions": {"KHR_lights_punctual": {"lights": [
+                {"type": "point", "intensity": 2.0},
+                {
+                    "type": "spot",
+                    "color": [1.0, 0.5, 0.0],
+                    "range": 10.0,
+                    "spot": {"innerConeAngle": 0.25, "outerConeAngle": 0.5}
+                }
+            ]}},
+            "nodes": [
+                {"translation": [0.0, 0.0, 5.0], "children": [1]},
+                {
+                    "translation": [1.0, 0.0, 0.0],
+                    "rotation": [0.0, 0.7071068, 0.0, 0.7071068],
+                    "extensions": {"KHR_lights_punctual": {"light": 1}}
+                },
+                {"extensions": {"KHR_lights_punctual": {"light": 0}}}
+            ],
+            "scenes": [{"nodes": [0]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let scene = document.scenes().next().unwrap();
+
+        let lights = scene.lights();
+        assert_eq!(lights.len(), 1);
+        let light = &lights[0];
+        assert_eq!(light.node.index(), 1);
+        assert_eq!(light.light.index(), 1);
+        assert_eq!(light.position, [1.0, 0.0, 5.0]);
+        // Rotating -Z by 90 degrees about +Y points it along -X.
+        assert_relative_eq!(light.direction[0], -1.0, epsilon = 1e-6);
+        assert_relative_eq!(light.direction[2], 0.0, epsilon = 1e-6);
+        assert_eq!(light.color, [1.0, 0.5, 0.0]);
+        assert_eq!(light.intensity, 1.0);
+        assert_eq!(light.range, Some(10.0));
+        assert_eq!(light.cone, Some((0.25, 0.5)));
+    }
+
+    #[test]
+    fn cameras() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "cameras": [
+                {"type": "perspective", "perspective": {"yfov": 1.0, "znear": 0.1, "aspectRatio": 2.0}},
+                {"type": "orthographic", "orthographic": {"xmag": 1.0, "ymag": 1.0, "znear": 0.0, "zfar": 1.0}}
+            ],
+            "nodes": [
+                {"translation": [0.0, 2.0, 0.0], "children": [1]},
+                {"translation": [0.0, 0.0, 5.0], "camera": 1},
+                {"camera": 0}
+            ],
+            "scenes": [{"nodes": [0]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let scene = document.scenes().next().unwrap();
+
+        let cameras = scene.cameras();
+        assert_eq!(cameras.len(), 1);
+        let camera = &cameras[0];
+        assert_eq!(camera.node.index(), 1);
+        assert_eq!(camera.camera.index(), 1);
+        assert_eq!(camera.transform[3], [0.0, 2.0, 5.0, 1.0]);
+        let view = camera.view.unwrap();
+        assert_relative_eq!(view[3][1], -2.0, epsilon = 1e-6);
+        assert_relative_eq!(view[3][2], -5.0, epsilon = 1e-6);
+        assert_eq!(camera.projection, camera.camera.projection_matrix(None));
+    }
 }
@@ -0,0 +1,210 @@
+use crate::math::*;
+use crate::mesh::BoundingBox;
+use crate::{Buffer, Node, Primitive, Scene};
+
+/// Tolerance below which a ray is considered parallel to a triangle.
+const EPSILON: f32 = 1e-7;
+
+/// The closest intersection of a ray with a scene, as found by `Scene::pick`.
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+#[derive(Clone, Debug)]
+pub struct Hit<'a> {
+    /// The node instancing the mesh that was hit.
+    pub node: Node<'a>,
+
+    /// The primitive that was hit.
+    pub primitive: Primitive<'a>,
+
+    /// The vertex indices of the triangle that was hit.
+    pub triangle: [u32; 3],
+
+    /// The barycentric co-ordinates of the hit point, weighting the vertices
+    /// of `triangle` in order.
+    pub barycentrics: [f32; 3],
+
+    /// The ray parameter of the hit, such that the hit point lies at
+    /// `origin + distance * direction`.
+    pub distance: f32,
+}
+
+/// Returns the ray parameters at which a ray enters and leaves a box, if it
+/// intersects the box at all.
+fn intersect_box(origin: Vector3, direction: Vector3, bounds: &BoundingBox) -> Option<(f32, f32)> {
+    let (mut near, mut far) = (f32::NEG_INFINITY, f32::INFINITY);
+    let origin = origin.as_array();
+    let direction = direction.as_array();
+    for axis in 0..3 {
+        let (min, max) = (bounds.min[axis], bounds.max[axis]);
+        if direction[axis] == 0.0 {
+            if origin[axis] < min || origin[axis] > max {
+                return None;
+            }
+            continue;
+        }
+        let a = (min - origin[axis]) / direction[axis];
+        let b = (max - origin[axis]) / direction[axis];
+        near = near.max(a.min(b));
+        far = far.min(a.max(b));
+    }
+    if near <= far && far >= 0.0 {
+        Some((near, far))
+    } else {
+        None
+    }
+}
+
+/// Möller–Trumbore ray-triangle intersection, returning the ray parameter
+/// and the barycentric co-ordinates of the second and third vertices.
+fn intersect_triangle(
+    origin: Vector3,
+    direction: Vector3,
+    [a, b, c]: [Vector3; 3],
+) -> Option<(f32, f32, f32)> {
+    let ab = b - a;
+    let ac = c - a;
+    let p = direction.cross(ac);
+    let determinant = ab.dot(p);
+    if determinant.abs() < EPSILON {
+        return None;
+    }
+    let inverse = 1.0 / determinant;
+    let s = origin - a;
+    let u = s.dot(p) * inverse;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(ab);
+    let v = direction.dot(q) * inverse;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = ac.dot(q) * inverse;
+    if t >= 0.0 {
+        Some((t, u, v))
+    } else {
+        None
+    }
+}
+
+impl<'a> Scene<'a> {
+    /// Intersects a ray with the triangles of this scene, returning the
+    /// closest hit.
+    ///
+    /// `origin` and `direction` are given in world space. Meshes are tested
+    /// in their bind pose; morph targets and skinning are not applied.
+    /// Primitives whose bounding box is missed by the ray are skipped.
+    #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+    pub fn pick<'s, F>(
+        &self,
+        origin: [f32; 3],
+        direction: [f32; 3],
+        get_buffer_data: F,
+    ) -> Option<Hit<'a>>
+    where
+        F: Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+    {
+        let mut closest: Option<Hit<'a>> = None;
+        for call in self.draw_calls() {
+            let inverse = match Matrix4::from_array(call.transform).invert() {
+                Some(inverse) => inverse,
+                None => continue,
+            };
+            let [x, y, z] = origin;
+            let o = inverse * Vector4::new(x, y, z, 1.0);
+            let o = Vector3::new(o.x, o.y, o.z);
+            let [x, y, z] = direction;
+            let d = inverse * Vector4::new(x, y, z, 0.0);
+            let d = Vector3::new(d.x, d.y, d.z);
+
+            let best = closest.as_ref().map_or(f32::INFINITY, |hit| hit.distance);
+            match intersect_box(o, d, &call.primitive.bounding_box()) {
+                Some((near, _)) if near <= best => {}
+                _ => continue,
+            }
+
+            let reader = call.primitive.reader(get_buffer_data.clone());
+            let positions: Vec<Vector3> = match reader.read_positions() {
+                Some(iter) => iter.map(Vector3::from_array).collect(),
+                None => continue,
+            };
+            let triangles = match call.primitive.triangles(get_buffer_data.clone()) {
+                Some(triangles) => triangles,
+                None => continue,
+            };
+
+            for triangle in triangles {
+                let vertices = match (
+                    positions.get(triangle[0] as usize),
+                    positions.get(triangle[1] as usize),
+                    positions.get(triangle[2] as usize),
+                ) {
+                    (Some(&a), Some(&b), Some(&c)) => [a, b, c],
+                    _ => continue,
+                };
+                let best = closest.as_ref().map_or(f32::INFINITY, |hit| hit.distance);
+                if let Some((t, u, v)) = intersect_triangle(o, d, vertices) {
+                    if t < best {
+                        closest = Some(Hit {
+                            node: call.node.clone(),
+                            primitive: call.primitive.clone(),
+                            triangle,
+                            barycentrics: [1.0 - u - v, u, v],
+                            distance: t,
+                        });
+                    }
+                }
+            }
+        }
+        closest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Document;
+
+    #[test]
+    fn pick_closest_triangle() {
+        let mut data = Vec::new();
+        for value in &[0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 36}],
+            "bufferViews": [{"buffer": 0, "byteLength": 36}],
+            "accessors": [{
+                "bufferView": 0,
+                "componentType": 5126,
+                "count": 3,
+                "type": "VEC3",
+                "min": [0.0, 0.0, 0.0],
+                "max": [1.0, 1.0, 0.0]
+            }],
+            "meshes": [{"primitives": [{"attributes": {"POSITION": 0}}]}],
+            "nodes": [
+                {"translation": [0.0, 0.0, -5.0], "mesh": 0},
+                {"translation": [0.0, 0.0, -2.0], "mesh": 0}
+            ],
+            "scenes": [{"nodes": [0, 1]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let scene = document.scenes().next().unwrap();
+        let get = |_| Some(data.as_slice());
+
+        let hit = scene
+            .pick([0.25, 0.25, 0.0], [0.0, 0.0, -1.0], get)
+            .unwrap();
+        assert_eq!(hit.node.index(), 1);
+        assert_eq!(hit.triangle, [0, 1, 2]);
+        assert_relative_eq!(hit.distance, 2.0);
+        assert_relative_eq!(hit.barycentrics[0], 0.5);
+        assert_relative_eq!(hit.barycentrics[1], 0.25);
+        assert_relative_eq!(hit.barycentrics[2], 0.25);
+
+        assert!(scene.pick([2.0, 2.0, 0.0], [0.0, 0.0, -1.0], get).is_none());
+        assert!(scene
+            .pick([0.25, 0.25, 0.0], [0.0, 0.0, 1.0], get)
+            .is_none());
+    }
+}
@@ -11,10 +11,15 @@ pub mod iter;
 #[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
 pub mod util;
 
+/// The joint hierarchy of skins.
+mod skeleton;
+
 #[cfg(feature = "utils")]
 #[doc(inline)]
 pub use self::util::Reader;
 
+pub use self::skeleton::{Joint, SkeletonTree};
+
 /// Joints and matrices defining a skin.
 #[derive(Clone, Debug)]
 pub struct Skin<'a> {
@@ -48,6 +53,18 @@ impl<'a> Skin<'a> {
         &self.json.extras
     }
 
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new().field("skins").index(self.index)
+        })
+    }
+
     /// Returns the accessor containing the 4x4 inverse-bind matrices.
     ///
     /// When `None`, each matrix is assumed to be the 4x4 identity matrix which
@@ -0,0 +1,127 @@
+use crate::scene::Transform;
+use crate::{Node, Skin};
+
+/// A joint of a skin, as listed by `Skin::skeleton_tree`.
+#[derive(Clone, Debug)]
+pub struct Joint<'a> {
+    /// The node used as the joint.
+    pub node: Node<'a>,
+
+    /// The position of the parent joint in `SkeletonTree::joints`, or `None`
+    /// for a root joint.
+    pub parent: Option<usize>,
+
+    /// The positions of the child joints in `SkeletonTree::joints`.
+    pub children: Vec<usize>,
+
+    /// The local rest pose transform of the node, relative to its parent
+    /// node.
+    pub transform: Transform,
+
+    /// Optional user-defined name of the node.
+    #[cfg(feature = "names")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "names")))]
+    pub name: Option<&'a str>,
+}
+
+/// The joint hierarchy of a skin.
+#[derive(Clone, Debug)]
+pub struct SkeletonTree<'a> {
+    /// The joints, in the order of the skin, so that the position of a joint
+    /// matches the joint indices of skinned vertices.
+    pub joints: Vec<Joint<'a>>,
+
+    /// The positions of the joints without a parent joint.
+    pub roots: Vec<usize>,
+}
+
+impl<'a> Skin<'a> {
+    /// Returns the hierarchy of the joints of this skin.
+    ///
+    /// The parent of a joint is its nearest ancestor node that is also a joint
+    /// of this skin; nodes in between that are not joints are skipped, though
+    /// their transforms still apply when posing the skeleton.
+    pub fn skeleton_tree(&self) -> SkeletonTree<'a> {
+        let nodes = self.document.nodes().len();
+        let mut parents = vec![None; nodes];
+        for node in self.document.nodes() {
+            for child in node.children() {
+                parents[child.index()] = Some(node.index());
+            }
+        }
+        let mut positions = vec![None; nodes];
+        for (position, joint) in self.joints().enumerate() {
+            positions[joint.index()] = Some(position);
+        }
+
+        let mut joints: Vec<Joint<'a>> = self
+            .joints()
+            .map(|node| {
+                // Walk up at most once per node, which guards against cycles.
+                let mut parent = None;
+                let mut ancestor = parents[node.index()];
+                for _ in 0..nodes {
+                    match ancestor {
+                        Some(index) if positions[index].is_some() => {
+                            parent = positions[index];
+                            break;
+                        }
+                        Some(index) => ancestor = parents[index],
+                        None => break,
+                    }
+                }
+                Joint {
+                    transform: node.transform(),
+                    #[cfg(feature = "names")]
+                    name: node.name(),
+                    node,
+                    parent,
+                    children: Vec::new(),
+                }
+            })
+            .collect();
+
+        let mut roots = Vec::new();
+        for position in 0..joints.len() {
+            match joints[position].parent {
+                Some(parent) => joints[parent].children.push(position),
+                None => roots.push(position),
+            }
+        }
+        SkeletonTree { joints, roots }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Document;
+
+    #[test]
+    fn skeleton_tree() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "nodes": [
+                {"name": "hips", "children": [1, 3]},
+                {"name": "offset", "children": [2]},
+                {"name": "spine", "translation": [0.0, 1.0, 0.0]},
+                {"name": "leg"},
+                {"name": "other"}
+            ],
+            "skins": [{"joints": [2, 0, 3]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let skin = document.skins().next().unwrap();
+
+        let tree = skin.skeleton_tree();
+        let nodes: Vec<_> = tree.joints.iter().map(|j| j.node.index()).collect();
+        assert_eq!(nodes, vec![2, 0, 3]);
+        assert_eq!(tree.roots, vec![1]);
+        assert_eq!(tree.joints[0].parent, Some(1));
+        assert_eq!(tree.joints[1].children, vec![0, 2]);
+        assert_eq!(tree.joints[2].parent, Some(1));
+        let (translation, _, _) = tree.joints[0].transform.clone().decomposed();
+        assert_eq!(translation, [0.0, 1.0, 0.0]);
+        #[cfg(feature = "names")]
+        assert_eq!(tree.joints[0].name, Some("spine"));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::accessor;
+use crate::math::Matrix4;
 
-use crate::{Buffer, Skin};
+use crate::{Buffer, Node, Skin};
 
 /// Inverse Bind Matrices of type `[[f32; 4]; 4]`.
 pub type ReadInverseBindMatrices<'a> = accessor::Iter<'a, [[f32; 4]; 4]>;
@@ -26,4 +27,136 @@ where
             .inverse_bind_matrices()
             .and_then(|accessor| accessor::Iter::new(accessor, self.get_buffer_data.clone()))
     }
+
+    /// Returns the joints of the skin, each paired with its inverse bind
+    /// matrix.
+    ///
+    /// When the skin has no inverse bind matrices, every joint is paired with
+    /// the identity matrix. Returns `None` if the matrices cannot be read, or
+    /// if there are fewer matrices than joints.
+    pub fn read_joints(&self) -> Option<Vec<(Node<'a>, [[f32; 4]; 4])>> {
+        let joints = self.skin.joints();
+        let matrices: Vec<_> = match self.skin.inverse_bind_matrices() {
+            Some(_) => self.read_inverse_bind_matrices()?.collect(),
+            None => vec![Matrix4::identity().as_array(); joints.len()],
+        };
+        if matrices.len() < joints.len() {
+            return None;
+        }
+        Some(joints.zip(matrices).collect())
+    }
+
+    /// Computes the column-major joint matrices that deform the vertices of a
+    /// mesh instanced by `mesh_node` into the pose given by `world_transforms`.
+    ///
+    /// `world_transforms` holds the world transform of every node of the
+    /// document, in node order, such as sampled from an animation and composed
+    /// with `Scene::traverse`. The matrix of each joint is
+    ///
+    /// ```text
+    /// inverse(world(mesh_node)) * world(joint) * inverse_bind_matrix
+    /// ```
+    ///
+    /// in the order of the joints of the skin, so that it may be indexed by the
+    /// joint indices of the skinned vertices. Returns `None` if the joints
+    /// cannot be read, if a node is missing from `world_transforms`, or if the
+    /// world transform of `mesh_node` is not invertible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// # let (document, buffers, _) = gltf::import("examples/Box.gltf")?;
+    /// let scene = document.default_scene().unwrap();
+    /// let mut world_transforms = vec![[[0.0; 4]; 4]; document.nodes().len()];
+    /// for (node, world) in scene.traverse() {
+    ///     world_transforms[node.index()] = world;
+    /// }
+    /// for node in scene.traverse().map(|(node, _)| node) {
+    ///     if let Some(skin) = node.skin() {
+    ///         let reader = skin.reader(|buffer| Some(&buffers[buffer.index()]));
+    ///         let joint_matrices = reader.joint_matrices(&node, &world_transforms);
+    ///         # let _ = joint_matrices;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().expect("test failure");
+    /// # }
+    /// ```
+    pub fn joint_matrices(
+        &self,
+        mesh_node: &Node,
+        world_transforms: &[[[f32; 4]; 4]],
+    ) -> Option<Vec<[[f32; 4]; 4]>> {
+        let world = |node: &Node| world_transforms.get(node.index()).copied();
+        let inverse = Matrix4::from_array(world(mesh_node)?).invert()?;
+        self.read_joints()?
+            .into_iter()
+            .map(|(joint, inverse_bind_matrix)| {
+                let matrix = inverse
+                    * Matrix4::from_array(world(&joint)?)
+                    * Matrix4::from_array(inverse_bind_matrix);
+                Some(matrix.as_array())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::{Matrix4, Vector3};
+    use crate::Document;
+
+    fn translation(x: f32, y: f32, z: f32) -> [[f32; 4]; 4] {
+        Matrix4::from_translation(Vector3::new(x, y, z)).as_array()
+    }
+
+    #[test]
+    fn joint_matrices() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 2, "type": "MAT4"}
+            ],
+            "buffers": [{"byteLength": 128}],
+            "bufferViews": [{"buffer": 0, "byteLength": 128}],
+            "nodes": [
+                {"skin": 0},
+                {"translation": [1.0, 0.0, 0.0], "children": [2]},
+                {"translation": [0.0, 1.0, 0.0]}
+            ],
+            "skins": [{"inverseBindMatrices": 0, "joints": [1, 2]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let mut data = Vec::new();
+        for matrix in [translation(-1.0, 0.0, 0.0), translation(-1.0, -1.0, 0.0)] {
+            for value in matrix.iter().flatten() {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        let skin = document.skins().next().unwrap();
+        let reader = skin.reader(|_| Some(&data[..]));
+        let joints = reader.read_joints().unwrap();
+        assert_eq!(joints.len(), 2);
+        assert_eq!(joints[1].0.index(), 2);
+        assert_eq!(joints[1].1, translation(-1.0, -1.0, 0.0));
+
+        // The second joint is raised by one, and the mesh node moved along x.
+        let mesh_node = document.nodes().next().unwrap();
+        let world_transforms = [
+            translation(5.0, 0.0, 0.0),
+            translation(1.0, 0.0, 0.0),
+            translation(1.0, 2.0, 0.0),
+        ];
+        let matrices = reader
+            .joint_matrices(&mesh_node, &world_transforms)
+            .unwrap();
+        assert_eq!(matrices[0], translation(-5.0, 0.0, 0.0));
+        assert_eq!(matrices[1], translation(-5.0, 1.0, 0.0));
+        assert!(reader
+            .joint_matrices(&mesh_node, &world_transforms[..2])
+            .is_none());
+    }
 }
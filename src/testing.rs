@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+
+use crate::buffer;
+use crate::transform::{compute_bounds, data::Appender};
+use json::accessor::{ComponentType, Type};
+use json::mesh::{Mode, Semantic};
+use json::validation::Checked;
+#[cfg(feature = "proptest")]
+use proptest::prelude::*;
+
+/// Bounds on the size of generated documents.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Settings {
+    /// The maximum number of nodes, at least 1.
+    pub max_nodes: usize,
+
+    /// The maximum number of meshes, at least 1.
+    pub max_meshes: usize,
+
+    /// The maximum number of primitives per mesh, at least 1.
+    pub max_primitives: usize,
+
+    /// The maximum number of materials.
+    pub max_materials: usize,
+
+    /// The maximum number of vertices per primitive, at least 3.
+    pub max_vertices: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            max_nodes: 16,
+            max_meshes: 4,
+            max_primitives: 3,
+            max_materials: 4,
+            max_vertices: 64,
+        }
+    }
+}
+
+/// Synthesizes random glTF documents that pass validation.
+///
+/// Documents are made of meshes with float positions and optional normals,
+/// texture co-ordinates, and indices; materials; a node hierarchy with
+/// random transforms; and one or two scenes. Every index is in range,
+/// accessor bounds are computed from the data, and all data is stored in a
+/// single buffer without a URI, as for binary glTF.
+///
+/// Generation is deterministic for a given seed and settings, so a failing
+/// case can be reproduced from its seed. Use it from any property testing or
+/// fuzzing harness by deriving the seed from the input, or through the
+/// [`documents`] strategy with the `proptest` feature.
+///
+/// [`documents`]: fn.documents.html
+///
+/// # Examples
+///
+/// ```
+/// use gltf::testing::Generator;
+///
+/// for seed in 0..16 {
+///     let (root, buffers) = Generator::new(seed).generate();
+///     let json = root.to_string().unwrap();
+///     let document = gltf::Document::from_json(gltf::json::Root::from_str(&json).unwrap());
+///     assert!(document.is_ok(), "seed {}", seed);
+///     # let _ = buffers;
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Generator {
+    /// The state of the random number generator.
+    state: u64,
+
+    /// Bounds on the size of generated documents.
+    settings: Settings,
+}
+
+impl Generator {
+    /// Creates a generator with default settings.
+    pub fn new(seed: u64) -> Self {
+        Self::with_settings(seed, Settings::default())
+    }
+
+    /// Creates a generator with the given settings.
+    pub fn with_settings(seed: u64, settings: Settings) -> Self {
+        Self {
+            state: seed,
+            settings,
+        }
+    }
+
+    /// Creates a generator seeded from arbitrary bytes, such as the input of
+    /// a fuzz target.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let seed = bytes.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+        Self::new(seed)
+    }
+
+    /// Returns the next random number, using SplitMix64.
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number in `min..=max`.
+    fn range(&mut self, min: usize, max: usize) -> usize {
+        let max = max.max(min);
+        min + (self.next() % (max - min + 1) as u64) as usize
+    }
+
+    /// Returns `true` with a probability of one half.
+    fn coin(&mut self) -> bool {
+        self.next() & 1 == 1
+    }
+
+    /// Returns a random multiple of 1/16 in `-range..=range`.
+    fn float(&mut self, range: usize) -> f64 {
+        (self.range(0, range * 32) as f64 - (range * 16) as f64) / 16.0
+    }
+
+    /// Generates a document, returning its JSON and buffer data.
+    pub fn generate(&mut self) -> (json::Root, Vec<buffer::Data>) {
+        let mut root = json::Root::default();
+        let mut buffers = Vec::new();
+        let mut appender = Appender::new(&root);
+
+        for _ in 0..self.range(0, self.settings.max_materials) {
+            let mut material = json::Material {
+                double_sided: self.coin(),
+                ..Default::default()
+            };
+            material.pbr_metallic_roughness.base_color_factor.0 = [
+                self.range(0, 16) as f32 / 16.0,
+                self.range(0, 16) as f32 / 16.0,
+                self.range(0, 16) as f32 / 16.0,
+                1.0,
+            ];
+            root.materials.push(material);
+        }
+
+        for _ in 0..self.range(1, self.settings.max_meshes) {
+            let primitives = (0..self.range(1, self.settings.max_primitives))
+                .map(|_| self.primitive(&mut root, &mut appender))
+                .collect();
+            root.meshes.push(json::Mesh {
+                extensions: Default::default(),
+                extras: Default::default(),
+                #[cfg(feature = "names")]
+                name: None,
+                primitives,
+                weights: None,
+            });
+        }
+
+        let count = self.range(1, self.settings.max_nodes);
+        let mut parentless = Vec::new();
+        for index in 0..count {
+            let node = self.node(root.meshes.len());
+            root.nodes.push(node);
+            // Parents come before their children, so there are no cycles.
+            if index > 0 && self.coin() {
+                let parent = self.range(0, index - 1);
+                root.nodes[parent]
+                    .children
+                    .get_or_insert_with(Vec::new)
+                    .push(json::Index::new(index as u32));
+            } else {
+                parentless.push(index);
+            }
+        }
+        for _ in 0..self.range(1, 2) {
+            let mut nodes: Vec<_> = parentless
+                .iter()
+                .filter(|_| self.coin())
+                .map(|&index| json::Index::new(index as u32))
+                .collect();
+            if nodes.is_empty() {
+                nodes.push(json::Index::new(parentless[0] as u32));
+            }
+            root.scenes.push(json::Scene {
+                extensions: Default::default(),
+                extras: Default::default(),
+                #[cfg(feature = "names")]
+                name: None,
+                nodes,
+            });
+        }
+        root.scene = Some(json::Index::new(0));
+
+        appender.finish(&mut root, &mut buffers);
+        compute_bounds(&mut root, &buffers).expect("generated data is readable");
+        (root, buffers)
+    }
+
+    /// Generates a triangle list primitive.
+    fn primitive(
+        &mut self,
+        root: &mut json::Root,
+        appender: &mut Appender,
+    ) -> json::mesh::Primitive {
+        let vertices = 3 * self.range(1, self.settings.max_vertices.max(3) / 3);
+        let mut attributes = HashMap::new();
+
+        let positions: Vec<f64> = (0..vertices * 3).map(|_| self.float(8)).collect();
+        let index = appender.push(root, ComponentType::F32, Type::Vec3, false, &positions);
+        attributes.insert(Checked::Valid(Semantic::Positions), index);
+
+        if self.coin() {
+            const NORMALS: &[[f64; 3]] = &[
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0],
+                [0.0, -1.0, 0.0],
+                [0.6, 0.8, 0.0],
+                [0.0, 0.6, -0.8],
+            ];
+            let normals: Vec<f64> = (0..vertices)
+                .flat_map(|_| NORMALS[self.range(0, NORMALS.len() - 1)].to_vec())
+                .collect();
+            let index = appender.push(root, ComponentType::F32, Type::Vec3, false, &normals);
+            attributes.insert(Checked::Valid(Semantic::Normals), index);
+        }
+
+        if self.coin() {
+            let tex_coords: Vec<f64> = (0..vertices * 2)
+                .map(|_| self.range(0, 16) as f64 / 16.0)
+                .collect();
+            let index = appender.push(root, ComponentType::F32, Type::Vec2, false, &tex_coords);
+            attributes.insert(Checked::Valid(Semantic::TexCoords(0)), index);
+        }
+
+        let indices = if self.coin() {
+            let count = 3 * self.range(1, vertices);
+            let values: Vec<f64> = (0..count)
+                .map(|_| self.range(0, vertices - 1) as f64)
+                .collect();
+            // The largest value of each type restarts strips, so it is never
+            // a valid index.
+            let component_type = match self.range(0, 2) {
+                0 if vertices <= 255 => ComponentType::U8,
+                0 | 1 if vertices <= 65535 => ComponentType::U16,
+                _ => ComponentType::U32,
+            };
+            Some(appender.push(root, component_type, Type::Scalar, false, &values))
+        } else {
+            None
+        };
+
+        let material = if !root.materials.is_empty() && self.coin() {
+            let index = self.range(0, root.materials.len() - 1);
+            Some(json::Index::new(index as u32))
+        } else {
+            None
+        };
+
+        json::mesh::Primitive {
+            attributes,
+            extensions: Default::default(),
+            extras: Default::default(),
+            indices,
+            material,
+            mode: Checked::Valid(Mode::Triangles),
+            targets: None,
+        }
+    }
+
+    /// Generates a node with a random transform, instancing one of `meshes`
+    /// meshes or none.
+    fn node(&mut self, meshes: usize) -> json::Node {
+        let mesh = if self.coin() {
+            Some(json::Index::new(self.range(0, meshes - 1) as u32))
+        } else {
+            None
+        };
+        let (translation, rotation, scale) = if self.coin() {
+            // A rotation about one of the axes by a multiple of 45 degrees.
+            let angle = self.range(0, 7) as f32 * std::f32::consts::FRAC_PI_4;
+            let (sin, cos) = (angle / 2.0).sin_cos();
+            let mut rotation = [0.0, 0.0, 0.0, cos];
+            rotation[self.range(0, 2)] = sin;
+            let scale = self.range(1, 8) as f32 / 4.0;
+            (
+                Some([
+                    self.float(4) as f32,
+                    self.float(4) as f32,
+                    self.float(4) as f32,
+                ]),
+                Some(json::scene::UnitQuaternion(rotation)),
+                Some([scale, scale, scale]),
+            )
+        } else {
+            (None, None, None)
+        };
+        json::Node {
+            camera: None,
+            children: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+            matrix: None,
+            mesh,
+            #[cfg(feature = "names")]
+            name: None,
+            rotation,
+            scale,
+            translation,
+            skin: None,
+            weights: None,
+        }
+    }
+}
+
+/// Returns a strategy for settings no larger than `max`.
+///
+/// Settings shrink towards a single node, mesh, and primitive of three
+/// vertices, without materials.
+#[cfg(feature = "proptest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+pub fn settings(max: Settings) -> impl Strategy<Value = Settings> {
+    (
+        1..=max.max_nodes.max(1),
+        1..=max.max_meshes.max(1),
+        1..=max.max_primitives.max(1),
+        0..=max.max_materials,
+        3..=max.max_vertices.max(3),
+    )
+        .prop_map(
+            |(max_nodes, max_meshes, max_primitives, max_materials, max_vertices)| Settings {
+                max_nodes,
+                max_meshes,
+                max_primitives,
+                max_materials,
+                max_vertices,
+            },
+        )
+}
+
+/// Returns a strategy for documents generated with settings no larger than
+/// `max`, along with their buffer data.
+///
+/// Failing cases shrink through their settings, towards smaller documents,
+/// and through their seed.
+///
+/// # Examples
+///
+/// ```
+/// use gltf::testing::{documents, Settings};
+/// use proptest::prelude::*;
+/// use proptest::test_runner::TestRunner;
+///
+/// let mut runner = TestRunner::default();
+/// let result = runner.run(&documents(Settings::default()), |(root, _)| {
+///     prop_assert!(gltf::Document::from_json(root).is_ok());
+///     Ok(())
+/// });
+/// assert!(result.is_ok());
+/// ```
+#[cfg(feature = "proptest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "proptest")))]
+pub fn documents(max: Settings) -> impl Strategy<Value = (json::Root, Vec<buffer::Data>)> {
+    (settings(max), any::<u64>())
+        .prop_map(|(settings, seed)| Generator::with_settings(seed, settings).generate())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Document;
+
+    #[test]
+    fn generated_documents_round_trip() {
+        for seed in 0..64 {
+            let (root, buffers) = Generator::new(seed).generate();
+            let json = root.to_string().unwrap();
+            let parsed = json::Root::from_str(&json).unwrap();
+            // Compare values, as attributes are serialized in no particular
+            // order.
+            assert_eq!(
+                json::serialize::to_value(&parsed).unwrap(),
+                json::serialize::to_value(&root).unwrap(),
+                "seed {}",
+                seed
+            );
+
+            let document = Document::from_json(parsed).unwrap();
+            for mesh in document.meshes() {
+                for primitive in mesh.primitives() {
+                    let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+                    let positions = reader.read_positions().unwrap().count();
+                    let accessor = primitive.get(&crate::Semantic::Positions).unwrap();
+                    assert_eq!(positions, accessor.count());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn deterministic() {
+        let value =
+            |generator: &mut Generator| json::serialize::to_value(&generator.generate().0).unwrap();
+        let a = value(&mut Generator::new(7));
+        assert_eq!(value(&mut Generator::new(7)), a);
+        assert_ne!(value(&mut Generator::from_bytes(b"seed")), a);
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest! {
+        #[test]
+        fn strategy_documents_validate((root, buffers) in documents(Settings::default())) {
+            let document = Document::from_json(root).unwrap();
+            prop_assert_eq!(buffers.len(), document.buffers().len());
+            prop_assert!(document.nodes().len() <= Settings::default().max_nodes);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::usage::Usage;
 use crate::{image, Document};
 
 pub use json::texture::{MagFilter, MinFilter, WrappingMode};
@@ -16,6 +17,26 @@ pub struct Info<'a> {
     json: &'a json::texture::Info,
 }
 
+/// The effective filtering and wrapping modes of a texture.
+///
+/// Unlike [`Sampler`], every property has a value: unset filters are
+/// resolved according to [`ResolvedSampler::DEFAULT_MAG_FILTER`] and
+/// [`ResolvedSampler::DEFAULT_MIN_FILTER`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ResolvedSampler {
+    /// Magnification filter.
+    pub mag_filter: MagFilter,
+
+    /// Minification filter.
+    pub min_filter: MinFilter,
+
+    /// `s` wrapping mode.
+    pub wrap_s: WrappingMode,
+
+    /// `t` wrapping mode.
+    pub wrap_t: WrappingMode,
+}
+
 ///  Texture sampler properties for filtering and wrapping modes.
 #[derive(Clone, Debug)]
 pub struct Sampler<'a> {
@@ -43,6 +64,14 @@ pub struct Texture<'a> {
     json: &'a json::texture::Texture,
 }
 
+impl ResolvedSampler {
+    /// The magnification filter chosen when a sampler does not specify one.
+    pub const DEFAULT_MAG_FILTER: MagFilter = MagFilter::Linear;
+
+    /// The minification filter chosen when a sampler does not specify one.
+    pub const DEFAULT_MIN_FILTER: MinFilter = MinFilter::LinearMipmapLinear;
+}
+
 impl<'a> Sampler<'a> {
     /// Constructs a `Sampler`.
     pub(crate) fn new(
@@ -99,10 +128,37 @@ impl<'a> Sampler<'a> {
         self.json.wrap_t.unwrap()
     }
 
+    /// Returns the effective filtering and wrapping modes of this sampler.
+    pub fn resolved(&self) -> ResolvedSampler {
+        ResolvedSampler {
+            mag_filter: self
+                .mag_filter()
+                .unwrap_or(ResolvedSampler::DEFAULT_MAG_FILTER),
+            min_filter: self
+                .min_filter()
+                .unwrap_or(ResolvedSampler::DEFAULT_MIN_FILTER),
+            wrap_s: self.wrap_s(),
+            wrap_t: self.wrap_t(),
+        }
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &json::Extras {
         &self.json.extras
     }
+
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            let path = json::Path::new().field("samplers");
+            self.index.map_or(path.clone(), |index| path.index(index))
+        })
+    }
 }
 
 impl<'a> Texture<'a> {
@@ -135,27 +191,49 @@ impl<'a> Texture<'a> {
         self.json
             .sampler
             .as_ref()
-            .map(|index| {
-                self.document
-                    .samplers()
-                    .nth(index.value() as usize)
-                    .unwrap()
-            })
+            .map(|index| self.document.samplers().nth(index.value()).unwrap())
             .unwrap_or_else(|| Sampler::default(self.document))
     }
 
+    /// Returns the effective filtering and wrapping modes of this texture.
+    ///
+    /// Textures without a sampler use `REPEAT` wrapping, and unset filters
+    /// are chosen as described by [`ResolvedSampler`].
+    pub fn resolved_sampler(&self) -> ResolvedSampler {
+        self.sampler().resolved()
+    }
+
     /// Returns the image used by this texture.
     pub fn source(&self) -> image::Image<'a> {
         self.document
             .images()
-            .nth(self.json.source.value() as usize)
+            .nth(self.json.source.value())
             .unwrap()
     }
 
+    /// Returns the materials, primitives, and nodes that use this texture.
+    ///
+    /// The `textures` of the returned usage contains only this texture.
+    pub fn usage(&self) -> Usage<'a> {
+        Usage::of_textures(self.document, vec![self.clone()])
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &json::Extras {
         &self.json.extras
     }
+
+    /// Deserializes the application specific data into a value of type `T`.
+    ///
+    /// Returns `None` if there is no application specific data. Errors are
+    /// reported with the JSON path of the data.
+    #[cfg(feature = "extras")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extras")))]
+    pub fn extras_as<T: serde::Deserialize<'a>>(&self) -> crate::Result<Option<T>> {
+        crate::extras_as(&self.json.extras, || {
+            json::Path::new().field("textures").index(self.index)
+        })
+    }
 }
 
 impl<'a> Info<'a> {
@@ -240,8 +318,85 @@ impl<'a> TextureTransform<'a> {
         self.json.tex_coord
     }
 
+    /// Returns the transform as a column-major 3x3 matrix applied to
+    /// homogeneous `[u, v, 1]` texture co-ordinates.
+    ///
+    /// The matrix is the product of translation, rotation, and scale, in that
+    /// order, as specified by the extension.
+    pub fn matrix(&self) -> [[f32; 3]; 3] {
+        let [x, y] = self.offset();
+        let [sx, sy] = self.scale();
+        let (s, c) = self.rotation().sin_cos();
+        [[c * sx, -s * sx, 0.0], [s * sy, c * sy, 0.0], [x, y, 1.0]]
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &json::Extras {
         &self.json.extras
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolved_sampler() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "images": [{"uri": "image.png"}],
+            "samplers": [{"magFilter": 9728, "wrapT": 33648}],
+            "textures": [{"source": 0}, {"source": 0, "sampler": 0}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let mut textures = document.textures();
+
+        let default = textures.next().unwrap().resolved_sampler();
+        assert_eq!(default.mag_filter, MagFilter::Linear);
+        assert_eq!(default.min_filter, MinFilter::LinearMipmapLinear);
+        assert_eq!(default.wrap_s, WrappingMode::Repeat);
+        assert_eq!(default.wrap_t, WrappingMode::Repeat);
+
+        let partial = textures.next().unwrap().resolved_sampler();
+        assert_eq!(partial.mag_filter, MagFilter::Nearest);
+        assert_eq!(partial.min_filter, MinFilter::LinearMipmapLinear);
+        assert_eq!(partial.wrap_s, WrappingMode::Repeat);
+        assert_eq!(partial.wrap_t, WrappingMode::MirroredRepeat);
+    }
+
+    #[cfg(feature = "KHR_texture_transform")]
+    #[test]
+    fn texture_transform_matrix() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "images": [{"uri": "image.png"}],
+            "textures": [{"source": 0}],
+            "materials": [{
+                "pbrMetallicRoughness": {
+                    "baseColorTexture": {
+                        "index": 0,
+                        "extensions": {
+                            "KHR_texture_transform": {
+                                "offset": [0.5, 0.25],
+                                "rotation": 1.5707964,
+                                "scale": [2.0, 4.0]
+                            }
+                        }
+                    }
+                }
+            }]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let material = document.materials().next().unwrap();
+        let info = material
+            .pbr_metallic_roughness()
+            .base_color_texture()
+            .unwrap();
+        let m = info.texture_transform().unwrap().matrix();
+
+        // (1, 0) is scaled to (2, 0), rotated to (0, -2), then offset.
+        assert_relative_eq!(m[0][0] + m[2][0], 0.5, epsilon = 1e-6);
+        assert_relative_eq!(m[0][1] + m[2][1], -1.75, epsilon = 1e-6);
+        assert_eq!(m[2], [0.5, 0.25, 1.0]);
+    }
+}
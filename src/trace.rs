@@ -0,0 +1,121 @@
+/// An entered span, exited when dropped.
+///
+/// Without the `tracing` feature this does nothing.
+pub(crate) struct Guard {
+    #[cfg(feature = "tracing")]
+    pub(crate) span: tracing::span::EnteredSpan,
+}
+
+impl Guard {
+    /// Records a field of the span, which must be one of the fields it was
+    /// entered with.
+    pub(crate) fn record(&mut self, name: &'static str, value: usize) {
+        #[cfg(feature = "tracing")]
+        self.span.record(name, value as u64);
+        #[cfg(not(feature = "tracing"))]
+        let _ = (name, value);
+    }
+}
+
+/// Enters a span with the given name and fields, to be recorded later.
+macro_rules! span {
+    ($name:literal $(, $field:ident)*) => {{
+        #[cfg(feature = "tracing")]
+        let guard = crate::trace::Guard {
+            span: tracing::debug_span!($name, $($field = tracing::field::Empty),*).entered(),
+        };
+        #[cfg(not(feature = "tracing"))]
+        let guard = crate::trace::Guard {};
+        guard
+    }};
+}
+
+pub(crate) use span;
+
+#[cfg(all(test, feature = "tracing", feature = "import"))]
+mod tests {
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata};
+
+    /// The name and fields of each span created.
+    type Spans = Arc<Mutex<Vec<(&'static str, HashMap<&'static str, u64>)>>>;
+
+    /// Collects the spans created on the current thread.
+    struct Collect(Spans);
+
+    struct Fields<'a>(&'a mut HashMap<&'static str, u64>);
+
+    impl Visit for Fields<'_> {
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            self.0.insert(field.name(), value);
+        }
+
+        fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
+    }
+
+    impl tracing::Subscriber for Collect {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes) -> Id {
+            let mut spans = self.0.lock().unwrap();
+            let mut fields = HashMap::new();
+            span.record(&mut Fields(&mut fields));
+            spans.push((span.metadata().name(), fields));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record) {
+            let mut spans = self.0.lock().unwrap();
+            let fields = &mut spans[span.into_u64() as usize - 1].1;
+            values.record(&mut Fields(fields));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn spans() {
+        let spans = Spans::default();
+        let (document, buffers, _) =
+            tracing::subscriber::with_default(Collect(spans.clone()), || {
+                let (document, buffers, images) = crate::import("examples/Box.glb").unwrap();
+                let mesh = document.meshes().next().unwrap();
+                let primitive = mesh.primitives().next().unwrap();
+                let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+                assert_eq!(reader.read_positions().unwrap().count(), 24);
+                (document, buffers, images)
+            });
+        let _ = buffers;
+
+        let spans = spans.lock().unwrap();
+        let find = |name| {
+            spans
+                .iter()
+                .find(|span| span.0 == name)
+                .map(|span| &span.1)
+                .unwrap_or_else(|| panic!("no {} span", name))
+        };
+        let glb = find("glb");
+        assert_eq!(
+            glb.get("bin_bytes").copied(),
+            Some(document.buffers().next().unwrap().length() as u64)
+        );
+        assert!(find("parse_json")["bytes"] > 0);
+        assert_eq!(find("validate").get("errors"), Some(&0));
+        let accessor = find("read_accessor");
+        assert_eq!(accessor.get("count"), Some(&24));
+        assert_eq!(accessor.get("bytes"), Some(&(24 * 12)));
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeSet;
+
+use crate::{buffer, Result};
+use json::accessor::ComponentType;
+
+use super::{data, is_compressed};
+
+/// Converts a stored component to a JSON number of the same kind.
+fn number(value: f64, component_type: ComponentType) -> json::Value {
+    match component_type {
+        ComponentType::F32 => json::Value::from(value),
+        _ => json::Value::from(value as i64),
+    }
+}
+
+/// Sets the `min` and `max` of every accessor that is required to have them.
+///
+/// This covers accessors used as vertex attributes, including morph target
+/// displacements, and as animation sampler inputs. Bounds are computed from
+/// the stored data, replacing any missing or stale values.
+///
+/// Returns `Error::Transform` without changing anything if an accessor has
+/// an invalid format or data outside of its buffer data.
+pub fn compute_bounds(root: &mut json::Root, buffers: &[buffer::Data]) -> Result<()> {
+    data::check(root, buffers)?;
+    let mut accessors = BTreeSet::new();
+    for mesh in &root.meshes {
+        for primitive in mesh.primitives.iter().filter(|p| !is_compressed(p)) {
+            accessors.extend(primitive.attributes.values().map(|index| index.value()));
+            for target in primitive.targets.iter().flatten() {
+                for index in [&target.positions, &target.normals, &target.tangents]
+                    .iter()
+                    .filter_map(|index| index.as_ref())
+                {
+                    accessors.insert(index.value());
+                }
+            }
+        }
+    }
+    for animation in &root.animations {
+        accessors.extend(
+            animation
+                .samplers
+                .iter()
+                .map(|sampler| sampler.input.value()),
+        );
+    }
+
+    for index in accessors {
+        if index < root.accessors.len() {
+            set_bounds(root, buffers, index)?;
+        }
+    }
+    Ok(())
+}
+
+/// Sets the `min` and `max` of an accessor from its stored data, leaving
+/// accessors without elements alone.
+pub(super) fn set_bounds(
+    root: &mut json::Root,
+    buffers: &[buffer::Data],
+    index: usize,
+) -> Result<()> {
+    let values = data::read(root, buffers, index)?;
+    let (component_type, type_) = data::format(&root.accessors[index], index)?;
+    let width = type_.multiplicity();
+    if values.is_empty() {
+        return Ok(());
+    }
+
+    let mut min = values[..width].to_vec();
+    let mut max = min.clone();
+    for element in values.chunks(width) {
+        for (j, &value) in element.iter().enumerate() {
+            min[j] = min[j].min(value);
+            max[j] = max[j].max(value);
+        }
+    }
+
+    let accessor = &mut root.accessors[index];
+    accessor.min = Some(
+        min.into_iter()
+            .map(|value| number(value, component_type))
+            .collect(),
+    );
+    accessor.max = Some(
+        max.into_iter()
+            .map(|value| number(value, component_type))
+            .collect(),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_stale_bounds() {
+        let mut bytes = Vec::new();
+        for value in &[1.0f32, -2.0, 0.5, -1.0, 3.0, 0.25] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 24}],
+            "bufferViews": [{"buffer": 0, "byteLength": 24}],
+            "accessors": [{
+                "bufferView": 0,
+                "componentType": 5126,
+                "count": 2,
+                "type": "VEC3",
+                "min": [0.0, 0.0, 0.0],
+                "max": [0.0, 0.0, 0.0]
+            }],
+            "meshes": [{"primitives": [{"attributes": {"POSITION": 0}}]}]
+        }"#;
+        let mut root = json::Root::from_str(json).unwrap();
+
+        compute_bounds(&mut root, &[buffer::Data(bytes)]).unwrap();
+
+        let accessor = &root.accessors[0];
+        assert_eq!(
+            accessor.min,
+            Some(json::Value::from(vec![-1.0, -2.0, 0.25]))
+        );
+        assert_eq!(accessor.max, Some(json::Value::from(vec![1.0, 3.0, 0.5])));
+    }
+
+    #[cfg(feature = "KHR_draco_mesh_compression")]
+    #[test]
+    fn keeps_compressed_bounds() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "extensionsUsed": ["KHR_draco_mesh_compression"],
+            "extensionsRequired": ["KHR_draco_mesh_compression"],
+            "buffers": [{"byteLength": 4}],
+            "bufferViews": [{"buffer": 0, "byteLength": 4}],
+            "accessors": [{
+                "componentType": 5126,
+                "count": 3,
+                "type": "VEC3",
+                "min": [-1.0, -1.0, -1.0],
+                "max": [1.0, 1.0, 1.0]
+            }],
+            "meshes": [{
+                "primitives": [{
+                    "attributes": {"POSITION": 0},
+                    "extensions": {
+                        "KHR_draco_mesh_compression": {
+                            "bufferView": 0,
+                            "attributes": {"POSITION": 0}
+                        }
+                    }
+                }]
+            }]
+        }"#;
+        let mut root = json::Root::from_str(json).unwrap();
+        let mut buffers = vec![buffer::Data(vec![0; 4])];
+
+        compute_bounds(&mut root, &buffers).unwrap();
+        #[cfg(feature = "utils")]
+        crate::transform::generate_normals(&mut root, &mut buffers).unwrap();
+
+        let accessor = &root.accessors[0];
+        assert_eq!(
+            accessor.min,
+            Some(json::Value::from(vec![-1.0, -1.0, -1.0]))
+        );
+        assert_eq!(accessor.max, Some(json::Value::from(vec![1.0, 1.0, 1.0])));
+        assert_eq!(root.meshes[0].primitives[0].attributes.len(), 1);
+        assert_eq!(buffers.len(), 1);
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 24}],
+            "bufferViews": [{"buffer": 0, "byteLength": 24}],
+            "accessors": [{
+                "bufferView": 0,
+                "componentType": 5126,
+                "count": 2,
+                "type": "VEC3",
+                "min": [0.0, 0.0, 0.0],
+                "max": [0.0, 0.0, 0.0]
+            }],
+            "meshes": [{"primitives": [{"attributes": {"POSITION": 0}}]}]
+        }"#;
+        let mut root = json::Root::from_str(json).unwrap();
+        let before = root.clone();
+
+        let result = compute_bounds(&mut root, &[buffer::Data(vec![0; 12])]);
+
+        assert!(matches!(
+            result,
+            Err(crate::Error::Transform(super::super::Error::View(0)))
+        ));
+        assert_eq!(root.to_string().unwrap(), before.to_string().unwrap());
+    }
+}
@@ -0,0 +1,495 @@
+use crate::buffer;
+use json::accessor::{ComponentType, GenericComponentType, Type};
+use json::validation::Checked;
+
+use super::{Error, ALIGNMENT};
+
+/// Reads a single component stored at the start of `bytes`.
+fn component(bytes: &[u8], component_type: ComponentType) -> f64 {
+    match component_type {
+        ComponentType::I8 => bytes[0] as i8 as f64,
+        ComponentType::U8 => bytes[0] as f64,
+        ComponentType::I16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+        ComponentType::U16 => u16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+        ComponentType::U32 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+        ComponentType::F32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+    }
+}
+
+/// Copies `count` elements of `width` components out of a buffer view,
+/// reporting data outside of the view or its buffer as an error of
+/// `accessor`.
+#[allow(clippy::too_many_arguments)]
+fn elements(
+    root: &json::Root,
+    buffers: &[buffer::Data],
+    accessor: usize,
+    view: usize,
+    offset: usize,
+    count: usize,
+    width: usize,
+    component_type: ComponentType,
+) -> Result<Vec<f64>, Error> {
+    let out_of_bounds = Error::Accessor(accessor);
+    let view = root.buffer_views.get(view).ok_or(out_of_bounds.clone())?;
+    let data = buffers
+        .get(view.buffer.value())
+        .ok_or(out_of_bounds.clone())?;
+    let size = component_type.size();
+    let stride = view.byte_stride.map(|s| s as usize).unwrap_or(size * width);
+    let view_begin = view.byte_offset.unwrap_or(0) as usize;
+    let view_end = view_begin
+        .checked_add(view.byte_length as usize)
+        .filter(|&end| end <= data.len())
+        .ok_or(out_of_bounds.clone())?;
+    let begin = view_begin
+        .checked_add(offset)
+        .ok_or(out_of_bounds.clone())?;
+    if count > 0 {
+        let end = (count - 1)
+            .checked_mul(stride)
+            .and_then(|last| last.checked_add(begin))
+            .and_then(|last| last.checked_add(size * width))
+            .filter(|&end| end <= view_end);
+        if end.is_none() {
+            return Err(out_of_bounds);
+        }
+    }
+
+    let mut values = Vec::with_capacity(count * width);
+    for i in 0..count {
+        for j in 0..width {
+            let at = begin + i * stride + j * size;
+            values.push(component(&data[at..at + size], component_type));
+        }
+    }
+    Ok(values)
+}
+
+/// Returns the component type and type of an accessor, or `Error::Format`
+/// with the given index if either is invalid.
+pub(crate) fn format(
+    accessor: &json::Accessor,
+    index: usize,
+) -> Result<(ComponentType, Type), Error> {
+    match (&accessor.component_type, &accessor.type_) {
+        (Checked::Valid(component_type), Checked::Valid(type_)) => Ok((component_type.0, *type_)),
+        _ => Err(Error::Format(index)),
+    }
+}
+
+/// Reads every component of an accessor as stored, with sparse substitution
+/// applied but without normalization.
+///
+/// The components of each element are consecutive in the returned vector.
+/// Returns an error if the accessor has an invalid format or its data lies
+/// outside of its buffer data.
+pub(crate) fn read(
+    root: &json::Root,
+    buffers: &[buffer::Data],
+    index: usize,
+) -> Result<Vec<f64>, Error> {
+    let accessor = root.accessors.get(index).ok_or(Error::Accessor(index))?;
+    let (component_type, type_) = format(accessor, index)?;
+    let width = type_.multiplicity();
+    let count = accessor.count as usize;
+    let mut values = match accessor.buffer_view {
+        Some(view) => elements(
+            root,
+            buffers,
+            index,
+            view.value(),
+            accessor.byte_offset as usize,
+            count,
+            width,
+            component_type,
+        )?,
+        None => vec![0.0; count * width],
+    };
+
+    if let Some(sparse) = accessor.sparse.as_ref() {
+        let index_type = match sparse.indices.component_type {
+            Checked::Valid(ref index_type) => index_type.0,
+            Checked::Invalid => return Err(Error::Format(index)),
+        };
+        let n = sparse.count as usize;
+        let indices = elements(
+            root,
+            buffers,
+            index,
+            sparse.indices.buffer_view.value(),
+            sparse.indices.byte_offset as usize,
+            n,
+            1,
+            index_type,
+        )?;
+        let substitutes = elements(
+            root,
+            buffers,
+            index,
+            sparse.values.buffer_view.value(),
+            sparse.values.byte_offset as usize,
+            n,
+            width,
+            component_type,
+        )?;
+        for (k, &i) in indices.iter().enumerate() {
+            let i = i as usize;
+            if i >= count {
+                return Err(Error::Accessor(index));
+            }
+            values[i * width..(i + 1) * width]
+                .copy_from_slice(&substitutes[k * width..(k + 1) * width]);
+        }
+    }
+
+    Ok(values)
+}
+
+/// Checks that every accessor of `root` has a valid format and data within its
+/// buffer data, and that every buffer view lies within its buffer data, so
+/// that a pass can fail before changing anything.
+pub(crate) fn check(root: &json::Root, buffers: &[buffer::Data]) -> Result<(), Error> {
+    for index in 0..root.buffer_views.len() {
+        view_bytes(root, buffers, index)?;
+    }
+    for index in 0..root.accessors.len() {
+        let accessor = &root.accessors[index];
+        let (component_type, type_) = format(accessor, index)?;
+        let width = type_.multiplicity();
+        if let Some(view) = accessor.buffer_view {
+            let view = root
+                .buffer_views
+                .get(view.value())
+                .ok_or(Error::Accessor(index))?;
+            let size = component_type.size();
+            let stride = view.byte_stride.map(|s| s as usize).unwrap_or(size * width);
+            let count = accessor.count as usize;
+            let end = match count {
+                0 => Some(accessor.byte_offset as usize),
+                _ => (count - 1)
+                    .checked_mul(stride)
+                    .and_then(|last| last.checked_add(accessor.byte_offset as usize))
+                    .and_then(|last| last.checked_add(size * width)),
+            };
+            if end
+                .filter(|&end| end <= view.byte_length as usize)
+                .is_none()
+            {
+                return Err(Error::Accessor(index));
+            }
+        }
+        if accessor.sparse.is_some() {
+            read(root, buffers, index)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the bytes of a buffer view, or `Error::View` if they lie outside
+/// of its buffer data.
+pub(crate) fn view_bytes<'a>(
+    root: &json::Root,
+    buffers: &'a [buffer::Data],
+    index: usize,
+) -> Result<&'a [u8], Error> {
+    let view = root.buffer_views.get(index).ok_or(Error::View(index))?;
+    let data = buffers.get(view.buffer.value()).ok_or(Error::View(index))?;
+    let begin = view.byte_offset.unwrap_or(0) as usize;
+    begin
+        .checked_add(view.byte_length as usize)
+        .and_then(|end| data.get(begin..end))
+        .ok_or(Error::View(index))
+}
+
+/// Converts a stored component to its normalized floating point value.
+pub(crate) fn normalize(value: f64, component_type: ComponentType) -> f64 {
+    match component_type {
+        ComponentType::I8 => (value / 127.0).max(-1.0),
+        ComponentType::U8 => value / 255.0,
+        ComponentType::I16 => (value / 32767.0).max(-1.0),
+        ComponentType::U16 => value / 65535.0,
+        ComponentType::U32 | ComponentType::F32 => value,
+    }
+}
+
+/// Converts a normalized floating point value to a stored component, the
+/// inverse of `normalize`.
+#[cfg(feature = "draco_decoder")]
+pub(crate) fn denormalize(value: f64, component_type: ComponentType) -> f64 {
+    match component_type {
+        ComponentType::I8 => (value.clamp(-1.0, 1.0) * 127.0).round(),
+        ComponentType::U8 => (value.clamp(0.0, 1.0) * 255.0).round(),
+        ComponentType::I16 => (value.clamp(-1.0, 1.0) * 32767.0).round(),
+        ComponentType::U16 => (value.clamp(0.0, 1.0) * 65535.0).round(),
+        ComponentType::U32 | ComponentType::F32 => value,
+    }
+}
+
+/// Reads every component of an accessor, normalizing integer components if
+/// the accessor is marked as normalized.
+pub(crate) fn read_normalized(
+    root: &json::Root,
+    buffers: &[buffer::Data],
+    index: usize,
+) -> Result<Vec<f64>, Error> {
+    let mut values = read(root, buffers, index)?;
+    let accessor = &root.accessors[index];
+    if accessor.normalized {
+        let (component_type, _) = format(accessor, index)?;
+        for value in &mut values {
+            *value = normalize(*value, component_type);
+        }
+    }
+    Ok(values)
+}
+
+/// Writes a single component to the end of `data`.
+fn put(data: &mut Vec<u8>, value: f64, component_type: ComponentType) {
+    match component_type {
+        ComponentType::I8 => data.push(value as i8 as u8),
+        ComponentType::U8 => data.push(value as u8),
+        ComponentType::I16 => data.extend_from_slice(&(value as i16).to_le_bytes()),
+        ComponentType::U16 => data.extend_from_slice(&(value as u16).to_le_bytes()),
+        ComponentType::U32 => data.extend_from_slice(&(value as u32).to_le_bytes()),
+        ComponentType::F32 => data.extend_from_slice(&(value as f32).to_le_bytes()),
+    }
+}
+
+/// Pads `data` with zeros up to the next multiple of `ALIGNMENT`.
+fn pad(data: &mut Vec<u8>) {
+    let remainder = data.len() % ALIGNMENT;
+    if remainder != 0 {
+        data.resize(data.len() + ALIGNMENT - remainder, 0);
+    }
+}
+
+/// The format and stored components of an accessor to be written.
+pub(crate) struct Column {
+    /// The component type of the accessor.
+    pub(crate) component_type: ComponentType,
+
+    /// The type of the accessor.
+    pub(crate) type_: Type,
+
+    /// Whether integer components are normalized.
+    pub(crate) normalized: bool,
+
+    /// The stored (not normalized) components.
+    pub(crate) values: Vec<f64>,
+}
+
+impl Column {
+    /// Reads the format and stored components of an existing accessor.
+    pub(crate) fn read(
+        root: &json::Root,
+        buffers: &[buffer::Data],
+        index: usize,
+    ) -> Result<Self, Error> {
+        let values = read(root, buffers, index)?;
+        let accessor = &root.accessors[index];
+        let (component_type, type_) = format(accessor, index)?;
+        Ok(Self {
+            component_type,
+            type_,
+            normalized: accessor.normalized,
+            values,
+        })
+    }
+
+    /// Returns the size in bytes of an element.
+    pub(crate) fn size(&self) -> usize {
+        self.component_type.size() * self.type_.multiplicity()
+    }
+
+    /// Returns the size in bytes of an element, padded to `ALIGNMENT`.
+    pub(crate) fn padded_size(&self) -> usize {
+        self.size().div_ceil(ALIGNMENT) * ALIGNMENT
+    }
+
+    /// Returns the number of elements.
+    pub(crate) fn count(&self) -> usize {
+        self.values.len() / self.type_.multiplicity()
+    }
+}
+
+/// Collects the data of new accessors into a new buffer.
+pub(crate) struct Appender {
+    /// Index of the buffer being written.
+    buffer: u32,
+
+    /// The buffer data written so far.
+    data: Vec<u8>,
+}
+
+impl Appender {
+    /// Starts a new buffer after the existing buffers of `root`.
+    pub(crate) fn new(root: &json::Root) -> Self {
+        Self {
+            buffer: root.buffers.len() as u32,
+            data: Vec::new(),
+        }
+    }
+
+    /// Writes tightly packed accessor data into a new buffer view and adds
+    /// an accessor for it, returning the index of the new accessor.
+    ///
+    /// `values` holds the stored (not normalized) components.
+    pub(crate) fn push(
+        &mut self,
+        root: &mut json::Root,
+        component_type: ComponentType,
+        type_: Type,
+        normalized: bool,
+        values: &[f64],
+    ) -> json::Index<json::Accessor> {
+        let view = self.push_view(root, component_type, values);
+        root.accessors.push(json::Accessor {
+            buffer_view: Some(view),
+            byte_offset: 0,
+            count: (values.len() / type_.multiplicity()) as u32,
+            component_type: Checked::Valid(GenericComponentType(component_type)),
+            extensions: Default::default(),
+            extras: Default::default(),
+            type_: Checked::Valid(type_),
+            min: None,
+            max: None,
+            #[cfg(feature = "names")]
+            name: None,
+            normalized,
+            sparse: None,
+        });
+        json::Index::new(root.accessors.len() as u32 - 1)
+    }
+
+    /// Writes tightly packed components into a new buffer view without an
+    /// accessor, returning the index of the new buffer view.
+    pub(crate) fn push_view(
+        &mut self,
+        root: &mut json::Root,
+        component_type: ComponentType,
+        values: &[f64],
+    ) -> json::Index<json::buffer::View> {
+        pad(&mut self.data);
+        let begin = self.data.len();
+        for &value in values {
+            put(&mut self.data, value, component_type);
+        }
+
+        root.buffer_views.push(json::buffer::View {
+            buffer: json::Index::new(self.buffer),
+            byte_length: (self.data.len() - begin) as u32,
+            byte_offset: Some(begin as u32),
+            byte_stride: None,
+            #[cfg(feature = "names")]
+            name: None,
+            target: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        });
+        json::Index::new(root.buffer_views.len() as u32 - 1)
+    }
+
+    /// Writes the data of a single vertex attribute into a new buffer view and
+    /// adds an accessor for it, returning the index of the new accessor.
+    ///
+    /// Elements are tightly packed unless their size is not a multiple of four
+    /// bytes, in which case they are padded and given a byte stride.
+    pub(crate) fn push_column(
+        &mut self,
+        root: &mut json::Root,
+        column: Column,
+    ) -> json::Index<json::Accessor> {
+        if column.size() == column.padded_size() {
+            self.push(
+                root,
+                column.component_type,
+                column.type_,
+                column.normalized,
+                &column.values,
+            )
+        } else {
+            self.push_interleaved(root, &[column])[0]
+        }
+    }
+
+    /// Writes the data of several accessors with the same element count into a
+    /// single buffer view with one element of each per vertex, and adds an
+    /// accessor for each, returning the indices of the new accessors.
+    ///
+    /// Each element starts on a four byte boundary within the vertex.
+    pub(crate) fn push_interleaved(
+        &mut self,
+        root: &mut json::Root,
+        columns: &[Column],
+    ) -> Vec<json::Index<json::Accessor>> {
+        let count = columns.first().map_or(0, Column::count);
+        let stride: usize = columns.iter().map(Column::padded_size).sum();
+
+        pad(&mut self.data);
+        let begin = self.data.len();
+        for i in 0..count {
+            for column in columns {
+                let width = column.type_.multiplicity();
+                for &value in &column.values[i * width..(i + 1) * width] {
+                    put(&mut self.data, value, column.component_type);
+                }
+                pad(&mut self.data);
+            }
+        }
+
+        root.buffer_views.push(json::buffer::View {
+            buffer: json::Index::new(self.buffer),
+            byte_length: (self.data.len() - begin) as u32,
+            byte_offset: Some(begin as u32),
+            byte_stride: Some(stride as u32),
+            #[cfg(feature = "names")]
+            name: None,
+            target: Some(Checked::Valid(json::buffer::Target::ArrayBuffer)),
+            extensions: Default::default(),
+            extras: Default::default(),
+        });
+        let view = json::Index::new(root.buffer_views.len() as u32 - 1);
+
+        let mut offset = 0;
+        let mut accessors = Vec::with_capacity(columns.len());
+        for column in columns {
+            root.accessors.push(json::Accessor {
+                buffer_view: Some(view),
+                byte_offset: offset as u32,
+                count: count as u32,
+                component_type: Checked::Valid(GenericComponentType(column.component_type)),
+                extensions: Default::default(),
+                extras: Default::default(),
+                type_: Checked::Valid(column.type_),
+                min: None,
+                max: None,
+                #[cfg(feature = "names")]
+                name: None,
+                normalized: column.normalized,
+                sparse: None,
+            });
+            accessors.push(json::Index::new(root.accessors.len() as u32 - 1));
+            offset += column.padded_size();
+        }
+        accessors
+    }
+
+    /// Adds the new buffer to `root` and its data to `buffers`, if anything
+    /// was written.
+    pub(crate) fn finish(mut self, root: &mut json::Root, buffers: &mut Vec<buffer::Data>) {
+        if self.data.is_empty() {
+            return;
+        }
+        pad(&mut self.data);
+        root.buffers.push(json::Buffer {
+            byte_length: self.data.len() as u32,
+            #[cfg(feature = "names")]
+            name: None,
+            uri: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        });
+        buffers.push(buffer::Data(self.data));
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::{btree_map, BTreeMap};
+
+use byteorder::{ByteOrder, LE};
+use draco_oxide_core::attribute::{Attribute, ComponentDataType};
+use draco_oxide_core::mesh::Mesh;
+use draco_oxide_core::types::PointIdx;
+use json::accessor::{ComponentType, Type};
+
+use crate::{buffer, Result};
+
+use super::data::{self, Appender, Column};
+use super::Error;
+
+/// The name of the extension.
+const EXTENSION: &str = "KHR_draco_mesh_compression";
+
+/// Decodes every primitive compressed with `KHR_draco_mesh_compression`, so
+/// that its attributes and indices can be read like those of any other
+/// primitive.
+///
+/// The decoded vertex attributes and indices are written to a new buffer,
+/// whose data is appended to `buffers`, and the accessors of each primitive
+/// are given buffer views of it, keeping their bounds. The extension is then
+/// removed from the primitives and from the used and required extensions of
+/// the document. The compressed data is left in place; run `prune` afterwards
+/// to remove it.
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let (document, mut buffers, _) = gltf::import("examples/Box.gltf")?;
+/// let mut root = document.into_json();
+/// gltf::transform::decode_draco(&mut root, &mut buffers)?;
+/// let document = gltf::Document::from_json(root)?;
+/// for mesh in document.meshes() {
+///     for primitive in mesh.primitives() {
+///         let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+///         assert!(reader.read_positions().is_some());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+///
+/// Returns `Error::Transform` without changing anything if an accessor has
+/// an invalid format or data outside of its buffer data, or if the compressed
+/// data of a primitive cannot be decoded or does not match its accessors.
+pub fn decode_draco(root: &mut json::Root, buffers: &mut Vec<buffer::Data>) -> Result<()> {
+    data::check(root, buffers)?;
+    let mut meshes = BTreeMap::new();
+    let mut columns = BTreeMap::new();
+    for primitive in root.meshes.iter().flat_map(|mesh| &mesh.primitives) {
+        let extension = match primitive
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.khr_draco_mesh_compression.as_ref())
+        {
+            Some(extension) => extension,
+            None => continue,
+        };
+        let view = extension.buffer_view.value();
+        let mesh: &Mesh = match meshes.entry(view) {
+            btree_map::Entry::Occupied(entry) => entry.into_mut(),
+            btree_map::Entry::Vacant(entry) => {
+                let bytes = data::view_bytes(root, buffers, view)?;
+                let mesh =
+                    draco_oxide_decoder::decode_mesh(bytes).map_err(|_| Error::Draco(view))?;
+                entry.insert(mesh)
+            }
+        };
+
+        for (semantic, &id) in &extension.attributes {
+            let index = match primitive.attributes.get(semantic) {
+                Some(index) => index.value(),
+                None => continue,
+            };
+            let attribute = mesh
+                .get_attributes()
+                .iter()
+                .find(|attribute| attribute.get_id().as_usize() == id as usize)
+                .ok_or(Error::Draco(view))?;
+            let column = attribute_column(root, index, attribute)?.ok_or(Error::Draco(view))?;
+            columns.insert(index, column);
+        }
+        if let Some(index) = primitive.indices {
+            let index = index.value();
+            let column = index_column(root, index, mesh)?.ok_or(Error::Draco(view))?;
+            columns.insert(index, column);
+        }
+    }
+
+    let mut appender = Appender::new(root);
+    for (index, column) in columns {
+        // Move the buffer view of the new accessor to the decoded one, so that
+        // references to the decoded accessor stay valid.
+        appender.push_column(root, column);
+        let decoded = root.accessors.pop().unwrap();
+        let accessor = &mut root.accessors[index];
+        accessor.buffer_view = decoded.buffer_view;
+        accessor.byte_offset = decoded.byte_offset;
+    }
+    appender.finish(root, buffers);
+
+    for primitive in root.meshes.iter_mut().flat_map(|mesh| &mut mesh.primitives) {
+        if let Some(extensions) = primitive.extensions.as_mut() {
+            extensions.khr_draco_mesh_compression = None;
+        }
+    }
+    root.extensions_used.retain(|name| name != EXTENSION);
+    root.extensions_required.retain(|name| name != EXTENSION);
+    Ok(())
+}
+
+/// Reads a component of a decoded attribute.
+fn component(bytes: &[u8], component_type: ComponentDataType) -> Option<f64> {
+    Some(match component_type {
+        ComponentDataType::I8 => bytes[0] as i8 as f64,
+        ComponentDataType::U8 => bytes[0] as f64,
+        ComponentDataType::I16 => LE::read_i16(bytes) as f64,
+        ComponentDataType::U16 => LE::read_u16(bytes) as f64,
+        ComponentDataType::I32 => LE::read_i32(bytes) as f64,
+        ComponentDataType::U32 => LE::read_u32(bytes) as f64,
+        ComponentDataType::I64 => LE::read_i64(bytes) as f64,
+        ComponentDataType::U64 => LE::read_u64(bytes) as f64,
+        ComponentDataType::F32 => LE::read_f32(bytes) as f64,
+        ComponentDataType::F64 => LE::read_f64(bytes),
+        ComponentDataType::Invalid => return None,
+    })
+}
+
+/// Returns the decoded values of an attribute in the format of its accessor,
+/// or `None` if their count or number of components does not match it.
+fn attribute_column(
+    root: &json::Root,
+    index: usize,
+    attribute: &Attribute,
+) -> Result<Option<Column>> {
+    let accessor = &root.accessors[index];
+    let (component_type, type_) = data::format(accessor, index)?;
+    let width = type_.multiplicity();
+    let source = attribute.get_component_type();
+    let size = source.size();
+    if attribute.len() != accessor.count as usize
+        || attribute.get_num_components() != width
+        || size == 0
+    {
+        return Ok(None);
+    }
+
+    let bytes = attribute.get_data_as_bytes();
+    let mut values = Vec::with_capacity(attribute.len() * width);
+    for point in 0..attribute.len() {
+        let value = usize::from(attribute.get_unique_val_idx(PointIdx::from(point)));
+        let element = match bytes.get(value * width * size..(value + 1) * width * size) {
+            Some(element) => element,
+            None => return Ok(None),
+        };
+        for bytes in element.chunks_exact(size) {
+            let value = match component(bytes, source) {
+                Some(value) => value,
+                None => return Ok(None),
+            };
+            values.push(match accessor.normalized && source.is_float() {
+                true => data::denormalize(value, component_type),
+                false => value,
+            });
+        }
+    }
+    Ok(Some(Column {
+        component_type,
+        type_,
+        normalized: accessor.normalized,
+        values,
+    }))
+}
+
+/// Returns the decoded triangles of a mesh in the format of its index
+/// accessor, or `None` if their count does not match it.
+fn index_column(root: &json::Root, index: usize, mesh: &Mesh) -> Result<Option<Column>> {
+    let accessor = &root.accessors[index];
+    let (component_type, type_) = data::format(accessor, index)?;
+    let faces = mesh.get_faces();
+    if type_ != Type::Scalar
+        || component_type == ComponentType::F32
+        || faces.len() * 3 != accessor.count as usize
+    {
+        return Ok(None);
+    }
+    Ok(Some(Column {
+        component_type,
+        type_,
+        normalized: false,
+        values: faces
+            .iter()
+            .flatten()
+            .map(|&point| usize::from(point) as f64)
+            .collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Document;
+
+    /// A unit quad facing +Z made of two triangles, compressed with Draco,
+    /// with texture coordinates that put the top of the image at +Y.
+    fn quad() -> (json::Root, Vec<buffer::Data>) {
+        let bytes = include_bytes!("../../tests/draco_quad.drc").to_vec();
+        let json = format!(
+            r#"{{
+                "asset": {{"version": "2.0"}},
+                "extensionsUsed": ["KHR_draco_mesh_compression"],
+                "extensionsRequired": ["KHR_draco_mesh_compression"],
+                "buffers": [{{"byteLength": {0}}}],
+                "bufferViews": [{{"buffer": 0, "byteLength": {0}}}],
+                "accessors": [
+                    {{
+                        "componentType": 5126,
+                        "count": 4,
+                        "type": "VEC3",
+                        "min": [0, 0, 0],
+                        "max": [1, 1, 0]
+                    }},
+                    {{"componentType": 5123, "normalized": true, "count": 4, "type": "VEC2"}},
+                    {{"componentType": 5121, "count": 6, "type": "SCALAR"}}
+                ],
+                "meshes": [{{
+                    "primitives": [{{
+                        "attributes": {{"POSITION": 0, "TEXCOORD_0": 1}},
+                        "indices": 2,
+                        "extensions": {{
+                            "KHR_draco_mesh_compression": {{
+                                "bufferView": 0,
+                                "attributes": {{"POSITION": 0, "TEXCOORD_0": 1}}
+                            }}
+                        }}
+                    }}]
+                }}]
+            }}"#,
+            bytes.len()
+        );
+        let root = json::deserialize::from_str(&json).unwrap();
+        (root, vec![buffer::Data(bytes)])
+    }
+
+    #[test]
+    fn decodes_attributes_and_indices() {
+        let (mut root, mut buffers) = quad();
+        decode_draco(&mut root, &mut buffers).unwrap();
+        assert!(root.extensions_used.is_empty());
+        assert!(root.extensions_required.is_empty());
+        assert_eq!(root.buffers.len(), 2);
+        assert_eq!(root.accessors.len(), 3);
+        assert_eq!(
+            root.accessors[0].max,
+            Some(json::Value::from(vec![1, 1, 0]))
+        );
+
+        let document = Document::from_json(root).unwrap();
+        let primitive = document
+            .meshes()
+            .next()
+            .unwrap()
+            .primitives()
+            .next()
+            .unwrap();
+        assert!(primitive.draco_mesh_compression().is_none());
+        let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()][..]));
+        let positions: Vec<[f32; 3]> = reader.read_positions().unwrap().collect();
+        let mut corners = positions.clone();
+        corners.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(
+            corners,
+            [
+                [0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0]
+            ]
+        );
+        let tex_coords: Vec<[f32; 2]> = reader.read_tex_coords(0).unwrap().into_f32().collect();
+        for ([x, y, _], [u, v]) in positions.iter().zip(&tex_coords) {
+            assert_eq!([*u, *v], [*x, 1.0 - *y]);
+        }
+        let indices: Vec<u32> = reader.read_indices().unwrap().into_u32().collect();
+        assert_eq!(indices.len(), 6);
+        assert!(indices.iter().all(|&index| index < 4));
+    }
+
+    #[test]
+    fn invalid_data() {
+        let (mut root, mut buffers) = quad();
+        root.accessors[0].count = 3;
+        let before = json::serialize::to_string(&root).unwrap();
+        match decode_draco(&mut root, &mut buffers) {
+            Err(crate::Error::Transform(Error::Draco(0))) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(json::serialize::to_string(&root).unwrap(), before);
+        assert_eq!(buffers.len(), 1);
+
+        buffers[0].0[0] = b'X';
+        assert!(decode_draco(&mut root, &mut buffers).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+use std::fmt;
+
+/// Represents data that a transform pass cannot read.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The accessor of the given index has an invalid component type or type.
+    Format(usize),
+
+    /// The data of the accessor of the given index, including any sparse
+    /// substitutes, lies outside of its buffer view or buffer data.
+    Accessor(usize),
+
+    /// The buffer view of the given index lies outside of its buffer data.
+    View(usize),
+
+    /// The buffer view of the given index does not hold Draco compressed
+    /// geometry matching the accessors of its primitive.
+    Draco(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Format(index) => write!(f, "accessor {} has an invalid format", index),
+            Error::Accessor(index) => {
+                write!(f, "accessor {} lies outside of its buffer data", index)
+            }
+            Error::View(index) => {
+                write!(f, "buffer view {} lies outside of its buffer data", index)
+            }
+            Error::Draco(index) => write!(
+                f,
+                "buffer view {} does not hold Draco geometry matching its accessors",
+                index
+            ),
+        }
+    }
+}
+
+impl ::std::error::Error for Error {}
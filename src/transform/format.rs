@@ -0,0 +1,400 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{buffer, Result};
+use json::accessor::{ComponentType, Type};
+use json::mesh::Semantic;
+use json::validation::Checked;
+
+use super::data::{self, Appender, Column};
+use super::is_compressed;
+
+/// The extension allowing vertex attributes of integer component types.
+const MESH_QUANTIZATION: &str = "KHR_mesh_quantization";
+
+/// The vertex attributes converted by `convert_vertex_formats`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VertexFormats {
+    /// Store `POSITION` as normalized unsigned shorts relative to the bounds
+    /// of each mesh, and instance the mesh through a new child node whose
+    /// translation and uniform scale restore the original positions.
+    pub positions: bool,
+
+    /// Store `NORMAL` and `TANGENT` as normalized signed shorts.
+    pub normals: bool,
+
+    /// Store `TEXCOORD_n` as normalized unsigned shorts, where all of the
+    /// co-ordinates lie in the range [0, 1].
+    pub tex_coords: bool,
+}
+
+/// Returns the JSON `min` and `max` of integer components.
+fn bounds(values: &[f64], width: usize) -> (json::Value, json::Value) {
+    let mut min = values[..width].to_vec();
+    let mut max = min.clone();
+    for element in values.chunks(width) {
+        for (j, &value) in element.iter().enumerate() {
+            min[j] = min[j].min(value);
+            max[j] = max[j].max(value);
+        }
+    }
+    let number = |values: Vec<f64>| {
+        json::Value::from(
+            values
+                .into_iter()
+                .map(|value| value as i64)
+                .collect::<Vec<_>>(),
+        )
+    };
+    (number(min), number(max))
+}
+
+/// Writes integer components as a new normalized accessor, returning its
+/// index.
+fn push(
+    appender: &mut Appender,
+    root: &mut json::Root,
+    component_type: ComponentType,
+    type_: Type,
+    values: Vec<f64>,
+) -> json::Index<json::Accessor> {
+    let (min, max) = if values.is_empty() {
+        (None, None)
+    } else {
+        let (min, max) = bounds(&values, type_.multiplicity());
+        (Some(min), Some(max))
+    };
+    let index = appender.push_column(
+        root,
+        Column {
+            component_type,
+            type_,
+            normalized: true,
+            values,
+        },
+    );
+    let accessor = &mut root.accessors[index.value()];
+    accessor.min = min;
+    accessor.max = max;
+    index
+}
+
+/// Returns `true` if an accessor stores floating point components.
+fn is_float(root: &json::Root, index: usize) -> bool {
+    data::format(&root.accessors[index], index)
+        .is_ok_and(|(component_type, _)| component_type == ComponentType::F32)
+}
+
+/// Converts vertex attributes from floating point to 16-bit normalized
+/// integer storage, roughly halving their size.
+///
+/// This is a lighter-weight alternative to full quantization that keeps the
+/// attribute layout of every primitive. Normals, tangents, and texture
+/// co-ordinates are stored as normalized integers rather than as half floats,
+/// since core glTF 2.0 has no 16-bit floating point component type while
+/// `KHR_mesh_quantization` allows normalized shorts. Only attributes stored as floating
+/// point are converted, each into a new accessor in a new buffer whose data is
+/// appended to `buffers`; the replaced accessors are left in place, so run
+/// `prune` afterwards to remove them. `KHR_mesh_quantization` is added to the
+/// used and required extensions when anything is converted.
+///
+/// Positions are only converted for meshes that are instanced by at least one
+/// node, are not skinned, and have no morph targets. The nodes instancing such
+/// a mesh instance it through a new child node instead.
+///
+/// The `Reader` of this crate converts the quantized positions, normals, and
+/// tangents back to floating point.
+///
+/// Returns `Error::Transform` without changing anything if an accessor has
+/// an invalid format or data outside of its buffer data.
+pub fn convert_vertex_formats(
+    root: &mut json::Root,
+    buffers: &mut Vec<buffer::Data>,
+    formats: VertexFormats,
+) -> Result<()> {
+    data::check(root, buffers)?;
+    let mut appender = Appender::new(root);
+    let mut changed = false;
+
+    let mut instanced = BTreeSet::new();
+    let mut skinned = BTreeSet::new();
+    for node in &root.nodes {
+        if let Some(mesh) = node.mesh {
+            instanced.insert(mesh.value());
+            if node.skin.is_some() {
+                skinned.insert(mesh.value());
+            }
+        }
+    }
+
+    // The translation and uniform scale restoring the positions of each mesh.
+    let mut dequantize: HashMap<usize, ([f32; 3], f32)> = HashMap::new();
+    for m in 0..root.meshes.len() {
+        let primitives = &root.meshes[m].primitives;
+        let positions: Vec<usize> = primitives
+            .iter()
+            .filter_map(|primitive| {
+                primitive
+                    .attributes
+                    .get(&Checked::Valid(Semantic::Positions))
+                    .map(|index| index.value())
+            })
+            .collect();
+        if !formats.positions
+            || !instanced.contains(&m)
+            || skinned.contains(&m)
+            || positions.is_empty()
+            || primitives
+                .iter()
+                .any(|primitive| primitive.targets.is_some() || is_compressed(primitive))
+            || positions.iter().any(|&index| !is_float(root, index))
+        {
+            continue;
+        }
+
+        let values = positions
+            .iter()
+            .map(|&index| Ok((index, data::read(root, buffers, index)?)))
+            .collect::<Result<HashMap<usize, Vec<f64>>>>()?;
+        let mut min = [f64::INFINITY; 3];
+        let mut max = [f64::NEG_INFINITY; 3];
+        for element in values.values().flat_map(|values| values.chunks(3)) {
+            for j in 0..3 {
+                min[j] = min[j].min(element[j]);
+                max[j] = max[j].max(element[j]);
+            }
+        }
+        if !min.iter().all(|value| value.is_finite()) {
+            continue;
+        }
+        let extent = (0..3).map(|j| max[j] - min[j]).fold(0.0, f64::max);
+        let scale = if extent > 0.0 { extent } else { 1.0 };
+
+        let mut converted = HashMap::new();
+        for p in 0..root.meshes[m].primitives.len() {
+            let semantic = Checked::Valid(Semantic::Positions);
+            let source = match root.meshes[m].primitives[p].attributes.get(&semantic) {
+                Some(index) => index.value(),
+                None => continue,
+            };
+            let accessor = *converted.entry(source).or_insert_with(|| {
+                let quantized = values[&source]
+                    .chunks(3)
+                    .flat_map(|element| {
+                        (0..3).map(move |j| ((element[j] - min[j]) / scale * 65535.0).round())
+                    })
+                    .collect();
+                push(
+                    &mut appender,
+                    root,
+                    ComponentType::U16,
+                    Type::Vec3,
+                    quantized,
+                )
+            });
+            root.meshes[m].primitives[p]
+                .attributes
+                .insert(semantic, accessor);
+        }
+        let offset = [min[0] as f32, min[1] as f32, min[2] as f32];
+        dequantize.insert(m, (offset, scale as f32));
+        changed = true;
+    }
+
+    let mut converted: HashMap<usize, Option<json::Index<json::Accessor>>> = HashMap::new();
+    for m in 0..root.meshes.len() {
+        for p in 0..root.meshes[m].primitives.len() {
+            if is_compressed(&root.meshes[m].primitives[p]) {
+                continue;
+            }
+            let attributes: Vec<_> = root.meshes[m].primitives[p]
+                .attributes
+                .iter()
+                .map(|(semantic, index)| (semantic.clone(), index.value()))
+                .collect();
+            for (semantic, source) in attributes {
+                let component_type = match semantic {
+                    Checked::Valid(Semantic::Normals) | Checked::Valid(Semantic::Tangents)
+                        if formats.normals =>
+                    {
+                        ComponentType::I16
+                    }
+                    Checked::Valid(Semantic::TexCoords(_)) if formats.tex_coords => {
+                        ComponentType::U16
+                    }
+                    _ => continue,
+                };
+                let accessor = match converted.get(&source) {
+                    Some(&accessor) => accessor,
+                    None => {
+                        let accessor = if is_float(root, source) {
+                            let values = data::read(root, buffers, source)?;
+                            let (_, type_) = data::format(&root.accessors[source], source)?;
+                            quantize(&mut appender, root, component_type, type_, values)
+                        } else {
+                            None
+                        };
+                        converted.insert(source, accessor);
+                        accessor
+                    }
+                };
+                if let Some(accessor) = accessor {
+                    root.meshes[m].primitives[p]
+                        .attributes
+                        .insert(semantic, accessor);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    for n in 0..root.nodes.len() {
+        let mesh = match root.nodes[n].mesh {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        let (translation, scale) = match dequantize.get(&mesh.value()) {
+            Some(&transform) => transform,
+            None => continue,
+        };
+        let weights = root.nodes[n].weights.take();
+        root.nodes.push(json::Node {
+            camera: None,
+            children: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+            matrix: None,
+            mesh: Some(mesh),
+            #[cfg(feature = "names")]
+            name: None,
+            rotation: None,
+            scale: Some([scale; 3]),
+            translation: Some(translation),
+            skin: None,
+            weights,
+        });
+        let child = json::Index::new(root.nodes.len() as u32 - 1);
+        let node = &mut root.nodes[n];
+        node.mesh = None;
+        node.children.get_or_insert_with(Vec::new).push(child);
+    }
+
+    if changed {
+        for extensions in [&mut root.extensions_used, &mut root.extensions_required] {
+            if !extensions.iter().any(|name| name == MESH_QUANTIZATION) {
+                extensions.push(MESH_QUANTIZATION.to_string());
+            }
+        }
+    }
+    appender.finish(root, buffers);
+    Ok(())
+}
+
+/// Writes floating point normals, tangents, or texture co-ordinates as a new
+/// normalized accessor of `component_type`, returning its index, or `None` if
+/// unsigned storage cannot hold the values.
+fn quantize(
+    appender: &mut Appender,
+    root: &mut json::Root,
+    component_type: ComponentType,
+    type_: Type,
+    values: Vec<f64>,
+) -> Option<json::Index<json::Accessor>> {
+    let signed = component_type == ComponentType::I16;
+    if !signed && values.iter().any(|value| !(0.0..=1.0).contains(value)) {
+        return None;
+    }
+    let quantized = values
+        .into_iter()
+        .map(|value| match signed {
+            true => (value.clamp(-1.0, 1.0) * 32767.0).round(),
+            false => (value * 65535.0).round(),
+        })
+        .collect();
+    Some(push(appender, root, component_type, type_, quantized))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_normalized_shorts() {
+        let mut bytes = Vec::new();
+        for value in &[
+            // Positions.
+            1.0f32, 1.0, 1.0, 3.0, 1.0, 1.0, 1.0, 2.0, 1.0, // Normals.
+            0.0, 0.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, // Texture co-ordinates.
+            0.0, 0.0, 1.0, 0.0, 0.0, 0.5,
+        ] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 96}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 36},
+                {"buffer": 0, "byteOffset": 36, "byteLength": 36},
+                {"buffer": 0, "byteOffset": 72, "byteLength": 24}
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0,
+                    "componentType": 5126,
+                    "count": 3,
+                    "type": "VEC3",
+                    "min": [1.0, 1.0, 1.0],
+                    "max": [3.0, 2.0, 1.0]
+                },
+                {"bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC3"},
+                {"bufferView": 2, "componentType": 5126, "count": 3, "type": "VEC2"}
+            ],
+            "meshes": [{"primitives": [{
+                "attributes": {"POSITION": 0, "NORMAL": 1, "TEXCOORD_0": 2}
+            }]}],
+            "nodes": [{"mesh": 0, "translation": [0.0, 5.0, 0.0]}],
+            "scenes": [{"nodes": [0]}]
+        }"#;
+        let mut root = json::Root::from_str(json).unwrap();
+        let mut buffers = vec![buffer::Data(bytes)];
+        let formats = VertexFormats {
+            positions: true,
+            normals: true,
+            tex_coords: true,
+        };
+
+        convert_vertex_formats(&mut root, &mut buffers, formats).unwrap();
+
+        let attributes = &root.meshes[0].primitives[0].attributes;
+        let positions = attributes[&Checked::Valid(Semantic::Positions)].value();
+        let normals = attributes[&Checked::Valid(Semantic::Normals)].value();
+        let tex_coords = attributes[&Checked::Valid(Semantic::TexCoords(0))].value();
+        assert_eq!(
+            data::read(&root, &buffers, positions).unwrap(),
+            vec![0.0, 0.0, 0.0, 65535.0, 0.0, 0.0, 0.0, 32768.0, 0.0]
+        );
+        assert_eq!(
+            data::read(&root, &buffers, normals).unwrap(),
+            vec![0.0, 0.0, 32767.0, 0.0, 0.0, -32767.0, 32767.0, 0.0, 0.0]
+        );
+        assert_eq!(
+            data::read(&root, &buffers, tex_coords).unwrap(),
+            vec![0.0, 0.0, 65535.0, 0.0, 0.0, 32768.0]
+        );
+        assert!(root.accessors[positions].normalized);
+        assert_eq!(
+            root.accessors[positions].max,
+            Some(json::Value::from(vec![65535, 32768, 0]))
+        );
+        let normal_view = root.accessors[normals].buffer_view.unwrap().value();
+        assert_eq!(root.buffer_views[normal_view].byte_stride, Some(8));
+
+        assert!(root.nodes[0].mesh.is_none());
+        assert_eq!(root.nodes[1].mesh.map(|mesh| mesh.value()), Some(0));
+        assert_eq!(root.nodes[1].translation, Some([1.0, 1.0, 1.0]));
+        assert_eq!(root.nodes[1].scale, Some([2.0; 3]));
+        assert_eq!(
+            root.extensions_required,
+            vec![MESH_QUANTIZATION.to_string()]
+        );
+    }
+}
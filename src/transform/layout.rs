@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use crate::{buffer, Result};
+
+use super::data::{self, Appender, Column};
+use super::{is_compressed, visit_indices, Kind};
+
+/// Interleaves the vertex attributes of every primitive into a single buffer
+/// view per primitive, with one element of each attribute per vertex.
+///
+/// The interleaved data is written to new accessors in a new buffer, whose
+/// data is appended to `buffers`. Primitives with the same attribute accessors
+/// share the new accessors. Primitives that have a single attribute, whose
+/// attributes are already interleaved, or whose vertex would exceed the
+/// maximum byte stride are left unchanged, as are morph targets.
+///
+/// The replaced accessors are left in place; run `prune` afterwards to remove
+/// them.
+///
+/// Returns `Error::Transform` without changing anything if an accessor has
+/// an invalid format or data outside of its buffer data.
+pub fn interleave(root: &mut json::Root, buffers: &mut Vec<buffer::Data>) -> Result<()> {
+    data::check(root, buffers)?;
+    let mut appender = Appender::new(root);
+    let mut replaced: HashMap<Vec<usize>, Vec<json::Index<json::Accessor>>> = HashMap::new();
+
+    for m in 0..root.meshes.len() {
+        for p in 0..root.meshes[m].primitives.len() {
+            let primitive = &root.meshes[m].primitives[p];
+            if is_compressed(primitive) {
+                continue;
+            }
+            let mut attributes: Vec<_> = primitive
+                .attributes
+                .iter()
+                .map(|(semantic, index)| (index.value(), semantic.clone()))
+                .collect();
+            attributes.sort_by_key(|&(index, _)| index);
+            let (sources, semantics): (Vec<usize>, Vec<_>) = attributes.into_iter().unzip();
+            if sources.len() < 2 || is_interleaved(root, &sources) {
+                continue;
+            }
+
+            let accessors = match replaced.get(&sources) {
+                Some(accessors) => accessors.clone(),
+                None => {
+                    let columns = sources
+                        .iter()
+                        .map(|&index| Ok(Column::read(root, buffers, index)?))
+                        .collect::<Result<Vec<Column>>>()?;
+                    let count = columns[0].count();
+                    let stride: usize = columns.iter().map(Column::padded_size).sum();
+                    if stride > json::buffer::MAX_BYTE_STRIDE as usize
+                        || columns.iter().any(|column| column.count() != count)
+                    {
+                        continue;
+                    }
+
+                    let accessors = appender.push_interleaved(root, &columns);
+                    for (&source, accessor) in sources.iter().zip(&accessors) {
+                        let source = root.accessors[source].clone();
+                        let accessor = &mut root.accessors[accessor.value()];
+                        accessor.min = source.min;
+                        accessor.max = source.max;
+                        #[cfg(feature = "names")]
+                        {
+                            accessor.name = source.name;
+                        }
+                    }
+                    replaced.insert(sources, accessors.clone());
+                    accessors
+                }
+            };
+
+            let attributes = &mut root.meshes[m].primitives[p].attributes;
+            for (semantic, accessor) in semantics.into_iter().zip(accessors) {
+                attributes.insert(semantic, accessor);
+            }
+        }
+    }
+
+    appender.finish(root, buffers);
+    Ok(())
+}
+
+/// Copies every accessor stored in a strided buffer view that it shares with
+/// others into a buffer view of its own, with its elements tightly packed.
+///
+/// This is the reverse of `interleave`, for consumers that expect one
+/// contiguous array per attribute. The copies are written to a new buffer,
+/// whose data is appended to `buffers`, and every reference to an original
+/// accessor is redirected to its copy. Elements whose size is not a multiple
+/// of four bytes are padded to keep vertex attributes aligned. Sparse
+/// accessors are copied with their substitutions applied.
+///
+/// The replaced accessors are left in place; run `prune` afterwards to remove
+/// them.
+///
+/// Returns `Error::Transform` without changing anything if an accessor has
+/// an invalid format or data outside of its buffer data.
+pub fn deinterleave(root: &mut json::Root, buffers: &mut Vec<buffer::Data>) -> Result<()> {
+    data::check(root, buffers)?;
+    let mut appender = Appender::new(root);
+    let mut replaced = vec![None; root.accessors.len()];
+
+    for (index, copy) in replaced.iter_mut().enumerate() {
+        let view = match root.accessors[index].buffer_view {
+            Some(view) => view.value(),
+            None => continue,
+        };
+        let column = Column::read(root, buffers, index)?;
+        match root.buffer_views[view].byte_stride {
+            Some(stride) if stride as usize > column.padded_size() => {}
+            _ => continue,
+        }
+
+        let accessor = appender.push_column(root, column);
+        let source = root.accessors[index].clone();
+        let target = &mut root.accessors[accessor.value()];
+        target.min = source.min;
+        target.max = source.max;
+        #[cfg(feature = "names")]
+        {
+            target.name = source.name;
+        }
+        *copy = Some(accessor.value() as u32);
+    }
+
+    visit_indices(root, &mut |kind, index| match kind {
+        Kind::Accessor => replaced
+            .get(index as usize)
+            .copied()
+            .flatten()
+            .unwrap_or(index),
+        _ => index,
+    });
+    appender.finish(root, buffers);
+    Ok(())
+}
+
+/// Returns `true` if the given accessors all belong to the same strided buffer
+/// view.
+fn is_interleaved(root: &json::Root, accessors: &[usize]) -> bool {
+    let views: Vec<_> = accessors
+        .iter()
+        .map(|&index| root.accessors[index].buffer_view.map(|view| view.value()))
+        .collect();
+    match views[0] {
+        Some(view) => {
+            root.buffer_views[view].byte_stride.is_some()
+                && views.iter().all(|&other| other == Some(view))
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::data;
+    use json::mesh::Semantic;
+    use json::validation::Checked;
+
+    #[test]
+    fn interleave_and_deinterleave() {
+        let mut bytes = Vec::new();
+        for value in &[0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(&[255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255]);
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 48}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 36},
+                {"buffer": 0, "byteOffset": 36, "byteLength": 12}
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0,
+                    "componentType": 5126,
+                    "count": 3,
+                    "type": "VEC3",
+                    "min": [0.0, 0.0, 0.0],
+                    "max": [1.0, 1.0, 0.0]
+                },
+                {
+                    "bufferView": 1,
+                    "componentType": 5121,
+                    "normalized": true,
+                    "count": 3,
+                    "type": "VEC4"
+                }
+            ],
+            "meshes": [
+                {"primitives": [{"attributes": {"POSITION": 0, "COLOR_0": 1}}]},
+                {"primitives": [{"attributes": {"POSITION": 0, "COLOR_0": 1}, "mode": 0}]}
+            ]
+        }"#;
+        let mut root = json::Root::from_str(json).unwrap();
+        let mut buffers = vec![buffer::Data(bytes)];
+
+        interleave(&mut root, &mut buffers).unwrap();
+
+        assert_eq!(root.accessors.len(), 4);
+        assert_eq!(root.buffer_views.len(), 3);
+        assert_eq!(root.buffer_views[2].byte_stride, Some(16));
+        assert_eq!(root.buffer_views[2].byte_length, 48);
+        let attributes = &root.meshes[0].primitives[0].attributes;
+        let positions = attributes[&Checked::Valid(Semantic::Positions)].value();
+        let colors = attributes[&Checked::Valid(Semantic::Colors(0))].value();
+        let shared = &root.meshes[1].primitives[0].attributes;
+        assert_eq!(
+            shared[&Checked::Valid(Semantic::Positions)].value(),
+            positions
+        );
+        assert_eq!(root.accessors[positions].byte_offset, 0);
+        assert_eq!(root.accessors[colors].byte_offset, 12);
+        assert!(root.accessors[colors].normalized);
+        assert_eq!(root.accessors[positions].max, root.accessors[0].max);
+        assert_eq!(
+            data::read(&root, &buffers, positions).unwrap(),
+            data::read(&root, &buffers, 0).unwrap()
+        );
+        assert_eq!(
+            data::read(&root, &buffers, colors).unwrap(),
+            data::read(&root, &buffers, 1).unwrap()
+        );
+
+        deinterleave(&mut root, &mut buffers).unwrap();
+
+        assert_eq!(root.accessors.len(), 6);
+        assert_eq!(root.buffers.len(), 3);
+        let attributes = &root.meshes[1].primitives[0].attributes;
+        let positions = attributes[&Checked::Valid(Semantic::Positions)].value();
+        let colors = attributes[&Checked::Valid(Semantic::Colors(0))].value();
+        assert!(positions >= 4 && colors >= 4);
+        for &index in &[positions, colors] {
+            let accessor = &root.accessors[index];
+            let view = &root.buffer_views[accessor.buffer_view.unwrap().value()];
+            let size = data::Column::read(&root, &buffers, index).unwrap().size();
+            assert_eq!(accessor.byte_offset, 0);
+            assert_eq!(view.byte_stride, None);
+            assert_eq!(view.byte_length as usize, accessor.count as usize * size);
+        }
+        assert_eq!(
+            data::read(&root, &buffers, positions).unwrap(),
+            data::read(&root, &buffers, 0).unwrap()
+        );
+        assert_eq!(
+            data::read(&root, &buffers, colors).unwrap(),
+            data::read(&root, &buffers, 1).unwrap()
+        );
+    }
+}
@@ -0,0 +1,498 @@
+use std::collections::HashMap;
+
+use crate::buffer;
+use crate::math::Matrix4;
+use crate::scene::Transform;
+use crate::visit::{self, KINDS};
+use json::animation::Property;
+use json::validation::Checked;
+
+use super::{canonical_forms, prune, remove, visit_indices, Kind, Remap, DEDUPLICABLE};
+
+/// Appends the objects of `other` to `root`, returning where the objects of
+/// `other` ended up.
+///
+/// Every index within `other` is offset past the objects of `root`, whose
+/// own objects keep their indices. Objects of `other` whose JSON is then
+/// identical to an object already in `root`, such as a shared sampler or
+/// material, are merged into it as by `dedup`. The buffers of `other` and
+/// their data are appended after those of `root`, so buffer views keep their
+/// offsets; follow with `pack_buffers` to concatenate them into a single
+/// buffer, as binary glTF requires.
+///
+/// The scenes of `other` are appended too, and become the default scene only
+/// when `root` has none. Extensions used or required by `other` are added to
+/// those of `root`, and the asset of `root` is kept.
+pub fn merge(
+    root: &mut json::Root,
+    buffers: &mut Vec<buffer::Data>,
+    mut other: json::Root,
+    other_buffers: Vec<buffer::Data>,
+) -> Remap {
+    let offsets: HashMap<Kind, u32> = KINDS
+        .iter()
+        .map(|&kind| (kind, visit::len(root, kind) as u32))
+        .collect();
+    let mut remap = Remap::default();
+    for &kind in KINDS {
+        let offset = offsets[&kind] as usize;
+        remap.record(
+            kind,
+            (0..visit::len(&other, kind))
+                .map(|index| Some(offset + index))
+                .collect(),
+        );
+    }
+    visit_indices(&mut other, &mut |kind, index| index + offsets[&kind]);
+
+    if root.scene.is_none() {
+        root.scene = other.scene.take();
+    }
+    for name in other.extensions_used.drain(..) {
+        if !root.extensions_used.contains(&name) {
+            root.extensions_used.push(name);
+        }
+    }
+    for name in other.extensions_required.drain(..) {
+        if !root.extensions_required.contains(&name) {
+            root.extensions_required.push(name);
+        }
+    }
+    visit::append(root, other);
+    buffers.extend(other_buffers);
+
+    for &kind in DEDUPLICABLE {
+        let offset = offsets[&kind] as usize;
+        let mut first = HashMap::new();
+        let canonical: Vec<u32> = canonical_forms(root, kind)
+            .into_iter()
+            .enumerate()
+            .map(|(index, form)| {
+                let canonical = *first.entry(form).or_insert(index as u32);
+                // Objects of `root` are never merged, even with each other.
+                if index < offset {
+                    index as u32
+                } else {
+                    canonical
+                }
+            })
+            .collect();
+        let keep: Vec<bool> = canonical
+            .iter()
+            .enumerate()
+            .map(|(index, &canonical)| index as u32 == canonical)
+            .collect();
+        if keep.contains(&false) {
+            visit_indices(root, &mut |k, index| {
+                if k == kind {
+                    canonical.get(index as usize).copied().unwrap_or(index)
+                } else {
+                    index
+                }
+            });
+            remap.record(
+                kind,
+                canonical
+                    .iter()
+                    .map(|&index| Some(index as usize))
+                    .collect(),
+            );
+            remap.record(kind, remove(root, kind, &keep));
+        }
+    }
+    remap
+}
+
+/// Reduces `root` to the given nodes and the objects they depend on.
+///
+/// The descendants of the given nodes are kept, as are the joints and
+/// skeletons of the skins they use. The scenes are replaced by a single
+/// default scene holding every kept node whose parent was removed. Such nodes
+/// stay where they were in the world: the transforms of their removed
+/// ancestors are baked into their matrix, or into a new parent node if their
+/// translation, rotation, or scale is animated. Animation channels targeting
+/// removed nodes are dropped, along with animations left without channels,
+/// and the document is then pruned. Buffers are left as
+/// they are; follow with `pack_buffers` to drop the data no longer used.
+///
+/// Returns where the remaining objects moved.
+///
+/// # Panics
+///
+/// Panics if a node index is out of bounds.
+pub fn extract(root: &mut json::Root, nodes: &[usize]) -> Remap {
+    let mut keep = vec![false; root.nodes.len()];
+    let mut stack = nodes.to_vec();
+    while let Some(index) = stack.pop() {
+        if std::mem::replace(&mut keep[index], true) {
+            continue;
+        }
+        let node = &root.nodes[index];
+        stack.extend(node.children.iter().flatten().map(|child| child.value()));
+        if let Some(skin) = node.skin.and_then(|skin| root.skins.get(skin.value())) {
+            stack.extend(skin.joints.iter().map(|joint| joint.value()));
+            stack.extend(skin.skeleton.map(|skeleton| skeleton.value()));
+        }
+    }
+
+    let mut parents = vec![None; root.nodes.len()];
+    let mut has_parent = vec![false; root.nodes.len()];
+    for (index, node) in root.nodes.iter().enumerate() {
+        for child in node.children.iter().flatten() {
+            parents[child.value()] = Some(index);
+            has_parent[child.value()] |= keep[index];
+        }
+    }
+    let animated: Vec<usize> = root
+        .animations
+        .iter()
+        .flat_map(|animation| &animation.channels)
+        .filter(|channel| channel.target.path != Checked::Valid(Property::MorphTargetWeights))
+        .map(|channel| channel.target.node.value())
+        .collect();
+    for index in 0..parents.len() {
+        if !keep[index] || has_parent[index] {
+            continue;
+        }
+        // Walk up at most once per node, which guards against cycles.
+        let mut ancestors = Matrix4::identity();
+        let mut parent = parents[index];
+        for _ in 0..parents.len() {
+            let node = match parent {
+                Some(node) => node,
+                None => break,
+            };
+            let local = Transform::from_json(&root.nodes[node]).matrix();
+            ancestors = Matrix4::from_array(local) * ancestors;
+            parent = parents[node];
+        }
+        if ancestors == Matrix4::identity() {
+            continue;
+        }
+        if animated.contains(&index) {
+            root.nodes.push(json::Node {
+                camera: None,
+                children: Some(vec![json::Index::new(index as u32)]),
+                extensions: Default::default(),
+                extras: Default::default(),
+                matrix: Some(flatten(ancestors)),
+                mesh: None,
+                #[cfg(feature = "names")]
+                name: None,
+                rotation: None,
+                scale: None,
+                translation: None,
+                skin: None,
+                weights: None,
+            });
+            keep.push(true);
+            has_parent.push(false);
+            has_parent[index] = true;
+        } else {
+            let node = &mut root.nodes[index];
+            let local = Transform::from_json(node).matrix();
+            node.matrix = Some(flatten(ancestors * Matrix4::from_array(local)));
+            node.translation = None;
+            node.rotation = None;
+            node.scale = None;
+        }
+    }
+    let roots = (0..root.nodes.len())
+        .filter(|&index| keep[index] && !has_parent[index])
+        .map(|index| json::Index::new(index as u32))
+        .collect();
+    root.scenes = vec![json::Scene {
+        extensions: Default::default(),
+        extras: Default::default(),
+        #[cfg(feature = "names")]
+        name: None,
+        nodes: roots,
+    }];
+    root.scene = Some(json::Index::new(0));
+
+    for animation in &mut root.animations {
+        animation
+            .channels
+            .retain(|channel| keep[channel.target.node.value()]);
+        let mut used = vec![false; animation.samplers.len()];
+        for channel in &animation.channels {
+            used[channel.sampler.value()] = true;
+        }
+        let mut next = 0;
+        let moved: Vec<u32> = used
+            .iter()
+            .map(|&used| {
+                next += used as u32;
+                next.saturating_sub(1)
+            })
+            .collect();
+        let mut i = 0;
+        animation.samplers.retain(|_| {
+            i += 1;
+            used[i - 1]
+        });
+        for channel in &mut animation.channels {
+            channel.sampler = json::Index::new(moved[channel.sampler.value()]);
+        }
+    }
+    let animations: Vec<bool> = root
+        .animations
+        .iter()
+        .map(|animation| !animation.channels.is_empty())
+        .collect();
+
+    let mut remap = Remap::default();
+    remap.record(Kind::Animation, remove(root, Kind::Animation, &animations));
+    remap.record(Kind::Node, remove(root, Kind::Node, &keep));
+    remap.extend(prune(root));
+    remap
+}
+
+/// Returns a matrix as the column-major array of a node.
+fn flatten(matrix: Matrix4) -> [f32; 16] {
+    let mut array = [0.0; 16];
+    for (chunk, column) in array.chunks_mut(4).zip(&matrix.as_array()) {
+        chunk.copy_from_slice(column);
+    }
+    array
+}
+
+/// Reduces `root` to the given meshes and the objects they depend on.
+///
+/// Each mesh is placed in a new node of its own, without a transform, and the
+/// document is then reduced to those nodes as by `extract`. Returns where the
+/// remaining objects moved; the new nodes come first, in the order given.
+///
+/// # Panics
+///
+/// Panics if a mesh index is out of bounds.
+pub fn extract_meshes(root: &mut json::Root, meshes: &[usize]) -> Remap {
+    let first = root.nodes.len();
+    for &mesh in meshes {
+        assert!(mesh < root.meshes.len(), "mesh index out of bounds");
+        root.nodes.push(json::Node {
+            camera: None,
+            children: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+            matrix: None,
+            mesh: Some(json::Index::new(mesh as u32)),
+            #[cfg(feature = "names")]
+            name: root.meshes[mesh].name.clone(),
+            rotation: None,
+            scale: None,
+            translation: None,
+            skin: None,
+            weights: None,
+        });
+    }
+    let nodes: Vec<usize> = (first..root.nodes.len()).collect();
+    extract(root, &nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(json: &str) -> json::Root {
+        json::deserialize::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn merges_documents() {
+        let mut root = document(
+            r#"{
+                "asset": {"version": "2.0"},
+                "buffers": [{"byteLength": 4}],
+                "bufferViews": [{"buffer": 0, "byteLength": 4}],
+                "images": [{"bufferView": 0, "mimeType": "image/png"}],
+                "samplers": [{"magFilter": 9729}],
+                "textures": [{"source": 0, "sampler": 0}],
+                "materials": [{"pbrMetallicRoughness": {"baseColorTexture": {"index": 0}}}],
+                "nodes": [{}],
+                "scenes": [{"nodes": [0]}],
+                "scene": 0
+            }"#,
+        );
+        let other = document(
+            r#"{
+                "asset": {"version": "2.0"},
+                "extensionsUsed": ["KHR_materials_unlit"],
+                "buffers": [{"byteLength": 4}],
+                "bufferViews": [{"buffer": 0, "byteLength": 4}],
+                "images": [{"bufferView": 0, "mimeType": "image/png"}],
+                "samplers": [{"magFilter": 9728}, {"magFilter": 9729}],
+                "textures": [{"source": 0, "sampler": 1}],
+                "materials": [{"doubleSided": true}, {"doubleSided": true}],
+                "meshes": [{"primitives": [{"attributes": {}, "material": 1}]}],
+                "nodes": [{"children": [1]}, {"mesh": 0}],
+                "scenes": [{"nodes": [0]}],
+                "scene": 0
+            }"#,
+        );
+        let mut buffers = vec![buffer::Data(vec![0, 1, 2, 3])];
+        let remap = merge(
+            &mut root,
+            &mut buffers,
+            other,
+            vec![buffer::Data(vec![4, 5, 6, 7])],
+        );
+
+        assert_eq!(root.extensions_used, ["KHR_materials_unlit"]);
+        assert_eq!(root.scene.unwrap().value(), 0);
+        assert_eq!(root.scenes[1].nodes[0].value(), 1);
+        assert_eq!(root.nodes[1].children.as_ref().unwrap()[0].value(), 2);
+        assert_eq!(remap.get(Kind::Node, 1), Some(2));
+
+        // The second sampler is shared, but the image data is not.
+        assert_eq!(root.samplers.len(), 2);
+        assert_eq!(remap.indices(Kind::Sampler), Some(&[Some(1), Some(0)][..]));
+        assert_eq!(root.textures[1].sampler.unwrap().value(), 0);
+        assert_eq!(root.images.len(), 2);
+        assert_eq!(root.buffer_views[1].buffer.value(), 1);
+
+        // Identical materials within `other` are merged as well.
+        assert_eq!(root.materials.len(), 2);
+        assert_eq!(remap.get(Kind::Material, 1), Some(1));
+        assert_eq!(root.meshes[0].primitives[0].material.unwrap().value(), 1);
+
+        assert_eq!(root.buffers.len(), 2);
+        let (packed, remap) = super::super::pack_buffers(&mut root, &buffers).unwrap();
+        assert_eq!(packed, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(remap.indices(Kind::Buffer), Some(&[Some(0), Some(0)][..]));
+        assert_eq!(root.buffer_views[1].byte_offset, Some(4));
+    }
+
+    #[test]
+    fn extracts_nodes() {
+        let mut root = document(
+            r#"{
+                "asset": {"version": "2.0"},
+                "buffers": [{"byteLength": 4}],
+                "bufferViews": [{"buffer": 0, "byteLength": 4}],
+                "accessors": [
+                    {"bufferView": 0, "componentType": 5126, "count": 1, "type": "SCALAR"},
+                    {"bufferView": 0, "componentType": 5126, "count": 1, "type": "SCALAR"}
+                ],
+                "materials": [{}, {"doubleSided": true}],
+                "meshes": [
+                    {"primitives": [{"attributes": {}, "material": 0}]},
+                    {"primitives": [{"attributes": {}, "material": 1}]}
+                ],
+                "nodes": [
+                    {"children": [1, 2]},
+                    {"mesh": 0},
+                    {"mesh": 1, "children": [3]},
+                    {}
+                ],
+                "animations": [{
+                    "channels": [
+                        {"sampler": 1, "target": {"node": 3, "path": "scale"}},
+                        {"sampler": 0, "target": {"node": 1, "path": "scale"}}
+                    ],
+                    "samplers": [
+                        {"input": 0, "output": 0},
+                        {"input": 1, "output": 1}
+                    ]
+                }],
+                "scenes": [{"nodes": [0]}]
+            }"#,
+        );
+        let remap = extract(&mut root, &[2]);
+
+        assert_eq!(root.nodes.len(), 2);
+        assert_eq!(remap.get(Kind::Node, 0), None);
+        assert_eq!(remap.get(Kind::Node, 2), Some(0));
+        assert_eq!(root.nodes[0].children.as_ref().unwrap()[0].value(), 1);
+        assert_eq!(root.scenes.len(), 1);
+        assert_eq!(root.scenes[0].nodes[0].value(), 0);
+
+        assert_eq!(root.meshes.len(), 1);
+        assert_eq!(root.materials.len(), 1);
+        assert_eq!(remap.get(Kind::Material, 1), Some(0));
+
+        let animation = &root.animations[0];
+        assert_eq!(animation.channels.len(), 1);
+        assert_eq!(animation.samplers.len(), 1);
+        assert_eq!(animation.channels[0].sampler.value(), 0);
+        assert_eq!(animation.channels[0].target.node.value(), 1);
+        assert_eq!(root.accessors.len(), 1);
+        assert_eq!(remap.get(Kind::Accessor, 1), Some(0));
+    }
+
+    #[test]
+    fn extracts_below_cycles() {
+        let mut root = document(
+            r#"{
+                "asset": {"version": "2.0"},
+                "nodes": [
+                    {"children": [1], "translation": [1, 0, 0]},
+                    {"children": [0, 2]},
+                    {}
+                ]
+            }"#,
+        );
+        extract(&mut root, &[2]);
+        assert_eq!(root.nodes.len(), 1);
+        assert_eq!(root.scenes[0].nodes[0].value(), 0);
+    }
+
+    #[test]
+    fn extracts_in_place() {
+        let mut root = document(
+            r#"{
+                "asset": {"version": "2.0"},
+                "accessors": [
+                    {"componentType": 5126, "count": 1, "type": "SCALAR"},
+                    {"componentType": 5126, "count": 1, "type": "VEC3"}
+                ],
+                "nodes": [
+                    {"translation": [1, 2, 3], "children": [1, 2]},
+                    {"scale": [2, 2, 2]},
+                    {"translation": [0, 1, 0]}
+                ],
+                "animations": [{
+                    "channels": [{"sampler": 0, "target": {"node": 2, "path": "translation"}}],
+                    "samplers": [{"input": 0, "output": 1}]
+                }],
+                "scenes": [{"nodes": [0]}]
+            }"#,
+        );
+        extract(&mut root, &[1, 2]);
+
+        // The translation of the removed parent is baked into the first node.
+        assert_eq!(root.nodes.len(), 3);
+        let matrix = root.nodes[0].matrix.unwrap();
+        assert_eq!(matrix[12..], [1.0, 2.0, 3.0, 1.0]);
+        assert_eq!(matrix[0], 2.0);
+        assert!(root.nodes[0].scale.is_none());
+
+        // The animated node gets a new parent holding it instead.
+        assert_eq!(root.nodes[1].translation, Some([0.0, 1.0, 0.0]));
+        assert_eq!(root.nodes[2].children.as_ref().unwrap()[0].value(), 1);
+        assert_eq!(root.nodes[2].matrix.unwrap()[12..], [1.0, 2.0, 3.0, 1.0]);
+        let roots: Vec<_> = root.scenes[0].nodes.iter().map(|n| n.value()).collect();
+        assert_eq!(roots, [0, 2]);
+    }
+
+    #[test]
+    fn extracts_meshes() {
+        let mut root = document(
+            r#"{
+                "asset": {"version": "2.0"},
+                "meshes": [
+                    {"name": "a", "primitives": [{"attributes": {}}]},
+                    {"name": "b", "primitives": [{"attributes": {}}]}
+                ],
+                "nodes": [{"mesh": 0}, {"mesh": 1}],
+                "scenes": [{"nodes": [0, 1]}]
+            }"#,
+        );
+        let remap = extract_meshes(&mut root, &[1]);
+        assert_eq!(root.nodes.len(), 1);
+        assert_eq!(root.meshes.len(), 1);
+        assert_eq!(remap.get(Kind::Mesh, 1), Some(0));
+        assert_eq!(root.nodes[0].mesh.unwrap().value(), 0);
+        assert_eq!(root.scenes[0].nodes.len(), 1);
+    }
+}
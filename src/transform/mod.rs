@@ -0,0 +1,592 @@
+use std::collections::HashMap;
+
+use crate::{buffer, Result};
+
+#[cfg(feature = "import")]
+use crate::import::Scheme;
+#[cfg(feature = "import")]
+use std::path::Path;
+
+#[allow(unused_imports)]
+pub(crate) use crate::visit::{len, remove, visit_indices, Kind};
+
+pub use self::bounds::compute_bounds;
+#[cfg(feature = "draco_decoder")]
+#[cfg_attr(docsrs, doc(cfg(feature = "draco_decoder")))]
+pub use self::draco::decode_draco;
+pub use self::error::Error;
+pub use self::format::{convert_vertex_formats, VertexFormats};
+pub use self::layout::{deinterleave, interleave};
+pub use self::merge::{extract, extract_meshes, merge};
+pub use self::morph::{sparsify_morph_targets, DEFAULT_SPARSE_DENSITY};
+#[cfg(feature = "utils")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utils")))]
+pub use self::normals::{generate_flat_normals, generate_normals, generate_tangents};
+pub use self::remap::Remap;
+pub use self::skin::{limit_joint_influences, MAX_JOINT_INFLUENCES};
+
+/// Computing accessor bounds.
+mod bounds;
+
+/// Reading and writing accessor data.
+pub(crate) mod data;
+
+/// Decoding geometry compressed by `KHR_draco_mesh_compression`.
+#[cfg(feature = "draco_decoder")]
+mod draco;
+
+/// Errors reading the data of a document.
+mod error;
+
+/// Passes that change the storage format of vertex data.
+mod format;
+
+/// Passes that rewrite the layout of vertex data.
+mod layout;
+
+/// Passes that combine documents and split them apart.
+mod merge;
+
+/// Passes that rewrite morph target data.
+mod morph;
+
+/// Passes that generate normal and tangent vectors.
+#[cfg(feature = "utils")]
+mod normals;
+
+/// Mapping old indices to new ones after a pass.
+mod remap;
+
+/// Passes that rewrite skinning data.
+mod skin;
+
+/// The kinds of object that `prune` may remove, ordered such that
+/// referencing objects are visited before the objects they refer to.
+const PRUNABLE: &[Kind] = &[
+    Kind::Mesh,
+    Kind::Skin,
+    Kind::Camera,
+    Kind::Material,
+    Kind::Texture,
+    Kind::Sampler,
+    Kind::Image,
+    Kind::Accessor,
+    Kind::BufferView,
+];
+
+/// The kinds of object that `dedup` may merge, ordered such that the objects
+/// being referred to are merged before the objects referring to them.
+const DEDUPLICABLE: &[Kind] = &[
+    Kind::BufferView,
+    Kind::Accessor,
+    Kind::Sampler,
+    Kind::Image,
+    Kind::Texture,
+    Kind::Material,
+    Kind::Mesh,
+];
+
+/// Byte alignment of buffer views written by `pack_buffers`.
+const ALIGNMENT: usize = 4;
+
+/// Returns `true` if the geometry of a primitive is compressed by
+/// `KHR_draco_mesh_compression`.
+///
+/// Passes reading vertex data leave such primitives unchanged, since their
+/// accessors describe the decoded data rather than the stored bytes.
+#[cfg(feature = "KHR_draco_mesh_compression")]
+fn is_compressed(primitive: &json::mesh::Primitive) -> bool {
+    primitive
+        .extensions
+        .as_ref()
+        .is_some_and(|extensions| extensions.khr_draco_mesh_compression.is_some())
+}
+
+/// Returns `true` if the geometry of a primitive is compressed by
+/// `KHR_draco_mesh_compression`, which is never the case when the extension
+/// is disabled.
+#[cfg(not(feature = "KHR_draco_mesh_compression"))]
+fn is_compressed(_: &json::mesh::Primitive) -> bool {
+    false
+}
+
+/// Counts the references to every object of the given kind.
+fn reference_counts(root: &mut json::Root, kind: Kind) -> Vec<usize> {
+    let mut counts = vec![0; len(root, kind)];
+    visit_indices(root, &mut |k, index| {
+        if k == kind {
+            if let Some(count) = counts.get_mut(index as usize) {
+                *count += 1;
+            }
+        }
+        index
+    });
+    counts
+}
+
+/// Serializes every object of the given kind to a canonical string, such
+/// that equal objects produce equal strings.
+fn canonical_forms(root: &json::Root, kind: Kind) -> Vec<String> {
+    macro_rules! forms {
+        ($items:expr) => {
+            $items
+                .iter()
+                .map(|item| {
+                    // Round trip through `Value` so hash map keys are sorted.
+                    let value = json::serialize::to_value(item).unwrap();
+                    json::serialize::to_string(&value).unwrap()
+                })
+                .collect()
+        };
+    }
+
+    match kind {
+        Kind::Accessor => forms!(root.accessors),
+        Kind::BufferView => forms!(root.buffer_views),
+        Kind::Image => forms!(root.images),
+        Kind::Material => forms!(root.materials),
+        Kind::Mesh => forms!(root.meshes),
+        Kind::Sampler => forms!(root.samplers),
+        Kind::Texture => forms!(root.textures),
+        _ => unreachable!(),
+    }
+}
+
+/// Removes objects that are not referenced by any other object.
+///
+/// Meshes, skins, cameras, materials, textures, samplers, images, accessors,
+/// and buffer views are removed when unreferenced. Scenes, nodes, animations,
+/// and buffers are always kept. Returns where the remaining objects moved.
+pub fn prune(root: &mut json::Root) -> Remap {
+    let mut remap = Remap::default();
+    loop {
+        let mut changed = false;
+        for &kind in PRUNABLE {
+            let keep: Vec<bool> = reference_counts(root, kind)
+                .into_iter()
+                .map(|count| count > 0)
+                .collect();
+            if keep.contains(&false) {
+                remap.record(kind, remove(root, kind, &keep));
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    remap
+}
+
+/// Merges objects whose JSON is identical, redirecting every reference to
+/// the first occurrence.
+///
+/// Buffer views, accessors, samplers, images, textures, materials, and
+/// meshes are considered. Objects that differ only by name are not merged.
+/// Returns where the objects moved, with merged objects mapped to the object
+/// they were merged into.
+pub fn dedup(root: &mut json::Root) -> Remap {
+    let mut remap = Remap::default();
+    for &kind in DEDUPLICABLE {
+        let mut first = HashMap::new();
+        let canonical: Vec<u32> = canonical_forms(root, kind)
+            .into_iter()
+            .enumerate()
+            .map(|(index, form)| *first.entry(form).or_insert(index as u32))
+            .collect();
+        let keep: Vec<bool> = canonical
+            .iter()
+            .enumerate()
+            .map(|(index, &canonical)| index as u32 == canonical)
+            .collect();
+        if keep.contains(&false) {
+            visit_indices(root, &mut |k, index| {
+                if k == kind {
+                    canonical.get(index as usize).copied().unwrap_or(index)
+                } else {
+                    index
+                }
+            });
+            remap.record(
+                kind,
+                canonical
+                    .iter()
+                    .map(|&index| Some(index as usize))
+                    .collect(),
+            );
+            remap.record(kind, remove(root, kind, &keep));
+        }
+    }
+    remap
+}
+
+/// Checks that the data of every buffer view and accessor, including sparse
+/// substitutes, lies within `buffers`, and that every accessor has a valid
+/// format.
+///
+/// The passes reading accessor data run this check first and return its
+/// error without changing anything. Running it once up front reports
+/// unreadable data before any pass has run.
+pub fn check(root: &json::Root, buffers: &[buffer::Data]) -> Result<()> {
+    Ok(data::check(root, buffers)?)
+}
+
+/// Copies the data of every buffer view into a single buffer, returning the
+/// new buffer data together with where the buffers moved.
+///
+/// Afterwards `root` has exactly one buffer without a URI, suitable for use
+/// as the `BIN` chunk of binary glTF, or no buffer at all if it has no buffer
+/// views, since a buffer must not be empty. Bytes not covered by any buffer
+/// view are dropped. Each buffer view starts on a four byte boundary, and buffer views
+/// with identical contents share a single copy of the data.
+///
+/// Returns `Error::Transform` without changing anything if a buffer view
+/// lies outside of its buffer data.
+pub fn pack_buffers(root: &mut json::Root, buffers: &[buffer::Data]) -> Result<(Vec<u8>, Remap)> {
+    let contents = (0..root.buffer_views.len())
+        .map(|index| Ok(data::view_bytes(root, buffers, index)?))
+        .collect::<Result<Vec<_>>>()?;
+    let mut packed = Vec::new();
+    let mut stored = HashMap::new();
+    for (view, bytes) in root.buffer_views.iter_mut().zip(contents) {
+        let offset = *stored.entry(bytes).or_insert_with(|| {
+            while packed.len() % ALIGNMENT != 0 {
+                packed.push(0);
+            }
+            let offset = packed.len() as u32;
+            packed.extend_from_slice(bytes);
+            offset
+        });
+        view.buffer = json::Index::new(0);
+        view.byte_offset = Some(offset);
+    }
+    while packed.len() % ALIGNMENT != 0 {
+        packed.push(0);
+    }
+
+    let mut remap = Remap::default();
+    if root.buffer_views.is_empty() {
+        remap.record(Kind::Buffer, vec![None; root.buffers.len()]);
+        root.buffers.clear();
+        return Ok((packed, remap));
+    }
+    remap.record(Kind::Buffer, vec![Some(0); root.buffers.len()]);
+    let mut buffer = root.buffers.drain(..).next().unwrap_or(json::Buffer {
+        byte_length: 0,
+        #[cfg(feature = "names")]
+        name: None,
+        uri: None,
+        extensions: Default::default(),
+        extras: Default::default(),
+    });
+    buffer.byte_length = packed.len() as u32;
+    buffer.uri = None;
+    root.buffers.push(buffer);
+    Ok((packed, remap))
+}
+
+/// Moves the data of every image referenced by URI into a new buffer.
+///
+/// The images are rewritten to refer to buffer views instead, and the new
+/// buffer data is appended to `buffers`. External files are resolved relative
+/// to `base`; when `base` is `None` only data URIs may be embedded.
+///
+/// Returns an error without changing anything if an image cannot be read or
+/// its MIME type cannot be determined.
+#[cfg(feature = "import")]
+#[cfg_attr(docsrs, doc(cfg(feature = "import")))]
+pub fn embed_images(
+    root: &mut json::Root,
+    buffers: &mut Vec<buffer::Data>,
+    base: Option<&Path>,
+) -> Result<()> {
+    let mut embedded = Vec::new();
+    for (index, image) in root.images.iter().enumerate() {
+        let uri = match image.uri.as_ref() {
+            Some(uri) => uri,
+            None => continue,
+        };
+        let mime_type = match Scheme::parse(uri) {
+            Scheme::Data(Some(mime_type), _) => Some(mime_type.to_string()),
+            _ => image
+                .mime_type
+                .as_ref()
+                .map(|mime_type| mime_type.0.clone()),
+        };
+        let mime_type = match mime_type {
+            Some(mime_type) => mime_type,
+            None => match uri.rsplit('.').next() {
+                Some("png") => "image/png".to_string(),
+                Some("jpg") | Some("jpeg") => "image/jpeg".to_string(),
+                _ => return Err(crate::Error::UnsupportedImageEncoding),
+            },
+        };
+        embedded.push((index, mime_type, Scheme::read(base, uri)?));
+    }
+
+    let buffer_index = root.buffers.len() as u32;
+    let mut data = Vec::new();
+    for (index, mime_type, bytes) in embedded {
+        while data.len() % ALIGNMENT != 0 {
+            data.push(0);
+        }
+        let image = &mut root.images[index];
+        image.uri = None;
+        image.buffer_view = Some(json::Index::new(root.buffer_views.len() as u32));
+        image.mime_type = Some(json::image::MimeType(mime_type));
+        root.buffer_views.push(json::buffer::View {
+            buffer: json::Index::new(buffer_index),
+            byte_length: bytes.len() as u32,
+            byte_offset: Some(data.len() as u32),
+            byte_stride: None,
+            #[cfg(feature = "names")]
+            name: None,
+            target: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        });
+        data.extend_from_slice(&bytes);
+    }
+
+    if !data.is_empty() {
+        while data.len() % ALIGNMENT != 0 {
+            data.push(0);
+        }
+        root.buffers.push(json::Buffer {
+            byte_length: data.len() as u32,
+            #[cfg(feature = "names")]
+            name: None,
+            uri: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        });
+        buffers.push(buffer::Data(data));
+    }
+    Ok(())
+}
+
+/// Moves the data of every image stored in a buffer view out of the buffers.
+///
+/// `uri` is called with the index and MIME type of each such image and
+/// returns the URI the image should refer to instead. The URIs are returned
+/// alongside the image data so the caller can write them out, together with
+/// where the buffer views moved, since those left unreferenced are removed.
+///
+/// Returns `Error::Transform` without changing anything if the buffer view of
+/// an image lies outside of its buffer data.
+#[allow(clippy::type_complexity)]
+pub fn extract_images<F>(
+    root: &mut json::Root,
+    buffers: &[buffer::Data],
+    mut uri: F,
+) -> Result<(Vec<(String, Vec<u8>)>, Remap)>
+where
+    F: FnMut(usize, Option<&str>) -> String,
+{
+    let contents = root
+        .images
+        .iter()
+        .map(|image| match image.buffer_view {
+            Some(view) => Ok(Some(data::view_bytes(root, buffers, view.value())?)),
+            None => Ok(None),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut extracted = Vec::new();
+    for (index, (image, bytes)) in root.images.iter_mut().zip(contents).enumerate() {
+        let bytes = match bytes {
+            Some(bytes) => bytes,
+            None => continue,
+        };
+        image.buffer_view = None;
+        let mime_type = image
+            .mime_type
+            .as_ref()
+            .map(|mime_type| mime_type.0.as_str());
+        let name = uri(index, mime_type);
+        image.uri = Some(name.clone());
+        extracted.push((name, bytes.to_vec()));
+    }
+
+    let keep: Vec<bool> = reference_counts(root, Kind::BufferView)
+        .into_iter()
+        .map(|count| count > 0)
+        .collect();
+    let mut remap = Remap::default();
+    if keep.contains(&false) {
+        remap.record(Kind::BufferView, remove(root, Kind::BufferView, &keep));
+    }
+    Ok((extracted, remap))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(offset: u32, length: u32) -> json::buffer::View {
+        json::buffer::View {
+            buffer: json::Index::new(0),
+            byte_length: length,
+            byte_offset: Some(offset),
+            byte_stride: None,
+            #[cfg(feature = "names")]
+            name: None,
+            target: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+
+    fn root(views: Vec<json::buffer::View>, byte_length: u32) -> json::Root {
+        json::Root {
+            buffers: vec![json::Buffer {
+                byte_length,
+                #[cfg(feature = "names")]
+                name: None,
+                uri: None,
+                extensions: Default::default(),
+                extras: Default::default(),
+            }],
+            buffer_views: views,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn prune_removes_unreferenced_views() {
+        let mut root = root(vec![view(0, 4), view(4, 4)], 8);
+        root.images.push(json::Image {
+            buffer_view: Some(json::Index::new(1)),
+            mime_type: Some(json::image::MimeType("image/png".to_string())),
+            #[cfg(feature = "names")]
+            name: None,
+            uri: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        });
+        root.textures.push(json::Texture {
+            #[cfg(feature = "names")]
+            name: None,
+            sampler: None,
+            source: json::Index::new(0),
+            extensions: Default::default(),
+            extras: Default::default(),
+        });
+
+        // The texture is unreferenced, and with it the image and views.
+        let remap = prune(&mut root);
+        assert_eq!(remap.get(Kind::Texture, 0), None);
+        assert_eq!(remap.indices(Kind::BufferView), Some(&[None, None][..]));
+        assert_eq!(remap.get(Kind::Buffer, 0), Some(0));
+        assert!(root.textures.is_empty());
+        assert!(root.images.is_empty());
+        assert!(root.buffer_views.is_empty());
+        assert_eq!(root.buffers.len(), 1);
+    }
+
+    #[test]
+    fn dedup_and_pack_views() {
+        let mut root = root(vec![view(0, 4), view(4, 4), view(0, 4)], 8);
+        for view in 0..3 {
+            root.images.push(json::Image {
+                buffer_view: Some(json::Index::new(view)),
+                mime_type: Some(json::image::MimeType("image/png".to_string())),
+                #[cfg(feature = "names")]
+                name: None,
+                uri: None,
+                extensions: Default::default(),
+                extras: Default::default(),
+            });
+        }
+
+        let remap = dedup(&mut root);
+        assert_eq!(
+            remap.indices(Kind::BufferView),
+            Some(&[Some(0), Some(1), Some(0)][..])
+        );
+        assert_eq!(
+            remap.indices(Kind::Image),
+            Some(&[Some(0), Some(1), Some(0)][..])
+        );
+        assert_eq!(root.buffer_views.len(), 2);
+        assert_eq!(root.images.len(), 2);
+        assert_eq!(root.images[1].buffer_view.unwrap().value(), 1);
+
+        let data = buffer::Data(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+        let (packed, remap) = pack_buffers(&mut root, &[data]).unwrap();
+        assert_eq!(packed, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        assert!(remap.is_empty());
+        assert_eq!(root.buffers[0].byte_length, 8);
+        assert_eq!(root.buffer_views[1].byte_offset, Some(4));
+    }
+
+    #[test]
+    fn pack_without_views_removes_buffers() {
+        let mut root = root(vec![], 4);
+        let (packed, remap) = pack_buffers(&mut root, &[buffer::Data(vec![0; 4])]).unwrap();
+        assert!(packed.is_empty());
+        assert!(root.buffers.is_empty());
+        assert_eq!(remap.get(Kind::Buffer, 0), None);
+
+        let (packed, remap) = pack_buffers(&mut json::Root::default(), &[]).unwrap();
+        assert!(packed.is_empty());
+        assert!(remap.is_empty());
+    }
+
+    #[test]
+    fn pack_shares_identical_ranges() {
+        let mut root = root(vec![view(0, 4), view(4, 2), view(8, 4)], 12);
+        let data = buffer::Data(vec![0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3]);
+        let (packed, _) = pack_buffers(&mut root, &[data]).unwrap();
+        assert_eq!(packed, vec![0, 1, 2, 3, 4, 5, 0, 0]);
+        assert_eq!(root.buffer_views[0].byte_offset, Some(0));
+        assert_eq!(root.buffer_views[1].byte_offset, Some(4));
+        assert_eq!(root.buffer_views[2].byte_offset, Some(0));
+    }
+
+    #[test]
+    fn remaps_compose() {
+        let mut remap = Remap::default();
+        remap.record(Kind::Node, vec![Some(0), None, Some(1), Some(2)]);
+        remap.record(Kind::Node, vec![None, Some(0), Some(1)]);
+        remap.record(Kind::Mesh, vec![Some(0), Some(1)]);
+        assert_eq!(
+            remap.indices(Kind::Node),
+            Some(&[None, None, Some(0), Some(1)][..])
+        );
+        assert_eq!(remap.indices(Kind::Mesh), None);
+        assert_eq!(remap.get(Kind::Mesh, 5), Some(5));
+        assert!(Remap::default().is_empty());
+    }
+
+    #[cfg(feature = "import")]
+    #[test]
+    fn embed_images_keeps_images_on_failure() {
+        let mut root = root(vec![], 0);
+        for uri in &["data:image/png;base64,AAAA", "missing.png"] {
+            root.images.push(json::Image {
+                buffer_view: None,
+                mime_type: None,
+                #[cfg(feature = "names")]
+                name: None,
+                uri: Some(uri.to_string()),
+                extensions: Default::default(),
+                extras: Default::default(),
+            });
+        }
+        let mut buffers = vec![buffer::Data(vec![])];
+        match embed_images(&mut root, &mut buffers, None) {
+            Err(crate::Error::ExternalReferenceInSliceImport) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(root.images.iter().all(|image| image.uri.is_some()));
+        assert!(root.images.iter().all(|image| image.mime_type.is_none()));
+        assert!(root.buffer_views.is_empty());
+        assert_eq!(buffers.len(), 1);
+
+        root.images.pop();
+        embed_images(&mut root, &mut buffers, None).unwrap();
+        assert!(root.images[0].uri.is_none());
+        assert_eq!(root.buffers.len(), 2);
+        assert_eq!(buffers.len(), 2);
+    }
+}
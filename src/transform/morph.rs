@@ -0,0 +1,180 @@
+use std::collections::BTreeSet;
+
+use crate::{buffer, Result};
+use json::accessor::{sparse, ComponentType, IndexComponentType};
+use json::validation::Checked;
+
+use super::data::{self, Appender};
+
+/// The suggested `density` for `sparsify_morph_targets`, which stores morph
+/// targets sparsely when at most half of their elements are non-zero.
+pub const DEFAULT_SPARSE_DENSITY: f32 = 0.5;
+
+/// Stores morph target displacements sparsely when few of them are non-zero.
+///
+/// Every accessor used as a morph target displacement in which the fraction
+/// of non-zero elements is at most `density` is rewritten in place as a
+/// sparse accessor without a buffer view, holding only the indices and values
+/// of the non-zero elements. Accessors without any non-zero element lose their
+/// buffer view altogether. The sparse data is written to a new buffer, whose
+/// data is appended to `buffers`.
+///
+/// Accessors that are already sparse or have no buffer view are left
+/// unchanged. The replaced buffer views are left in place; run `prune`
+/// afterwards to remove them.
+///
+/// Returns `Error::Transform` without changing anything if an accessor has
+/// an invalid format or data outside of its buffer data.
+pub fn sparsify_morph_targets(
+    root: &mut json::Root,
+    buffers: &mut Vec<buffer::Data>,
+    density: f32,
+) -> Result<()> {
+    data::check(root, buffers)?;
+    let mut accessors = BTreeSet::new();
+    for mesh in &root.meshes {
+        for primitive in &mesh.primitives {
+            for target in primitive.targets.iter().flatten() {
+                for index in [&target.positions, &target.normals, &target.tangents]
+                    .iter()
+                    .filter_map(|index| index.as_ref())
+                {
+                    accessors.insert(index.value());
+                }
+            }
+        }
+    }
+
+    let mut appender = Appender::new(root);
+    for index in accessors {
+        let accessor = match root.accessors.get(index) {
+            Some(accessor) if accessor.sparse.is_none() && accessor.buffer_view.is_some() => {
+                accessor
+            }
+            _ => continue,
+        };
+        let (component_type, type_) = data::format(accessor, index)?;
+        let width = type_.multiplicity();
+        let values = data::read(root, buffers, index)?;
+        let count = values.len() / width;
+        let non_zero: Vec<usize> = values
+            .chunks(width)
+            .enumerate()
+            .filter(|(_, element)| element.iter().any(|&value| value != 0.0))
+            .map(|(i, _)| i)
+            .collect();
+        if count == 0 || non_zero.len() as f32 > density * count as f32 {
+            continue;
+        }
+
+        let sparse = if non_zero.is_empty() {
+            None
+        } else {
+            let index_type = match count - 1 {
+                last if last <= u8::MAX as usize => ComponentType::U8,
+                last if last <= u16::MAX as usize => ComponentType::U16,
+                _ => ComponentType::U32,
+            };
+            let indices: Vec<f64> = non_zero.iter().map(|&i| i as f64).collect();
+            let sparse_values: Vec<f64> = non_zero
+                .iter()
+                .flat_map(|&i| values[i * width..(i + 1) * width].iter().copied())
+                .collect();
+            let indices_view = appender.push_view(root, index_type, &indices);
+            let values_view = appender.push_view(root, component_type, &sparse_values);
+            Some(sparse::Sparse {
+                count: non_zero.len() as u32,
+                indices: sparse::Indices {
+                    buffer_view: indices_view,
+                    byte_offset: 0,
+                    component_type: Checked::Valid(IndexComponentType(index_type)),
+                    extensions: Default::default(),
+                    extras: Default::default(),
+                },
+                values: sparse::Values {
+                    buffer_view: values_view,
+                    byte_offset: 0,
+                    extensions: Default::default(),
+                    extras: Default::default(),
+                },
+                extensions: Default::default(),
+                extras: Default::default(),
+            })
+        };
+
+        let accessor = &mut root.accessors[index];
+        accessor.buffer_view = None;
+        accessor.byte_offset = 0;
+        accessor.sparse = sparse;
+    }
+    appender.finish(root, buffers);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparsifies_mostly_zero_targets() {
+        let mut bytes = Vec::new();
+        for value in &[
+            0.0f32, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0,
+        ] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in &[
+            1.0f32, 1.0, 1.0, 2.0, 2.0, 2.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0,
+        ] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 96}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 48},
+                {"buffer": 0, "byteOffset": 48, "byteLength": 48}
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0,
+                    "componentType": 5126,
+                    "count": 4,
+                    "type": "VEC3",
+                    "min": [0.0, 0.0, 0.0],
+                    "max": [1.0, 2.0, 3.0]
+                },
+                {
+                    "bufferView": 1,
+                    "componentType": 5126,
+                    "count": 4,
+                    "type": "VEC3",
+                    "min": [0.0, 0.0, 0.0],
+                    "max": [2.0, 2.0, 3.0]
+                }
+            ],
+            "meshes": [{"primitives": [{
+                "attributes": {"POSITION": 1},
+                "targets": [{"POSITION": 0}, {"POSITION": 1}]
+            }]}]
+        }"#;
+        let mut root = json::Root::from_str(json).unwrap();
+        let mut buffers = vec![buffer::Data(bytes)];
+        let before = data::read(&root, &buffers, 0).unwrap();
+
+        sparsify_morph_targets(&mut root, &mut buffers, DEFAULT_SPARSE_DENSITY).unwrap();
+
+        let accessor = &root.accessors[0];
+        assert!(accessor.buffer_view.is_none());
+        let sparse = accessor.sparse.as_ref().unwrap();
+        assert_eq!(sparse.count, 1);
+        match sparse.indices.component_type {
+            Checked::Valid(IndexComponentType(ComponentType::U8)) => {}
+            _ => panic!("expected unsigned byte indices"),
+        }
+        assert_eq!(buffers.len(), 2);
+        assert_eq!(data::read(&root, &buffers, 0).unwrap(), before);
+        assert!(root.accessors[1].sparse.is_none());
+        assert!(root.accessors[1].buffer_view.is_some());
+    }
+}
@@ -0,0 +1,598 @@
+use std::collections::HashMap;
+
+use crate::math::Vector3;
+use crate::mesh::util::triangles::Triangles;
+use crate::{buffer, Result};
+use json::accessor::{ComponentType, Type};
+use json::mesh::{Mode, Primitive, Semantic};
+use json::validation::Checked;
+
+use super::bounds::set_bounds;
+use super::data::{self, Appender, Column};
+use super::is_compressed;
+
+/// Generates a `NORMAL` attribute for every triangle primitive without one.
+///
+/// Each vertex is given the average of the normals of the triangles using it,
+/// weighted by their area, so that vertices shared between faces are smoothed.
+/// Vertices used only by degenerate triangles, or by none, point along +Z.
+/// The specification asks for flat normals when a primitive has none, which
+/// `generate_flat_normals` provides by splitting the vertices.
+///
+/// The normals are written to new accessors in a new buffer, whose data is
+/// appended to `buffers`. Primitives with the same positions and triangles
+/// share a single accessor.
+///
+/// Returns `Error::Transform` without changing anything if an accessor has
+/// an invalid format or data outside of its buffer data.
+pub fn generate_normals(root: &mut json::Root, buffers: &mut Vec<buffer::Data>) -> Result<()> {
+    data::check(root, buffers)?;
+    let mut appender = Appender::new(root);
+    let mut generated = HashMap::new();
+
+    for m in 0..root.meshes.len() {
+        for p in 0..root.meshes[m].primitives.len() {
+            let primitive = &root.meshes[m].primitives[p];
+            if is_compressed(primitive)
+                || primitive
+                    .attributes
+                    .contains_key(&Checked::Valid(Semantic::Normals))
+            {
+                continue;
+            }
+            let (positions, mode) =
+                match (attribute(primitive, Semantic::Positions), mode(primitive)) {
+                    (Some(positions), Some(mode)) => (positions, mode),
+                    _ => continue,
+                };
+            let indices = primitive.indices.map(|indices| indices.value());
+
+            let key = (positions, indices, mode.as_gl_enum());
+            let normals = match generated.get(&key) {
+                Some(&normals) => normals,
+                None => {
+                    let positions = vectors(root, buffers, positions)?;
+                    let triangles = triangles(root, buffers, indices, mode, positions.len())?;
+                    let normals = vertex_normals(&positions, &triangles);
+                    let values = flatten(normals.iter().map(Vector3::as_array));
+                    let normals =
+                        appender.push(root, ComponentType::F32, Type::Vec3, false, &values);
+                    generated.insert(key, normals);
+                    normals
+                }
+            };
+            root.meshes[m].primitives[p]
+                .attributes
+                .insert(Checked::Valid(Semantic::Normals), normals);
+        }
+    }
+
+    appender.finish(root, buffers);
+    Ok(())
+}
+
+/// Generates a flat `NORMAL` attribute for every triangle primitive without
+/// one, splitting its vertices so that no two triangles share a vertex.
+///
+/// Every vertex attribute and morph target of such a primitive is rewritten
+/// with one vertex per triangle corner, its indices are removed, and its mode
+/// becomes `Triangles`. Each vertex is then given the normal of its triangle,
+/// as the specification asks for primitives without normals. Vertices of
+/// degenerate triangles point along +Z, and triangles with vertices beyond
+/// the data of an attribute are left out.
+///
+/// The new vertex data is written to new accessors in a new buffer, whose data
+/// is appended to `buffers`, and the accessors are given bounds. The replaced
+/// accessors are left in place, so run `prune` afterwards to remove them.
+/// Primitives with the same vertex data and triangles share the new accessors.
+///
+/// Returns `Error::Transform` without changing anything if an accessor has
+/// an invalid format or data outside of its buffer data.
+pub fn generate_flat_normals(root: &mut json::Root, buffers: &mut Vec<buffer::Data>) -> Result<()> {
+    data::check(root, buffers)?;
+    let mut appender = Appender::new(root);
+    let mut split = HashMap::new();
+    let mut generated = HashMap::new();
+
+    for m in 0..root.meshes.len() {
+        for p in 0..root.meshes[m].primitives.len() {
+            let mut primitive = root.meshes[m].primitives[p].clone();
+            if is_compressed(&primitive)
+                || primitive
+                    .attributes
+                    .contains_key(&Checked::Valid(Semantic::Normals))
+            {
+                continue;
+            }
+            let (positions, mode) =
+                match (attribute(&primitive, Semantic::Positions), mode(&primitive)) {
+                    (Some(positions), Some(mode)) => (positions, mode),
+                    _ => continue,
+                };
+            let indices = primitive.indices.map(|indices| indices.value());
+
+            let mut accessors: Vec<&mut json::Index<json::Accessor>> =
+                primitive.attributes.values_mut().collect();
+            for target in primitive.targets.iter_mut().flatten() {
+                for accessor in [
+                    &mut target.positions,
+                    &mut target.normals,
+                    &mut target.tangents,
+                ] {
+                    accessors.extend(accessor.as_mut());
+                }
+            }
+            let count = accessors
+                .iter()
+                .map(|accessor| root.accessors[accessor.value()].count as usize)
+                .min()
+                .unwrap_or(0);
+            let triangles = triangles(root, buffers, indices, mode, count)?;
+            if triangles.is_empty() {
+                continue;
+            }
+
+            let corners = triangles.concat();
+            for accessor in accessors {
+                let key = (accessor.value(), indices, mode.as_gl_enum(), count);
+                *accessor = match split.get(&key) {
+                    Some(&index) => index,
+                    None => {
+                        let mut column = Column::read(root, buffers, accessor.value())?;
+                        let width = column.type_.multiplicity();
+                        column.values = corners
+                            .iter()
+                            .flat_map(|&vertex| &column.values[vertex * width..][..width])
+                            .copied()
+                            .collect();
+                        let index = appender.push_column(root, column);
+                        split.insert(key, index);
+                        index
+                    }
+                };
+            }
+
+            let key = (positions, indices, mode.as_gl_enum(), count);
+            let normals = match generated.get(&key) {
+                Some(&normals) => normals,
+                None => {
+                    let positions = vectors(root, buffers, positions)?;
+                    let normals = face_normals(&positions, &triangles);
+                    let values = flatten(normals.iter().map(Vector3::as_array));
+                    let normals =
+                        appender.push(root, ComponentType::F32, Type::Vec3, false, &values);
+                    generated.insert(key, normals);
+                    normals
+                }
+            };
+            primitive
+                .attributes
+                .insert(Checked::Valid(Semantic::Normals), normals);
+            primitive.indices = None;
+            primitive.mode = Checked::Valid(Mode::Triangles);
+            root.meshes[m].primitives[p] = primitive;
+        }
+    }
+
+    appender.finish(root, buffers);
+    for index in split.values() {
+        set_bounds(root, buffers, index.value())?;
+    }
+    Ok(())
+}
+
+/// Generates a `TANGENT` attribute for every normal mapped triangle primitive
+/// without one.
+///
+/// Primitives are considered when their material has a normal texture and
+/// they have the `NORMAL` attribute, which `generate_normals` can provide, as
+/// well as the texture coordinate set read by the normal texture. Tangents
+/// follow the MikkTSpace conventions: the tangent points along increasing U,
+/// is orthogonal to the normal, and its W component gives the handedness of
+/// the bitangent. As in `generate_normals`, the tangents of the triangles
+/// sharing a vertex are averaged rather than the vertex being split, so
+/// results may differ slightly from the reference implementation at UV seams.
+///
+/// The tangents are written to new accessors in a new buffer, whose data is
+/// appended to `buffers`. Primitives with the same vertex data and triangles
+/// share a single accessor.
+///
+/// Returns `Error::Transform` without changing anything if an accessor has
+/// an invalid format or data outside of its buffer data.
+pub fn generate_tangents(root: &mut json::Root, buffers: &mut Vec<buffer::Data>) -> Result<()> {
+    data::check(root, buffers)?;
+    let mut appender = Appender::new(root);
+    let mut generated = HashMap::new();
+
+    for m in 0..root.meshes.len() {
+        for p in 0..root.meshes[m].primitives.len() {
+            let primitive = &root.meshes[m].primitives[p];
+            if is_compressed(primitive)
+                || primitive
+                    .attributes
+                    .contains_key(&Checked::Valid(Semantic::Tangents))
+            {
+                continue;
+            }
+            let set = match primitive
+                .material
+                .and_then(|material| root.materials.get(material.value()))
+                .and_then(|material| material.normal_texture.as_ref())
+            {
+                Some(texture) => texture.tex_coord,
+                None => continue,
+            };
+            let (positions, normals, tex_coords, mode) = match (
+                attribute(primitive, Semantic::Positions),
+                attribute(primitive, Semantic::Normals),
+                attribute(primitive, Semantic::TexCoords(set)),
+                mode(primitive),
+            ) {
+                (Some(positions), Some(normals), Some(tex_coords), Some(mode)) => {
+                    (positions, normals, tex_coords, mode)
+                }
+                _ => continue,
+            };
+            let indices = primitive.indices.map(|indices| indices.value());
+
+            let key = (positions, normals, tex_coords, indices, mode.as_gl_enum());
+            let tangents = match generated.get(&key) {
+                Some(&tangents) => tangents,
+                None => {
+                    let positions = vectors(root, buffers, positions)?;
+                    let normals = vectors(root, buffers, normals)?;
+                    let tex_coords = data::read_normalized(root, buffers, tex_coords)?;
+                    let triangles = triangles(root, buffers, indices, mode, positions.len())?;
+                    let tangents = vertex_tangents(&positions, &normals, &tex_coords, &triangles);
+                    let values = flatten(tangents.into_iter());
+                    let tangents =
+                        appender.push(root, ComponentType::F32, Type::Vec4, false, &values);
+                    generated.insert(key, tangents);
+                    tangents
+                }
+            };
+            root.meshes[m].primitives[p]
+                .attributes
+                .insert(Checked::Valid(Semantic::Tangents), tangents);
+        }
+    }
+
+    appender.finish(root, buffers);
+    Ok(())
+}
+
+/// Returns the accessor of a vertex attribute, if present.
+fn attribute(primitive: &Primitive, semantic: Semantic) -> Option<usize> {
+    primitive
+        .attributes
+        .get(&Checked::Valid(semantic))
+        .map(|accessor| accessor.value())
+}
+
+/// Returns the mode of a primitive made of triangles.
+fn mode(primitive: &Primitive) -> Option<Mode> {
+    match primitive.mode {
+        Checked::Valid(mode @ Mode::Triangles)
+        | Checked::Valid(mode @ Mode::TriangleStrip)
+        | Checked::Valid(mode @ Mode::TriangleFan) => Some(mode),
+        _ => None,
+    }
+}
+
+/// Reads a three component accessor as vectors.
+fn vectors(root: &json::Root, buffers: &[buffer::Data], accessor: usize) -> Result<Vec<Vector3>> {
+    Ok(data::read_normalized(root, buffers, accessor)?
+        .chunks_exact(3)
+        .map(|v| Vector3::new(v[0] as f32, v[1] as f32, v[2] as f32))
+        .collect())
+}
+
+/// Returns the vertex indices of the triangles of a primitive, leaving out
+/// triangles with vertices beyond `count`.
+fn triangles(
+    root: &json::Root,
+    buffers: &[buffer::Data],
+    indices: Option<usize>,
+    mode: Mode,
+    count: usize,
+) -> Result<Vec<[usize; 3]>> {
+    let triangles = match indices {
+        Some(indices) => {
+            let indices = data::read(root, buffers, indices)?;
+            Triangles::owned(
+                mode,
+                indices.into_iter().map(|index| index as u32).collect(),
+            )
+        }
+        None => Triangles::sequential(mode, count as u32),
+    };
+    Ok(triangles
+        .map(|[a, b, c]| [a as usize, b as usize, c as usize])
+        .filter(|triangle| triangle.iter().all(|&vertex| vertex < count))
+        .collect())
+}
+
+/// Flattens the components of each element into one vector.
+fn flatten<I, const N: usize>(elements: I) -> Vec<f64>
+where
+    I: Iterator<Item = [f32; N]>,
+{
+    elements.flatten().map(f64::from).collect()
+}
+
+/// Normalizes `v`, or returns `fallback` if it has no length.
+fn normalize_or(v: Vector3, fallback: Vector3) -> Vector3 {
+    let magnitude = v.magnitude();
+    if magnitude > f32::EPSILON {
+        v * (1.0 / magnitude)
+    } else {
+        fallback
+    }
+}
+
+/// Computes area weighted vertex normals.
+fn vertex_normals(positions: &[Vector3], triangles: &[[usize; 3]]) -> Vec<Vector3> {
+    let zero = Vector3::new(0.0, 0.0, 0.0);
+    let mut sums = vec![zero; positions.len()];
+    for &[a, b, c] in triangles {
+        // The length of the cross product is twice the area of the triangle.
+        let normal = (positions[b] - positions[a]).cross(positions[c] - positions[a]);
+        for vertex in [a, b, c] {
+            sums[vertex] = sums[vertex] + normal;
+        }
+    }
+    sums.into_iter()
+        .map(|sum| normalize_or(sum, Vector3::new(0.0, 0.0, 1.0)))
+        .collect()
+}
+
+/// Computes the normal of each triangle, once for each of its corners.
+fn face_normals(positions: &[Vector3], triangles: &[[usize; 3]]) -> Vec<Vector3> {
+    triangles
+        .iter()
+        .flat_map(|&[a, b, c]| {
+            let normal = (positions[b] - positions[a]).cross(positions[c] - positions[a]);
+            [normalize_or(normal, Vector3::new(0.0, 0.0, 1.0)); 3]
+        })
+        .collect()
+}
+
+/// Computes vertex tangents with the handedness of the bitangent in W, one
+/// for each position.
+///
+/// Vertices without a normal are taken to face +Z, and triangles with
+/// vertices without texture coordinates do not contribute.
+fn vertex_tangents(
+    positions: &[Vector3],
+    normals: &[Vector3],
+    tex_coords: &[f64],
+    triangles: &[[usize; 3]],
+) -> Vec<[f32; 4]> {
+    let count = positions.len();
+    let mapped = tex_coords.len() / 2;
+    // glTF puts the texture origin at the top left, while the bitangent of a
+    // normal texture points up the image, towards decreasing V.
+    let uv = |vertex: usize| {
+        (
+            tex_coords[2 * vertex] as f32,
+            -tex_coords[2 * vertex + 1] as f32,
+        )
+    };
+
+    let zero = Vector3::new(0.0, 0.0, 0.0);
+    let mut tangents = vec![zero; count];
+    let mut bitangents = vec![zero; count];
+    for &[a, b, c] in triangles {
+        if [a, b, c]
+            .iter()
+            .any(|&vertex| vertex >= count || vertex >= mapped)
+        {
+            continue;
+        }
+        let (edge1, edge2) = (positions[b] - positions[a], positions[c] - positions[a]);
+        let ((u0, v0), (u1, v1), (u2, v2)) = (uv(a), uv(b), uv(c));
+        let (du1, dv1, du2, dv2) = (u1 - u0, v1 - v0, u2 - u0, v2 - v0);
+        let determinant = du1 * dv2 - du2 * dv1;
+        if determinant.abs() <= f32::EPSILON {
+            continue;
+        }
+        let r = 1.0 / determinant;
+        let tangent = (edge1 * dv2 - edge2 * dv1) * r;
+        let bitangent = (edge2 * du1 - edge1 * du2) * r;
+        for vertex in [a, b, c] {
+            tangents[vertex] = tangents[vertex] + tangent;
+            bitangents[vertex] = bitangents[vertex] + bitangent;
+        }
+    }
+
+    (0..count)
+        .map(|vertex| {
+            let normal = normals
+                .get(vertex)
+                .copied()
+                .unwrap_or(Vector3::new(0.0, 0.0, 1.0));
+            let normal = normalize_or(normal, Vector3::new(0.0, 0.0, 1.0));
+            let tangent = tangents[vertex] - normal * normal.dot(tangents[vertex]);
+            // Any direction orthogonal to the normal will do for vertices
+            // without usable texture coordinates.
+            let fallback = if normal.x.abs() < 0.9 {
+                Vector3::new(1.0, 0.0, 0.0)
+            } else {
+                Vector3::new(0.0, 1.0, 0.0)
+            };
+            let fallback = normalize_or(fallback - normal * normal.dot(fallback), fallback);
+            let tangent = normalize_or(tangent, fallback);
+            let w = if normal.cross(tangent).dot(bitangents[vertex]) < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            [tangent.x, tangent.y, tangent.z, w]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A unit quad facing +Z made of two triangles, with texture coordinates
+    /// that put the top of the image at +Y.
+    fn quad(material: bool) -> (json::Root, Vec<buffer::Data>) {
+        let positions: [f32; 12] = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0];
+        let tex_coords: [f32; 8] = [0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0];
+        let indices: [u16; 6] = [0, 1, 2, 0, 2, 3];
+        let mut bytes = Vec::new();
+        for value in positions.iter().chain(&tex_coords) {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for index in &indices {
+            bytes.extend_from_slice(&index.to_le_bytes());
+        }
+        let json = format!(
+            r#"{{
+                "asset": {{"version": "2.0"}},
+                "buffers": [{{"byteLength": 92}}],
+                "bufferViews": [
+                    {{"buffer": 0, "byteLength": 48}},
+                    {{"buffer": 0, "byteOffset": 48, "byteLength": 32}},
+                    {{"buffer": 0, "byteOffset": 80, "byteLength": 12}}
+                ],
+                "accessors": [
+                    {{"bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3"}},
+                    {{"bufferView": 1, "componentType": 5126, "count": 4, "type": "VEC2"}},
+                    {{"bufferView": 2, "componentType": 5123, "count": 6, "type": "SCALAR"}}
+                ],
+                "images": [{{"uri": "normal.png"}}],
+                "textures": [{{"source": 0}}],
+                "materials": [{{"normalTexture": {{"index": 0}}}}],
+                "meshes": [{{
+                    "primitives": [
+                        {{"attributes": {{"POSITION": 0, "TEXCOORD_0": 1}}, "indices": 2{}}},
+                        {{"attributes": {{"POSITION": 0, "TEXCOORD_0": 1}}, "indices": 2}}
+                    ]
+                }}]
+            }}"#,
+            if material { r#", "material": 0"# } else { "" },
+        );
+        let root = json::deserialize::from_str(&json).unwrap();
+        (root, vec![buffer::Data(bytes)])
+    }
+
+    #[test]
+    fn generates_normals() {
+        let (mut root, mut buffers) = quad(false);
+        generate_normals(&mut root, &mut buffers).unwrap();
+
+        let primitives = &root.meshes[0].primitives;
+        let normals = attribute(&primitives[0], Semantic::Normals).unwrap();
+        assert_eq!(attribute(&primitives[1], Semantic::Normals), Some(normals));
+        assert_eq!(root.buffers.len(), 2);
+        let values = data::read(&root, &buffers, normals).unwrap();
+        assert_eq!(values.len(), 12);
+        for normal in values.chunks(3) {
+            assert_eq!(normal, [0.0, 0.0, 1.0]);
+        }
+
+        // Primitives that have normals are left alone.
+        generate_normals(&mut root, &mut buffers).unwrap();
+        assert_eq!(root.buffers.len(), 2);
+    }
+
+    #[test]
+    fn generates_tangents() {
+        let (mut root, mut buffers) = quad(true);
+        generate_tangents(&mut root, &mut buffers).unwrap();
+        assert!(attribute(&root.meshes[0].primitives[0], Semantic::Tangents).is_none());
+
+        generate_normals(&mut root, &mut buffers).unwrap();
+        generate_tangents(&mut root, &mut buffers).unwrap();
+        let primitives = &root.meshes[0].primitives;
+        let tangents = attribute(&primitives[0], Semantic::Tangents).unwrap();
+        // Without a normal texture the second primitive needs no tangents.
+        assert_eq!(attribute(&primitives[1], Semantic::Tangents), None);
+        let values = data::read(&root, &buffers, tangents).unwrap();
+        assert_eq!(values.len(), 16);
+        for tangent in values.chunks(4) {
+            assert_eq!(tangent, [1.0, 0.0, 0.0, 1.0]);
+        }
+    }
+
+    #[test]
+    fn generates_flat_normals() {
+        let (mut root, mut buffers) = quad(false);
+        generate_flat_normals(&mut root, &mut buffers).unwrap();
+
+        let primitives = &root.meshes[0].primitives;
+        assert!(primitives[0].indices.is_none());
+        assert_eq!(primitives[0].mode, Checked::Valid(Mode::Triangles));
+        let positions = attribute(&primitives[0], Semantic::Positions).unwrap();
+        let tex_coords = attribute(&primitives[0], Semantic::TexCoords(0)).unwrap();
+        let normals = attribute(&primitives[0], Semantic::Normals).unwrap();
+        assert_eq!(
+            attribute(&primitives[1], Semantic::Positions),
+            Some(positions)
+        );
+        assert_eq!(attribute(&primitives[1], Semantic::Normals), Some(normals));
+        assert_eq!(
+            data::read(&root, &buffers, positions).unwrap(),
+            [
+                0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, //
+                0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0,
+            ]
+        );
+        assert_eq!(
+            root.accessors[positions].min,
+            Some(json::Value::from(vec![0.0, 0.0, 0.0]))
+        );
+        assert_eq!(data::read(&root, &buffers, tex_coords).unwrap().len(), 12);
+        let values = data::read(&root, &buffers, normals).unwrap();
+        assert_eq!(values.len(), 18);
+        for normal in values.chunks(3) {
+            assert_eq!(normal, [0.0, 0.0, 1.0]);
+        }
+
+        // Primitives that have normals are left alone.
+        let accessors = root.accessors.len();
+        generate_flat_normals(&mut root, &mut buffers).unwrap();
+        assert_eq!(root.accessors.len(), accessors);
+    }
+
+    #[test]
+    fn flat_normals_of_a_fold() {
+        let positions = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ];
+        let normals = face_normals(&positions, &[[0, 1, 2], [0, 3, 1]]);
+        let up = Vector3::new(0.0, 0.0, 1.0);
+        let front = Vector3::new(0.0, 1.0, 0.0);
+        assert_eq!(normals, [up, up, up, front, front, front]);
+    }
+
+    #[test]
+    fn one_tangent_per_position() {
+        let positions = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(1.0, 1.0, 0.0),
+        ];
+        let normals = [Vector3::new(0.0, 0.0, 1.0); 2];
+        let tangents = vertex_tangents(&positions, &normals, &[0.0; 6], &[[0, 1, 2]]);
+        assert_eq!(tangents.len(), positions.len());
+    }
+
+    #[test]
+    fn degenerate_triangles() {
+        let positions = [Vector3::new(0.0, 0.0, 0.0); 3];
+        let normals = vertex_normals(&positions, &[[0, 1, 2]]);
+        assert_eq!(normals, [Vector3::new(0.0, 0.0, 1.0); 3]);
+
+        let normals = [Vector3::new(1.0, 0.0, 0.0); 3];
+        let tangents = vertex_tangents(&positions, &normals, &[0.0; 6], &[[0, 1, 2]]);
+        assert_eq!(tangents, [[0.0, 1.0, 0.0, 1.0]; 3]);
+    }
+}
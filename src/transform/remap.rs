@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use super::Kind;
+
+/// Where a transform pass moved objects, mapping the old index of every object
+/// to its new index.
+///
+/// Returned by passes that remove or merge objects, so that indices held
+/// outside of the document, such as the selected node of an editor, can be
+/// updated to match.
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// use gltf::usage::Kind;
+///
+/// let (document, _, _) = gltf::import("examples/Box.gltf")?;
+/// let mut root = document.into_json();
+/// let selected = 0;
+/// let remap = gltf::transform::prune(&mut root);
+/// // Nodes are never pruned, so the selection is still valid.
+/// assert_eq!(remap.get(Kind::Node, selected), Some(selected));
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Remap {
+    /// The new index of every old index, or `None` for removed objects, for
+    /// every kind of object that was moved.
+    maps: HashMap<Kind, Vec<Option<usize>>>,
+}
+
+impl Remap {
+    /// Returns the new index of the object of the given kind and old index,
+    /// or `None` if the object was removed.
+    ///
+    /// Indices of objects that were not moved are returned unchanged, and
+    /// objects that were merged into another return the index of the object
+    /// they were merged into.
+    pub fn get(&self, kind: Kind, index: usize) -> Option<usize> {
+        match self.maps.get(&kind) {
+            Some(map) => map.get(index).copied().flatten(),
+            None => Some(index),
+        }
+    }
+
+    /// Returns the new index of every old index of the given kind of object,
+    /// or `None` if no object of that kind was moved.
+    pub fn indices(&self, kind: Kind) -> Option<&[Option<usize>]> {
+        self.maps.get(&kind).map(Vec::as_slice)
+    }
+
+    /// Returns `true` if no objects were moved.
+    pub fn is_empty(&self) -> bool {
+        self.maps.is_empty()
+    }
+
+    /// Records that the objects of the given kind were moved according to
+    /// `map`, after any moves already recorded.
+    pub(crate) fn record(&mut self, kind: Kind, map: Vec<Option<usize>>) {
+        if map.iter().enumerate().all(|(old, &new)| new == Some(old)) {
+            return;
+        }
+        match self.maps.get_mut(&kind) {
+            Some(existing) => {
+                for new in existing.iter_mut() {
+                    *new = new.and_then(|index| map.get(index).copied().flatten());
+                }
+            }
+            None => {
+                self.maps.insert(kind, map);
+            }
+        }
+    }
+
+    /// Records every move of `later`, which happened after the moves already
+    /// recorded.
+    pub fn extend(&mut self, later: Remap) {
+        for (kind, map) in later.maps {
+            self.record(kind, map);
+        }
+    }
+}
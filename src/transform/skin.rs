@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use crate::{buffer, Result};
+use json::accessor::{ComponentType, Type};
+use json::mesh::Semantic;
+use json::validation::Checked;
+
+use super::data::{self, Appender};
+use super::is_compressed;
+
+/// The number of joint influences kept per vertex by
+/// `limit_joint_influences`.
+pub const MAX_JOINT_INFLUENCES: usize = 4;
+
+/// Reduces every skinned primitive to a single set of joints and weights.
+///
+/// For primitives with `JOINTS_1` / `WEIGHTS_1` and beyond, the four joints
+/// with the largest weights are kept for each vertex and their weights are
+/// renormalized to sum to one. The result is written to new `JOINTS_0` and
+/// `WEIGHTS_0` accessors in a new buffer, whose data is appended to
+/// `buffers`, and the remaining sets are dropped from the primitive.
+///
+/// The replaced accessors are left in place; run `prune` afterwards to remove
+/// them.
+///
+/// Returns `Error::Transform` without changing anything if an accessor has
+/// an invalid format or data outside of its buffer data.
+pub fn limit_joint_influences(
+    root: &mut json::Root,
+    buffers: &mut Vec<buffer::Data>,
+) -> Result<()> {
+    data::check(root, buffers)?;
+    let mut appender = Appender::new(root);
+    let mut replaced = HashMap::new();
+
+    for m in 0..root.meshes.len() {
+        for p in 0..root.meshes[m].primitives.len() {
+            let primitive = &root.meshes[m].primitives[p];
+            if is_compressed(primitive) {
+                continue;
+            }
+            let attributes = &primitive.attributes;
+            let mut sets = Vec::new();
+            while let (Some(joints), Some(weights)) = (
+                attributes.get(&Checked::Valid(Semantic::Joints(sets.len() as u32))),
+                attributes.get(&Checked::Valid(Semantic::Weights(sets.len() as u32))),
+            ) {
+                sets.push((joints.value(), weights.value()));
+            }
+            if sets.len() < 2 {
+                continue;
+            }
+
+            let (joints, weights) = match replaced.get(&sets) {
+                Some(&accessors) => accessors,
+                None => {
+                    let accessors = combine(root, buffers, &mut appender, &sets)?;
+                    replaced.insert(sets, accessors);
+                    accessors
+                }
+            };
+
+            let attributes = &mut root.meshes[m].primitives[p].attributes;
+            attributes.retain(|semantic, _| match *semantic {
+                Checked::Valid(Semantic::Joints(set)) | Checked::Valid(Semantic::Weights(set)) => {
+                    set == 0
+                }
+                _ => true,
+            });
+            attributes.insert(Checked::Valid(Semantic::Joints(0)), joints);
+            attributes.insert(Checked::Valid(Semantic::Weights(0)), weights);
+        }
+    }
+
+    appender.finish(root, buffers);
+    Ok(())
+}
+
+/// Writes the strongest influences of the given joint and weight accessor
+/// pairs into new accessors.
+fn combine(
+    root: &mut json::Root,
+    buffers: &[buffer::Data],
+    appender: &mut Appender,
+    sets: &[(usize, usize)],
+) -> Result<(json::Index<json::Accessor>, json::Index<json::Accessor>)> {
+    let data = sets
+        .iter()
+        .map(|&(joints, weights)| {
+            Ok((
+                data::read(root, buffers, joints)?,
+                data::read_normalized(root, buffers, weights)?,
+            ))
+        })
+        .collect::<Result<Vec<(Vec<f64>, Vec<f64>)>>>()?;
+    let count = data
+        .iter()
+        .map(|(joints, _)| joints.len() / 4)
+        .min()
+        .unwrap_or(0);
+
+    let mut joints = Vec::with_capacity(count * MAX_JOINT_INFLUENCES);
+    let mut weights = Vec::with_capacity(count * MAX_JOINT_INFLUENCES);
+    let mut influences = Vec::with_capacity(data.len() * 4);
+    for vertex in 0..count {
+        influences.clear();
+        for (j, w) in &data {
+            for k in vertex * 4..(vertex + 1) * 4 {
+                influences.push((j[k], w[k]));
+            }
+        }
+        // Stable, so ties favour the earlier sets.
+        influences.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        influences.truncate(MAX_JOINT_INFLUENCES);
+
+        let sum: f64 = influences.iter().map(|&(_, weight)| weight).sum();
+        for &(joint, weight) in &influences {
+            joints.push(if weight > 0.0 { joint } else { 0.0 });
+            weights.push(if sum > 0.0 { weight / sum } else { 0.0 });
+        }
+    }
+
+    let joint_type = if joints.iter().all(|&joint| joint <= u8::MAX as f64) {
+        ComponentType::U8
+    } else {
+        ComponentType::U16
+    };
+    let joints = appender.push(root, joint_type, Type::Vec4, false, &joints);
+    let weights = appender.push(root, ComponentType::F32, Type::Vec4, false, &weights);
+    Ok((joints, weights))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_strongest_influences() {
+        let joints: Vec<u8> = vec![0, 1, 2, 3, 4, 5, 6, 7];
+        let weights: Vec<f32> = vec![0.1, 0.3, 0.05, 0.05, 0.2, 0.0, 0.25, 0.05];
+        let mut bytes = joints.clone();
+        for weight in &weights {
+            bytes.extend_from_slice(&weight.to_le_bytes());
+        }
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 40}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 4},
+                {"buffer": 0, "byteOffset": 4, "byteLength": 4},
+                {"buffer": 0, "byteOffset": 8, "byteLength": 16},
+                {"buffer": 0, "byteOffset": 24, "byteLength": 16}
+            ],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5121, "count": 1, "type": "VEC4"},
+                {"bufferView": 1, "componentType": 5121, "count": 1, "type": "VEC4"},
+                {"bufferView": 2, "componentType": 5126, "count": 1, "type": "VEC4"},
+                {"bufferView": 3, "componentType": 5126, "count": 1, "type": "VEC4"}
+            ],
+            "meshes": [{
+                "primitives": [{
+                    "attributes": {
+                        "JOINTS_0": 0,
+                        "JOINTS_1": 1,
+                        "WEIGHTS_0": 2,
+                        "WEIGHTS_1": 3
+                    }
+                }]
+            }]
+        }"#;
+        let mut root = json::Root::from_str(json).unwrap();
+        let mut buffers = vec![buffer::Data(bytes)];
+
+        limit_joint_influences(&mut root, &mut buffers).unwrap();
+
+        let attributes = &root.meshes[0].primitives[0].attributes;
+        assert_eq!(attributes.len(), 2);
+        let joints = attributes[&Checked::Valid(Semantic::Joints(0))].value();
+        let weights = attributes[&Checked::Valid(Semantic::Weights(0))].value();
+        assert_eq!(
+            data::read(&root, &buffers, joints).unwrap(),
+            vec![1.0, 6.0, 4.0, 0.0]
+        );
+        let weights = data::read(&root, &buffers, weights).unwrap();
+        let expected = [0.3 / 0.85, 0.25 / 0.85, 0.2 / 0.85, 0.1 / 0.85];
+        for (weight, expected) in weights.iter().zip(&expected) {
+            assert_relative_eq!(*weight, *expected, epsilon = 1e-6);
+        }
+    }
+}
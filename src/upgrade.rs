@@ -0,0 +1,1213 @@
+use byteorder::{ByteOrder, LittleEndian};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{binary, Document, Error, Glb, Gltf, Result};
+use json::validation::Error as Invalid;
+use json::{Path, Value};
+
+/// A construct of a glTF 1.0 document that was approximated or dropped while
+/// upgrading it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warning {
+    /// The JSON path of the construct in the glTF 1.0 document, such as
+    /// `materials["skin"].technique`.
+    pub path: Path,
+
+    /// What was done with the construct.
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Splits binary glTF 1.0, as defined by `KHR_binary_glTF`, into its JSON
+/// content and its body.
+fn split_binary_gltf(slice: &[u8]) -> Result<(&[u8], &[u8])> {
+    // magic, version, length, contentLength, and contentFormat.
+    const HEADER: usize = 20;
+    if slice.len() < HEADER {
+        return Err(Error::Binary(binary::Error::Length {
+            length: HEADER as u32,
+            length_read: slice.len(),
+        }));
+    }
+    let length = LittleEndian::read_u32(&slice[8..]);
+    let content_length = LittleEndian::read_u32(&slice[12..]);
+    let content_format = LittleEndian::read_u32(&slice[16..]);
+    if content_format != 0 {
+        // 0 is the only format, JSON.
+        return Err(Error::Binary(binary::Error::UnknownChunkType(
+            content_format.to_le_bytes(),
+        )));
+    }
+    if length as usize > slice.len() {
+        return Err(Error::Binary(binary::Error::Length {
+            length,
+            length_read: slice.len(),
+        }));
+    }
+    let body = &slice[HEADER.min(length as usize)..length as usize];
+    if content_length as usize > body.len() {
+        return Err(Error::Binary(binary::Error::ChunkLength {
+            ty: binary::ChunkType::Json,
+            length: content_length,
+            length_read: body.len(),
+        }));
+    }
+    Ok(body.split_at(content_length as usize))
+}
+
+/// Upgrades glTF 1.0, or returns glTF 2.0 as is, then validates the result.
+///
+/// Both JSON and binary glTF are accepted. The body of binary glTF 1.0 becomes
+/// the blob of the result, and the `binary_glTF` buffer referring to it
+/// becomes the first buffer, without a URI, as for binary glTF 2.0.
+///
+/// See [`upgrade`].
+///
+/// [`upgrade`]: fn.upgrade.html
+pub fn from_slice(slice: &[u8]) -> Result<(Gltf, Vec<Warning>)> {
+    let (json, blob) = match slice.get(..8) {
+        Some(header) if header[..4] == *b"glTF" => {
+            if LittleEndian::read_u32(&header[4..]) == 1 {
+                let (json, body) = split_binary_gltf(slice)?;
+                (Cow::Borrowed(json), Some(body.to_vec()))
+            } else {
+                let glb = Glb::from_slice(slice)?;
+                (glb.json, glb.bin.map(Cow::into_owned))
+            }
+        }
+        _ => (Cow::Borrowed(slice), None),
+    };
+    let value: Value =
+        json::deserialize::from_slice(crate::trim_json(&json)).map_err(Error::Deserialize)?;
+    let (root, warnings) = upgrade(&value)?;
+    let document = Document::from_json(root)?;
+    Ok((Gltf { document, blob }, warnings))
+}
+
+/// Converts glTF 1.0 JSON to glTF 2.0.
+///
+/// Objects keyed by ID become arrays ordered by ID, and the IDs become the
+/// names of objects without one. Materials are approximated by metallic
+/// roughness materials from the common `diffuse`, `emission`, `shininess`,
+/// and `transparency` parameters, whether given to a technique or through
+/// `KHR_materials_common`. Accessor strides move to their buffer views, nodes
+/// instancing several meshes instance one mesh with all of their primitives,
+/// and skins find their joints by the `jointName` of nodes. The `binary_glTF`
+/// buffer of `KHR_binary_glTF` becomes the first buffer, without a URI.
+///
+/// Techniques, programs, shaders, and other constructs without a glTF 2.0
+/// equivalent are dropped with a warning. Documents that are already glTF 2.0
+/// are deserialized without warnings. The result is not validated.
+///
+/// Returns `Error::Validation` if an ID refers to no object.
+///
+/// # Examples
+///
+/// ```
+/// # fn run() -> Result<(), gltf::Error> {
+/// let v1 = br#"{
+///     "asset": {"version": "1.0"},
+///     "scene": "main",
+///     "scenes": {"main": {"nodes": ["root"]}},
+///     "nodes": {"root": {"children": []}}
+/// }"#;
+/// let (gltf, warnings) = gltf::upgrade::from_slice(v1)?;
+/// assert!(warnings.is_empty());
+/// assert_eq!(gltf.nodes().len(), 1);
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().expect("test failure");
+/// # }
+/// ```
+pub fn upgrade(v1: &Value) -> Result<(json::Root, Vec<Warning>)> {
+    let version = v1
+        .get("asset")
+        .and_then(|asset| asset.get("version"))
+        .and_then(Value::as_str);
+    if version.is_some_and(|version| version.starts_with('2')) {
+        let root = json::deserialize::from_value(v1.clone()).map_err(Error::Deserialize)?;
+        return Ok((root, Vec::new()));
+    }
+    if !v1.is_object() {
+        return Err(Error::Validation(vec![(Path::new(), Invalid::Invalid)]));
+    }
+
+    let mut upgrader = Upgrader::new(v1);
+    let v2 = upgrader.root();
+    if !upgrader.errors.is_empty() {
+        return Err(Error::Validation(upgrader.errors));
+    }
+    let root = json::deserialize::from_value(v2).map_err(Error::Deserialize)?;
+    Ok((root, upgrader.warnings))
+}
+
+/// Top-level dictionaries of glTF 1.0 that become arrays of glTF 2.0.
+const DICTIONARIES: &[&str] = &[
+    "accessors",
+    "animations",
+    "buffers",
+    "bufferViews",
+    "cameras",
+    "images",
+    "materials",
+    "meshes",
+    "nodes",
+    "samplers",
+    "scenes",
+    "skins",
+    "textures",
+];
+
+/// Top-level dictionaries of glTF 1.0 without a glTF 2.0 equivalent.
+const DROPPED: &[&str] = &["programs", "shaders", "techniques"];
+
+/// Extensions of glTF 1.0 that the upgrade applies.
+const UPGRADED_EXTENSIONS: &[&str] = &["KHR_binary_glTF", "KHR_materials_common"];
+
+/// `GL_CULL_FACE`, which a technique enables for single sided materials.
+const CULL_FACE: u64 = 2884;
+
+/// `GL_BLEND`, which a technique enables for transparent materials.
+const BLEND: u64 = 3042;
+
+/// `GL_ELEMENT_ARRAY_BUFFER`, the target of buffer views of indices.
+const ELEMENT_ARRAY_BUFFER: u64 = 34963;
+
+/// Returns an empty JSON object.
+fn object() -> Value {
+    Value::Object(Default::default())
+}
+
+/// The ID of the buffer holding the body of binary glTF 1.0.
+const BINARY_GLTF: &str = "binary_glTF";
+
+/// Returns the objects of a top-level dictionary with their IDs, in ID order
+/// but for the `binary_glTF` buffer, which comes first as binary glTF 2.0
+/// requires.
+fn objects<'a>(v1: &'a Value, dictionary: &str) -> Vec<(&'a str, &'a Value)> {
+    let mut objects: Vec<_> = v1
+        .get(dictionary)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(id, value)| (id.as_str(), value))
+        .collect();
+    if dictionary == "buffers" {
+        objects.sort_by_key(|&(id, _)| id != BINARY_GLTF);
+    }
+    objects
+}
+
+/// Returns the path of an object of a top-level dictionary.
+fn at(dictionary: &str, id: &str) -> Path {
+    Path::new().field(dictionary).key(id)
+}
+
+/// The state of an upgrade.
+struct Upgrader<'a> {
+    /// The glTF 1.0 document.
+    v1: &'a Value,
+
+    /// The index of each object of the top-level dictionaries, by ID.
+    indices: HashMap<&'static str, HashMap<&'a str, usize>>,
+
+    /// The primitives of the meshes created for nodes instancing several
+    /// meshes.
+    merged: Vec<Vec<Value>>,
+
+    /// The IDs that refer to no object.
+    errors: Vec<(Path, Invalid)>,
+
+    /// The constructs approximated or dropped.
+    warnings: Vec<Warning>,
+}
+
+impl<'a> Upgrader<'a> {
+    fn new(v1: &'a Value) -> Self {
+        let mut indices = HashMap::new();
+        for &dictionary in DICTIONARIES {
+            let ids = objects(v1, dictionary)
+                .into_iter()
+                .enumerate()
+                .map(|(index, (id, _))| (id, index))
+                .collect();
+            indices.insert(dictionary, ids);
+        }
+        Self {
+            v1,
+            indices,
+            merged: Vec::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn warn(&mut self, path: Path, message: impl Into<String>) {
+        self.warnings.push(Warning {
+            path,
+            message: message.into(),
+        });
+    }
+
+    /// Returns the objects of a top-level dictionary with their IDs.
+    fn objects(&self, dictionary: &str) -> Vec<(&'a str, &'a Value)> {
+        objects(self.v1, dictionary)
+    }
+
+    /// Returns the index of the object of a top-level dictionary with the ID
+    /// given by `id`, recording an error at `path` if there is none.
+    fn index(&mut self, dictionary: &'static str, id: &Value, path: Path) -> Value {
+        let index = id
+            .as_str()
+            .and_then(|id| self.indices[dictionary].get(id).cloned());
+        match index {
+            Some(index) => Value::from(index),
+            None => {
+                self.errors.push((path, Invalid::IndexOutOfBounds));
+                Value::Null
+            }
+        }
+    }
+
+    /// Copies the given members of `v1`, if present, to `v2`.
+    fn copy(v1: &Value, v2: &mut Value, members: &[&str]) {
+        for &member in members {
+            if let Some(value) = v1.get(member) {
+                v2[member] = value.clone();
+            }
+        }
+    }
+
+    /// Starts a glTF 2.0 object from a glTF 1.0 one, keeping its name, or
+    /// naming it by its ID, and its extras, and dropping its extensions.
+    fn start(&mut self, v1: &Value, id: &str, path: &Path) -> Value {
+        let mut v2 = object();
+        v2["name"] = v1.get("name").cloned().unwrap_or_else(|| Value::from(id));
+        Self::copy(v1, &mut v2, &["extras"]);
+        self.drop_extensions(v1, path);
+        v2
+    }
+
+    /// Warns about the extensions of an object that are not upgraded.
+    fn drop_extensions(&mut self, v1: &Value, path: &Path) {
+        let names = v1
+            .get("extensions")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flat_map(|extensions| extensions.keys());
+        for name in names {
+            if !UPGRADED_EXTENSIONS.contains(&name.as_str()) {
+                self.warn(
+                    path.field("extensions").key(name),
+                    "extension has no glTF 2.0 equivalent and was dropped",
+                );
+            }
+        }
+    }
+
+    fn root(&mut self) -> Value {
+        let v1 = self.v1;
+        let mut v2 = object();
+        v2["asset"] = self.asset();
+        Self::copy(v1, &mut v2, &["extras"]);
+        self.drop_extensions(v1, &Path::new());
+        for &dictionary in DROPPED {
+            if v1.get(dictionary).is_some() {
+                self.warn(
+                    Path::new().field(dictionary),
+                    "has no glTF 2.0 equivalent and was dropped",
+                );
+            }
+        }
+        for name in v1
+            .get("extensionsUsed")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !UPGRADED_EXTENSIONS.contains(&name) {
+                self.warn(
+                    Path::new().field("extensionsUsed"),
+                    format!("extension `{}` is not upgraded and was dropped", name),
+                );
+            }
+        }
+
+        v2["buffers"] = self.buffers();
+        v2["bufferViews"] = self.buffer_views();
+        v2["accessors"] = self.accessors();
+        v2["images"] = self.images();
+        v2["samplers"] = self.samplers();
+        v2["textures"] = self.textures();
+        v2["materials"] = self.materials();
+        v2["cameras"] = self.cameras();
+        v2["skins"] = self.skins();
+        v2["nodes"] = self.nodes();
+        v2["animations"] = self.animations();
+        v2["scenes"] = self.scenes();
+        let mut meshes = self.meshes();
+        for primitives in self.merged.drain(..) {
+            let mut mesh = object();
+            mesh["primitives"] = Value::from(primitives);
+            meshes.push(mesh);
+        }
+        v2["meshes"] = Value::from(meshes);
+        if let Some(scene) = v1.get("scene") {
+            v2["scene"] = self.index("scenes", scene, Path::new().field("scene"));
+        }
+        v2
+    }
+
+    fn asset(&mut self) -> Value {
+        let mut asset = object();
+        asset["version"] = Value::from("2.0");
+        if let Some(v1) = self.v1.get("asset") {
+            Self::copy(v1, &mut asset, &["copyright", "generator", "extras"]);
+            if v1.get("premultipliedAlpha").and_then(Value::as_bool) == Some(true) {
+                self.warn(
+                    Path::new().field("asset").field("premultipliedAlpha"),
+                    "premultiplied alpha has no glTF 2.0 equivalent and was dropped",
+                );
+            }
+        }
+        asset
+    }
+
+    fn buffers(&mut self) -> Value {
+        let mut buffers = Vec::new();
+        for (id, v1) in self.objects("buffers") {
+            let path = at("buffers", id);
+            let mut v2 = self.start(v1, id, &path);
+            // The body of binary glTF 1.0 becomes the blob of binary glTF 2.0,
+            // which is referred to without a URI.
+            if id != BINARY_GLTF {
+                Self::copy(v1, &mut v2, &["uri"]);
+            }
+            v2["byteLength"] = v1.get("byteLength").cloned().unwrap_or_else(|| 0.into());
+            buffers.push(v2);
+        }
+        Value::from(buffers)
+    }
+
+    fn buffer_views(&mut self) -> Value {
+        // glTF 2.0 moves the stride of accessors to their buffer views.
+        let mut strides = HashMap::new();
+        for (id, accessor) in self.objects("accessors") {
+            let stride = accessor.get("byteStride").and_then(Value::as_u64);
+            let view = accessor.get("bufferView").and_then(Value::as_str);
+            if let (Some(stride), Some(view)) = (stride.filter(|&stride| stride > 0), view) {
+                let first = *strides.entry(view).or_insert(stride);
+                if first != stride {
+                    self.warn(
+                        at("accessors", id).field("byteStride"),
+                        format!(
+                            "buffer view `{}` is shared by accessors of different strides, \
+                             so stride {} was used",
+                            view, first
+                        ),
+                    );
+                }
+            }
+        }
+
+        let mut views = Vec::new();
+        for (id, v1) in self.objects("bufferViews") {
+            let path = at("bufferViews", id);
+            let mut v2 = self.start(v1, id, &path);
+            v2["buffer"] = self.index(
+                "buffers",
+                v1.get("buffer").unwrap_or(&Value::Null),
+                path.field("buffer"),
+            );
+            Self::copy(v1, &mut v2, &["byteOffset", "target"]);
+            v2["byteLength"] = v1.get("byteLength").cloned().unwrap_or_else(|| 0.into());
+            let target = v1.get("target").and_then(Value::as_u64);
+            if let Some(&stride) = strides.get(id) {
+                if target != Some(ELEMENT_ARRAY_BUFFER) {
+                    v2["byteStride"] = Value::from(stride);
+                }
+            }
+            views.push(v2);
+        }
+        Value::from(views)
+    }
+
+    fn accessors(&mut self) -> Value {
+        let mut accessors = Vec::new();
+        for (id, v1) in self.objects("accessors") {
+            let path = at("accessors", id);
+            let mut v2 = self.start(v1, id, &path);
+            if let Some(view) = v1.get("bufferView") {
+                v2["bufferView"] = self.index("bufferViews", view, path.field("bufferView"));
+            }
+            Self::copy(
+                v1,
+                &mut v2,
+                &["byteOffset", "componentType", "count", "type", "min", "max"],
+            );
+            accessors.push(v2);
+        }
+        Value::from(accessors)
+    }
+
+    fn images(&mut self) -> Value {
+        let mut images = Vec::new();
+        for (id, v1) in self.objects("images") {
+            let path = at("images", id);
+            let mut v2 = self.start(v1, id, &path);
+            let binary = v1
+                .get("extensions")
+                .and_then(|extensions| extensions.get("KHR_binary_glTF"));
+            match binary {
+                Some(binary) => {
+                    v2["bufferView"] = self.index(
+                        "bufferViews",
+                        binary.get("bufferView").unwrap_or(&Value::Null),
+                        path.field("extensions")
+                            .field("KHR_binary_glTF")
+                            .field("bufferView"),
+                    );
+                    Self::copy(binary, &mut v2, &["mimeType"]);
+                }
+                None => Self::copy(v1, &mut v2, &["uri"]),
+            }
+            images.push(v2);
+        }
+        Value::from(images)
+    }
+
+    fn samplers(&mut self) -> Value {
+        let mut samplers = Vec::new();
+        for (id, v1) in self.objects("samplers") {
+            let path = at("samplers", id);
+            let mut v2 = self.start(v1, id, &path);
+            Self::copy(v1, &mut v2, &["magFilter", "minFilter", "wrapS", "wrapT"]);
+            samplers.push(v2);
+        }
+        Value::from(samplers)
+    }
+
+    fn textures(&mut self) -> Value {
+        let mut textures = Vec::new();
+        for (id, v1) in self.objects("textures") {
+            let path = at("textures", id);
+            let mut v2 = self.start(v1, id, &path);
+            if let Some(sampler) = v1.get("sampler") {
+                v2["sampler"] = self.index("samplers", sampler, path.field("sampler"));
+            }
+            if let Some(source) = v1.get("source") {
+                v2["source"] = self.index("images", source, path.field("source"));
+            }
+            // The defaults are `GL_TEXTURE_2D` and `GL_UNSIGNED_BYTE`.
+            for (member, default) in [("target", 3553), ("type", 5121)] {
+                let value = v1.get(member).and_then(Value::as_u64);
+                if value.is_some_and(|value| value != default) {
+                    self.warn(
+                        path.field(member),
+                        "only 2D textures of unsigned bytes exist in glTF 2.0",
+                    );
+                }
+            }
+            textures.push(v2);
+        }
+        Value::from(textures)
+    }
+
+    fn materials(&mut self) -> Value {
+        let mut materials = Vec::new();
+        for (id, v1) in self.objects("materials") {
+            let path = at("materials", id);
+            let mut v2 = self.start(v1, id, &path);
+            let common = v1
+                .get("extensions")
+                .and_then(|extensions| extensions.get("KHR_materials_common"));
+            let (values, values_path) = match common {
+                Some(common) => (
+                    common.get("values"),
+                    path.field("extensions")
+                        .field("KHR_materials_common")
+                        .field("values"),
+                ),
+                None => (v1.get("values"), path.field("values")),
+            };
+
+            let mut double_sided = false;
+            let mut blend = false;
+            if let Some(common) = common {
+                double_sided = common.get("doubleSided").and_then(Value::as_bool) == Some(true);
+                blend = common.get("transparent").and_then(Value::as_bool) == Some(true);
+            } else if let Some(technique) = v1.get("technique").and_then(Value::as_str) {
+                self.warn(
+                    path.field("technique"),
+                    format!(
+                        "technique `{}` was replaced by a metallic roughness approximation",
+                        technique
+                    ),
+                );
+                let enable = self
+                    .v1
+                    .get("techniques")
+                    .and_then(|techniques| techniques.get(technique))
+                    .and_then(|technique| technique.get("states"))
+                    .and_then(|states| states.get("enable"))
+                    .and_then(Value::as_array);
+                let enabled = |state| {
+                    enable.is_some_and(|enable| {
+                        enable.iter().any(|value| value.as_u64() == Some(state))
+                    })
+                };
+                double_sided = !enabled(CULL_FACE);
+                blend = enabled(BLEND);
+            }
+
+            let mut pbr = object();
+            pbr["metallicFactor"] = Value::from(0.0);
+            let mut alpha = 1.0;
+            let values = values.and_then(Value::as_object).into_iter().flatten();
+            for (name, value) in values {
+                let path = || values_path.key(name);
+                match name.trim_start_matches("u_") {
+                    "diffuse" => match value {
+                        Value::String(_) => {
+                            pbr["baseColorTexture"] = self.texture(value, path());
+                        }
+                        _ => pbr["baseColorFactor"] = Self::color(value, 4),
+                    },
+                    "emission" => match value {
+                        Value::String(_) => v2["emissiveTexture"] = self.texture(value, path()),
+                        _ => v2["emissiveFactor"] = Self::color(value, 3),
+                    },
+                    "shininess" => {
+                        if let Some(shininess) = Self::number(value) {
+                            let roughness = (2.0 / (shininess.max(0.0) + 2.0)).sqrt();
+                            pbr["roughnessFactor"] = Value::from(roughness);
+                        }
+                    }
+                    "transparency" => {
+                        if let Some(transparency) = Self::number(value) {
+                            alpha = transparency;
+                        }
+                    }
+                    _ => self.warn(path(), "has no glTF 2.0 equivalent and was dropped"),
+                }
+            }
+            if alpha < 1.0 {
+                let factor = pbr
+                    .get("baseColorFactor")
+                    .cloned()
+                    .unwrap_or_else(|| Value::from(vec![1.0, 1.0, 1.0, 1.0]));
+                let mut factor: Vec<f64> = factor
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_f64)
+                    .collect();
+                factor.resize(4, 1.0);
+                factor[3] *= alpha;
+                pbr["baseColorFactor"] = Value::from(factor);
+                blend = true;
+            }
+            v2["pbrMetallicRoughness"] = pbr;
+            if blend {
+                v2["alphaMode"] = Value::from("BLEND");
+            }
+            if double_sided {
+                v2["doubleSided"] = Value::from(true);
+            }
+            materials.push(v2);
+        }
+        Value::from(materials)
+    }
+
+    /// Returns the number of a material parameter, which glTF 1.0 may give
+    /// as an array of one number.
+    fn number(value: &Value) -> Option<f64> {
+        match value {
+            Value::Array(array) => array.first().and_then(Value::as_f64),
+            _ => value.as_f64(),
+        }
+    }
+
+    /// Returns a color of `len` components, padding colors without alpha.
+    fn color(value: &Value, len: usize) -> Value {
+        let mut color: Vec<f64> = value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_f64)
+            .map(|component| component.clamp(0.0, 1.0))
+            .collect();
+        color.resize(len, 1.0);
+        Value::from(color)
+    }
+
+    /// Returns the texture info of a material parameter naming a texture.
+    fn texture(&mut self, id: &Value, path: Path) -> Value {
+        let mut info = object();
+        info["index"] = self.index("textures", id, path);
+        info
+    }
+
+    fn cameras(&mut self) -> Value {
+        let mut cameras = Vec::new();
+        for (id, v1) in self.objects("cameras") {
+            let path = at("cameras", id);
+            let mut v2 = self.start(v1, id, &path);
+            Self::copy(v1, &mut v2, &["type", "perspective", "orthographic"]);
+            cameras.push(v2);
+        }
+        Value::from(cameras)
+    }
+
+    fn skins(&mut self) -> Value {
+        let joints: HashMap<&str, usize> = self
+            .objects("nodes")
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, (_, node))| {
+                let name = node.get("jointName")?.as_str()?;
+                Some((name, index))
+            })
+            .collect();
+
+        let mut skins = Vec::new();
+        for (id, v1) in self.objects("skins") {
+            let path = at("skins", id);
+            let mut v2 = self.start(v1, id, &path);
+            if let Some(matrices) = v1.get("inverseBindMatrices") {
+                v2["inverseBindMatrices"] =
+                    self.index("accessors", matrices, path.field("inverseBindMatrices"));
+            }
+            let names = v1.get("jointNames").and_then(Value::as_array);
+            let mut indices = Vec::new();
+            for (i, name) in names.into_iter().flatten().enumerate() {
+                match name.as_str().and_then(|name| joints.get(name)) {
+                    Some(&index) => indices.push(Value::from(index)),
+                    None => self
+                        .errors
+                        .push((path.field("jointNames").index(i), Invalid::IndexOutOfBounds)),
+                }
+            }
+            v2["joints"] = Value::from(indices);
+
+            // glTF 2.0 names the skeleton on the skin rather than the nodes
+            // instancing it.
+            let skeleton = self
+                .objects("nodes")
+                .into_iter()
+                .filter(|(_, node)| node.get("skin").and_then(Value::as_str) == Some(id))
+                .find_map(|(node, v1)| {
+                    let skeleton = v1.get("skeletons")?.as_array()?.first()?;
+                    Some((node, skeleton))
+                });
+            if let Some((node, skeleton)) = skeleton {
+                v2["skeleton"] = self.index(
+                    "nodes",
+                    skeleton,
+                    at("nodes", node).field("skeletons").index(0),
+                );
+            }
+
+            let identity = [
+                1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+            ];
+            let bind_shape = v1.get("bindShapeMatrix").and_then(Value::as_array);
+            if bind_shape.is_some_and(|matrix| {
+                matrix.len() != 16
+                    || matrix
+                        .iter()
+                        .zip(identity)
+                        .any(|(value, identity)| value.as_f64() != Some(identity))
+            }) {
+                self.warn(
+                    path.field("bindShapeMatrix"),
+                    "bind shape matrices other than the identity have no glTF 2.0 \
+                     equivalent and were dropped",
+                );
+            }
+            skins.push(v2);
+        }
+        Value::from(skins)
+    }
+
+    fn nodes(&mut self) -> Value {
+        let mesh_count = self.indices["meshes"].len();
+        let mut nodes = Vec::new();
+        for (id, v1) in self.objects("nodes") {
+            let path = at("nodes", id);
+            let mut v2 = self.start(v1, id, &path);
+            Self::copy(v1, &mut v2, &["rotation", "scale", "translation"]);
+            let identity = [
+                1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0,
+            ];
+            if let Some(matrix) = v1.get("matrix").and_then(Value::as_array) {
+                let matrix: Vec<f64> = matrix.iter().filter_map(Value::as_f64).collect();
+                if matrix[..] != identity[..] {
+                    v2["matrix"] = Value::from(matrix);
+                }
+            }
+            if let Some(camera) = v1.get("camera") {
+                v2["camera"] = self.index("cameras", camera, path.field("camera"));
+            }
+            if let Some(skin) = v1.get("skin") {
+                v2["skin"] = self.index("skins", skin, path.field("skin"));
+            }
+            let children = v1.get("children").and_then(Value::as_array);
+            let children: Vec<Value> = children
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .map(|(i, child)| self.index("nodes", child, path.field("children").index(i)))
+                .collect();
+            if !children.is_empty() {
+                v2["children"] = Value::from(children);
+            }
+
+            let meshes = v1.get("meshes").and_then(Value::as_array);
+            let meshes: Vec<(usize, &Value)> = meshes.into_iter().flatten().enumerate().collect();
+            match meshes[..] {
+                [] => {}
+                [(i, mesh)] => {
+                    v2["mesh"] = self.index("meshes", mesh, path.field("meshes").index(i));
+                }
+                _ => {
+                    // Instance one mesh with the primitives of all of them.
+                    let mut primitives = Vec::new();
+                    for (i, mesh) in meshes {
+                        self.index("meshes", mesh, path.field("meshes").index(i));
+                        let mesh_path = at("meshes", mesh.as_str().unwrap_or_default());
+                        let mesh = mesh
+                            .as_str()
+                            .and_then(|mesh| self.v1.get("meshes")?.get(mesh));
+                        if let Some(mesh) = mesh {
+                            primitives.extend(self.primitives(mesh, &mesh_path));
+                        }
+                    }
+                    v2["mesh"] = Value::from(mesh_count + self.merged.len());
+                    self.merged.push(primitives);
+                }
+            }
+            if v1.get("light").is_some() {
+                self.warn(
+                    path.field("light"),
+                    "lights of `KHR_materials_common` are not upgraded and were dropped",
+                );
+            }
+            nodes.push(v2);
+        }
+        Value::from(nodes)
+    }
+
+    fn meshes(&mut self) -> Vec<Value> {
+        let mut meshes = Vec::new();
+        for (id, v1) in self.objects("meshes") {
+            let path = at("meshes", id);
+            let mut v2 = self.start(v1, id, &path);
+            v2["primitives"] = Value::from(self.primitives(v1, &path));
+            meshes.push(v2);
+        }
+        meshes
+    }
+
+    fn primitives(&mut self, mesh: &Value, path: &Path) -> Vec<Value> {
+        let primitives = mesh.get("primitives").and_then(Value::as_array);
+        let mut upgraded = Vec::new();
+        for (i, v1) in primitives.into_iter().flatten().enumerate() {
+            let path = path.field("primitives").index(i);
+            let mut v2 = object();
+            Self::copy(v1, &mut v2, &["mode", "extras"]);
+            let mut attributes = object();
+            let v1_attributes = v1.get("attributes").and_then(Value::as_object);
+            for (semantic, accessor) in v1_attributes.into_iter().flatten() {
+                let path = path.field("attributes").key(semantic);
+                let index = self.index("accessors", accessor, path.clone());
+                let upgraded = match Self::semantic(semantic) {
+                    Some(upgraded) => upgraded,
+                    None => {
+                        self.warn(
+                            path,
+                            "semantic has no glTF 2.0 equivalent and was made \
+                             application specific",
+                        );
+                        format!("_{}", semantic)
+                    }
+                };
+                attributes[upgraded] = index;
+            }
+            v2["attributes"] = attributes;
+            if let Some(indices) = v1.get("indices") {
+                v2["indices"] = self.index("accessors", indices, path.field("indices"));
+            }
+            if let Some(material) = v1.get("material") {
+                v2["material"] = self.index("materials", material, path.field("material"));
+            }
+            self.drop_extensions(v1, &path);
+            upgraded.push(v2);
+        }
+        upgraded
+    }
+
+    /// Returns the glTF 2.0 name of a glTF 1.0 attribute semantic, if any.
+    fn semantic(semantic: &str) -> Option<String> {
+        let (name, set) = match semantic.rfind('_') {
+            Some(i) if semantic[i + 1..].parse::<u32>().is_ok() => {
+                (&semantic[..i], &semantic[i + 1..])
+            }
+            _ => (semantic, "0"),
+        };
+        match name {
+            "POSITION" | "NORMAL" if set == "0" => Some(name.to_string()),
+            _ if semantic.starts_with('_') => Some(semantic.to_string()),
+            "TEXCOORD" | "COLOR" => Some(format!("{}_{}", name, set)),
+            "JOINT" => Some(format!("JOINTS_{}", set)),
+            "WEIGHT" => Some(format!("WEIGHTS_{}", set)),
+            _ => None,
+        }
+    }
+
+    fn animations(&mut self) -> Value {
+        let mut animations = Vec::new();
+        for (id, v1) in self.objects("animations") {
+            let path = at("animations", id);
+            let mut v2 = self.start(v1, id, &path);
+            let parameters = v1.get("parameters");
+
+            let samplers = v1.get("samplers").and_then(Value::as_object);
+            let mut sampler_indices = HashMap::new();
+            let mut upgraded_samplers = Vec::new();
+            for (index, (sampler_id, sampler)) in samplers.into_iter().flatten().enumerate() {
+                let path = path.field("samplers").key(sampler_id);
+                sampler_indices.insert(sampler_id.as_str(), index);
+                let mut upgraded = object();
+                Self::copy(sampler, &mut upgraded, &["interpolation", "extras"]);
+                for member in ["input", "output"] {
+                    let accessor = sampler
+                        .get(member)
+                        .and_then(Value::as_str)
+                        .and_then(|parameter| parameters?.get(parameter))
+                        .unwrap_or(&Value::Null);
+                    upgraded[member] = self.index("accessors", accessor, path.field(member));
+                }
+                upgraded_samplers.push(upgraded);
+            }
+
+            let mut channels = Vec::new();
+            let v1_channels = v1.get("channels").and_then(Value::as_array);
+            for (i, channel) in v1_channels.into_iter().flatten().enumerate() {
+                let path = path.field("channels").index(i);
+                let mut upgraded = object();
+                Self::copy(channel, &mut upgraded, &["extras"]);
+                let sampler = channel.get("sampler").and_then(Value::as_str);
+                match sampler.and_then(|sampler| sampler_indices.get(sampler)) {
+                    Some(&index) => upgraded["sampler"] = Value::from(index),
+                    None => self
+                        .errors
+                        .push((path.field("sampler"), Invalid::IndexOutOfBounds)),
+                }
+                let target = channel.get("target");
+                let mut upgraded_target = object();
+                upgraded_target["node"] = self.index(
+                    "nodes",
+                    target
+                        .and_then(|target| target.get("id"))
+                        .unwrap_or(&Value::Null),
+                    path.field("target").field("id"),
+                );
+                if let Some(property) = target.and_then(|target| target.get("path")) {
+                    upgraded_target["path"] = property.clone();
+                }
+                upgraded["target"] = upgraded_target;
+                channels.push(upgraded);
+            }
+
+            v2["channels"] = Value::from(channels);
+            v2["samplers"] = Value::from(upgraded_samplers);
+            animations.push(v2);
+        }
+        Value::from(animations)
+    }
+
+    fn scenes(&mut self) -> Value {
+        let mut scenes = Vec::new();
+        for (id, v1) in self.objects("scenes") {
+            let path = at("scenes", id);
+            let mut v2 = self.start(v1, id, &path);
+            let nodes = v1.get("nodes").and_then(Value::as_array);
+            let nodes: Vec<Value> = nodes
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .map(|(i, node)| self.index("nodes", node, path.field("nodes").index(i)))
+                .collect();
+            v2["nodes"] = Value::from(nodes);
+            scenes.push(v2);
+        }
+        Value::from(scenes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upgrade_str(v1: &str) -> Result<(json::Root, Vec<Warning>)> {
+        upgrade(&json::deserialize::from_str(v1).unwrap())
+    }
+
+    #[test]
+    fn upgrade_document() {
+        let (root, warnings) = upgrade_str(
+            r#"{
+                "asset": {"version": "1.0", "generator": "modeller"},
+                "scene": "main",
+                "scenes": {"main": {"nodes": ["root"]}},
+                "nodes": {
+                    "root": {"children": ["a", "b"], "meshes": ["box", "cone"]},
+                    "a": {"matrix": [1,0,0,0, 0,1,0,0, 0,0,1,0, 0,0,0,1]},
+                    "b": {"meshes": ["box"], "translation": [1, 2, 3]}
+                },
+                "buffers": {"data": {"uri": "data.bin", "byteLength": 96, "type": "arraybuffer"}},
+                "bufferViews": {
+                    "indices": {"buffer": "data", "byteLength": 12, "target": 34963},
+                    "vertices": {"buffer": "data", "byteOffset": 12, "byteLength": 84, "target": 34962}
+                },
+                "accessors": {
+                    "index": {"bufferView": "indices", "byteStride": 0, "componentType": 5123,
+                        "count": 6, "type": "SCALAR"},
+                    "position": {"bufferView": "vertices", "byteStride": 28, "componentType": 5126,
+                        "count": 3, "type": "VEC3", "min": [0, 0, 0], "max": [1, 1, 0]},
+                    "tint": {"bufferView": "vertices", "byteOffset": 12, "byteStride": 28,
+                        "componentType": 5126, "count": 3, "type": "VEC4"}
+                },
+                "materials": {
+                    "red": {
+                        "technique": "blinn",
+                        "values": {"diffuse": [1, 0, 0, 1], "shininess": 2, "transparency": 0.5,
+                            "specular": [1, 1, 1, 1]}
+                    },
+                    "common": {
+                        "name": "Common",
+                        "extensions": {"KHR_materials_common": {
+                            "technique": "LAMBERT",
+                            "doubleSided": true,
+                            "values": {"diffuse": "wood", "emission": [0.1, 0.2, 0.3, 1]}
+                        }}
+                    }
+                },
+                "textures": {"wood": {"source": "wood", "sampler": "linear"}},
+                "images": {"wood": {"uri": "wood.png"}},
+                "samplers": {"linear": {"magFilter": 9729}},
+                "techniques": {"blinn": {"states": {"enable": [2884, 2929]}}},
+                "meshes": {
+                    "box": {"primitives": [{"attributes": {"POSITION": "position", "COLOR": "tint"},
+                        "indices": "index", "material": "red"}]},
+                    "cone": {"primitives": [{"attributes": {"POSITION": "position"},
+                        "material": "common"}]}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(root.asset.version, "2.0");
+        assert_eq!(root.asset.generator.as_deref(), Some("modeller"));
+        assert_eq!(root.scene.map(|scene| scene.value()), Some(0));
+        // Objects are ordered by ID: "a", "b", then "root".
+        assert_eq!(root.scenes[0].nodes[0].value(), 2);
+        let children = root.nodes[2].children.as_ref().unwrap();
+        assert_eq!(
+            children
+                .iter()
+                .map(|child| child.value())
+                .collect::<Vec<_>>(),
+            [0, 1]
+        );
+        assert!(root.nodes[0].matrix.is_none());
+        assert_eq!(root.nodes[1].mesh.unwrap().value(), 0);
+        assert_eq!(root.nodes[2].mesh.unwrap().value(), 2);
+        assert_eq!(root.meshes.len(), 3);
+        assert_eq!(root.meshes[2].primitives.len(), 2);
+
+        // Strides move to vertex buffer views only.
+        assert_eq!(root.buffer_views[0].byte_stride, None);
+        assert_eq!(root.buffer_views[1].byte_stride, Some(28));
+        let attributes = &root.meshes[0].primitives[0].attributes;
+        let color = json::validation::Checked::Valid(json::mesh::Semantic::Colors(0));
+        assert_eq!(attributes[&color].value(), 2);
+
+        let common = &root.materials[0];
+        assert!(common.double_sided);
+        let texture = common.pbr_metallic_roughness.base_color_texture.as_ref();
+        assert_eq!(texture.unwrap().index.value(), 0);
+        assert_eq!(common.emissive_factor.0, [0.1, 0.2, 0.3]);
+        let red = &root.materials[1];
+        assert!(!red.double_sided);
+        assert_eq!(
+            red.alpha_mode,
+            json::validation::Checked::Valid(json::material::AlphaMode::Blend)
+        );
+        assert_eq!(
+            red.pbr_metallic_roughness.base_color_factor.0,
+            [1.0, 0.0, 0.0, 0.5]
+        );
+        assert_eq!(red.pbr_metallic_roughness.metallic_factor.0, 0.0);
+        assert_eq!(red.pbr_metallic_roughness.roughness_factor.0, 0.5f32.sqrt());
+
+        let messages: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "techniques: has no glTF 2.0 equivalent and was dropped",
+                "materials[\"red\"].technique: technique `blinn` was replaced by a metallic \
+                 roughness approximation",
+                "materials[\"red\"].values[\"specular\"]: has no glTF 2.0 equivalent and was \
+                 dropped",
+            ]
+        );
+        Document::from_json(root).unwrap();
+    }
+
+    #[test]
+    fn upgrade_skin_and_animation() {
+        let (root, warnings) = upgrade_str(
+            r#"{
+                "asset": {"version": "1.0"},
+                "nodes": {
+                    "mesh": {"skin": "skin", "skeletons": ["hip"]},
+                    "hip": {"jointName": "Hip", "children": ["knee"]},
+                    "knee": {"jointName": "Knee"}
+                },
+                "skins": {"skin": {"jointNames": ["Knee", "Hip"], "inverseBindMatrices": "ibm",
+                    "bindShapeMatrix": [2,0,0,0, 0,1,0,0, 0,0,1,0, 0,0,0,1]}},
+                "accessors": {
+                    "ibm": {"componentType": 5126, "count": 2, "type": "MAT4"},
+                    "time": {"componentType": 5126, "count": 2, "type": "SCALAR"},
+                    "angle": {"componentType": 5126, "count": 2, "type": "VEC4"}
+                },
+                "animations": {"bend": {
+                    "parameters": {"TIME": "time", "rotation": "angle"},
+                    "samplers": {"s": {"input": "TIME", "output": "rotation",
+                        "interpolation": "LINEAR"}},
+                    "channels": [{"sampler": "s", "target": {"id": "knee", "path": "rotation"}}]
+                }}
+            }"#,
+        )
+        .unwrap();
+
+        // Nodes are ordered "hip", "knee", "mesh".
+        let skin = &root.skins[0];
+        let joints: Vec<_> = skin.joints.iter().map(|joint| joint.value()).collect();
+        assert_eq!(joints, [1, 0]);
+        assert_eq!(skin.skeleton.unwrap().value(), 0);
+        assert_eq!(skin.inverse_bind_matrices.unwrap().value(), 1);
+        assert_eq!(root.nodes[2].skin.unwrap().value(), 0);
+
+        let animation = &root.animations[0];
+        assert_eq!(animation.samplers[0].input.value(), 2);
+        assert_eq!(animation.samplers[0].output.value(), 0);
+        assert_eq!(animation.channels[0].target.node.value(), 1);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].path,
+            Path::new()
+                .field("skins")
+                .key("skin")
+                .field("bindShapeMatrix")
+        );
+    }
+
+    #[test]
+    fn unknown_ids() {
+        let result = upgrade_str(
+            r#"{
+                "asset": {"version": "1.0"},
+                "scenes": {"main": {"nodes": ["missing"]}},
+                "meshes": {"box": {"primitives": [{"attributes": {"POSITION": "none"}}]}}
+            }"#,
+        );
+        match result {
+            Err(Error::Validation(errors)) => assert_eq!(
+                errors,
+                [
+                    (
+                        Path::new()
+                            .field("scenes")
+                            .key("main")
+                            .field("nodes")
+                            .index(0),
+                        Invalid::IndexOutOfBounds
+                    ),
+                    (
+                        Path::new()
+                            .field("meshes")
+                            .key("box")
+                            .field("primitives")
+                            .index(0)
+                            .field("attributes")
+                            .key("POSITION"),
+                        Invalid::IndexOutOfBounds
+                    ),
+                ]
+            ),
+            other => panic!("unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn upgrade_binary_gltf() {
+        let json = br#"{
+            "asset": {"version": "1.0"},
+            "extensionsUsed": ["KHR_binary_glTF"],
+            "buffers": {
+                "binary_glTF": {"uri": "data:,", "byteLength": 4},
+                "another": {"uri": "another.bin", "byteLength": 8}
+            },
+            "bufferViews": {"view": {"buffer": "binary_glTF", "byteLength": 4}}
+        }"#;
+        let body = [1, 2, 3, 4];
+        let mut glb = Vec::new();
+        glb.extend_from_slice(b"glTF");
+        for word in &[
+            1,
+            (20 + json.len() + body.len()) as u32,
+            json.len() as u32,
+            0,
+        ] {
+            glb.extend_from_slice(&word.to_le_bytes());
+        }
+        glb.extend_from_slice(json);
+        glb.extend_from_slice(&body);
+
+        let (gltf, warnings) = from_slice(&glb).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(gltf.blob.as_deref(), Some(&body[..]));
+        let buffers: Vec<_> = gltf.buffers().collect();
+        assert!(matches!(buffers[0].source(), crate::buffer::Source::Bin));
+        assert_eq!(buffers[0].length(), 4);
+        assert_eq!(buffers[1].name(), Some("another"));
+        let views: Vec<_> = gltf.views().collect();
+        assert_eq!(views[0].buffer().index(), 0);
+
+        // A body shorter than the content is rejected.
+        glb.truncate(24);
+        assert!(matches!(
+            from_slice(&glb),
+            Err(Error::Binary(binary::Error::Length { .. }))
+        ));
+    }
+
+    #[test]
+    fn version_2() {
+        let (root, warnings) = upgrade_str(r#"{"asset": {"version": "2.0"}}"#).unwrap();
+        assert!(warnings.is_empty());
+        assert!(root.nodes.is_empty());
+    }
+}
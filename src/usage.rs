@@ -0,0 +1,401 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{visit, Document, Material, Node, Primitive, Scene, Texture};
+
+pub use crate::visit::Kind;
+
+/// The objects that make use of an image, texture, or material, directly or
+/// through other objects.
+///
+/// Each object is listed once, in index order.
+#[derive(Clone, Debug)]
+pub struct Usage<'a> {
+    /// Textures sampling the image.
+    pub textures: Vec<Texture<'a>>,
+
+    /// Materials referring to the textures.
+    pub materials: Vec<Material<'a>>,
+
+    /// Primitives rendered with the materials, including through
+    /// `KHR_materials_variants` mappings.
+    pub primitives: Vec<Primitive<'a>>,
+
+    /// Nodes instancing meshes that contain the primitives.
+    pub nodes: Vec<Node<'a>>,
+}
+
+impl<'a> Usage<'a> {
+    /// Finds the usage of the given textures.
+    pub(crate) fn of_textures(document: &'a Document, textures: Vec<Texture<'a>>) -> Self {
+        let indices: BTreeSet<usize> = textures.iter().map(Texture::index).collect();
+        let materials = document
+            .materials()
+            .filter(|material| {
+                material
+                    .textures()
+                    .iter()
+                    .any(|texture| indices.contains(&texture.index()))
+            })
+            .collect();
+        Self::of_materials(document, textures, materials)
+    }
+
+    /// Finds the usage of the given materials.
+    pub(crate) fn of_materials(
+        document: &'a Document,
+        textures: Vec<Texture<'a>>,
+        materials: Vec<Material<'a>>,
+    ) -> Self {
+        let indices: BTreeSet<usize> = materials.iter().filter_map(Material::index).collect();
+        let uses = |primitive: &Primitive<'a>| {
+            #[allow(unused_mut)]
+            let mut used = primitive
+                .material()
+                .index()
+                .is_some_and(|index| indices.contains(&index));
+            #[cfg(feature = "KHR_materials_variants")]
+            {
+                used |= primitive.mappings().any(|mapping| {
+                    mapping
+                        .material()
+                        .index()
+                        .is_some_and(|index| indices.contains(&index))
+                });
+            }
+            used
+        };
+
+        let mut primitives = Vec::new();
+        let mut meshes = BTreeSet::new();
+        for mesh in document.meshes() {
+            for primitive in mesh.primitives() {
+                if uses(&primitive) {
+                    meshes.insert(mesh.index());
+                    primitives.push(primitive);
+                }
+            }
+        }
+        let nodes = document
+            .nodes()
+            .filter(|node| {
+                node.mesh()
+                    .is_some_and(|mesh| meshes.contains(&mesh.index()))
+            })
+            .collect();
+
+        Self {
+            textures,
+            materials,
+            primitives,
+            nodes,
+        }
+    }
+}
+
+/// A root-level object type that can be the target of an `Index`.
+pub trait Referent {
+    /// The kind of object.
+    const KIND: Kind;
+}
+
+macro_rules! referent {
+    ($($ty:ty => $kind:ident,)*) => {
+        $(
+            impl Referent for $ty {
+                const KIND: Kind = Kind::$kind;
+            }
+        )*
+    };
+}
+
+referent! {
+    json::Accessor => Accessor,
+    json::Animation => Animation,
+    json::Buffer => Buffer,
+    json::buffer::View => BufferView,
+    json::Camera => Camera,
+    json::Image => Image,
+    json::Material => Material,
+    json::Mesh => Mesh,
+    json::Node => Node,
+    json::texture::Sampler => Sampler,
+    json::Scene => Scene,
+    json::Skin => Skin,
+    json::Texture => Texture,
+}
+
+#[cfg(feature = "KHR_lights_punctual")]
+referent! {
+    json::extensions::scene::khr_lights_punctual::Light => Light,
+}
+
+#[cfg(feature = "KHR_materials_variants")]
+referent! {
+    json::extensions::scene::khr_materials_variants::Variant => Variant,
+}
+
+/// An index stored somewhere in a document, as found by
+/// `Document::references_to`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reference {
+    /// The kind and index of the root-level object holding the index, or
+    /// `None` for the default scene of the root.
+    pub owner: Option<(Kind, usize)>,
+
+    /// The JSON path of the index, such as
+    /// `meshes[0].primitives[1].material`.
+    pub path: json::Path,
+}
+
+impl Document {
+    /// Returns every reference to the given object.
+    ///
+    /// References are grouped by their holding object, in the order of the
+    /// root-level arrays; the vertex attributes of a primitive are visited in
+    /// no particular order.
+    ///
+    /// An object with no references can be removed without leaving dangling
+    /// indices, provided the indices after it are shifted down.
+    pub fn references_to<T: Referent>(&self, target: json::Index<T>) -> Vec<Reference> {
+        let target = target.value() as u32;
+        let mut references = Vec::new();
+        visit::walk(&self.0, &mut |kind, index, owner, path| {
+            if kind == T::KIND && index == target {
+                references.push(Reference {
+                    owner,
+                    path: path(),
+                });
+            }
+        });
+        references
+    }
+}
+
+/// The objects reachable from a scene, as found by `Scene::reachable`.
+#[derive(Clone, Debug, Default)]
+pub struct Reachable {
+    objects: HashSet<(Kind, usize)>,
+}
+
+impl Reachable {
+    /// Returns whether the object of the given kind and index is reachable.
+    pub fn contains(&self, kind: Kind, index: usize) -> bool {
+        self.objects.contains(&(kind, index))
+    }
+
+    /// Returns the number of reachable objects of every kind.
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Returns `true` if no object is reachable.
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+}
+
+impl<'a> Scene<'a> {
+    /// Returns every object reachable from this scene through the reference
+    /// graph, including the scene itself.
+    ///
+    /// Objects are reachable when referred to by a reachable object, such as
+    /// the nodes of the scene, their meshes, and the accessors, buffer views,
+    /// and buffers of those, or the materials, textures, and images they are
+    /// drawn with. Animations targeting a reachable node are reachable too,
+    /// along with the data they refer to.
+    pub fn reachable(&self) -> Reachable {
+        let mut edges: HashMap<(Kind, usize), Vec<(Kind, usize)>> = HashMap::new();
+        visit::walk(&self.document.0, &mut |kind, index, owner, _| {
+            if let Some(owner) = owner {
+                let target = (kind, index as usize);
+                edges.entry(owner).or_default().push(target);
+                if owner.0 == Kind::Animation && kind == Kind::Node {
+                    // Animations are owned by the root, so follow their
+                    // target nodes backwards.
+                    edges.entry(target).or_default().push(owner);
+                }
+            }
+        });
+
+        let mut objects = HashSet::new();
+        let mut stack = vec![(Kind::Scene, self.index())];
+        while let Some(object) = stack.pop() {
+            if objects.insert(object) {
+                if let Some(targets) = edges.get(&object) {
+                    stack.extend(targets.iter().copied());
+                }
+            }
+        }
+        Reachable { objects }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_usage() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 36}],
+            "bufferViews": [{"buffer": 0, "byteLength": 36}],
+            "accessors": [{
+                "bufferView": 0,
+                "componentType": 5126,
+                "count": 3,
+                "type": "VEC3",
+                "min": [0.0, 0.0, 0.0],
+                "max": [1.0, 1.0, 0.0]
+            }],
+            "images": [{"uri": "a.png"}, {"uri": "b.png"}],
+            "textures": [{"source": 1}, {"source": 0}, {"source": 1}],
+            "materials": [
+                {"emissiveTexture": {"index": 0}},
+                {"normalTexture": {"index": 1}},
+                {"occlusionTexture": {"index": 1}}
+            ],
+            "meshes": [
+                {"primitives": [{"attributes": {"POSITION": 0}, "material": 1}]},
+                {"primitives": [
+                    {"attributes": {"POSITION": 0}, "material": 2},
+                    {"attributes": {"POSITION": 0}, "material": 0}
+                ]}
+            ],
+            "nodes": [{"mesh": 0}, {"mesh": 1}, {"mesh": 1}, {}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+
+        let image = document.images().next().unwrap();
+        let usage = image.usage();
+        let textures: Vec<_> = usage.textures.iter().map(|t| t.index()).collect();
+        let materials: Vec<_> = usage.materials.iter().map(|m| m.index()).collect();
+        let primitives: Vec<_> = usage.primitives.iter().map(|p| p.index()).collect();
+        let nodes: Vec<_> = usage.nodes.iter().map(|n| n.index()).collect();
+        assert_eq!(textures, vec![1]);
+        assert_eq!(materials, vec![Some(1), Some(2)]);
+        assert_eq!(primitives, vec![0, 0]);
+        assert_eq!(nodes, vec![0, 1, 2]);
+
+        let material = document.materials().next().unwrap();
+        let usage = material.usage();
+        assert_eq!(usage.primitives.len(), 1);
+        assert_eq!(usage.primitives[0].index(), 1);
+        assert_eq!(usage.nodes.len(), 2);
+    }
+
+    #[test]
+    fn references_to_accessor() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 36}],
+            "bufferViews": [{"buffer": 0, "byteLength": 36}],
+            "accessors": [{
+                "bufferView": 0,
+                "componentType": 5126,
+                "count": 3,
+                "type": "VEC3",
+                "min": [0.0, 0.0, 0.0],
+                "max": [1.0, 1.0, 0.0]
+            }],
+            "meshes": [
+                {"primitives": [{"attributes": {"POSITION": 0}}]},
+                {"primitives": [
+                    {"attributes": {"NORMAL": 0, "POSITION": 0}, "targets": [{"POSITION": 0}]}
+                ]}
+            ]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+
+        let references = document.references_to(json::Index::<json::Accessor>::new(0));
+        let mut paths: Vec<_> = references.iter().map(|r| r.path.as_str()).collect();
+        paths.sort_unstable();
+        assert_eq!(
+            paths,
+            vec![
+                "meshes[0].primitives[0].attributes[\"POSITION\"]",
+                "meshes[1].primitives[0].attributes[\"NORMAL\"]",
+                "meshes[1].primitives[0].attributes[\"POSITION\"]",
+                "meshes[1].primitives[0].targets[0][\"POSITION\"]",
+            ]
+        );
+        assert_eq!(references[0].owner, Some((Kind::Mesh, 0)));
+        assert_eq!(references[3].owner, Some((Kind::Mesh, 1)));
+
+        let references = document.references_to(json::Index::<json::buffer::View>::new(0));
+        assert_eq!(references.len(), 1);
+        assert_eq!(references[0].owner, Some((Kind::Accessor, 0)));
+        assert!(document
+            .references_to(json::Index::<json::Buffer>::new(1))
+            .is_empty());
+    }
+
+    #[test]
+    fn reachable_from_scene() {
+        let json = r#"{
+            "asset": {"version": "2.0"},
+            "buffers": [{"byteLength": 40}, {"byteLength": 36}],
+            "bufferViews": [
+                {"buffer": 0, "byteLength": 36},
+                {"buffer": 1, "byteLength": 36},
+                {"buffer": 0, "byteOffset": 36, "byteLength": 4}
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0,
+                    "componentType": 5126,
+                    "count": 3,
+                    "type": "VEC3",
+                    "min": [0.0, 0.0, 0.0],
+                    "max": [1.0, 1.0, 0.0]
+                },
+                {
+                    "bufferView": 1,
+                    "componentType": 5126,
+                    "count": 3,
+                    "type": "VEC3",
+                    "min": [0.0, 0.0, 0.0],
+                    "max": [1.0, 1.0, 0.0]
+                },
+                {
+                    "bufferView": 2,
+                    "componentType": 5126,
+                    "count": 1,
+                    "type": "SCALAR",
+                    "min": [0.0],
+                    "max": [0.0]
+                }
+            ],
+            "images": [{"uri": "a.png"}, {"uri": "b.png"}],
+            "textures": [{"source": 1}],
+            "materials": [{"emissiveTexture": {"index": 0}}],
+            "meshes": [
+                {"primitives": [{"attributes": {"POSITION": 0}, "material": 0}]},
+                {"primitives": [{"attributes": {"POSITION": 1}}]}
+            ],
+            "animations": [{
+                "channels": [{"sampler": 0, "target": {"node": 1, "path": "translation"}}],
+                "samplers": [{"input": 2, "output": 0}]
+            }],
+            "nodes": [{"children": [1]}, {"mesh": 0}, {"mesh": 1}],
+            "scenes": [{"nodes": [0]}, {"nodes": [2]}]
+        }"#;
+        let document = Document::from_json(json::Root::from_str(json).unwrap()).unwrap();
+        let scenes: Vec<_> = document.scenes().collect();
+
+        let reachable = scenes[0].reachable();
+        assert!(reachable.contains(Kind::Node, 1));
+        assert!(reachable.contains(Kind::Animation, 0));
+        assert!(reachable.contains(Kind::Accessor, 2));
+        assert!(reachable.contains(Kind::Buffer, 0));
+        assert!(reachable.contains(Kind::Image, 1));
+        assert!(!reachable.contains(Kind::Image, 0));
+        assert!(!reachable.contains(Kind::Buffer, 1));
+        assert!(!reachable.contains(Kind::Scene, 1));
+
+        let reachable = scenes[1].reachable();
+        assert!(reachable.contains(Kind::Buffer, 1));
+        assert!(!reachable.contains(Kind::Buffer, 0));
+        assert!(!reachable.contains(Kind::Animation, 0));
+    }
+}
@@ -0,0 +1,656 @@
+use json::{Index, Path, Root};
+
+/// The kind of a root-level object.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Kind {
+    /// An index into `accessors`.
+    Accessor,
+
+    /// An index into `animations`.
+    Animation,
+
+    /// An index into `buffers`.
+    Buffer,
+
+    /// An index into `bufferViews`.
+    BufferView,
+
+    /// An index into `cameras`.
+    Camera,
+
+    /// An index into `images`.
+    Image,
+
+    /// An index into the `KHR_lights_punctual` lights.
+    #[cfg(feature = "KHR_lights_punctual")]
+    Light,
+
+    /// An index into `materials`.
+    Material,
+
+    /// An index into `meshes`.
+    Mesh,
+
+    /// An index into `nodes`.
+    Node,
+
+    /// An index into `samplers`.
+    Sampler,
+
+    /// An index into `scenes`.
+    Scene,
+
+    /// An index into `skins`.
+    Skin,
+
+    /// An index into `textures`.
+    Texture,
+
+    /// An index into the `KHR_materials_variants` variants.
+    #[cfg(feature = "KHR_materials_variants")]
+    Variant,
+}
+
+/// Every kind of root-level object.
+pub(crate) const KINDS: &[Kind] = &[
+    Kind::Accessor,
+    Kind::Animation,
+    Kind::Buffer,
+    Kind::BufferView,
+    Kind::Camera,
+    Kind::Image,
+    #[cfg(feature = "KHR_lights_punctual")]
+    Kind::Light,
+    Kind::Material,
+    Kind::Mesh,
+    Kind::Node,
+    Kind::Sampler,
+    Kind::Scene,
+    Kind::Skin,
+    Kind::Texture,
+    #[cfg(feature = "KHR_materials_variants")]
+    Kind::Variant,
+];
+
+/// Every kind of object, with the JSON path of the array holding it.
+pub(crate) const ARRAYS: &[(Kind, &str)] = &[
+    (Kind::Accessor, "accessors"),
+    (Kind::Animation, "animations"),
+    (Kind::Buffer, "buffers"),
+    (Kind::BufferView, "bufferViews"),
+    (Kind::Camera, "cameras"),
+    (Kind::Image, "images"),
+    #[cfg(feature = "KHR_lights_punctual")]
+    (Kind::Light, "extensions.KHR_lights_punctual.lights"),
+    (Kind::Material, "materials"),
+    (Kind::Mesh, "meshes"),
+    (Kind::Node, "nodes"),
+    (Kind::Sampler, "samplers"),
+    (Kind::Scene, "scenes"),
+    (Kind::Skin, "skins"),
+    (Kind::Texture, "textures"),
+    #[cfg(feature = "KHR_materials_variants")]
+    (Kind::Variant, "extensions.KHR_materials_variants.variants"),
+];
+
+/// The kind and index of the root-level object holding an index, or `None`
+/// for indices held by the root itself.
+pub(crate) type Owner = Option<(Kind, usize)>;
+
+/// The JSON path of an index, built on demand.
+pub(crate) type LazyPath<'a> = dyn Fn() -> Path + 'a;
+
+/// Callback receiving the kind and value of an index, the object holding it
+/// (`None` for the root), and its JSON path, returning the new value.
+type Callback<'f> = dyn FnMut(Kind, u32, Owner, &LazyPath) -> u32 + 'f;
+
+/// A stored index that may or may not be writable.
+trait Slot {
+    fn get(&self) -> u32;
+    fn set(&mut self, value: u32);
+}
+
+impl<T> Slot for &Index<T> {
+    fn get(&self) -> u32 {
+        self.value() as u32
+    }
+
+    fn set(&mut self, _: u32) {}
+}
+
+impl<T> Slot for &mut Index<T> {
+    fn get(&self) -> u32 {
+        self.value() as u32
+    }
+
+    fn set(&mut self, value: u32) {
+        **self = Index::new(value);
+    }
+}
+
+impl Slot for &u32 {
+    fn get(&self) -> u32 {
+        **self
+    }
+
+    fn set(&mut self, _: u32) {}
+}
+
+impl Slot for &mut u32 {
+    fn get(&self) -> u32 {
+        **self
+    }
+
+    fn set(&mut self, value: u32) {
+        **self = value;
+    }
+}
+
+/// Helper for visiting indices.
+struct Visitor<'a, 'f> {
+    f: &'a mut Callback<'f>,
+}
+
+impl<'a, 'f> Visitor<'a, 'f> {
+    fn visit<S: Slot>(&mut self, kind: Kind, mut slot: S, owner: Owner, path: &LazyPath) {
+        let value = (self.f)(kind, slot.get(), owner, path);
+        slot.set(value);
+    }
+}
+
+/// Returns the path of an element of a root-level array.
+fn at(array: &str, index: usize) -> Path {
+    Path::new().field(array).index(index)
+}
+
+/// Visits every index stored in a root, through either shared or mutable
+/// references.
+macro_rules! walk {
+    ($root:expr, $v:expr, $($mut:tt)?) => {{
+        let root = $root;
+        let v = $v;
+
+        if let Some(scene) = & $($mut)? root.scene {
+            v.visit(Kind::Scene, scene, None, &|| Path::new().field("scene"));
+        }
+
+        for (i, accessor) in (& $($mut)? root.accessors).into_iter().enumerate() {
+            let owner = Some((Kind::Accessor, i));
+            let path = || at("accessors", i);
+            if let Some(view) = & $($mut)? accessor.buffer_view {
+                v.visit(Kind::BufferView, view, owner, &|| path().field("bufferView"));
+            }
+            if let Some(sparse) = & $($mut)? accessor.sparse {
+                let path = || path().field("sparse");
+                v.visit(
+                    Kind::BufferView,
+                    & $($mut)? sparse.indices.buffer_view,
+                    owner,
+                    &|| path().field("indices").field("bufferView"),
+                );
+                v.visit(
+                    Kind::BufferView,
+                    & $($mut)? sparse.values.buffer_view,
+                    owner,
+                    &|| path().field("values").field("bufferView"),
+                );
+            }
+        }
+
+        for (i, animation) in (& $($mut)? root.animations).into_iter().enumerate() {
+            let owner = Some((Kind::Animation, i));
+            let path = || at("animations", i);
+            for (j, channel) in (& $($mut)? animation.channels).into_iter().enumerate() {
+                v.visit(Kind::Node, & $($mut)? channel.target.node, owner, &|| {
+                    path().field("channels").index(j).field("target").field("node")
+                });
+            }
+            for (j, sampler) in (& $($mut)? animation.samplers).into_iter().enumerate() {
+                let path = || path().field("samplers").index(j);
+                v.visit(Kind::Accessor, & $($mut)? sampler.input, owner, &|| {
+                    path().field("input")
+                });
+                v.visit(Kind::Accessor, & $($mut)? sampler.output, owner, &|| {
+                    path().field("output")
+                });
+            }
+        }
+
+        for (i, view) in (& $($mut)? root.buffer_views).into_iter().enumerate() {
+            v.visit(Kind::Buffer, & $($mut)? view.buffer, Some((Kind::BufferView, i)), &|| {
+                at("bufferViews", i).field("buffer")
+            });
+        }
+
+        for (i, image) in (& $($mut)? root.images).into_iter().enumerate() {
+            if let Some(view) = & $($mut)? image.buffer_view {
+                v.visit(Kind::BufferView, view, Some((Kind::Image, i)), &|| {
+                    at("images", i).field("bufferView")
+                });
+            }
+        }
+
+        for (i, material) in (& $($mut)? root.materials).into_iter().enumerate() {
+            let owner = Some((Kind::Material, i));
+            let path = || at("materials", i);
+            let pbr = & $($mut)? material.pbr_metallic_roughness;
+            if let Some(info) = & $($mut)? pbr.base_color_texture {
+                v.visit(Kind::Texture, & $($mut)? info.index, owner, &|| {
+                    path().field("pbrMetallicRoughness").field("baseColorTexture").field("index")
+                });
+            }
+            if let Some(info) = & $($mut)? pbr.metallic_roughness_texture {
+                v.visit(Kind::Texture, & $($mut)? info.index, owner, &|| {
+                    path()
+                        .field("pbrMetallicRoughness")
+                        .field("metallicRoughnessTexture")
+                        .field("index")
+                });
+            }
+            if let Some(normal) = & $($mut)? material.normal_texture {
+                v.visit(Kind::Texture, & $($mut)? normal.index, owner, &|| {
+                    path().field("normalTexture").field("index")
+                });
+            }
+            if let Some(occlusion) = & $($mut)? material.occlusion_texture {
+                v.visit(Kind::Texture, & $($mut)? occlusion.index, owner, &|| {
+                    path().field("occlusionTexture").field("index")
+                });
+            }
+            if let Some(info) = & $($mut)? material.emissive_texture {
+                v.visit(Kind::Texture, & $($mut)? info.index, owner, &|| {
+                    path().field("emissiveTexture").field("index")
+                });
+            }
+
+            #[allow(unused_variables)]
+            if let Some(extensions) = & $($mut)? material.extensions {
+                let path = || path().field("extensions");
+                #[cfg(feature = "KHR_materials_pbrSpecularGlossiness")]
+                if let Some(spec_gloss) = & $($mut)? extensions.pbr_specular_glossiness {
+                    let path = || path().field("KHR_materials_pbrSpecularGlossiness");
+                    if let Some(info) = & $($mut)? spec_gloss.diffuse_texture {
+                        v.visit(Kind::Texture, & $($mut)? info.index, owner, &|| {
+                            path().field("diffuseTexture").field("index")
+                        });
+                    }
+                    if let Some(info) = & $($mut)? spec_gloss.specular_glossiness_texture {
+                        v.visit(Kind::Texture, & $($mut)? info.index, owner, &|| {
+                            path().field("specularGlossinessTexture").field("index")
+                        });
+                    }
+                }
+                #[cfg(feature = "KHR_materials_transmission")]
+                if let Some(transmission) = & $($mut)? extensions.transmission {
+                    if let Some(info) = & $($mut)? transmission.transmission_texture {
+                        v.visit(Kind::Texture, & $($mut)? info.index, owner, &|| {
+                            path()
+                                .field("KHR_materials_transmission")
+                                .field("transmissionTexture")
+                                .field("index")
+                        });
+                    }
+                }
+                #[cfg(feature = "KHR_materials_volume")]
+                if let Some(volume) = & $($mut)? extensions.volume {
+                    if let Some(info) = & $($mut)? volume.thickness_texture {
+                        v.visit(Kind::Texture, & $($mut)? info.index, owner, &|| {
+                            path()
+                                .field("KHR_materials_volume")
+                                .field("thicknessTexture")
+                                .field("index")
+                        });
+                    }
+                }
+                #[cfg(feature = "KHR_materials_specular")]
+                if let Some(specular) = & $($mut)? extensions.specular {
+                    let path = || path().field("KHR_materials_specular");
+                    if let Some(info) = & $($mut)? specular.specular_texture {
+                        v.visit(Kind::Texture, & $($mut)? info.index, owner, &|| {
+                            path().field("specularTexture").field("index")
+                        });
+                    }
+                    if let Some(info) = & $($mut)? specular.specular_color_texture {
+                        v.visit(Kind::Texture, & $($mut)? info.index, owner, &|| {
+                            path().field("specularColorTexture").field("index")
+                        });
+                    }
+                }
+            }
+        }
+
+        for (i, mesh) in (& $($mut)? root.meshes).into_iter().enumerate() {
+            let owner = Some((Kind::Mesh, i));
+            for (j, primitive) in (& $($mut)? mesh.primitives).into_iter().enumerate() {
+                let path = || at("meshes", i).field("primitives").index(j);
+                for (semantic, accessor) in & $($mut)? primitive.attributes {
+                    v.visit(Kind::Accessor, accessor, owner, &|| {
+                        path().field("attributes").key(&semantic.to_string())
+                    });
+                }
+                if let Some(indices) = & $($mut)? primitive.indices {
+                    v.visit(Kind::Accessor, indices, owner, &|| path().field("indices"));
+                }
+                if let Some(material) = & $($mut)? primitive.material {
+                    v.visit(Kind::Material, material, owner, &|| path().field("material"));
+                }
+                let targets = (& $($mut)? primitive.targets).into_iter().flatten();
+                for (k, target) in targets.enumerate() {
+                    let path = || path().field("targets").index(k);
+                    if let Some(positions) = & $($mut)? target.positions {
+                        v.visit(Kind::Accessor, positions, owner, &|| {
+                            path().key("POSITION")
+                        });
+                    }
+                    if let Some(normals) = & $($mut)? target.normals {
+                        v.visit(Kind::Accessor, normals, owner, &|| path().key("NORMAL"));
+                    }
+                    if let Some(tangents) = & $($mut)? target.tangents {
+                        v.visit(Kind::Accessor, tangents, owner, &|| path().key("TANGENT"));
+                    }
+                }
+
+                #[cfg(feature = "KHR_draco_mesh_compression")]
+                if let Some(extensions) = & $($mut)? primitive.extensions {
+                    if let Some(draco) = & $($mut)? extensions.khr_draco_mesh_compression {
+                        v.visit(Kind::BufferView, & $($mut)? draco.buffer_view, owner, &|| {
+                            path()
+                                .field("extensions")
+                                .field("KHR_draco_mesh_compression")
+                                .field("bufferView")
+                        });
+                    }
+                }
+
+                #[cfg(feature = "KHR_materials_variants")]
+                if let Some(extensions) = & $($mut)? primitive.extensions {
+                    if let Some(variants) = & $($mut)? extensions.khr_materials_variants {
+                        for (k, mapping) in (& $($mut)? variants.mappings).into_iter().enumerate() {
+                            let path = || {
+                                path()
+                                    .field("extensions")
+                                    .field("KHR_materials_variants")
+                                    .field("mappings")
+                                    .index(k)
+                            };
+                            v.visit(Kind::Material, & $($mut)? mapping.material, owner, &|| {
+                                path().field("material")
+                            });
+                            let variants = (& $($mut)? mapping.variants).into_iter();
+                            for (l, variant) in variants.enumerate() {
+                                v.visit(Kind::Variant, variant, owner, &|| {
+                                    path().field("variants").index(l)
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        for (i, node) in (& $($mut)? root.nodes).into_iter().enumerate() {
+            let owner = Some((Kind::Node, i));
+            let path = || at("nodes", i);
+            if let Some(camera) = & $($mut)? node.camera {
+                v.visit(Kind::Camera, camera, owner, &|| path().field("camera"));
+            }
+            for (j, child) in (& $($mut)? node.children).into_iter().flatten().enumerate() {
+                v.visit(Kind::Node, child, owner, &|| path().field("children").index(j));
+            }
+            if let Some(mesh) = & $($mut)? node.mesh {
+                v.visit(Kind::Mesh, mesh, owner, &|| path().field("mesh"));
+            }
+            if let Some(skin) = & $($mut)? node.skin {
+                v.visit(Kind::Skin, skin, owner, &|| path().field("skin"));
+            }
+
+            #[cfg(feature = "KHR_lights_punctual")]
+            if let Some(extensions) = & $($mut)? node.extensions {
+                if let Some(light) = & $($mut)? extensions.khr_lights_punctual {
+                    v.visit(Kind::Light, & $($mut)? light.light, owner, &|| {
+                        path().field("extensions").field("KHR_lights_punctual").field("light")
+                    });
+                }
+            }
+        }
+
+        for (i, scene) in (& $($mut)? root.scenes).into_iter().enumerate() {
+            for (j, node) in (& $($mut)? scene.nodes).into_iter().enumerate() {
+                v.visit(Kind::Node, node, Some((Kind::Scene, i)), &|| {
+                    at("scenes", i).field("nodes").index(j)
+                });
+            }
+        }
+
+        for (i, skin) in (& $($mut)? root.skins).into_iter().enumerate() {
+            let owner = Some((Kind::Skin, i));
+            let path = || at("skins", i);
+            if let Some(matrices) = & $($mut)? skin.inverse_bind_matrices {
+                v.visit(Kind::Accessor, matrices, owner, &|| path().field("inverseBindMatrices"));
+            }
+            for (j, joint) in (& $($mut)? skin.joints).into_iter().enumerate() {
+                v.visit(Kind::Node, joint, owner, &|| path().field("joints").index(j));
+            }
+            if let Some(skeleton) = & $($mut)? skin.skeleton {
+                v.visit(Kind::Node, skeleton, owner, &|| path().field("skeleton"));
+            }
+        }
+
+        for (i, texture) in (& $($mut)? root.textures).into_iter().enumerate() {
+            let owner = Some((Kind::Texture, i));
+            if let Some(sampler) = & $($mut)? texture.sampler {
+                v.visit(Kind::Sampler, sampler, owner, &|| at("textures", i).field("sampler"));
+            }
+            v.visit(Kind::Image, & $($mut)? texture.source, owner, &|| {
+                at("textures", i).field("source")
+            });
+        }
+    }};
+}
+
+/// Calls `f` with every index stored in `root`, along with the object holding
+/// the index and its JSON path.
+///
+/// Indices that are local to their parent object, such as the sampler index of
+/// an animation channel, are not visited.
+pub(crate) fn walk(root: &Root, f: &mut dyn FnMut(Kind, u32, Owner, &LazyPath)) {
+    let mut f = |kind, index, owner, path: &LazyPath| {
+        f(kind, index, owner, path);
+        index
+    };
+    walk!(root, &mut Visitor { f: &mut f },);
+}
+
+/// Calls `f` with every index stored in `root`, replacing each index with the
+/// value returned.
+///
+/// Indices that are local to their parent object, such as the sampler index of
+/// an animation channel, are not visited.
+pub(crate) fn visit_indices(root: &mut Root, f: &mut dyn FnMut(Kind, u32) -> u32) {
+    let mut f = |kind, index, _, _: &LazyPath| f(kind, index);
+    walk!(root, &mut Visitor { f: &mut f }, mut);
+}
+
+/// Calls `f` with every index stored in `root` and its JSON path, replacing
+/// each index with the value returned.
+pub(crate) fn rewrite_indices(root: &mut Root, f: &mut dyn FnMut(Kind, u32, &LazyPath) -> u32) {
+    let mut f = |kind, index, _, path: &LazyPath| f(kind, index, path);
+    walk!(root, &mut Visitor { f: &mut f }, mut);
+}
+
+/// Returns the JSON path of the array holding objects of the given kind.
+pub(crate) fn array_path(kind: Kind) -> Path {
+    let array = ARRAYS
+        .iter()
+        .find(|(k, _)| *k == kind)
+        .map(|(_, array)| *array)
+        .unwrap_or_default();
+    Path(array.to_string())
+}
+
+/// Returns the number of objects of the given kind stored in `root`.
+pub(crate) fn len(root: &Root, kind: Kind) -> usize {
+    match kind {
+        Kind::Accessor => root.accessors.len(),
+        Kind::Animation => root.animations.len(),
+        Kind::Buffer => root.buffers.len(),
+        Kind::BufferView => root.buffer_views.len(),
+        Kind::Camera => root.cameras.len(),
+        Kind::Image => root.images.len(),
+        #[cfg(feature = "KHR_lights_punctual")]
+        Kind::Light => root
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.khr_lights_punctual.as_ref())
+            .map(|lights| lights.lights.len())
+            .unwrap_or(0),
+        Kind::Material => root.materials.len(),
+        Kind::Mesh => root.meshes.len(),
+        Kind::Node => root.nodes.len(),
+        Kind::Sampler => root.samplers.len(),
+        Kind::Scene => root.scenes.len(),
+        Kind::Skin => root.skins.len(),
+        Kind::Texture => root.textures.len(),
+        #[cfg(feature = "KHR_materials_variants")]
+        Kind::Variant => root
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.khr_materials_variants.as_ref())
+            .map(|variants| variants.variants.len())
+            .unwrap_or(0),
+    }
+}
+
+/// Appends every root-level object of `other` to `root`.
+///
+/// Indices within `other` are not rewritten; callers are expected to first
+/// offset them by the lengths of the arrays of `root`.
+pub(crate) fn append(root: &mut Root, other: Root) {
+    let Root {
+        accessors,
+        animations,
+        buffers,
+        buffer_views,
+        cameras,
+        images,
+        materials,
+        meshes,
+        nodes,
+        samplers,
+        scenes,
+        skins,
+        textures,
+        extensions,
+        ..
+    } = other;
+    root.accessors.extend(accessors);
+    root.animations.extend(animations);
+    root.buffers.extend(buffers);
+    root.buffer_views.extend(buffer_views);
+    root.cameras.extend(cameras);
+    root.images.extend(images);
+    root.materials.extend(materials);
+    root.meshes.extend(meshes);
+    root.nodes.extend(nodes);
+    root.samplers.extend(samplers);
+    root.scenes.extend(scenes);
+    root.skins.extend(skins);
+    root.textures.extend(textures);
+
+    if let Some(extensions) = extensions {
+        let target = root.extensions.get_or_insert_with(Default::default);
+        #[cfg(feature = "KHR_lights_punctual")]
+        if let Some(lights) = extensions.khr_lights_punctual {
+            target
+                .khr_lights_punctual
+                .get_or_insert_with(Default::default)
+                .lights
+                .extend(lights.lights);
+        }
+        #[cfg(feature = "KHR_materials_variants")]
+        if let Some(variants) = extensions.khr_materials_variants {
+            target
+                .khr_materials_variants
+                .get_or_insert_with(Default::default)
+                .variants
+                .extend(variants.variants);
+        }
+        for (name, value) in extensions.others {
+            target.others.entry(name).or_insert(value);
+        }
+    }
+}
+
+/// Removes the objects of the given kind with a `false` entry in `keep` and
+/// rewrites every index into the array accordingly.
+///
+/// Returns the new index of every old index, or `None` for removed objects.
+/// References to removed objects are left dangling; callers are expected to
+/// only remove objects that are no longer referenced.
+pub(crate) fn remove(root: &mut Root, kind: Kind, keep: &[bool]) -> Vec<Option<usize>> {
+    fn retain<T>(items: &mut Vec<T>, keep: &[bool]) {
+        let mut i = 0;
+        items.retain(|_| {
+            i += 1;
+            keep[i - 1]
+        });
+    }
+
+    let mut remap = Vec::with_capacity(keep.len());
+    let mut next = 0;
+    for &kept in keep {
+        remap.push(next);
+        if kept {
+            next += 1;
+        }
+    }
+
+    match kind {
+        Kind::Accessor => retain(&mut root.accessors, keep),
+        Kind::Animation => retain(&mut root.animations, keep),
+        Kind::Buffer => retain(&mut root.buffers, keep),
+        Kind::BufferView => retain(&mut root.buffer_views, keep),
+        Kind::Camera => retain(&mut root.cameras, keep),
+        Kind::Image => retain(&mut root.images, keep),
+        #[cfg(feature = "KHR_lights_punctual")]
+        Kind::Light => {
+            if let Some(lights) = root
+                .extensions
+                .as_mut()
+                .and_then(|extensions| extensions.khr_lights_punctual.as_mut())
+            {
+                retain(&mut lights.lights, keep);
+            }
+        }
+        Kind::Material => retain(&mut root.materials, keep),
+        Kind::Mesh => retain(&mut root.meshes, keep),
+        Kind::Node => retain(&mut root.nodes, keep),
+        Kind::Sampler => retain(&mut root.samplers, keep),
+        Kind::Scene => retain(&mut root.scenes, keep),
+        Kind::Skin => retain(&mut root.skins, keep),
+        Kind::Texture => retain(&mut root.textures, keep),
+        #[cfg(feature = "KHR_materials_variants")]
+        Kind::Variant => {
+            if let Some(variants) = root
+                .extensions
+                .as_mut()
+                .and_then(|extensions| extensions.khr_materials_variants.as_mut())
+            {
+                retain(&mut variants.variants, keep);
+            }
+        }
+    }
+
+    visit_indices(root, &mut |k, index| {
+        if k == kind {
+            remap.get(index as usize).copied().unwrap_or(index)
+        } else {
+            index
+        }
+    });
+
+    keep.iter()
+        .zip(remap)
+        .map(|(&kept, new)| if kept { Some(new as usize) } else { None })
+        .collect()
+}
@@ -21,7 +21,7 @@ fn sanity_check(
 
 fn run() -> Result<(), Box<dyn StdError>> {
     let sample_dir_path = path::Path::new(SAMPLE_MODELS_DIRECTORY_PATH);
-    for entry in fs::read_dir(&sample_dir_path)? {
+    for entry in fs::read_dir(sample_dir_path)? {
         let entry = entry?;
         let metadata = entry.metadata()?;
         if metadata.is_dir() {
@@ -67,13 +67,13 @@ fn run() -> Result<(), Box<dyn StdError>> {
 fn sparse_accessor_without_buffer_view_test() -> Result<(), Box<dyn StdError>> {
     let glb_path = path::Path::new("tests/box_sparse.glb");
     print!("{:?}: ", glb_path);
-    let result = gltf::import(&glb_path)?;
+    let result = gltf::import(glb_path)?;
     sanity_check(&result.0, &result.1, &result.2);
     println!("ok");
 
     let gltf_path = path::Path::new("tests/box_sparse.gltf");
     print!("{:?}: ", gltf_path);
-    let result = gltf::import(&gltf_path)?;
+    let result = gltf::import(gltf_path)?;
     sanity_check(&result.0, &result.1, &result.2);
     println!("ok");
     Ok(())
@@ -22,3 +22,105 @@ fn test_accessor_bounds() {
         }
     );
 }
+
+#[test]
+fn test_lenient_json_framing() {
+    let json = br#"{"asset": {"version": "2.0"}, "nodes": [{}]}"#;
+
+    let mut with_bom = b"\xEF\xBB\xBF".to_vec();
+    with_bom.extend_from_slice(json);
+    let gltf = gltf::Gltf::from_slice(&with_bom).unwrap();
+    assert_eq!(gltf.nodes().count(), 1);
+    let gltf = gltf::Gltf::from_reader(io::Cursor::new(&with_bom)).unwrap();
+    assert_eq!(gltf.nodes().count(), 1);
+
+    let mut padded = json.to_vec();
+    padded.extend_from_slice(b" \n\0\0\0");
+    let glb = gltf::Glb {
+        header: gltf::binary::Header {
+            magic: *b"glTF",
+            version: 2,
+            length: 0,
+        },
+        json: padded.into(),
+        bin: None,
+    };
+    let bytes = glb.to_vec().unwrap();
+    let gltf = gltf::Gltf::from_slice(&bytes).unwrap();
+    assert_eq!(gltf.nodes().count(), 1);
+    let gltf = gltf::Gltf::from_reader(io::Cursor::new(&bytes)).unwrap();
+    assert_eq!(gltf.nodes().count(), 1);
+}
+
+#[test]
+fn test_gltf_1_0_detection() {
+    let json = br#"{
+        "asset": {"version": "1.0", "profile": {"api": "WebGL", "version": "1.0.2"}},
+        "nodes": {"node_0": {"children": []}},
+        "scene": "defaultScene"
+    }"#;
+    match gltf::Gltf::from_slice(json) {
+        Err(gltf::Error::UnsupportedVersion(version)) => assert_eq!(version, "1.0"),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+
+    let json = br#"{"scenes": {"defaultScene": {"nodes": []}}}"#;
+    match gltf::Gltf::from_reader(io::Cursor::new(&json[..])) {
+        Err(gltf::Error::UnsupportedVersion(version)) => assert_eq!(version, "1.0"),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+
+    let json = br#"{"asset": {"version": "1.0"}}"#;
+    assert!(matches!(
+        gltf::Gltf::from_slice(json),
+        Err(gltf::Error::UnsupportedVersion(_))
+    ));
+}
+
+#[test]
+fn test_detect() {
+    use gltf::{Detected, Format};
+
+    let detected = gltf::detect(br#"{"asset": {"version": "2.0"}}"#).unwrap();
+    assert_eq!(
+        detected,
+        Detected {
+            format: Format::Json,
+            version: Some("2.0".to_string()),
+        }
+    );
+    assert!(detected.is_supported());
+
+    let detected = gltf::detect(br#"{"meshes": {"mesh_0": {}}}"#).unwrap();
+    assert_eq!(detected.version.as_deref(), Some("1.0"));
+    assert!(!detected.is_supported());
+
+    let mut glb_1_0 = b"glTF".to_vec();
+    glb_1_0.extend_from_slice(&1u32.to_le_bytes());
+    glb_1_0.extend_from_slice(&20u32.to_le_bytes());
+    let detected = gltf::detect(&glb_1_0).unwrap();
+    assert_eq!(detected.format, Format::Binary);
+    assert_eq!(detected.version.as_deref(), Some("1.0"));
+
+    let file = fs::File::open("tests/box_sparse.glb").unwrap();
+    let mut bytes = vec![];
+    io::BufReader::new(file).read_to_end(&mut bytes).unwrap();
+    let detected = gltf::detect(&bytes).unwrap();
+    assert_eq!(detected.format, Format::Binary);
+    assert!(detected.is_supported());
+}
+
+#[test]
+fn test_strict_parsing() {
+    let json = br#"{"asset": {"version": "2.0", "vendor": "modeller"}, "nodes": [{}]}"#;
+    assert!(gltf::Gltf::from_slice(json).is_ok());
+    match gltf::Gltf::from_slice_strict(json) {
+        Err(gltf::Error::Deserialize(err)) => {
+            assert_eq!(err.to_string(), "unknown field `asset.vendor`")
+        }
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+
+    let glb = fs::read("examples/Box.glb").unwrap();
+    assert!(gltf::Gltf::from_slice_strict(&glb).is_ok());
+}